@@ -0,0 +1,62 @@
+//! Arbitrary account data must either be rejected by the Borsh decoders or
+//! decode to a value no larger than the bytes it came from.
+
+#![no_main]
+
+use borsh::BorshDeserialize;
+use libfuzzer_sys::fuzz_target;
+use quiz_game::state::*;
+
+fn check<T: BorshDeserialize + borsh::BorshSerialize>(data: &[u8]) {
+    let Ok(value) = T::try_from_slice(data) else {
+        return;
+    };
+    let encoded = borsh::to_vec(&value).expect("decoded state must encode");
+    assert_eq!(encoded.len(), data.len());
+}
+
+fuzz_target!(|data: &[u8]| {
+    // The first byte picks the account type, the rest is its data
+    let Some((&kind, data)) = data.split_first() else {
+        return;
+    };
+    match kind % 37 {
+        0 => check::<QuizSession>(data),
+        1 => check::<QuizQuestion>(data),
+        2 => check::<PlayerAnswer>(data),
+        3 => check::<PlayerScore>(data),
+        4 => check::<Team>(data),
+        5 => check::<TeamMember>(data),
+        6 => check::<Tournament>(data),
+        7 => check::<DailyConfig>(data),
+        8 => check::<DailyQuiz>(data),
+        9 => check::<DailyEntry>(data),
+        10 => check::<DailyStreak>(data),
+        11 => check::<DailyLeaderboard>(data),
+        12 => check::<QuizRegistry>(data),
+        13 => check::<QuizScoreboard>(data),
+        14 => check::<AnswerSession>(data),
+        15 => check::<QuestionHint>(data),
+        16 => check::<QuestionStats>(data),
+        17 => check::<QuizAnalytics>(data),
+        18 => check::<HostStats>(data),
+        19 => check::<QuestionDispute>(data),
+        20 => check::<DisputeFlag>(data),
+        21 => check::<ScoreAudit>(data),
+        22 => check::<QuizEventLog>(data),
+        23 => check::<LeaderboardClaim>(data),
+        24 => check::<AnswerTree>(data),
+        25 => check::<QuizTemplate>(data),
+        26 => check::<QuestionTranslation>(data),
+        27 => check::<PlayerProfile>(data),
+        28 => check::<NicknameClaim>(data),
+        29 => check::<LiveLeaderboard>(data),
+        30 => check::<QuizReactions>(data),
+        31 => check::<Series>(data),
+        32 => check::<PrizeContribution>(data),
+        33 => check::<AdaptiveBank>(data),
+        34 => check::<QuestionExplanation>(data),
+        35 => check::<QuestionCommitment>(data),
+        _ => check::<SeasonArchive>(data),
+    }
+});
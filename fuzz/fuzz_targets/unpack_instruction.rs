@@ -0,0 +1,26 @@
+//! Arbitrary instruction data must either be rejected or decode to an
+//! instruction that survives a pack/unpack round trip.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use quiz_game::instruction::QuizInstruction;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(instruction) = QuizInstruction::unpack(data) else {
+        return;
+    };
+
+    // Every decoded field came out of the input, so the compact form can't
+    // be larger than it
+    let compact = instruction.pack_compact();
+    assert!(compact.len() <= data.len());
+
+    // Both packed forms decode back to the same instruction
+    for packed in [compact, instruction.pack()] {
+        let repacked = QuizInstruction::unpack(&packed)
+            .expect("packed instruction must unpack")
+            .pack_compact();
+        assert_eq!(instruction.pack_compact(), repacked);
+    }
+});
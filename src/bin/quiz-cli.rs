@@ -0,0 +1,341 @@
+//! Command line tool for hosting and playing quizzes against any RPC endpoint.
+
+use std::{error::Error, fs, path::PathBuf};
+
+use borsh::BorshDeserialize;
+use clap::{Parser, Subcommand};
+use quiz_game::{
+    client,
+    filters::{self, AccountFilter},
+    state::{DelegationConfig, PlayerScore, QuizSession},
+};
+use serde::Deserialize;
+use solana_client::{
+    rpc_client::RpcClient,
+    rpc_config::RpcProgramAccountsConfig,
+    rpc_filter::{Memcmp, RpcFilterType},
+};
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair, Signer},
+    transaction::Transaction,
+};
+
+#[derive(Parser)]
+#[command(
+    name = "quiz-cli",
+    about = "Host and play quizzes on the quiz-game program"
+)]
+struct Cli {
+    /// RPC endpoint
+    #[arg(long, default_value = "https://api.devnet.solana.com")]
+    url: String,
+    /// Keypair that signs and pays for transactions
+    #[arg(long, default_value = "~/.config/solana/id.json")]
+    keypair: String,
+    /// Deployed quiz-game program id
+    #[arg(long)]
+    program_id: Pubkey,
+    /// Priority fee in micro-lamports per compute unit, for congested slots
+    #[arg(long, default_value_t = 0)]
+    priority_fee: u64,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create a quiz hosted by the keypair and add questions from a TOML or JSON file
+    Create {
+        #[arg(long)]
+        quiz_id: u64,
+        #[arg(long)]
+        questions: PathBuf,
+        /// How often the ephemeral rollup commits answer sheets
+        #[arg(long, default_value_t = DelegationConfig::DEFAULT_COMMIT_FREQUENCY_MS)]
+        commit_frequency_ms: u32,
+        /// Rollup validator to delegate answer sheets to, any if omitted
+        #[arg(long)]
+        validator: Option<Pubkey>,
+        /// Play entirely on chain, without an ephemeral rollup
+        #[arg(long)]
+        base_layer: bool,
+    },
+    /// Open a quiz hosted by the keypair for players
+    Start {
+        #[arg(long)]
+        quiz_id: u64,
+        /// Schedule the start for this slot instead of opening it now
+        #[arg(long)]
+        at_slot: Option<u64>,
+    },
+    /// Join a quiz as the keypair, delegating the answer sheet unless the quiz
+    /// is on the base layer
+    Join {
+        #[arg(long)]
+        host: Pubkey,
+        #[arg(long)]
+        quiz_id: u64,
+        /// Ephemeral validator to delegate to, the quiz's pinned one if omitted
+        #[arg(long)]
+        validator: Option<Pubkey>,
+    },
+    /// Submit a full answer sheet, e.g. `--answers 0,2,1`
+    Submit {
+        #[arg(long)]
+        host: Pubkey,
+        #[arg(long)]
+        quiz_id: u64,
+        #[arg(long, value_delimiter = ',')]
+        answers: Vec<u8>,
+        /// Confidence 1-3 per answer, for confidence-weighted quizzes
+        #[arg(long, value_delimiter = ',')]
+        confidence: Vec<u8>,
+    },
+    /// Score every player of a quiz hosted by the keypair
+    Finalize {
+        #[arg(long)]
+        quiz_id: u64,
+        #[arg(long, value_delimiter = ',')]
+        players: Vec<Pubkey>,
+    },
+    /// Print the scores of a finalized quiz, best first
+    Leaderboard {
+        #[arg(long)]
+        host: Pubkey,
+        #[arg(long)]
+        quiz_id: u64,
+    },
+}
+
+#[derive(Deserialize)]
+struct QuestionFile {
+    questions: Vec<QuestionSpec>,
+}
+
+#[derive(Deserialize)]
+struct QuestionSpec {
+    text: String,
+    options: [String; 4],
+    answer: u8,
+}
+
+struct Context {
+    rpc: RpcClient,
+    payer: Keypair,
+    program_id: Pubkey,
+    priority_fee: u64,
+}
+
+impl Context {
+    fn send(&self, instructions: &[Instruction]) -> Result<(), Box<dyn Error>> {
+        self.send_sized(instructions, 0, 0)
+    }
+
+    /// `send` with the compute budget of a priority fee sized for
+    /// `question_count` answers and `player_count` graded players.
+    fn send_sized(
+        &self,
+        instructions: &[Instruction],
+        question_count: u8,
+        player_count: u8,
+    ) -> Result<(), Box<dyn Error>> {
+        let instructions = if self.priority_fee > 0 {
+            client::with_priority_fee(
+                &self.program_id,
+                instructions,
+                question_count,
+                player_count,
+                self.priority_fee,
+            )
+        } else {
+            instructions.to_vec()
+        };
+        let blockhash = self.rpc.get_latest_blockhash()?;
+        let transaction = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&self.payer.pubkey()),
+            &[&self.payer],
+            blockhash,
+        );
+        let signature = self.rpc.send_and_confirm_transaction(&transaction)?;
+        println!("{}", signature);
+        Ok(())
+    }
+
+    fn program_accounts(
+        &self,
+        filters: Vec<AccountFilter>,
+    ) -> Result<Vec<Vec<u8>>, Box<dyn Error>> {
+        let filters = filters
+            .into_iter()
+            .map(|filter| match filter {
+                AccountFilter::DataSize(size) => RpcFilterType::DataSize(size),
+                AccountFilter::Memcmp { offset, bytes } => {
+                    RpcFilterType::Memcmp(Memcmp::new_raw_bytes(offset, bytes))
+                }
+            })
+            .collect();
+        let config = RpcProgramAccountsConfig {
+            filters: Some(filters),
+            ..RpcProgramAccountsConfig::default()
+        };
+        Ok(self
+            .rpc
+            .get_program_accounts_with_config(&self.program_id, config)?
+            .into_iter()
+            .map(|(_, account)| account.data)
+            .collect())
+    }
+
+    fn load_quiz(&self, quiz: &Pubkey) -> Result<QuizSession, Box<dyn Error>> {
+        let data = self.rpc.get_account_data(quiz)?;
+        Ok(QuizSession::try_from_slice(&data)?)
+    }
+}
+
+fn read_questions(path: &PathBuf) -> Result<Vec<QuestionSpec>, Box<dyn Error>> {
+    let contents = fs::read_to_string(path)?;
+    let file: QuestionFile = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("json") => serde_json::from_str(&contents)?,
+        _ => toml::from_str(&contents)?,
+    };
+    Ok(file.questions)
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), std::env::var_os("HOME")) {
+        (Some(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let cli = Cli::parse();
+    let ctx = Context {
+        rpc: RpcClient::new(cli.url),
+        payer: read_keypair_file(expand_home(&cli.keypair))?,
+        program_id: cli.program_id,
+        priority_fee: cli.priority_fee,
+    };
+    let program_id = &ctx.program_id;
+    let me = ctx.payer.pubkey();
+
+    match cli.command {
+        Command::Create {
+            quiz_id,
+            questions,
+            commit_frequency_ms,
+            validator,
+            base_layer,
+        } => {
+            let questions = read_questions(&questions)?;
+            let question_count = u8::try_from(questions.len())?;
+            let quiz = client::find_quiz_address(program_id, &me, quiz_id);
+            let delegation = DelegationConfig {
+                commit_frequency_ms,
+                validator: validator.unwrap_or_default(),
+                base_layer,
+            };
+            ctx.send(&[client::initialize_quiz_with_delegation_ix(
+                program_id,
+                &me,
+                quiz_id,
+                question_count,
+                delegation,
+            )])?;
+            for (index, question) in questions.into_iter().enumerate() {
+                ctx.send(&[client::add_question_ix(
+                    program_id,
+                    &me,
+                    &quiz,
+                    index as u8,
+                    question.text,
+                    question.options,
+                    question.answer,
+                )])?;
+            }
+            println!("Quiz {} created with {} questions", quiz, question_count);
+        }
+        Command::Start { quiz_id, at_slot } => {
+            let quiz = client::find_quiz_address(program_id, &me, quiz_id);
+            let ix = match at_slot {
+                Some(start_slot) => client::schedule_start_ix(program_id, &me, &quiz, start_slot),
+                None => client::start_quiz_ix(program_id, &me, &quiz),
+            };
+            ctx.send(&[ix])?;
+        }
+        Command::Join {
+            host,
+            quiz_id,
+            validator,
+        } => {
+            let quiz = client::find_quiz_address(program_id, &host, quiz_id);
+            let quiz_data = ctx.load_quiz(&quiz)?;
+            let tournament =
+                (quiz_data.tournament != Pubkey::default()).then_some(&quiz_data.tournament);
+            let paid = quiz_data.entry_fee + quiz_data.join_stake;
+            let join = if quiz_data.delegation.base_layer {
+                client::join_quiz_ix(program_id, &me, &quiz, paid, tournament)
+            } else {
+                client::delegate_player_ix(program_id, &me, &quiz, paid, tournament, validator)
+            };
+            ctx.send(&[join])?;
+        }
+        Command::Submit {
+            host,
+            quiz_id,
+            answers,
+            confidence,
+        } => {
+            let quiz = client::find_quiz_address(program_id, &host, quiz_id);
+            let question_count = u8::try_from(answers.len())?;
+            ctx.send_sized(
+                &[client::submit_weighted_answers_ix(
+                    program_id, &me, &quiz, answers, confidence,
+                )],
+                question_count,
+                0,
+            )?;
+        }
+        Command::Finalize { quiz_id, players } => {
+            let quiz = client::find_quiz_address(program_id, &me, quiz_id);
+            let quiz_data = ctx.load_quiz(&quiz)?;
+            let player_count = u8::try_from(players.len())?;
+            ctx.send_sized(
+                &[client::calculate_scores_ix(
+                    program_id,
+                    &me,
+                    &quiz,
+                    quiz_data.question_count,
+                    &players,
+                )],
+                quiz_data.question_count,
+                player_count,
+            )?;
+        }
+        Command::Leaderboard { host, quiz_id } => {
+            let quiz = client::find_quiz_address(program_id, &host, quiz_id);
+            let quiz_data = ctx.load_quiz(&quiz)?;
+
+            let mut scores: Vec<PlayerScore> = ctx
+                .program_accounts(filters::scores_for_quiz(&quiz))?
+                .iter()
+                .filter_map(|data| PlayerScore::try_from_slice(data).ok())
+                .collect();
+            scores.sort_by_key(|score| std::cmp::Reverse(score.score));
+
+            for (rank, score) in scores.iter().enumerate() {
+                println!(
+                    "{:>3}. {}  {}/{}",
+                    rank + 1,
+                    score.player,
+                    score.score,
+                    quiz_data.question_count
+                );
+            }
+        }
+    }
+    Ok(())
+}
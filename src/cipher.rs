@@ -0,0 +1,90 @@
+//! Stream cipher for question text uploaded before a quiz starts. The host
+//! commits to a 32-byte key with `SetQuestionKey`, uploads every question's
+//! text and options encrypted under it and publishes the key with
+//! `RevealQuestions`, after which anyone can decrypt.
+//!
+//! Each field is XORed with a keystream of `sha256(key, question_index,
+//! field, block)` blocks, field 0 being the text and 1-4 the options, and
+//! stored as lowercase hex so question accounts still hold strings. Only the
+//! text is hidden: the correct answer and the other question settings stay
+//! readable.
+
+use solana_program::hash::{hash, hashv};
+
+/// Field number of the question text, options follow from 1.
+const TEXT_FIELD: u8 = 0;
+
+/// The commitment `SetQuestionKey` takes for `key`.
+pub fn key_hash(key: &[u8; 32]) -> [u8; 32] {
+    hash(key).to_bytes()
+}
+
+fn apply_keystream(key: &[u8; 32], question_index: u8, field: u8, data: &mut [u8]) {
+    for (block, chunk) in data.chunks_mut(32).enumerate() {
+        let stream = hashv(&[
+            key,
+            &[question_index],
+            &[field],
+            &(block as u32).to_le_bytes(),
+        ]);
+        for (byte, key_byte) in chunk.iter_mut().zip(stream.to_bytes()) {
+            *byte ^= key_byte;
+        }
+    }
+}
+
+fn encrypt_field(key: &[u8; 32], question_index: u8, field: u8, plaintext: &str) -> String {
+    let mut data = plaintext.as_bytes().to_vec();
+    apply_keystream(key, question_index, field, &mut data);
+    data.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn decrypt_field(
+    key: &[u8; 32],
+    question_index: u8,
+    field: u8,
+    ciphertext: &str,
+) -> Option<String> {
+    let mut data = (0..ciphertext.len())
+        .step_by(2)
+        .map(|at| u8::from_str_radix(ciphertext.get(at..at + 2)?, 16).ok())
+        .collect::<Option<Vec<u8>>>()?;
+    apply_keystream(key, question_index, field, &mut data);
+    String::from_utf8(data).ok()
+}
+
+/// Encrypt a question's text and options for `AddQuestion`.
+pub fn encrypt_question(
+    key: &[u8; 32],
+    question_index: u8,
+    question_text: &str,
+    options: &[String; 4],
+) -> (String, [String; 4]) {
+    (
+        encrypt_field(key, question_index, TEXT_FIELD, question_text),
+        std::array::from_fn(|option| {
+            encrypt_field(
+                key,
+                question_index,
+                TEXT_FIELD + 1 + option as u8,
+                &options[option],
+            )
+        }),
+    )
+}
+
+/// Decrypt what `encrypt_question` produced. None if it isn't hex or doesn't
+/// decrypt to UTF-8, as text encrypted under another key almost never does.
+pub fn decrypt_question(
+    key: &[u8; 32],
+    question_index: u8,
+    question_text: &str,
+    options: &[String; 4],
+) -> Option<(String, [String; 4])> {
+    let text = decrypt_field(key, question_index, TEXT_FIELD, question_text)?;
+    let mut decrypted: [String; 4] = Default::default();
+    for (option, (plain, cipher)) in decrypted.iter_mut().zip(options).enumerate() {
+        *plain = decrypt_field(key, question_index, TEXT_FIELD + 1 + option as u8, cipher)?;
+    }
+    Some((text, decrypted))
+}
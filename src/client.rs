@@ -0,0 +1,3531 @@
+//! Off-chain helpers that derive the program's PDAs and build instructions
+//! with their accounts in the order the processor reads them.
+
+use ephemeral_rollups_sdk::consts::{DELEGATION_PROGRAM_ID, MAGIC_CONTEXT_ID, MAGIC_PROGRAM_ID};
+use solana_program::{
+    bpf_loader_upgradeable, ed25519_program,
+    hash::Hash,
+    instruction::{AccountMeta, Instruction},
+    message::Message,
+    pubkey::Pubkey,
+    system_instruction, system_program, sysvar,
+};
+
+use crate::{
+    ed25519::{CURRENT_INSTRUCTION, SIGNATURE_OFFSETS_SIZE, SIGNATURE_OFFSETS_START},
+    instruction::QuizInstruction,
+    state::{
+        AdaptiveBank, AnswerSession, AnswerTree, CompressedAnswers, CompressedSheet, DailyConfig,
+        DailyEntry, DailyLeaderboard, DailyQuiz, DailyStreak, DelegationConfig, DisputeFlag,
+        HostPlay, HostStats, LeaderboardClaim, LiveLeaderboard, NicknameClaim, PlayerAnswer,
+        PlayerProfile, PlayerScore, PostedStanding, PrizeContribution, PrizeRemainder,
+        QuestionCommitment, QuestionData, QuestionDispute, QuestionExplanation, QuestionHint,
+        QuestionStats, QuestionTranslation, QuizAnalytics, QuizEventLog, QuizMode, QuizQuestion,
+        QuizReactions, QuizRegistry, QuizScoreboard, QuizSession, QuizTemplate, ScoreAudit,
+        ScoringMode, SeasonArchive, Series, Team, TeamMember, Tournament, VoidMode,
+    },
+};
+
+pub fn find_quiz_address(program_id: &Pubkey, host: &Pubkey, quiz_id: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[QuizSession::SEED, host.as_ref(), &quiz_id.to_le_bytes()],
+        program_id,
+    )
+    .0
+}
+
+pub fn find_question_address(program_id: &Pubkey, quiz: &Pubkey, question_index: u8) -> Pubkey {
+    Pubkey::find_program_address(
+        &[QuizQuestion::SEED, quiz.as_ref(), &[question_index]],
+        program_id,
+    )
+    .0
+}
+
+pub fn find_question_stats_address(
+    program_id: &Pubkey,
+    quiz: &Pubkey,
+    question_index: u8,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &[QuestionStats::SEED, quiz.as_ref(), &[question_index]],
+        program_id,
+    )
+    .0
+}
+
+pub fn find_question_dispute_address(
+    program_id: &Pubkey,
+    quiz: &Pubkey,
+    question_index: u8,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &[QuestionDispute::SEED, quiz.as_ref(), &[question_index]],
+        program_id,
+    )
+    .0
+}
+
+pub fn find_score_audit_address(program_id: &Pubkey, quiz: &Pubkey, page: u32) -> Pubkey {
+    Pubkey::find_program_address(
+        &[ScoreAudit::SEED, quiz.as_ref(), &page.to_le_bytes()],
+        program_id,
+    )
+    .0
+}
+
+pub fn find_event_log_address(program_id: &Pubkey, quiz: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[QuizEventLog::SEED, quiz.as_ref()], program_id).0
+}
+
+pub fn find_answer_tree_address(program_id: &Pubkey, quiz: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[AnswerTree::SEED, quiz.as_ref()], program_id).0
+}
+
+pub fn find_compressed_sheet_address(
+    program_id: &Pubkey,
+    quiz: &Pubkey,
+    player: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &[CompressedSheet::SEED, quiz.as_ref(), player.as_ref()],
+        program_id,
+    )
+    .0
+}
+
+pub fn find_template_address(program_id: &Pubkey, author: &Pubkey, template_id: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            QuizTemplate::SEED,
+            author.as_ref(),
+            &template_id.to_le_bytes(),
+        ],
+        program_id,
+    )
+    .0
+}
+
+pub fn find_template_question_address(
+    program_id: &Pubkey,
+    template: &Pubkey,
+    question_index: u8,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            QuizTemplate::QUESTION_SEED,
+            template.as_ref(),
+            &[question_index],
+        ],
+        program_id,
+    )
+    .0
+}
+
+pub fn find_leaderboard_claim_address(
+    program_id: &Pubkey,
+    quiz: &Pubkey,
+    player: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &[LeaderboardClaim::SEED, quiz.as_ref(), player.as_ref()],
+        program_id,
+    )
+    .0
+}
+
+pub fn find_prize_contribution_address(
+    program_id: &Pubkey,
+    quiz: &Pubkey,
+    contributor: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &[PrizeContribution::SEED, quiz.as_ref(), contributor.as_ref()],
+        program_id,
+    )
+    .0
+}
+
+pub fn find_dispute_flag_address(
+    program_id: &Pubkey,
+    quiz: &Pubkey,
+    question_index: u8,
+    player: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            DisputeFlag::SEED,
+            quiz.as_ref(),
+            &[question_index],
+            player.as_ref(),
+        ],
+        program_id,
+    )
+    .0
+}
+
+pub fn find_host_stats_address(program_id: &Pubkey, host: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[HostStats::SEED, host.as_ref()], program_id).0
+}
+
+pub fn find_quiz_analytics_address(program_id: &Pubkey, quiz: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[QuizAnalytics::SEED, quiz.as_ref()], program_id).0
+}
+
+pub fn find_hint_address(program_id: &Pubkey, quiz: &Pubkey, question_index: u8) -> Pubkey {
+    Pubkey::find_program_address(
+        &[QuestionHint::SEED, quiz.as_ref(), &[question_index]],
+        program_id,
+    )
+    .0
+}
+
+pub fn find_question_commitment_address(
+    program_id: &Pubkey,
+    quiz: &Pubkey,
+    question_index: u8,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &[QuestionCommitment::SEED, quiz.as_ref(), &[question_index]],
+        program_id,
+    )
+    .0
+}
+
+pub fn find_explanation_address(program_id: &Pubkey, quiz: &Pubkey, question_index: u8) -> Pubkey {
+    Pubkey::find_program_address(
+        &[QuestionExplanation::SEED, quiz.as_ref(), &[question_index]],
+        program_id,
+    )
+    .0
+}
+
+pub fn find_translation_address(
+    program_id: &Pubkey,
+    quiz: &Pubkey,
+    question_index: u8,
+    language: [u8; 2],
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            QuestionTranslation::SEED,
+            quiz.as_ref(),
+            &[question_index],
+            &language,
+        ],
+        program_id,
+    )
+    .0
+}
+
+pub fn find_player_answer_address(program_id: &Pubkey, quiz: &Pubkey, player: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[PlayerAnswer::SEED, quiz.as_ref(), player.as_ref()],
+        program_id,
+    )
+    .0
+}
+
+pub fn find_player_score_address(program_id: &Pubkey, quiz: &Pubkey, player: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[PlayerScore::SEED, quiz.as_ref(), player.as_ref()],
+        program_id,
+    )
+    .0
+}
+
+pub fn find_scoreboard_address(program_id: &Pubkey, quiz: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[QuizScoreboard::SEED, quiz.as_ref()], program_id).0
+}
+
+pub fn find_answer_session_address(program_id: &Pubkey, quiz: &Pubkey, player: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[AnswerSession::SEED, quiz.as_ref(), player.as_ref()],
+        program_id,
+    )
+    .0
+}
+
+pub fn find_quiz_vault_address(program_id: &Pubkey, quiz: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[QuizSession::VAULT_SEED, quiz.as_ref()], program_id).0
+}
+
+pub fn find_team_address(program_id: &Pubkey, quiz: &Pubkey, team_id: u8) -> Pubkey {
+    Pubkey::find_program_address(&[Team::SEED, quiz.as_ref(), &[team_id]], program_id).0
+}
+
+pub fn find_team_member_address(program_id: &Pubkey, quiz: &Pubkey, player: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[TeamMember::SEED, quiz.as_ref(), player.as_ref()],
+        program_id,
+    )
+    .0
+}
+
+pub fn find_tournament_address(
+    program_id: &Pubkey,
+    organizer: &Pubkey,
+    tournament_id: u64,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            Tournament::SEED,
+            organizer.as_ref(),
+            &tournament_id.to_le_bytes(),
+        ],
+        program_id,
+    )
+    .0
+}
+
+pub fn find_tournament_vault_address(program_id: &Pubkey, tournament: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[Tournament::VAULT_SEED, tournament.as_ref()], program_id).0
+}
+
+pub fn find_series_address(program_id: &Pubkey, organizer: &Pubkey, series_id: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[Series::SEED, organizer.as_ref(), &series_id.to_le_bytes()],
+        program_id,
+    )
+    .0
+}
+
+pub fn find_series_vault_address(program_id: &Pubkey, series: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[Series::VAULT_SEED, series.as_ref()], program_id).0
+}
+
+pub fn find_season_archive_address(program_id: &Pubkey, board: &Pubkey, season: u32) -> Pubkey {
+    Pubkey::find_program_address(
+        &[SeasonArchive::SEED, board.as_ref(), &season.to_le_bytes()],
+        program_id,
+    )
+    .0
+}
+
+pub fn find_daily_config_address(program_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[DailyConfig::SEED], program_id).0
+}
+
+pub fn find_daily_leaderboard_address(program_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[DailyLeaderboard::SEED], program_id).0
+}
+
+pub fn find_daily_quiz_address(program_id: &Pubkey, day_index: u64) -> Pubkey {
+    Pubkey::find_program_address(&[DailyQuiz::SEED, &day_index.to_le_bytes()], program_id).0
+}
+
+pub fn find_daily_entry_address(
+    program_id: &Pubkey,
+    daily_quiz: &Pubkey,
+    player: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &[DailyEntry::SEED, daily_quiz.as_ref(), player.as_ref()],
+        program_id,
+    )
+    .0
+}
+
+pub fn find_daily_streak_address(program_id: &Pubkey, player: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[DailyStreak::SEED, player.as_ref()], program_id).0
+}
+
+pub fn find_profile_address(program_id: &Pubkey, player: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[PlayerProfile::SEED, player.as_ref()], program_id).0
+}
+
+pub fn find_nickname_claim_address(program_id: &Pubkey, nickname: &str) -> Pubkey {
+    Pubkey::find_program_address(
+        &[NicknameClaim::SEED, &NicknameClaim::seed(nickname)],
+        program_id,
+    )
+    .0
+}
+
+pub fn find_live_leaderboard_address(program_id: &Pubkey, quiz: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[LiveLeaderboard::SEED, quiz.as_ref()], program_id).0
+}
+
+pub fn find_adaptive_bank_address(program_id: &Pubkey, quiz: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[AdaptiveBank::SEED, quiz.as_ref()], program_id).0
+}
+
+pub fn find_reactions_address(program_id: &Pubkey, quiz: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[QuizReactions::SEED, quiz.as_ref()], program_id).0
+}
+
+pub fn find_registry_address(program_id: &Pubkey, shard_index: u32) -> Pubkey {
+    Pubkey::find_program_address(
+        &[QuizRegistry::SEED, &shard_index.to_le_bytes()],
+        program_id,
+    )
+    .0
+}
+
+/// Delegation program PDAs for an account delegated by this program.
+pub struct DelegationAddresses {
+    pub buffer: Pubkey,
+    pub record: Pubkey,
+    pub metadata: Pubkey,
+}
+
+pub fn find_delegation_addresses(program_id: &Pubkey, delegated: &Pubkey) -> DelegationAddresses {
+    DelegationAddresses {
+        buffer: Pubkey::find_program_address(&[b"buffer", delegated.as_ref()], program_id).0,
+        record: Pubkey::find_program_address(
+            &[b"delegation", delegated.as_ref()],
+            &DELEGATION_PROGRAM_ID,
+        )
+        .0,
+        metadata: Pubkey::find_program_address(
+            &[b"delegation-metadata", delegated.as_ref()],
+            &DELEGATION_PROGRAM_ID,
+        )
+        .0,
+    }
+}
+
+fn question_metas(program_id: &Pubkey, quiz: &Pubkey, question_count: u8) -> Vec<AccountMeta> {
+    (0..question_count)
+        .map(|index| {
+            AccountMeta::new_readonly(find_question_address(program_id, quiz, index), false)
+        })
+        .collect()
+}
+
+/// The audit pages up to `new_records` corrections after the quiz's
+/// `correction_count` fall on.
+fn score_audit_metas(
+    program_id: &Pubkey,
+    quiz: &Pubkey,
+    correction_count: u32,
+    new_records: usize,
+) -> Vec<AccountMeta> {
+    if new_records == 0 {
+        return Vec::new();
+    }
+    let per_page = ScoreAudit::MAX_RECORDS as u32;
+    let last = correction_count + new_records as u32 - 1;
+    (correction_count / per_page..=last / per_page)
+        .map(|page| AccountMeta::new(find_score_audit_address(program_id, quiz, page), false))
+        .collect()
+}
+
+fn build(program_id: &Pubkey, accounts: Vec<AccountMeta>, ix: QuizInstruction) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: ix.pack_compact(),
+    }
+}
+
+pub fn initialize_quiz_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz_id: u64,
+    question_count: u8,
+) -> Instruction {
+    initialize_quiz_with_delegation_ix(
+        program_id,
+        host,
+        quiz_id,
+        question_count,
+        DelegationConfig::default(),
+    )
+}
+
+pub fn initialize_quiz_with_delegation_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz_id: u64,
+    question_count: u8,
+    delegation: DelegationConfig,
+) -> Instruction {
+    let quiz = find_quiz_address(program_id, host, quiz_id);
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*host, true),
+            AccountMeta::new(quiz, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        QuizInstruction::InitializeQuiz {
+            question_count,
+            quiz_id,
+            delegation,
+        },
+    )
+}
+
+pub fn add_question_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    question_index: u8,
+    question_text: String,
+    options: [String; 4],
+    correct_answer_index: u8,
+) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*host, true),
+            AccountMeta::new_readonly(*quiz, false),
+            AccountMeta::new(
+                find_question_address(program_id, quiz, question_index),
+                false,
+            ),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        QuizInstruction::AddQuestion {
+            question_index,
+            question_text,
+            options,
+            correct_answer_index,
+        },
+    )
+}
+
+/// Create one question PDA per entry of `questions`, from `first_index` on.
+pub fn import_questions_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    first_index: u8,
+    questions: Vec<QuestionData>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*host, true),
+        AccountMeta::new_readonly(*quiz, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+    for index in (first_index..).take(questions.len()) {
+        accounts.push(AccountMeta::new(
+            find_question_address(program_id, quiz, index),
+            false,
+        ));
+    }
+    build(
+        program_id,
+        accounts,
+        QuizInstruction::ImportQuestions {
+            first_index,
+            questions,
+        },
+    )
+}
+
+/// Ed25519 program instruction verifying `signer`'s `signature` over
+/// `message`, for instructions that take off-chain signatures. It goes
+/// before them in the same transaction.
+pub fn ed25519_signature_ix(signer: &Pubkey, signature: &[u8; 64], message: &[u8]) -> Instruction {
+    let key_at = SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_SIZE;
+    let signature_at = key_at + 32;
+    let message_at = signature_at + 64;
+    let mut data = vec![1, 0];
+    for offset in [
+        signature_at as u16,
+        CURRENT_INSTRUCTION,
+        key_at as u16,
+        CURRENT_INSTRUCTION,
+        message_at as u16,
+        message.len() as u16,
+        CURRENT_INSTRUCTION,
+    ] {
+        data.extend_from_slice(&offset.to_le_bytes());
+    }
+    data.extend_from_slice(signer.as_ref());
+    data.extend_from_slice(signature);
+    data.extend_from_slice(message);
+    Instruction {
+        program_id: ed25519_program::ID,
+        accounts: vec![],
+        data,
+    }
+}
+
+/// Import a pack `curator` signed over `QuestionData::pack_message`. Send
+/// after `ed25519_signature_ix` with the curator's signature.
+pub fn import_signed_questions_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    curator: &Pubkey,
+    first_index: u8,
+    questions: Vec<QuestionData>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*host, true),
+        AccountMeta::new(*quiz, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new_readonly(sysvar::instructions::ID, false),
+    ];
+    for index in (first_index..).take(questions.len()) {
+        accounts.push(AccountMeta::new(
+            find_question_address(program_id, quiz, index),
+            false,
+        ));
+    }
+    build(
+        program_id,
+        accounts,
+        QuizInstruction::ImportSignedQuestions {
+            first_index,
+            curator: *curator,
+            questions,
+        },
+    )
+}
+
+/// Add the `language` version of a question, options in the question's order.
+pub fn add_translation_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    question_index: u8,
+    language: [u8; 2],
+    question_text: String,
+    options: [String; 4],
+) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*host, true),
+            AccountMeta::new_readonly(*quiz, false),
+            AccountMeta::new_readonly(
+                find_question_address(program_id, quiz, question_index),
+                false,
+            ),
+            AccountMeta::new(
+                find_translation_address(program_id, quiz, question_index, language),
+                false,
+            ),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        QuizInstruction::AddTranslation {
+            question_index,
+            language,
+            question_text,
+            options,
+        },
+    )
+}
+
+/// Reveal `index` with answers taken from `open_slot` to `close_slot`.
+pub fn open_question_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    index: u8,
+    open_slot: u64,
+    close_slot: u64,
+) -> Instruction {
+    host_quiz_ix(
+        program_id,
+        host,
+        quiz,
+        QuizInstruction::OpenQuestion {
+            index,
+            open_slot,
+            close_slot,
+        },
+    )
+}
+
+pub fn set_round_multiplier_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    first_question: u8,
+    last_question: u8,
+    multiplier: u8,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*host, true),
+        AccountMeta::new_readonly(*quiz, false),
+    ];
+    accounts.extend(
+        (first_question..=last_question)
+            .map(|index| AccountMeta::new(find_question_address(program_id, quiz, index), false)),
+    );
+    build(
+        program_id,
+        accounts,
+        QuizInstruction::SetRoundMultiplier {
+            first_question,
+            last_question,
+            multiplier,
+        },
+    )
+}
+
+pub fn set_question_difficulty_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    first_question: u8,
+    last_question: u8,
+    difficulty: u8,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*host, true),
+        AccountMeta::new_readonly(*quiz, false),
+    ];
+    accounts.extend(
+        (first_question..=last_question)
+            .map(|index| AccountMeta::new(find_question_address(program_id, quiz, index), false)),
+    );
+    build(
+        program_id,
+        accounts,
+        QuizInstruction::SetQuestionDifficulty {
+            first_question,
+            last_question,
+            difficulty,
+        },
+    )
+}
+
+/// A nonzero `lamport_cost` also passes the quiz vault, creating it if needed.
+pub fn set_hint_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    question_index: u8,
+    hint: String,
+    point_cost: u8,
+    lamport_cost: u64,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*host, true),
+        AccountMeta::new_readonly(*quiz, false),
+        AccountMeta::new_readonly(
+            find_question_address(program_id, quiz, question_index),
+            false,
+        ),
+        AccountMeta::new(find_hint_address(program_id, quiz, question_index), false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+    if lamport_cost > 0 {
+        accounts.push(AccountMeta::new(
+            find_quiz_vault_address(program_id, quiz),
+            false,
+        ));
+    }
+    build(
+        program_id,
+        accounts,
+        QuizInstruction::SetHint {
+            question_index,
+            hint,
+            point_cost,
+            lamport_cost,
+        },
+    )
+}
+
+pub fn set_explanation_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    question_index: u8,
+    explanation: String,
+) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*host, true),
+            AccountMeta::new_readonly(*quiz, false),
+            AccountMeta::new_readonly(
+                find_question_address(program_id, quiz, question_index),
+                false,
+            ),
+            AccountMeta::new(
+                find_explanation_address(program_id, quiz, question_index),
+                false,
+            ),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        QuizInstruction::SetExplanation {
+            question_index,
+            explanation,
+        },
+    )
+}
+
+/// `lamport_cost` must match the hint, a paid hint writes the quiz and pays
+/// into its vault, so it is sent to Solana rather than the rollup.
+pub fn buy_hint_ix(
+    program_id: &Pubkey,
+    player: &Pubkey,
+    quiz: &Pubkey,
+    question_index: u8,
+    lamport_cost: u64,
+) -> Instruction {
+    let paid = lamport_cost > 0;
+    let mut accounts = vec![
+        AccountMeta::new(*player, true),
+        AccountMeta::new(find_player_answer_address(program_id, quiz, player), false),
+        AccountMeta {
+            pubkey: *quiz,
+            is_signer: false,
+            is_writable: paid,
+        },
+        AccountMeta::new_readonly(find_hint_address(program_id, quiz, question_index), false),
+    ];
+    if paid {
+        accounts.push(AccountMeta::new(
+            find_quiz_vault_address(program_id, quiz),
+            false,
+        ));
+        accounts.push(AccountMeta::new_readonly(system_program::ID, false));
+    }
+    build(
+        program_id,
+        accounts,
+        QuizInstruction::BuyHint { question_index },
+    )
+}
+
+fn host_quiz_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    ix: QuizInstruction,
+) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new_readonly(*host, true),
+            AccountMeta::new(*quiz, false),
+        ],
+        ix,
+    )
+}
+
+pub fn start_quiz_ix(program_id: &Pubkey, host: &Pubkey, quiz: &Pubkey) -> Instruction {
+    host_quiz_ix(program_id, host, quiz, QuizInstruction::StartQuiz)
+}
+
+pub fn schedule_start_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    start_slot: u64,
+) -> Instruction {
+    host_quiz_ix(
+        program_id,
+        host,
+        quiz,
+        QuizInstruction::ScheduleStart { start_slot },
+    )
+}
+
+pub fn activate_quiz_ix(program_id: &Pubkey, quiz: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        vec![AccountMeta::new(*quiz, false)],
+        QuizInstruction::ActivateQuiz,
+    )
+}
+
+pub fn cancel_quiz_ix(program_id: &Pubkey, host: &Pubkey, quiz: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*host, true),
+            AccountMeta::new(*quiz, false),
+            AccountMeta::new(find_host_stats_address(program_id, host), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        QuizInstruction::CancelQuiz,
+    )
+}
+
+pub fn close_submissions_ix(program_id: &Pubkey, caller: &Pubkey, quiz: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new_readonly(*caller, true),
+            AccountMeta::new(*quiz, false),
+        ],
+        QuizInstruction::CloseSubmissions,
+    )
+}
+
+/// Size of a system nonce account: version (4) + state (4) + authority (32)
+/// + durable nonce (32) + lamports per signature (8).
+pub const NONCE_ACCOUNT_SIZE: usize = 80;
+
+/// Create and initialize a system nonce account advanced by `authority`,
+/// funded with `lamports` (rent exemption for `NONCE_ACCOUNT_SIZE`). The
+/// nonce account's keypair signs along with `payer`.
+pub fn create_nonce_account_ixs(
+    payer: &Pubkey,
+    nonce_account: &Pubkey,
+    authority: &Pubkey,
+    lamports: u64,
+) -> Vec<Instruction> {
+    system_instruction::create_nonce_account(payer, nonce_account, authority, lamports)
+}
+
+/// The authority and current durable nonce of an initialized nonce
+/// account's data, read by hand as the system program lays it out.
+pub fn read_nonce_account(data: &[u8]) -> Option<(Pubkey, Hash)> {
+    const INITIALIZED: u32 = 1;
+    if data.len() != NONCE_ACCOUNT_SIZE || data[4..8] != INITIALIZED.to_le_bytes() {
+        return None;
+    }
+    let authority = Pubkey::new_from_array(data[8..40].try_into().ok()?);
+    let nonce = Hash::new_from_array(data[40..72].try_into().ok()?);
+    Some((authority, nonce))
+}
+
+/// A message for `instructions` built on `nonce_account`'s durable `nonce`
+/// instead of a recent blockhash, so it can be signed ahead of time and
+/// sent whenever. It first advances the nonce, which makes the signed
+/// transaction single-use, so `nonce_authority` signs it as well as `payer`.
+/// Every pre-signed transaction needs its own nonce account, as sending one
+/// invalidates the others built on the same nonce.
+pub fn durable_nonce_message(
+    instructions: &[Instruction],
+    payer: &Pubkey,
+    nonce_account: &Pubkey,
+    nonce_authority: &Pubkey,
+    nonce: &Hash,
+) -> Message {
+    let mut message = Message::new_with_nonce(
+        instructions.to_vec(),
+        Some(payer),
+        nonce_account,
+        nonce_authority,
+    );
+    message.recent_blockhash = *nonce;
+    message
+}
+
+/// `StartQuiz` for the host to pre-sign, e.g. before going on stage at a
+/// live event, with the host as payer and nonce authority.
+pub fn durable_start_quiz_message(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    nonce_account: &Pubkey,
+    nonce: &Hash,
+) -> Message {
+    durable_nonce_message(
+        &[start_quiz_ix(program_id, host, quiz)],
+        host,
+        nonce_account,
+        host,
+        nonce,
+    )
+}
+
+/// `CloseSubmissions` for the host to pre-sign, ending the quiz whenever
+/// it is sent.
+pub fn durable_close_submissions_message(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    nonce_account: &Pubkey,
+    nonce: &Hash,
+) -> Message {
+    durable_nonce_message(
+        &[close_submissions_ix(program_id, host, quiz)],
+        host,
+        nonce_account,
+        host,
+        nonce,
+    )
+}
+
+pub fn set_submission_policy_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    allow_resubmission: bool,
+    refresh_slot_on_update: bool,
+    deadline_slot: u64,
+    join_grace_slots: u64,
+) -> Instruction {
+    host_quiz_ix(
+        program_id,
+        host,
+        quiz,
+        QuizInstruction::SetSubmissionPolicy {
+            allow_resubmission,
+            refresh_slot_on_update,
+            deadline_slot,
+            join_grace_slots,
+        },
+    )
+}
+
+pub fn set_late_policy_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    late_window_slots: u64,
+    late_slots_per_point: u64,
+) -> Instruction {
+    host_quiz_ix(
+        program_id,
+        host,
+        quiz,
+        QuizInstruction::SetLatePolicy {
+            late_window_slots,
+            late_slots_per_point,
+        },
+    )
+}
+
+pub fn set_confidence_scoring_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    enabled: bool,
+) -> Instruction {
+    host_quiz_ix(
+        program_id,
+        host,
+        quiz,
+        QuizInstruction::SetConfidenceScoring { enabled },
+    )
+}
+
+pub fn set_difficulty_scoring_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    enabled: bool,
+) -> Instruction {
+    host_quiz_ix(
+        program_id,
+        host,
+        quiz,
+        QuizInstruction::SetDifficultyScoring { enabled },
+    )
+}
+
+pub fn set_question_key_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    key_hash: [u8; 32],
+) -> Instruction {
+    host_quiz_ix(
+        program_id,
+        host,
+        quiz,
+        QuizInstruction::SetQuestionKey { key_hash },
+    )
+}
+
+pub fn reveal_questions_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    key: [u8; 32],
+) -> Instruction {
+    host_quiz_ix(
+        program_id,
+        host,
+        quiz,
+        QuizInstruction::RevealQuestions { key },
+    )
+}
+
+pub fn set_question_subset_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    questions_per_player: u8,
+    seed: [u8; 32],
+) -> Instruction {
+    host_quiz_ix(
+        program_id,
+        host,
+        quiz,
+        QuizInstruction::SetQuestionSubset {
+            questions_per_player,
+            seed,
+        },
+    )
+}
+
+/// Passes every question PDA of the quiz, `question_count` of them.
+pub fn reveal_answers_ix(program_id: &Pubkey, quiz: &Pubkey, question_count: u8) -> Instruction {
+    let mut accounts = vec![AccountMeta::new(*quiz, false)];
+    accounts.extend((0..question_count).map(|index| {
+        AccountMeta::new_readonly(find_question_address(program_id, quiz, index), false)
+    }));
+    build(program_id, accounts, QuizInstruction::RevealAnswers)
+}
+
+/// Passes every question PDA of the quiz, `question_count` of them.
+pub fn init_adaptive_bank_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    question_count: u8,
+    questions_per_player: u8,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*host, true),
+        AccountMeta::new_readonly(*quiz, false),
+        AccountMeta::new(find_adaptive_bank_address(program_id, quiz), false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+    accounts.extend((0..question_count).map(|index| {
+        AccountMeta::new_readonly(find_question_address(program_id, quiz, index), false)
+    }));
+    build(
+        program_id,
+        accounts,
+        QuizInstruction::InitAdaptiveBank {
+            questions_per_player,
+        },
+    )
+}
+
+pub fn set_quiz_mode_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    mode: QuizMode,
+    answer_window_slots: u64,
+) -> Instruction {
+    host_quiz_ix(
+        program_id,
+        host,
+        quiz,
+        QuizInstruction::SetQuizMode {
+            mode,
+            answer_window_slots,
+        },
+    )
+}
+
+pub fn reveal_question_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    index: u8,
+) -> Instruction {
+    host_quiz_ix(
+        program_id,
+        host,
+        quiz,
+        QuizInstruction::RevealQuestion { index },
+    )
+}
+
+/// Commits to `question` by its hash, only the hash goes on chain.
+pub fn commit_question_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    question_index: u8,
+    question: &QuestionData,
+) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*host, true),
+            AccountMeta::new_readonly(*quiz, false),
+            AccountMeta::new(
+                find_question_commitment_address(program_id, quiz, question_index),
+                false,
+            ),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        QuizInstruction::CommitQuestion {
+            question_index,
+            question_hash: QuestionCommitment::hash_question(quiz, question_index, question),
+        },
+    )
+}
+
+pub fn reveal_blind_question_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    index: u8,
+    question: QuestionData,
+) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*host, true),
+            AccountMeta::new(*quiz, false),
+            AccountMeta::new(
+                find_question_commitment_address(program_id, quiz, index),
+                false,
+            ),
+            AccountMeta::new(find_question_address(program_id, quiz, index), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        QuizInstruction::RevealBlindQuestion { index, question },
+    )
+}
+
+pub fn set_entry_fee_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    lamports: u64,
+) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*host, true),
+            AccountMeta::new(*quiz, false),
+            AccountMeta::new(find_quiz_vault_address(program_id, quiz), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        QuizInstruction::SetEntryFee { lamports },
+    )
+}
+
+pub fn set_join_stake_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    lamports: u64,
+) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*host, true),
+            AccountMeta::new(*quiz, false),
+            AccountMeta::new(find_quiz_vault_address(program_id, quiz), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        QuizInstruction::SetJoinStake { lamports },
+    )
+}
+
+pub fn reclaim_stake_ix(program_id: &Pubkey, player: &Pubkey, quiz: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*player, true),
+            AccountMeta::new(*quiz, false),
+            AccountMeta::new(find_player_answer_address(program_id, quiz, player), false),
+            AccountMeta::new(find_quiz_vault_address(program_id, quiz), false),
+        ],
+        QuizInstruction::ReclaimStake,
+    )
+}
+
+pub fn contribute_to_prize_pool_ix(
+    program_id: &Pubkey,
+    contributor: &Pubkey,
+    quiz: &Pubkey,
+    lamports: u64,
+) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*contributor, true),
+            AccountMeta::new(*quiz, false),
+            AccountMeta::new(find_quiz_vault_address(program_id, quiz), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(
+                find_prize_contribution_address(program_id, quiz, contributor),
+                false,
+            ),
+        ],
+        QuizInstruction::ContributeToPrizePool { lamports },
+    )
+}
+
+pub fn refund_contribution_ix(
+    program_id: &Pubkey,
+    contributor: &Pubkey,
+    quiz: &Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*contributor, false),
+            AccountMeta::new(*quiz, false),
+            AccountMeta::new(find_quiz_vault_address(program_id, quiz), false),
+            AccountMeta::new(
+                find_prize_contribution_address(program_id, quiz, contributor),
+                false,
+            ),
+        ],
+        QuizInstruction::RefundContribution,
+    )
+}
+
+/// `feed_id` is the Pyth feed players' price updates must come from, usually
+/// `pyth::SOL_USD_FEED_ID`.
+pub fn set_usd_entry_fee_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    cents: u64,
+    feed_id: [u8; 32],
+) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*host, true),
+            AccountMeta::new(*quiz, false),
+            AccountMeta::new(find_quiz_vault_address(program_id, quiz), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        QuizInstruction::SetUsdEntryFee { cents, feed_id },
+    )
+}
+
+/// `entry_fee` and `tournament` must match the quiz session, they decide
+/// which optional accounts are appended. Quizzes that only take a stake
+/// need a nonzero `entry_fee` as well, for the vault. Quizzes priced in USD also need
+/// `with_price_update`. `validator` picks the ephemeral
+/// validator, `None` defers to the quiz's delegation config.
+pub fn delegate_player_ix(
+    program_id: &Pubkey,
+    player: &Pubkey,
+    quiz: &Pubkey,
+    entry_fee: u64,
+    tournament: Option<&Pubkey>,
+    validator: Option<Pubkey>,
+) -> Instruction {
+    let player_answer = find_player_answer_address(program_id, quiz, player);
+    let delegation = find_delegation_addresses(program_id, &player_answer);
+    let mut accounts = vec![
+        AccountMeta::new(*player, true),
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new(player_answer, false),
+        AccountMeta::new_readonly(*program_id, false),
+        AccountMeta::new(delegation.buffer, false),
+        AccountMeta::new(delegation.record, false),
+        AccountMeta::new(delegation.metadata, false),
+        AccountMeta::new_readonly(DELEGATION_PROGRAM_ID, false),
+        AccountMeta::new(*quiz, false),
+    ];
+    accounts.extend(join_optional_metas(program_id, quiz, entry_fee, tournament));
+    build(
+        program_id,
+        accounts,
+        QuizInstruction::DelegatePlayer {
+            validator,
+            referrer: None,
+        },
+    )
+}
+
+/// Join a base layer quiz, the optional accounts work as in
+/// `delegate_player_ix`.
+pub fn join_quiz_ix(
+    program_id: &Pubkey,
+    player: &Pubkey,
+    quiz: &Pubkey,
+    entry_fee: u64,
+    tournament: Option<&Pubkey>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*player, true),
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new(find_player_answer_address(program_id, quiz, player), false),
+        AccountMeta::new(*quiz, false),
+    ];
+    accounts.extend(join_optional_metas(program_id, quiz, entry_fee, tournament));
+    build(
+        program_id,
+        accounts,
+        QuizInstruction::JoinQuiz { referrer: None },
+    )
+}
+
+/// Make the quiz host pay the rent of a `join_quiz_ix` or `delegate_player_ix`
+/// so the player's wallet can stay empty. The host must sign and should pay
+/// the transaction fee as well.
+pub fn sponsored_by(mut ix: Instruction, host: &Pubkey) -> Instruction {
+    ix.accounts.push(AccountMeta::new(*host, true));
+    ix
+}
+
+/// Credit `referrer` with a `join_quiz_ix` or `delegate_player_ix` player, so
+/// they are owed the quiz's referral cut of the entry fee. Other instructions
+/// are returned unchanged.
+pub fn referred_by(mut ix: Instruction, referrer: &Pubkey) -> Instruction {
+    let referrer = Some(*referrer);
+    let referred = match QuizInstruction::unpack(&ix.data) {
+        Ok(QuizInstruction::JoinQuiz { .. }) => QuizInstruction::JoinQuiz { referrer },
+        Ok(QuizInstruction::DelegatePlayer { validator, .. }) => QuizInstruction::DelegatePlayer {
+            validator,
+            referrer,
+        },
+        _ => return ix,
+    };
+    ix.data = referred.pack_compact();
+    ix
+}
+
+/// Pass the Pyth `price_update` converting the entry fee of a quiz priced in
+/// USD to a `join_quiz_ix` or `delegate_player_ix`, adding the quiz vault if
+/// it was built without one. Other instructions are returned unchanged.
+pub fn with_price_update(
+    program_id: &Pubkey,
+    quiz: &Pubkey,
+    price_update: &Pubkey,
+    mut ix: Instruction,
+) -> Instruction {
+    if !matches!(
+        QuizInstruction::unpack(&ix.data),
+        Ok(QuizInstruction::JoinQuiz { .. } | QuizInstruction::DelegatePlayer { .. })
+    ) {
+        return ix;
+    }
+    let vault = find_quiz_vault_address(program_id, quiz);
+    let price_update = AccountMeta::new_readonly(*price_update, false);
+    match ix.accounts.iter().position(|meta| meta.pubkey == vault) {
+        Some(index) => ix.accounts.insert(index + 1, price_update),
+        None => {
+            // The vault follows the quiz and its event log
+            let event_log = find_event_log_address(program_id, quiz);
+            let index = ix
+                .accounts
+                .iter()
+                .rposition(|meta| meta.pubkey == *quiz || meta.pubkey == event_log)
+                .map_or(ix.accounts.len(), |index| index + 1);
+            ix.accounts
+                .splice(index..index, [AccountMeta::new(vault, false), price_update]);
+        }
+    }
+    ix
+}
+
+fn join_optional_metas(
+    program_id: &Pubkey,
+    quiz: &Pubkey,
+    entry_fee: u64,
+    tournament: Option<&Pubkey>,
+) -> Vec<AccountMeta> {
+    let mut accounts = Vec::new();
+    if entry_fee > 0 {
+        accounts.push(AccountMeta::new(
+            find_quiz_vault_address(program_id, quiz),
+            false,
+        ));
+    }
+    if let Some(tournament) = tournament {
+        accounts.push(AccountMeta::new_readonly(*tournament, false));
+    }
+    accounts
+}
+
+fn answer_sheet_metas(program_id: &Pubkey, player: &Pubkey, quiz: &Pubkey) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new_readonly(*player, true),
+        AccountMeta::new(find_player_answer_address(program_id, quiz, player), false),
+        AccountMeta::new_readonly(*quiz, false),
+    ]
+}
+
+pub fn submit_answers_ix(
+    program_id: &Pubkey,
+    player: &Pubkey,
+    quiz: &Pubkey,
+    answers: Vec<u8>,
+) -> Instruction {
+    submit_weighted_answers_ix(program_id, player, quiz, answers, Vec::new())
+}
+
+/// For confidence-weighted quizzes, `confidence` holds 1-3 per answer.
+pub fn submit_weighted_answers_ix(
+    program_id: &Pubkey,
+    player: &Pubkey,
+    quiz: &Pubkey,
+    answers: Vec<u8>,
+    confidence: Vec<u8>,
+) -> Instruction {
+    build(
+        program_id,
+        answer_sheet_metas(program_id, player, quiz),
+        QuizInstruction::SubmitAnswers {
+            answers,
+            confidence,
+        },
+    )
+}
+
+/// Send answers `player` signed over `PlayerAnswer::relay_message`, with
+/// `relayer` paying. Goes after `ed25519_signature_ix` with the player's
+/// signature.
+pub fn submit_relayed_answers_ix(
+    program_id: &Pubkey,
+    relayer: &Pubkey,
+    player: &Pubkey,
+    quiz: &Pubkey,
+    answers: Vec<u8>,
+    confidence: Vec<u8>,
+    nonce: u64,
+) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new_readonly(*relayer, true),
+            AccountMeta::new(find_player_answer_address(program_id, quiz, player), false),
+            AccountMeta::new_readonly(*quiz, false),
+            AccountMeta::new_readonly(sysvar::instructions::ID, false),
+        ],
+        QuizInstruction::SubmitRelayedAnswers {
+            answers,
+            confidence,
+            nonce,
+        },
+    )
+}
+
+pub fn update_answers_ix(
+    program_id: &Pubkey,
+    player: &Pubkey,
+    quiz: &Pubkey,
+    answers: Vec<u8>,
+) -> Instruction {
+    update_weighted_answers_ix(program_id, player, quiz, answers, Vec::new())
+}
+
+pub fn update_weighted_answers_ix(
+    program_id: &Pubkey,
+    player: &Pubkey,
+    quiz: &Pubkey,
+    answers: Vec<u8>,
+    confidence: Vec<u8>,
+) -> Instruction {
+    build(
+        program_id,
+        answer_sheet_metas(program_id, player, quiz),
+        QuizInstruction::UpdateAnswers {
+            answers,
+            confidence,
+        },
+    )
+}
+
+/// Elimination quizzes also need the question account to check the answer.
+pub fn submit_answer_ix(
+    program_id: &Pubkey,
+    player: &Pubkey,
+    quiz: &Pubkey,
+    question_index: u8,
+    answer: u8,
+    elimination: bool,
+) -> Instruction {
+    let mut accounts = answer_sheet_metas(program_id, player, quiz);
+    if elimination {
+        accounts.push(AccountMeta::new_readonly(
+            find_question_address(program_id, quiz, question_index),
+            false,
+        ));
+    }
+    build(
+        program_id,
+        accounts,
+        QuizInstruction::SubmitAnswer {
+            question_index,
+            answer,
+        },
+    )
+}
+
+pub fn create_session_ix(
+    program_id: &Pubkey,
+    player: &Pubkey,
+    quiz: &Pubkey,
+    session_key: &Pubkey,
+    expires_slot: u64,
+    lamports: u64,
+) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*player, true),
+            AccountMeta::new_readonly(*quiz, false),
+            AccountMeta::new(find_answer_session_address(program_id, quiz, player), false),
+            AccountMeta::new(*session_key, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        QuizInstruction::CreateSession {
+            expires_slot,
+            lamports,
+        },
+    )
+}
+
+/// Turn a `submit_answers_ix`, `update_answers_ix` or `submit_answer_ix` into
+/// one signed by the player's session key instead of the player.
+pub fn sign_with_session(
+    program_id: &Pubkey,
+    mut ix: Instruction,
+    session_key: &Pubkey,
+) -> Instruction {
+    let player = ix.accounts[0].pubkey;
+    let quiz = ix.accounts[2].pubkey;
+    ix.accounts[0] = AccountMeta::new_readonly(*session_key, true);
+    ix.accounts.push(AccountMeta::new_readonly(
+        find_answer_session_address(program_id, &quiz, &player),
+        false,
+    ));
+    ix
+}
+
+fn commit_ix(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    quiz: &Pubkey,
+    players: &[Pubkey],
+    ix: QuizInstruction,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(*quiz, false),
+        AccountMeta::new_readonly(MAGIC_PROGRAM_ID, false),
+        AccountMeta::new(MAGIC_CONTEXT_ID, false),
+    ];
+    accounts.extend(players.iter().map(|player| {
+        AccountMeta::new(find_player_answer_address(program_id, quiz, player), false)
+    }));
+    build(program_id, accounts, ix)
+}
+
+/// Commit the answer sheets of `players`, sent to the ephemeral rollup.
+pub fn commit_answers_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    players: &[Pubkey],
+) -> Instruction {
+    commit_ix(
+        program_id,
+        host,
+        quiz,
+        players,
+        QuizInstruction::CommitAnswers,
+    )
+}
+
+/// Commit and undelegate the answer sheets of `players`, sent to the
+/// ephemeral rollup once the quiz is over. `payer` is the host, or anyone
+/// after the quiz deadline.
+pub fn commit_and_undelegate_answers_ix(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    quiz: &Pubkey,
+    players: &[Pubkey],
+) -> Instruction {
+    commit_ix(
+        program_id,
+        payer,
+        quiz,
+        players,
+        QuizInstruction::CommitAndUndelegateAnswers,
+    )
+}
+
+/// Delegate a started quiz session to the ephemeral rollup. `validator` is
+/// handled as in `delegate_player_ix`.
+pub fn delegate_quiz_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    validator: Option<Pubkey>,
+) -> Instruction {
+    let delegation = find_delegation_addresses(program_id, quiz);
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*host, true),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(*quiz, false),
+            AccountMeta::new_readonly(*program_id, false),
+            AccountMeta::new(delegation.buffer, false),
+            AccountMeta::new(delegation.record, false),
+            AccountMeta::new(delegation.metadata, false),
+            AccountMeta::new_readonly(DELEGATION_PROGRAM_ID, false),
+        ],
+        QuizInstruction::DelegateQuiz { validator },
+    )
+}
+
+/// Commit and undelegate a delegated quiz session, sent to the ephemeral
+/// rollup before scoring on chain.
+pub fn commit_and_undelegate_quiz_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*host, true),
+            AccountMeta::new(*quiz, false),
+            AccountMeta::new_readonly(MAGIC_PROGRAM_ID, false),
+            AccountMeta::new(MAGIC_CONTEXT_ID, false),
+        ],
+        QuizInstruction::CommitAndUndelegateQuiz,
+    )
+}
+
+/// Create and delegate the scoreboard once players have joined. `validator`
+/// is handled as in `delegate_player_ix`.
+pub fn delegate_scoreboard_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    validator: Option<Pubkey>,
+) -> Instruction {
+    let scoreboard = find_scoreboard_address(program_id, quiz);
+    let delegation = find_delegation_addresses(program_id, &scoreboard);
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*host, true),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(*quiz, false),
+            AccountMeta::new(scoreboard, false),
+            AccountMeta::new_readonly(*program_id, false),
+            AccountMeta::new(delegation.buffer, false),
+            AccountMeta::new(delegation.record, false),
+            AccountMeta::new(delegation.metadata, false),
+            AccountMeta::new_readonly(DELEGATION_PROGRAM_ID, false),
+        ],
+        QuizInstruction::DelegateScoreboard { validator },
+    )
+}
+
+/// Grade the answer sheets of `players` into the scoreboard, sent to the
+/// ephemeral rollup.
+pub fn grade_answers_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    question_count: u8,
+    players: &[Pubkey],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*host, true),
+        AccountMeta::new_readonly(*quiz, false),
+        AccountMeta::new(find_scoreboard_address(program_id, quiz), false),
+    ];
+    accounts.extend(question_metas(program_id, quiz, question_count));
+    accounts.extend(players.iter().map(|player| {
+        AccountMeta::new_readonly(find_player_answer_address(program_id, quiz, player), false)
+    }));
+    build(program_id, accounts, QuizInstruction::GradeAnswers)
+}
+
+/// Finalize and commit the scoreboard, sent to the ephemeral rollup.
+/// Create the live leaderboard, delegated unless the quiz plays on the base
+/// layer.
+pub fn init_live_leaderboard_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    base_layer: bool,
+    validator: Option<Pubkey>,
+) -> Instruction {
+    let leaderboard = find_live_leaderboard_address(program_id, quiz);
+    let mut accounts = vec![
+        AccountMeta::new(*host, true),
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new(*quiz, false),
+        AccountMeta::new(leaderboard, false),
+    ];
+    if !base_layer {
+        let delegation = find_delegation_addresses(program_id, &leaderboard);
+        accounts.extend([
+            AccountMeta::new_readonly(*program_id, false),
+            AccountMeta::new(delegation.buffer, false),
+            AccountMeta::new(delegation.record, false),
+            AccountMeta::new(delegation.metadata, false),
+            AccountMeta::new_readonly(DELEGATION_PROGRAM_ID, false),
+        ]);
+    }
+    build(
+        program_id,
+        accounts,
+        QuizInstruction::InitLiveLeaderboard { validator },
+    )
+}
+
+/// Pass the adaptive bank and the answered question to a `SubmitAnswer`, as
+/// adaptive quizzes require. Add the live leaderboard and any session account
+/// after.
+pub fn with_adaptive_bank(program_id: &Pubkey, quiz: &Pubkey, mut ix: Instruction) -> Instruction {
+    let Ok(QuizInstruction::SubmitAnswer { question_index, .. }) =
+        QuizInstruction::unpack(&ix.data)
+    else {
+        return ix;
+    };
+    ix.accounts.extend([
+        AccountMeta::new_readonly(find_adaptive_bank_address(program_id, quiz), false),
+        AccountMeta::new_readonly(
+            find_question_address(program_id, quiz, question_index),
+            false,
+        ),
+    ]);
+    ix
+}
+
+/// Pass the live leaderboard and the answered question to a `SubmitAnswer`,
+/// as quizzes with a live leaderboard require. Add any session account after.
+pub fn with_live_leaderboard(
+    program_id: &Pubkey,
+    quiz: &Pubkey,
+    mut ix: Instruction,
+) -> Instruction {
+    let Ok(QuizInstruction::SubmitAnswer { question_index, .. }) =
+        QuizInstruction::unpack(&ix.data)
+    else {
+        return ix;
+    };
+    ix.accounts.extend([
+        AccountMeta::new(find_live_leaderboard_address(program_id, quiz), false),
+        AccountMeta::new_readonly(
+            find_question_address(program_id, quiz, question_index),
+            false,
+        ),
+    ]);
+    ix
+}
+
+pub fn commit_live_leaderboard_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*host, true),
+            AccountMeta::new_readonly(*quiz, false),
+            AccountMeta::new(find_live_leaderboard_address(program_id, quiz), false),
+            AccountMeta::new_readonly(MAGIC_PROGRAM_ID, false),
+            AccountMeta::new(MAGIC_CONTEXT_ID, false),
+        ],
+        QuizInstruction::CommitLiveLeaderboard,
+    )
+}
+
+/// Open reactions for the quiz, delegated unless the quiz plays on the base
+/// layer.
+pub fn init_reactions_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    base_layer: bool,
+    cooldown_slots: u64,
+    validator: Option<Pubkey>,
+) -> Instruction {
+    let reactions = find_reactions_address(program_id, quiz);
+    let mut accounts = vec![
+        AccountMeta::new(*host, true),
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new_readonly(*quiz, false),
+        AccountMeta::new(reactions, false),
+    ];
+    if !base_layer {
+        let delegation = find_delegation_addresses(program_id, &reactions);
+        accounts.extend([
+            AccountMeta::new_readonly(*program_id, false),
+            AccountMeta::new(delegation.buffer, false),
+            AccountMeta::new(delegation.record, false),
+            AccountMeta::new(delegation.metadata, false),
+            AccountMeta::new_readonly(DELEGATION_PROGRAM_ID, false),
+        ]);
+    }
+    build(
+        program_id,
+        accounts,
+        QuizInstruction::InitReactions {
+            cooldown_slots,
+            validator,
+        },
+    )
+}
+
+pub fn send_reaction_ix(
+    program_id: &Pubkey,
+    player: &Pubkey,
+    quiz: &Pubkey,
+    emoji_code: u8,
+) -> Instruction {
+    let mut accounts = answer_sheet_metas(program_id, player, quiz);
+    accounts.push(AccountMeta::new(
+        find_reactions_address(program_id, quiz),
+        false,
+    ));
+    build(
+        program_id,
+        accounts,
+        QuizInstruction::SendReaction { emoji_code },
+    )
+}
+
+pub fn commit_reactions_ix(program_id: &Pubkey, host: &Pubkey, quiz: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*host, true),
+            AccountMeta::new_readonly(*quiz, false),
+            AccountMeta::new(find_reactions_address(program_id, quiz), false),
+            AccountMeta::new_readonly(MAGIC_PROGRAM_ID, false),
+            AccountMeta::new(MAGIC_CONTEXT_ID, false),
+        ],
+        QuizInstruction::CommitReactions,
+    )
+}
+
+pub fn commit_scoreboard_ix(program_id: &Pubkey, host: &Pubkey, quiz: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*host, true),
+            AccountMeta::new_readonly(*quiz, false),
+            AccountMeta::new(find_scoreboard_address(program_id, quiz), false),
+            AccountMeta::new_readonly(MAGIC_PROGRAM_ID, false),
+            AccountMeta::new(MAGIC_CONTEXT_ID, false),
+        ],
+        QuizInstruction::CommitScoreboard,
+    )
+}
+
+pub fn calculate_scores_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    question_count: u8,
+    players: &[Pubkey],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*host, true),
+        AccountMeta::new(*quiz, false),
+        AccountMeta::new(find_host_stats_address(program_id, host), false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+    let mut questions = question_metas(program_id, quiz, question_count);
+    for question in &mut questions {
+        question.is_writable = true; // buzzer winners are written back
+    }
+    accounts.extend(questions);
+    for player in players {
+        accounts.push(AccountMeta::new_readonly(
+            find_player_answer_address(program_id, quiz, player),
+            false,
+        ));
+        accounts.push(AccountMeta::new(
+            find_player_score_address(program_id, quiz, player),
+            false,
+        ));
+        accounts.push(AccountMeta::new_readonly(system_program::ID, false));
+    }
+    build(program_id, accounts, QuizInstruction::CalculateScores)
+}
+
+/// Make a `calculate_scores_ix` also tally how the players answered each
+/// question into its `QuestionStats` PDA. The host pays the rent.
+pub fn with_question_stats(
+    program_id: &Pubkey,
+    mut ix: Instruction,
+    question_count: u8,
+) -> Instruction {
+    let quiz = ix.accounts[1].pubkey;
+    ix.accounts
+        .push(AccountMeta::new_readonly(system_program::ID, false));
+    ix.accounts.extend((0..question_count).map(|index| {
+        AccountMeta::new(find_question_stats_address(program_id, &quiz, index), false)
+    }));
+    ix
+}
+
+/// `players` must list every player of the quiz.
+pub fn compute_analytics_ix(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    quiz: &Pubkey,
+    question_count: u8,
+    players: &[Pubkey],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(*quiz, false),
+        AccountMeta::new(find_quiz_analytics_address(program_id, quiz), false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+    accounts.extend(players.iter().map(|player| {
+        AccountMeta::new_readonly(find_player_score_address(program_id, quiz, player), false)
+    }));
+    for index in 0..question_count {
+        accounts.push(AccountMeta::new_readonly(
+            find_question_address(program_id, quiz, index),
+            false,
+        ));
+        accounts.push(AccountMeta::new_readonly(
+            find_question_stats_address(program_id, quiz, index),
+            false,
+        ));
+    }
+    build(program_id, accounts, QuizInstruction::ComputeAnalytics)
+}
+
+pub fn set_dispute_window_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    slots: u64,
+) -> Instruction {
+    host_quiz_ix(
+        program_id,
+        host,
+        quiz,
+        QuizInstruction::SetDisputeWindow { slots },
+    )
+}
+
+/// `reason_code` is one of the `QuestionDispute::REASON_*` codes.
+pub fn flag_question_ix(
+    program_id: &Pubkey,
+    player: &Pubkey,
+    quiz: &Pubkey,
+    question_index: u8,
+    reason_code: u8,
+) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*player, true),
+            AccountMeta::new_readonly(*quiz, false),
+            AccountMeta::new_readonly(find_player_score_address(program_id, quiz, player), false),
+            AccountMeta::new(
+                find_question_dispute_address(program_id, quiz, question_index),
+                false,
+            ),
+            AccountMeta::new(
+                find_dispute_flag_address(program_id, quiz, question_index, player),
+                false,
+            ),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        QuizInstruction::FlagQuestion {
+            question_index,
+            reason_code,
+        },
+    )
+}
+
+pub fn void_question_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    question_index: u8,
+    mode: VoidMode,
+) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*host, true),
+            AccountMeta::new_readonly(*quiz, false),
+            AccountMeta::new(
+                find_question_address(program_id, quiz, question_index),
+                false,
+            ),
+            AccountMeta::new(find_host_stats_address(program_id, host), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        QuizInstruction::VoidQuestion {
+            question_index,
+            mode,
+        },
+    )
+}
+
+/// `players` must list every player of the quiz, `correction_count` is the
+/// quiz's current `correction_count`.
+pub fn regrade_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    question_count: u8,
+    players: &[Pubkey],
+    correction_count: u32,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*host, true),
+        AccountMeta::new(*quiz, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+    accounts.extend(question_metas(program_id, quiz, question_count));
+    for player in players {
+        accounts.push(AccountMeta::new_readonly(
+            find_player_answer_address(program_id, quiz, player),
+            false,
+        ));
+        accounts.push(AccountMeta::new(
+            find_player_score_address(program_id, quiz, player),
+            false,
+        ));
+    }
+    accounts.extend(score_audit_metas(
+        program_id,
+        quiz,
+        correction_count,
+        players.len(),
+    ));
+    build(program_id, accounts, QuizInstruction::Regrade)
+}
+
+pub fn init_event_log_ix(program_id: &Pubkey, host: &Pubkey, quiz: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*host, true),
+            AccountMeta::new(*quiz, false),
+            AccountMeta::new(find_event_log_address(program_id, quiz), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        QuizInstruction::InitEventLog,
+    )
+}
+
+/// Pass the event log of `quiz` to an instruction that logs, as quizzes
+/// with a log require. Other instructions are returned unchanged.
+pub fn with_event_log(program_id: &Pubkey, quiz: &Pubkey, mut ix: Instruction) -> Instruction {
+    let index = match QuizInstruction::unpack(&ix.data) {
+        Ok(
+            QuizInstruction::RevealQuestion { .. }
+            | QuizInstruction::OpenQuestion { .. }
+            | QuizInstruction::PostLeaderboardRoot { .. },
+        ) => 2,
+        Ok(
+            QuizInstruction::SubmitAnswers { .. }
+            | QuizInstruction::UpdateAnswers { .. }
+            | QuizInstruction::SubmitAnswer { .. },
+        ) => 3,
+        Ok(
+            QuizInstruction::JoinQuiz { .. }
+            | QuizInstruction::CalculateScores
+            | QuizInstruction::SubmitRelayedAnswers { .. },
+        ) => 4,
+        Ok(QuizInstruction::PracticeQuiz { .. }) => 5,
+        _ => return ix,
+    };
+    ix.accounts.insert(
+        index,
+        AccountMeta::new(find_event_log_address(program_id, quiz), false),
+    );
+    ix
+}
+
+pub fn set_prize_split_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    split_bps: [u16; QuizSession::PRIZE_RANKS],
+) -> Instruction {
+    host_quiz_ix(
+        program_id,
+        host,
+        quiz,
+        QuizInstruction::SetPrizeSplit { split_bps },
+    )
+}
+
+pub fn set_host_play_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    policy: HostPlay,
+) -> Instruction {
+    host_quiz_ix(
+        program_id,
+        host,
+        quiz,
+        QuizInstruction::SetHostPlay { policy },
+    )
+}
+
+pub fn set_scoring_mode_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    mode: ScoringMode,
+) -> Instruction {
+    host_quiz_ix(
+        program_id,
+        host,
+        quiz,
+        QuizInstruction::SetScoringMode { mode },
+    )
+}
+
+pub fn set_charity_split_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    charity: &Pubkey,
+    bps: u16,
+) -> Instruction {
+    host_quiz_ix(
+        program_id,
+        host,
+        quiz,
+        QuizInstruction::SetCharitySplit {
+            charity: *charity,
+            bps,
+        },
+    )
+}
+
+pub fn pay_charity_ix(program_id: &Pubkey, quiz: &Pubkey, charity: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*quiz, false),
+            AccountMeta::new(find_quiz_vault_address(program_id, quiz), false),
+            AccountMeta::new(*charity, false),
+        ],
+        QuizInstruction::PayCharity,
+    )
+}
+
+pub fn set_referral_cut_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    bps: u16,
+) -> Instruction {
+    host_quiz_ix(
+        program_id,
+        host,
+        quiz,
+        QuizInstruction::SetReferralCut { bps },
+    )
+}
+
+pub fn set_claim_window_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    slots: u64,
+) -> Instruction {
+    host_quiz_ix(
+        program_id,
+        host,
+        quiz,
+        QuizInstruction::SetClaimWindow { slots },
+    )
+}
+
+/// Sweep what's left in the vault of `quiz` to `recipient`.
+pub fn sweep_unclaimed_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    recipient: &Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new_readonly(*host, true),
+            AccountMeta::new(*quiz, false),
+            AccountMeta::new(find_quiz_vault_address(program_id, quiz), false),
+            AccountMeta::new(*recipient, false),
+        ],
+        QuizInstruction::SweepUnclaimed,
+    )
+}
+
+pub fn set_prize_vesting_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    min_lamports: u64,
+    slots: u64,
+) -> Instruction {
+    host_quiz_ix(
+        program_id,
+        host,
+        quiz,
+        QuizInstruction::SetPrizeVesting {
+            min_lamports,
+            slots,
+        },
+    )
+}
+
+pub fn claim_vested_ix(program_id: &Pubkey, player: &Pubkey, quiz: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*player, true),
+            AccountMeta::new(*quiz, false),
+            AccountMeta::new(find_quiz_vault_address(program_id, quiz), false),
+            AccountMeta::new(
+                find_leaderboard_claim_address(program_id, quiz, player),
+                false,
+            ),
+        ],
+        QuizInstruction::ClaimVested,
+    )
+}
+
+pub fn set_prize_threshold_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    min_score: u8,
+    min_score_bps: u16,
+    remainder: PrizeRemainder,
+) -> Instruction {
+    host_quiz_ix(
+        program_id,
+        host,
+        quiz,
+        QuizInstruction::SetPrizeThreshold {
+            min_score,
+            min_score_bps,
+            remainder,
+        },
+    )
+}
+
+/// Forfeit the prize of `rank`. `player`, `score` and `proof` are the
+/// standing at that rank, and are ignored for ranks beyond the player count.
+pub fn forfeit_prize_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    rank: u32,
+    player: Pubkey,
+    score: u32,
+    proof: Vec<[u8; 32]>,
+) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*quiz, false),
+            AccountMeta::new(find_quiz_vault_address(program_id, quiz), false),
+            AccountMeta::new(*host, false),
+        ],
+        QuizInstruction::ForfeitPrize {
+            rank,
+            player,
+            score,
+            proof,
+        },
+    )
+}
+
+pub fn set_proportional_prizes_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    enabled: bool,
+) -> Instruction {
+    host_quiz_ix(
+        program_id,
+        host,
+        quiz,
+        QuizInstruction::SetProportionalPrizes { enabled },
+    )
+}
+
+pub fn claim_score_prize_ix(program_id: &Pubkey, player: &Pubkey, quiz: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*player, true),
+            AccountMeta::new(*quiz, false),
+            AccountMeta::new(find_quiz_vault_address(program_id, quiz), false),
+            AccountMeta::new_readonly(find_player_score_address(program_id, quiz, player), false),
+            AccountMeta::new(
+                find_leaderboard_claim_address(program_id, quiz, player),
+                false,
+            ),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        QuizInstruction::ClaimScorePrize,
+    )
+}
+
+/// Pay `referrer` the cut of `player`'s entry fee.
+pub fn pay_referral_ix(
+    program_id: &Pubkey,
+    referrer: &Pubkey,
+    quiz: &Pubkey,
+    player: &Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*referrer, false),
+            AccountMeta::new_readonly(*quiz, false),
+            AccountMeta::new(find_player_answer_address(program_id, quiz, player), false),
+            AccountMeta::new(find_quiz_vault_address(program_id, quiz), false),
+        ],
+        QuizInstruction::PayReferral,
+    )
+}
+
+/// `root` is `merkle::root` over the `merkle::leaderboard_leaf` of every
+/// player.
+pub fn post_leaderboard_root_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    root: [u8; 32],
+) -> Instruction {
+    host_quiz_ix(
+        program_id,
+        host,
+        quiz,
+        QuizInstruction::PostLeaderboardRoot { root },
+    )
+}
+
+pub fn find_scorer_bond_address(program_id: &Pubkey, quiz: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[QuizSession::SCORER_BOND_SEED, quiz.as_ref()], program_id).0
+}
+
+pub fn appoint_scorer_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    scorer: &Pubkey,
+    bond_lamports: u64,
+) -> Instruction {
+    host_quiz_ix(
+        program_id,
+        host,
+        quiz,
+        QuizInstruction::AppointScorer {
+            scorer: *scorer,
+            bond_lamports,
+        },
+    )
+}
+
+/// `post_leaderboard_root_ix` for an appointed scorer, who deposits the bond.
+pub fn post_bonded_root_ix(
+    program_id: &Pubkey,
+    scorer: &Pubkey,
+    quiz: &Pubkey,
+    root: [u8; 32],
+) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*scorer, true),
+            AccountMeta::new(*quiz, false),
+            AccountMeta::new(find_scorer_bond_address(program_id, quiz), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        QuizInstruction::PostLeaderboardRoot { root },
+    )
+}
+
+/// `proof` is `merkle::proof` for the posted leaf of `player`.
+#[allow(clippy::too_many_arguments)]
+pub fn challenge_score_ix(
+    program_id: &Pubkey,
+    challenger: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    player: &Pubkey,
+    question_count: u8,
+    score: u32,
+    rank: u32,
+    proof: Vec<[u8; 32]>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*challenger, true),
+        AccountMeta::new(*quiz, false),
+        AccountMeta::new_readonly(find_player_answer_address(program_id, quiz, player), false),
+        AccountMeta::new(find_scorer_bond_address(program_id, quiz), false),
+        AccountMeta::new(find_host_stats_address(program_id, host), false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+    accounts.extend(question_metas(program_id, quiz, question_count));
+    build(
+        program_id,
+        accounts,
+        QuizInstruction::ChallengeScore { score, rank, proof },
+    )
+}
+
+pub fn challenge_missing_sheet_ix(
+    program_id: &Pubkey,
+    challenger: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    standing: PostedStanding,
+) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*challenger, true),
+            AccountMeta::new(*quiz, false),
+            AccountMeta::new_readonly(
+                find_player_answer_address(program_id, quiz, &standing.player),
+                false,
+            ),
+            AccountMeta::new_readonly(
+                find_compressed_sheet_address(program_id, quiz, &standing.player),
+                false,
+            ),
+            AccountMeta::new(find_scorer_bond_address(program_id, quiz), false),
+            AccountMeta::new(find_host_stats_address(program_id, host), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        QuizInstruction::ChallengeMissingSheet { standing },
+    )
+}
+
+pub fn challenge_ranks_ix(
+    program_id: &Pubkey,
+    challenger: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    first: PostedStanding,
+    second: PostedStanding,
+) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*challenger, true),
+            AccountMeta::new(*quiz, false),
+            AccountMeta::new(find_scorer_bond_address(program_id, quiz), false),
+            AccountMeta::new(find_host_stats_address(program_id, host), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        QuizInstruction::ChallengeRanks { first, second },
+    )
+}
+
+pub fn release_bond_ix(program_id: &Pubkey, scorer: &Pubkey, quiz: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*scorer, true),
+            AccountMeta::new_readonly(*quiz, false),
+            AccountMeta::new(find_scorer_bond_address(program_id, quiz), false),
+        ],
+        QuizInstruction::ReleaseBond,
+    )
+}
+
+/// Copy the settings and questions of the host's completed `source_quiz`
+/// into a new draft quiz at `quiz_id`.
+pub fn clone_quiz_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    source_quiz: &Pubkey,
+    quiz_id: u64,
+    question_count: u8,
+) -> Instruction {
+    let quiz = find_quiz_address(program_id, host, quiz_id);
+    let mut accounts = vec![
+        AccountMeta::new(*host, true),
+        AccountMeta::new_readonly(*source_quiz, false),
+        AccountMeta::new(quiz, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+    for index in 0..question_count {
+        accounts.push(AccountMeta::new_readonly(
+            find_question_address(program_id, source_quiz, index),
+            false,
+        ));
+        accounts.push(AccountMeta::new(
+            find_question_address(program_id, &quiz, index),
+            false,
+        ));
+    }
+    build(program_id, accounts, QuizInstruction::CloneQuiz { quiz_id })
+}
+
+/// Publish the author's `source_quiz` as template `template_id`.
+pub fn create_template_ix(
+    program_id: &Pubkey,
+    author: &Pubkey,
+    source_quiz: &Pubkey,
+    question_count: u8,
+    template_id: u64,
+    title: String,
+    public: bool,
+) -> Instruction {
+    let template = find_template_address(program_id, author, template_id);
+    let mut accounts = vec![
+        AccountMeta::new(*author, true),
+        AccountMeta::new_readonly(*source_quiz, false),
+        AccountMeta::new(template, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+    for index in 0..question_count {
+        accounts.push(AccountMeta::new_readonly(
+            find_question_address(program_id, source_quiz, index),
+            false,
+        ));
+        accounts.push(AccountMeta::new(
+            find_template_question_address(program_id, &template, index),
+            false,
+        ));
+    }
+    build(
+        program_id,
+        accounts,
+        QuizInstruction::CreateTemplate {
+            template_id,
+            title,
+            public,
+        },
+    )
+}
+
+pub fn set_template_access_ix(
+    program_id: &Pubkey,
+    author: &Pubkey,
+    template: &Pubkey,
+    public: bool,
+    allowed: Vec<Pubkey>,
+) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new_readonly(*author, true),
+            AccountMeta::new(*template, false),
+        ],
+        QuizInstruction::SetTemplateAccess { public, allowed },
+    )
+}
+
+/// Create quiz `quiz_id` of `host` from `template`.
+pub fn create_from_template_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    template: &Pubkey,
+    question_count: u8,
+    quiz_id: u64,
+    delegation: DelegationConfig,
+) -> Instruction {
+    let quiz = find_quiz_address(program_id, host, quiz_id);
+    let mut accounts = vec![
+        AccountMeta::new(*host, true),
+        AccountMeta::new(*template, false),
+        AccountMeta::new(quiz, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+    for index in 0..question_count {
+        accounts.push(AccountMeta::new_readonly(
+            find_template_question_address(program_id, template, index),
+            false,
+        ));
+        accounts.push(AccountMeta::new(
+            find_question_address(program_id, &quiz, index),
+            false,
+        ));
+    }
+    build(
+        program_id,
+        accounts,
+        QuizInstruction::CreateFromTemplate {
+            quiz_id,
+            delegation,
+        },
+    )
+}
+
+pub fn close_questions_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    question_count: u8,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*host, true),
+        AccountMeta::new_readonly(*quiz, false),
+        AccountMeta::new_readonly(find_answer_tree_address(program_id, quiz), false),
+    ];
+    accounts.extend(
+        (0..question_count)
+            .map(|index| AccountMeta::new(find_question_address(program_id, quiz, index), false)),
+    );
+    build(program_id, accounts, QuizInstruction::CloseQuestions)
+}
+
+/// Add `lamports` to a program-owned `account`, which must end up
+/// rent-exempt.
+pub fn top_up_rent_ix(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    account: &Pubkey,
+    lamports: u64,
+) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(*account, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        QuizInstruction::TopUpRent { lamports },
+    )
+}
+
+/// Move a score written while scores were a u8 to the current layout.
+pub fn migrate_player_score_ix(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    player_score: &Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(*player_score, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        QuizInstruction::MigratePlayerScore,
+    )
+}
+
+pub fn init_answer_tree_ix(program_id: &Pubkey, host: &Pubkey, quiz: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*host, true),
+            AccountMeta::new_readonly(*quiz, false),
+            AccountMeta::new(find_answer_tree_address(program_id, quiz), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        QuizInstruction::InitAnswerTree,
+    )
+}
+
+/// `confidence` is empty unless the quiz is confidence-weighted.
+pub fn submit_compressed_answers_ix(
+    program_id: &Pubkey,
+    player: &Pubkey,
+    quiz: &Pubkey,
+    answers: Vec<u8>,
+    confidence: Vec<u8>,
+) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*player, true),
+            AccountMeta::new_readonly(*quiz, false),
+            AccountMeta::new(find_answer_tree_address(program_id, quiz), false),
+            AccountMeta::new(
+                find_compressed_sheet_address(program_id, quiz, player),
+                false,
+            ),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        QuizInstruction::SubmitCompressedAnswers {
+            answers,
+            confidence,
+        },
+    )
+}
+
+/// `proof` is `merkle::padded_proof` for the sheet's leaf, over the leaves
+/// of every `CompressedAnswersSubmitted` event in order.
+pub fn grade_compressed_answers_ix(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    quiz: &Pubkey,
+    question_count: u8,
+    sheet: CompressedAnswers,
+    proof: Vec<[u8; 32]>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(*quiz, false),
+        AccountMeta::new(find_answer_tree_address(program_id, quiz), false),
+        AccountMeta::new(
+            find_player_score_address(program_id, quiz, &sheet.player),
+            false,
+        ),
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new_readonly(
+            find_compressed_sheet_address(program_id, quiz, &sheet.player),
+            false,
+        ),
+    ];
+    accounts.extend(question_metas(program_id, quiz, question_count));
+    build(
+        program_id,
+        accounts,
+        QuizInstruction::GradeCompressedAnswers { sheet, proof },
+    )
+}
+
+/// `proof` is `merkle::proof` for the player's leaf.
+pub fn claim_leaderboard_prize_ix(
+    program_id: &Pubkey,
+    player: &Pubkey,
+    quiz: &Pubkey,
+    score: u32,
+    rank: u32,
+    proof: Vec<[u8; 32]>,
+) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*player, true),
+            AccountMeta::new(*quiz, false),
+            AccountMeta::new(find_quiz_vault_address(program_id, quiz), false),
+            AccountMeta::new(
+                find_leaderboard_claim_address(program_id, quiz, player),
+                false,
+            ),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        QuizInstruction::ClaimLeaderboardPrize { score, rank, proof },
+    )
+}
+
+/// `correction_count` is the quiz's current `correction_count`.
+pub fn correct_score_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    player: &Pubkey,
+    new_score: u32,
+    reason: u8,
+    correction_count: u32,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*host, true),
+        AccountMeta::new(*quiz, false),
+        AccountMeta::new(find_player_score_address(program_id, quiz, player), false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+    accounts.extend(score_audit_metas(program_id, quiz, correction_count, 1));
+    build(
+        program_id,
+        accounts,
+        QuizInstruction::CorrectScore { new_score, reason },
+    )
+}
+
+pub fn create_team_ix(
+    program_id: &Pubkey,
+    captain: &Pubkey,
+    quiz: &Pubkey,
+    team_id: u8,
+    name: String,
+) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*captain, true),
+            AccountMeta::new(*quiz, false),
+            AccountMeta::new(find_team_address(program_id, quiz, team_id), false),
+            AccountMeta::new(find_team_member_address(program_id, quiz, captain), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        QuizInstruction::CreateTeam { team_id, name },
+    )
+}
+
+pub fn join_team_ix(
+    program_id: &Pubkey,
+    player: &Pubkey,
+    quiz: &Pubkey,
+    team_id: u8,
+) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*player, true),
+            AccountMeta::new_readonly(*quiz, false),
+            AccountMeta::new(find_team_address(program_id, quiz, team_id), false),
+            AccountMeta::new(find_team_member_address(program_id, quiz, player), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        QuizInstruction::JoinTeam { team_id },
+    )
+}
+
+pub fn leave_team_ix(
+    program_id: &Pubkey,
+    player: &Pubkey,
+    quiz: &Pubkey,
+    team_id: u8,
+) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*player, true),
+            AccountMeta::new(*quiz, false),
+            AccountMeta::new(find_team_address(program_id, quiz, team_id), false),
+            AccountMeta::new(find_team_member_address(program_id, quiz, player), false),
+        ],
+        QuizInstruction::LeaveTeam { team_id },
+    )
+}
+
+/// `members` must be in the team's member order.
+pub fn finalize_team_score_ix(
+    program_id: &Pubkey,
+    quiz: &Pubkey,
+    team_id: u8,
+    members: &[Pubkey],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*quiz, false),
+        AccountMeta::new(find_team_address(program_id, quiz, team_id), false),
+    ];
+    accounts.extend(members.iter().map(|member| {
+        AccountMeta::new_readonly(find_player_score_address(program_id, quiz, member), false)
+    }));
+    build(
+        program_id,
+        accounts,
+        QuizInstruction::FinalizeTeamScore { team_id },
+    )
+}
+
+/// `team_ids` lists every team of the quiz.
+pub fn claim_team_prize_ix(
+    program_id: &Pubkey,
+    player: &Pubkey,
+    quiz: &Pubkey,
+    team_id: u8,
+    team_ids: &[u8],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*player, true),
+        AccountMeta::new(*quiz, false),
+        AccountMeta::new(find_quiz_vault_address(program_id, quiz), false),
+        AccountMeta::new(
+            find_leaderboard_claim_address(program_id, quiz, player),
+            false,
+        ),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+    accounts.extend(
+        team_ids
+            .iter()
+            .map(|id| AccountMeta::new_readonly(find_team_address(program_id, quiz, *id), false)),
+    );
+    build(
+        program_id,
+        accounts,
+        QuizInstruction::ClaimTeamPrize { team_id },
+    )
+}
+
+pub fn settle_duel_ix(
+    program_id: &Pubkey,
+    quiz: &Pubkey,
+    first_player: &Pubkey,
+    second_player: &Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*quiz, false),
+            AccountMeta::new(find_quiz_vault_address(program_id, quiz), false),
+            AccountMeta::new_readonly(
+                find_player_score_address(program_id, quiz, first_player),
+                false,
+            ),
+            AccountMeta::new_readonly(
+                find_player_score_address(program_id, quiz, second_player),
+                false,
+            ),
+            AccountMeta::new(*first_player, false),
+            AccountMeta::new(*second_player, false),
+        ],
+        QuizInstruction::SettleDuel,
+    )
+}
+
+pub fn refund_duel_ix(program_id: &Pubkey, player: &Pubkey, quiz: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*player, true),
+            AccountMeta::new(*quiz, false),
+            AccountMeta::new_readonly(find_player_answer_address(program_id, quiz, player), false),
+            AccountMeta::new(find_quiz_vault_address(program_id, quiz), false),
+        ],
+        QuizInstruction::RefundDuel,
+    )
+}
+
+pub fn create_tournament_ix(
+    program_id: &Pubkey,
+    organizer: &Pubkey,
+    tournament_id: u64,
+    round_count: u8,
+    advance_count: u8,
+) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*organizer, true),
+            AccountMeta::new(
+                find_tournament_address(program_id, organizer, tournament_id),
+                false,
+            ),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        QuizInstruction::CreateTournament {
+            tournament_id,
+            round_count,
+            advance_count,
+        },
+    )
+}
+
+pub fn link_tournament_round_ix(
+    program_id: &Pubkey,
+    organizer: &Pubkey,
+    host: &Pubkey,
+    tournament: &Pubkey,
+    quiz: &Pubkey,
+    round: u8,
+) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new_readonly(*organizer, true),
+            AccountMeta::new_readonly(*host, true),
+            AccountMeta::new(*tournament, false),
+            AccountMeta::new(*quiz, false),
+        ],
+        QuizInstruction::LinkTournamentRound { round },
+    )
+}
+
+pub fn fund_tournament_ix(
+    program_id: &Pubkey,
+    funder: &Pubkey,
+    tournament: &Pubkey,
+    lamports: u64,
+) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*funder, true),
+            AccountMeta::new(*tournament, false),
+            AccountMeta::new(find_tournament_vault_address(program_id, tournament), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        QuizInstruction::FundTournament { lamports },
+    )
+}
+
+/// `players` must include every player of the round's quiz.
+pub fn advance_bracket_ix(
+    program_id: &Pubkey,
+    tournament: &Pubkey,
+    quiz: &Pubkey,
+    players: &[Pubkey],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*tournament, false),
+        AccountMeta::new_readonly(*quiz, false),
+    ];
+    accounts.extend(players.iter().map(|player| {
+        AccountMeta::new_readonly(find_player_score_address(program_id, quiz, player), false)
+    }));
+    build(program_id, accounts, QuizInstruction::AdvanceBracket)
+}
+
+pub fn claim_tournament_prize_ix(
+    program_id: &Pubkey,
+    champion: &Pubkey,
+    tournament: &Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*champion, true),
+            AccountMeta::new(*tournament, false),
+            AccountMeta::new(find_tournament_vault_address(program_id, tournament), false),
+        ],
+        QuizInstruction::ClaimTournamentPrize,
+    )
+}
+
+pub fn create_series_ix(
+    program_id: &Pubkey,
+    organizer: &Pubkey,
+    series_id: u64,
+    quiz_count: u8,
+) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*organizer, true),
+            AccountMeta::new(find_series_address(program_id, organizer, series_id), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        QuizInstruction::CreateSeries {
+            series_id,
+            quiz_count,
+        },
+    )
+}
+
+pub fn link_series_quiz_ix(
+    program_id: &Pubkey,
+    organizer: &Pubkey,
+    host: &Pubkey,
+    series: &Pubkey,
+    quiz: &Pubkey,
+    position: u8,
+) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new_readonly(*organizer, true),
+            AccountMeta::new_readonly(*host, true),
+            AccountMeta::new(*series, false),
+            AccountMeta::new_readonly(*quiz, false),
+        ],
+        QuizInstruction::LinkSeriesQuiz { position },
+    )
+}
+
+pub fn fund_series_ix(
+    program_id: &Pubkey,
+    funder: &Pubkey,
+    series: &Pubkey,
+    lamports: u64,
+) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*funder, true),
+            AccountMeta::new(*series, false),
+            AccountMeta::new(find_series_vault_address(program_id, series), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        QuizInstruction::FundSeries { lamports },
+    )
+}
+
+/// `players` must include every player of the quiz.
+pub fn record_series_quiz_ix(
+    program_id: &Pubkey,
+    series: &Pubkey,
+    quiz: &Pubkey,
+    players: &[Pubkey],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*series, false),
+        AccountMeta::new_readonly(*quiz, false),
+    ];
+    accounts.extend(players.iter().map(|player| {
+        AccountMeta::new_readonly(find_player_score_address(program_id, quiz, player), false)
+    }));
+    build(program_id, accounts, QuizInstruction::RecordSeriesQuiz)
+}
+
+pub fn claim_series_prize_ix(program_id: &Pubkey, leader: &Pubkey, series: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*leader, true),
+            AccountMeta::new(*series, false),
+            AccountMeta::new(find_series_vault_address(program_id, series), false),
+        ],
+        QuizInstruction::ClaimSeriesPrize,
+    )
+}
+
+/// Roll the jackpot of `quiz` into `next_quiz`, the quiz after it in
+/// `series`.
+pub fn rollover_jackpot_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    series: &Pubkey,
+    quiz: &Pubkey,
+    next_quiz: &Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*host, true),
+            AccountMeta::new_readonly(*series, false),
+            AccountMeta::new(*quiz, false),
+            AccountMeta::new(find_quiz_vault_address(program_id, quiz), false),
+            AccountMeta::new(*next_quiz, false),
+            AccountMeta::new(find_quiz_vault_address(program_id, next_quiz), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        QuizInstruction::RolloverJackpot,
+    )
+}
+
+/// `season` is the series' current season, the one being archived.
+pub fn end_series_season_ix(
+    program_id: &Pubkey,
+    organizer: &Pubkey,
+    series: &Pubkey,
+    season: u32,
+    quiz_count: u8,
+) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*organizer, true),
+            AccountMeta::new(*series, false),
+            AccountMeta::new(
+                find_season_archive_address(program_id, series, season),
+                false,
+            ),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        QuizInstruction::EndSeriesSeason { quiz_count },
+    )
+}
+
+pub fn initialize_daily_quiz_ix(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    curator: Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(find_daily_config_address(program_id), false),
+            AccountMeta::new(find_daily_leaderboard_address(program_id), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(
+                bpf_loader_upgradeable::get_program_data_address(program_id),
+                false,
+            ),
+        ],
+        QuizInstruction::InitializeDailyQuiz { curator },
+    )
+}
+
+pub fn publish_daily_question_ix(
+    program_id: &Pubkey,
+    curator: &Pubkey,
+    day_index: u64,
+    question_index: u8,
+    question_text: String,
+    options: [String; 4],
+    correct_answer_index: u8,
+) -> Instruction {
+    let daily_quiz = find_daily_quiz_address(program_id, day_index);
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*curator, true),
+            AccountMeta::new_readonly(find_daily_config_address(program_id), false),
+            AccountMeta::new(daily_quiz, false),
+            AccountMeta::new(
+                find_question_address(program_id, &daily_quiz, question_index),
+                false,
+            ),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        QuizInstruction::PublishDailyQuestion {
+            day_index,
+            question_index,
+            question_text,
+            options,
+            correct_answer_index,
+        },
+    )
+}
+
+pub fn play_daily_quiz_ix(
+    program_id: &Pubkey,
+    player: &Pubkey,
+    day_index: u64,
+    answers: Vec<u8>,
+) -> Instruction {
+    let daily_quiz = find_daily_quiz_address(program_id, day_index);
+    let mut accounts = vec![
+        AccountMeta::new(*player, true),
+        AccountMeta::new(daily_quiz, false),
+        AccountMeta::new(
+            find_daily_entry_address(program_id, &daily_quiz, player),
+            false,
+        ),
+        AccountMeta::new(find_daily_streak_address(program_id, player), false),
+        AccountMeta::new(find_daily_leaderboard_address(program_id), false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+    accounts.extend(question_metas(program_id, &daily_quiz, answers.len() as u8));
+    build(
+        program_id,
+        accounts,
+        QuizInstruction::PlayDailyQuiz { answers },
+    )
+}
+
+pub fn set_daily_season_length_ix(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    season_days: u64,
+) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(find_daily_config_address(program_id), false),
+        ],
+        QuizInstruction::SetDailySeasonLength { season_days },
+    )
+}
+
+/// `season` is the leaderboard's current season, the one being archived.
+pub fn end_daily_season_ix(program_id: &Pubkey, caller: &Pubkey, season: u32) -> Instruction {
+    let leaderboard = find_daily_leaderboard_address(program_id);
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*caller, true),
+            AccountMeta::new_readonly(find_daily_config_address(program_id), false),
+            AccountMeta::new(leaderboard, false),
+            AccountMeta::new(
+                find_season_archive_address(program_id, &leaderboard, season),
+                false,
+            ),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        QuizInstruction::EndDailySeason,
+    )
+}
+
+pub fn practice_quiz_ix(
+    program_id: &Pubkey,
+    player: &Pubkey,
+    quiz: &Pubkey,
+    answers: Vec<u8>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*player, true),
+        AccountMeta::new(*quiz, false),
+        AccountMeta::new(find_player_answer_address(program_id, quiz, player), false),
+        AccountMeta::new(find_player_score_address(program_id, quiz, player), false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+    accounts.extend(question_metas(program_id, quiz, answers.len() as u8));
+    build(
+        program_id,
+        accounts,
+        QuizInstruction::PracticeQuiz { answers },
+    )
+}
+
+pub fn list_quiz_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    shard_index: u32,
+    start_slot: u64,
+) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*host, true),
+            AccountMeta::new_readonly(*quiz, false),
+            AccountMeta::new(find_registry_address(program_id, shard_index), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        QuizInstruction::ListQuiz {
+            shard_index,
+            start_slot,
+        },
+    )
+}
+
+pub fn delist_quiz_ix(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    shard_index: u32,
+) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new_readonly(*host, true),
+            AccountMeta::new_readonly(*quiz, false),
+            AccountMeta::new(find_registry_address(program_id, shard_index), false),
+        ],
+        QuizInstruction::DelistQuiz { shard_index },
+    )
+}
+
+/// Set the player's nickname. `reserved` is the nickname the player
+/// currently holds a claim on, if any, which is released unless the new name
+/// keeps it.
+pub fn set_nickname_ix(
+    program_id: &Pubkey,
+    player: &Pubkey,
+    nickname: String,
+    reserve: bool,
+    reserved: Option<&str>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*player, true),
+        AccountMeta::new(find_profile_address(program_id, player), false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+    let keeps_claim = reserve
+        && reserved.is_some_and(|previous| {
+            NicknameClaim::seed(previous) == NicknameClaim::seed(&nickname)
+        });
+    if !keeps_claim {
+        if reserve {
+            accounts.push(AccountMeta::new(
+                find_nickname_claim_address(program_id, &nickname),
+                false,
+            ));
+        }
+        if let Some(previous) = reserved {
+            accounts.push(AccountMeta::new(
+                find_nickname_claim_address(program_id, previous),
+                false,
+            ));
+        }
+    }
+    build(
+        program_id,
+        accounts,
+        QuizInstruction::SetNickname { nickname, reserve },
+    )
+}
+
+/// The compute budget program, whose instructions are built by hand here.
+pub const COMPUTE_BUDGET_PROGRAM_ID: Pubkey =
+    solana_program::pubkey!("ComputeBudget111111111111111111111111111111");
+/// Most compute units a transaction can request.
+pub const MAX_COMPUTE_UNITS: u32 = 1_400_000;
+
+/// Loading and checking the quiz and a few more accounts.
+const BASE_UNITS: u32 = 40_000;
+/// Finding a PDA's bump and creating the account.
+const CREATE_ACCOUNT_UNITS: u32 = 20_000;
+/// Placing or grading one answer.
+const ANSWER_UNITS: u32 = 1_500;
+/// A CPI into the delegation or magic program.
+const ROLLUP_CPI_UNITS: u32 = 60_000;
+/// A builtin or precompile instruction such as a transfer or ed25519 check.
+const BUILTIN_UNITS: u32 = 3_000;
+
+/// Request a compute unit limit for the whole transaction.
+pub fn set_compute_unit_limit_ix(units: u32) -> Instruction {
+    let mut data = vec![2];
+    data.extend_from_slice(&units.to_le_bytes());
+    Instruction {
+        program_id: COMPUTE_BUDGET_PROGRAM_ID,
+        accounts: vec![],
+        data,
+    }
+}
+
+/// Pay `micro_lamports` per requested compute unit on top of the base fee,
+/// so leaders schedule the transaction ahead of cheaper ones.
+pub fn set_compute_unit_price_ix(micro_lamports: u64) -> Instruction {
+    let mut data = vec![3];
+    data.extend_from_slice(&micro_lamports.to_le_bytes());
+    Instruction {
+        program_id: COMPUTE_BUDGET_PROGRAM_ID,
+        accounts: vec![],
+        data,
+    }
+}
+
+/// A rough compute unit limit for `instructions` in a quiz of
+/// `question_count` questions, `player_count` being the players graded in a
+/// scoring batch. Costs grow with the answers placed or graded and the
+/// accounts created, plus 20% headroom. Requesting close to what a
+/// transaction uses keeps its priority fee down, as the fee is charged on
+/// the limit.
+pub fn estimate_compute_units(
+    program_id: &Pubkey,
+    instructions: &[Instruction],
+    question_count: u8,
+    player_count: u8,
+) -> u32 {
+    let questions = question_count as u32;
+    let players = player_count as u32;
+    let units: u32 = instructions
+        .iter()
+        .map(|ix| {
+            if ix.program_id != *program_id {
+                return BUILTIN_UNITS;
+            }
+            let Ok(instruction) = QuizInstruction::unpack(&ix.data) else {
+                return BASE_UNITS;
+            };
+            BASE_UNITS
+                + match instruction {
+                    QuizInstruction::CalculateScores
+                    | QuizInstruction::GradeAnswers
+                    | QuizInstruction::GradeCompressedAnswers { .. }
+                    | QuizInstruction::Regrade => {
+                        players * (CREATE_ACCOUNT_UNITS + questions * ANSWER_UNITS)
+                    }
+                    QuizInstruction::SubmitAnswers { .. }
+                    | QuizInstruction::UpdateAnswers { .. }
+                    | QuizInstruction::SubmitRelayedAnswers { .. }
+                    | QuizInstruction::SubmitCompressedAnswers { .. } => questions * ANSWER_UNITS,
+                    QuizInstruction::PracticeQuiz { .. }
+                    | QuizInstruction::PlayDailyQuiz { .. } => {
+                        2 * CREATE_ACCOUNT_UNITS + questions * ANSWER_UNITS
+                    }
+                    QuizInstruction::ImportQuestions { questions, .. }
+                    | QuizInstruction::ImportSignedQuestions { questions, .. } => {
+                        questions.len() as u32 * CREATE_ACCOUNT_UNITS
+                    }
+                    QuizInstruction::CloneQuiz { .. }
+                    | QuizInstruction::CreateTemplate { .. }
+                    | QuizInstruction::CreateFromTemplate { .. } => {
+                        (1 + questions) * CREATE_ACCOUNT_UNITS
+                    }
+                    QuizInstruction::DelegatePlayer { .. }
+                    | QuizInstruction::DelegateQuiz { .. }
+                    | QuizInstruction::DelegateScoreboard { .. }
+                    | QuizInstruction::InitLiveLeaderboard { .. }
+                    | QuizInstruction::CommitAnswers
+                    | QuizInstruction::CommitAndUndelegateAnswers
+                    | QuizInstruction::CommitAndUndelegateQuiz
+                    | QuizInstruction::CommitScoreboard
+                    | QuizInstruction::CommitLiveLeaderboard
+                    | QuizInstruction::CommitReactions => ROLLUP_CPI_UNITS,
+                    _ => CREATE_ACCOUNT_UNITS,
+                }
+        })
+        .fold(0, u32::saturating_add);
+    units.saturating_add(units / 5).min(MAX_COMPUTE_UNITS)
+}
+
+/// `instructions` behind a compute unit limit from `estimate_compute_units`
+/// and, unless `micro_lamports` is 0, a price per unit, so they still land
+/// in congested slots.
+pub fn with_priority_fee(
+    program_id: &Pubkey,
+    instructions: &[Instruction],
+    question_count: u8,
+    player_count: u8,
+    micro_lamports: u64,
+) -> Vec<Instruction> {
+    let units = estimate_compute_units(program_id, instructions, question_count, player_count);
+    let mut prioritized = vec![set_compute_unit_limit_ix(units)];
+    if micro_lamports > 0 {
+        prioritized.push(set_compute_unit_price_ix(micro_lamports));
+    }
+    prioritized.extend_from_slice(instructions);
+    prioritized
+}
@@ -0,0 +1,71 @@
+//! Signatures checked by the native ed25519 program instead of by
+//! transaction signers, so content signed off-chain can be sent by anyone.
+//! The sender puts an ed25519 program instruction ahead of ours in the same
+//! transaction, which fails the whole transaction unless its signatures are
+//! valid. All that's left here is to find it through the instructions sysvar
+//! and check it covers the expected signer and message.
+//!
+//! Ed25519 instruction data: signature count (1) + padding (1) + one 14-byte
+//! offsets entry per signature, each seven u16s: signature_offset,
+//! signature_instruction_index, public_key_offset,
+//! public_key_instruction_index, message_data_offset, message_data_size,
+//! message_instruction_index.
+
+use solana_program::{
+    account_info::AccountInfo,
+    ed25519_program,
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+    sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+};
+
+use crate::error::QuizError;
+
+pub const SIGNATURE_OFFSETS_START: usize = 2;
+pub const SIGNATURE_OFFSETS_SIZE: usize = 14;
+/// Instruction index an offsets entry uses for the ed25519 instruction's
+/// own data.
+pub const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+/// Fails unless an ed25519 program instruction earlier in this transaction
+/// verified `signer`'s signature over `message`.
+pub fn assert_signed(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> ProgramResult {
+    // Also checks this is the instructions sysvar
+    let current = load_current_index_checked(instructions_sysvar)?;
+    for index in 0..current {
+        let instruction = load_instruction_at_checked(index as usize, instructions_sysvar)?;
+        if instruction.program_id == ed25519_program::ID
+            && covers(&instruction.data, signer, message)
+        {
+            return Ok(());
+        }
+    }
+    Err(QuizError::SignatureNotVerified.into())
+}
+
+fn covers(data: &[u8], signer: &Pubkey, message: &[u8]) -> bool {
+    let count = data.first().copied().unwrap_or(0) as usize;
+    (0..count).any(|entry| {
+        let at = SIGNATURE_OFFSETS_START + entry * SIGNATURE_OFFSETS_SIZE;
+        let Some(offsets) = data.get(at..at + SIGNATURE_OFFSETS_SIZE) else {
+            return false;
+        };
+        let field = |n: usize| u16::from_le_bytes([offsets[2 * n], offsets[2 * n + 1]]);
+        // Offsets may point into other instructions, only entries whose key,
+        // signature and message all sit in this one are read here
+        if field(1) != CURRENT_INSTRUCTION
+            || field(3) != CURRENT_INSTRUCTION
+            || field(6) != CURRENT_INSTRUCTION
+        {
+            return false;
+        }
+        let key_at = field(2) as usize;
+        let message_at = field(4) as usize;
+        data.get(key_at..key_at + 32) == Some(signer.as_ref())
+            && data.get(message_at..message_at + field(5) as usize) == Some(message)
+    })
+}
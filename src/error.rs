@@ -0,0 +1,178 @@
+use solana_program::program_error::ProgramError;
+use thiserror::Error;
+
+#[derive(Error, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum QuizError {
+    #[error("Question index is out of range for this quiz")]
+    InvalidQuestionIndex,
+    #[error("Correct answer index must point at one of the question options")]
+    InvalidCorrectAnswerIndex,
+    #[error("Submitted answer must point at one of the question options")]
+    InvalidAnswerIndex,
+    #[error("Account is not owned by the expected program")]
+    InvalidAccountOwner,
+    #[error("Account does not match the PDA derived from its seeds")]
+    InvalidPda,
+    #[error("Player has already joined this quiz")]
+    PlayerAlreadyJoined,
+    #[error("Player has already submitted answers for this quiz")]
+    AnswersAlreadySubmitted,
+    #[error("The submission deadline for this quiz has passed")]
+    SubmissionDeadlinePassed,
+    #[error("Player has not submitted answers yet")]
+    AnswersNotSubmitted,
+    #[error("The host has not enabled answer updates for this quiz")]
+    AnswerUpdatesDisabled,
+    #[error("Player has already answered this question")]
+    QuestionAlreadyAnswered,
+    #[error("Instruction is not available in this quiz mode")]
+    WrongQuizMode,
+    #[error("Question is not open for answers")]
+    AnswerWindowClosed,
+    #[error("Player has been eliminated from this quiz")]
+    PlayerEliminated,
+    #[error("Team name is too long")]
+    TeamNameTooLong,
+    #[error("Team has no free member slots")]
+    TeamFull,
+    #[error("Player is not a member of this team")]
+    NotTeamMember,
+    #[error("Team score has already been finalized")]
+    TeamAlreadyFinalized,
+    #[error("Quiz has no free player slots")]
+    QuizFull,
+    #[error("Payouts for this quiz have already been settled")]
+    AlreadySettled,
+    #[error("Quiz session is not the current round of this tournament")]
+    WrongTournamentRound,
+    #[error("Player did not advance to this tournament round")]
+    NotTournamentEntrant,
+    #[error("Tournament bracket has already been decided")]
+    TournamentDecided,
+    #[error("Registry shard has no free slots")]
+    RegistryFull,
+    #[error("The host only allows delegating to a different validator")]
+    ValidatorNotAllowed,
+    #[error("Scoreboard has no slot left for another player")]
+    ScoreboardFull,
+    #[error("Answer sheet is back from the rollup and can no longer change")]
+    AnswersCommitted,
+    #[error("Session key is not valid for this player or has expired")]
+    InvalidSession,
+    #[error("Start slot must be in the future")]
+    InvalidStartSlot,
+    #[error("Quiz has no scheduled start")]
+    QuizNotScheduled,
+    #[error("Scheduled start slot has not been reached")]
+    StartSlotNotReached,
+    #[error("Submissions for this quiz are closed")]
+    SubmissionsClosed,
+    #[error("Quiz still holds entry fees")]
+    PrizePoolNotEmpty,
+    #[error("The join window for this quiz has closed")]
+    JoinWindowClosed,
+    #[error("Confidence must be given for every answer, from 1 to 3")]
+    InvalidConfidence,
+    #[error("Hint already bought")]
+    HintAlreadyBought,
+    #[error("Question multiplier must be from 1 to 3")]
+    InvalidMultiplier,
+    #[error("Answer window must close after it opens")]
+    InvalidAnswerWindow,
+    #[error("Dispute window is closed")]
+    DisputeWindowClosed,
+    #[error("Unknown reason code")]
+    InvalidReasonCode,
+    #[error("Scores may still change while the dispute window is open")]
+    DisputeWindowOpen,
+    #[error("Prize split adds up to more than the whole pool")]
+    InvalidPrizeSplit,
+    #[error("Merkle proof doesn't match the leaderboard root")]
+    InvalidMerkleProof,
+    #[error("Rank wins no prize")]
+    NoPrizeForRank,
+    #[error("Regrading the sheet gives the posted score")]
+    ScoreNotFraudulent,
+    #[error("Answer tree is full")]
+    AnswerTreeFull,
+    #[error("Template title is too long")]
+    TemplateTitleTooLong,
+    #[error("Host may not use this template")]
+    TemplateAccessDenied,
+    #[error("Language code must be two lowercase ASCII letters")]
+    InvalidLanguageCode,
+    #[error("Nickname must be 1 to 32 bytes without NUL characters")]
+    InvalidNickname,
+    #[error("Nickname is reserved by another player")]
+    NicknameTaken,
+    #[error("Unknown reaction")]
+    InvalidReaction,
+    #[error("Reactions are rate limited, try again later")]
+    ReactionCooldown,
+    #[error("Quiz is not an unrecorded quiz of this series")]
+    WrongSeriesQuiz,
+    #[error("Series has quizzes left to record")]
+    SeriesNotComplete,
+    #[error("Season can't end yet")]
+    SeasonNotOver,
+    #[error("Players can't refer themselves")]
+    InvalidReferrer,
+    #[error("Score is below the prize threshold")]
+    BelowPrizeThreshold,
+    #[error("Player at this rank qualified for the prize")]
+    PrizeRankQualified,
+    #[error("Quiz has no jackpot to roll over")]
+    NoJackpot,
+    #[error("Prize claims have closed")]
+    ClaimWindowClosed,
+    #[error("Prizes can still be claimed")]
+    ClaimWindowOpen,
+    #[error("Nothing more of the prize has vested yet")]
+    NothingVested,
+    #[error("Price feed is too old")]
+    StalePrice,
+    #[error("Price feed is not reliable enough")]
+    PriceUncertain,
+    #[error("Charity share has not been paid yet")]
+    CharityUnpaid,
+    #[error("The host and scorer can't play this quiz")]
+    HostCannotPlay,
+    #[error("Question difficulty must be from 1 to 5")]
+    InvalidDifficulty,
+    #[error("Question was not served to this player")]
+    QuestionNotServed,
+    #[error("Answers were already revealed")]
+    AnswersAlreadyRevealed,
+    #[error("Explanation is longer than 512 bytes")]
+    ExplanationTooLong,
+    #[error("Key does not match the committed question key hash")]
+    WrongQuestionKey,
+    #[error("Questions are still encrypted")]
+    QuestionsNotRevealed,
+    #[error("Question does not match its commitment")]
+    QuestionHashMismatch,
+    #[error("No ed25519 instruction verifies this signature")]
+    SignatureNotVerified,
+    #[error("Questions were already imported from another curator")]
+    CuratorMismatch,
+    #[error("Relay nonce was already used")]
+    StaleRelayNonce,
+    #[error("The duel can still be joined")]
+    DuelStillOpen,
+    #[error("Nobody played this tournament round")]
+    EmptyRound,
+    #[error("Signer is not the program's upgrade authority")]
+    NotUpgradeAuthority,
+    #[error("Compressed sheets are still waiting to be graded")]
+    SheetsUngraded,
+    #[error("An appointed scorer needs a dispute window")]
+    NoDisputeWindow,
+    #[error("Every team must be finalized before team prizes are paid")]
+    TeamNotFinalized,
+}
+
+impl From<QuizError> for ProgramError {
+    fn from(e: QuizError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
@@ -0,0 +1,110 @@
+//! Events logged with `sol_log_data`. Each log entry is one Borsh-encoded
+//! `QuizEvent`, so the first byte is the variant index and indexers can
+//! decode `Program data:` lines without parsing `msg!` text.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{entrypoint::ProgramResult, log::sol_log_data, pubkey::Pubkey};
+
+use crate::state::CompressedAnswers;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct QuizInitialized {
+    pub quiz: Pubkey,
+    pub host: Pubkey,
+    pub quiz_id: u64,
+    pub question_count: u8,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct QuestionAdded {
+    pub quiz: Pubkey,
+    pub question_index: u8,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct QuestionRevealed {
+    pub quiz: Pubkey,
+    pub question_index: u8,
+    pub open_slot: u64,
+    pub close_slot: u64, // 0 when the window stays open until the next reveal
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PlayerJoined {
+    pub quiz: Pubkey,
+    pub player: Pubkey,
+    pub entry_fee: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct AnswersSubmitted {
+    pub quiz: Pubkey,
+    pub player: Pubkey,
+    pub answered_count: u8,
+    pub slot: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ScoresFinalized {
+    pub quiz: Pubkey,
+    pub player_count: u8,
+    pub question_count: u8,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PrizeClaimed {
+    pub source: Pubkey, // quiz session or tournament that paid out
+    pub recipient: Pubkey,
+    pub lamports: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CompressedAnswersSubmitted {
+    pub quiz: Pubkey,
+    pub leaf_index: u32,
+    pub sheet: CompressedAnswers,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ReactionSent {
+    pub quiz: Pubkey,
+    pub player: Pubkey,
+    pub emoji_code: u8,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PrizePoolContributed {
+    pub quiz: Pubkey,
+    pub contributor: Pubkey,
+    pub lamports: u64,
+    pub prize_pool: u64, // after the contribution
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct AnswersRevealed {
+    pub quiz: Pubkey,
+    pub correct_answers: Vec<u8>, // by question index
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub enum QuizEvent {
+    QuizInitialized(QuizInitialized),
+    QuestionAdded(QuestionAdded),
+    PlayerJoined(PlayerJoined),
+    AnswersSubmitted(AnswersSubmitted),
+    ScoresFinalized(ScoresFinalized),
+    PrizeClaimed(PrizeClaimed),
+    QuestionRevealed(QuestionRevealed),
+    CompressedAnswersSubmitted(CompressedAnswersSubmitted),
+    ReactionSent(ReactionSent),
+    PrizePoolContributed(PrizePoolContributed),
+    AnswersRevealed(AnswersRevealed),
+}
+
+impl QuizEvent {
+    pub fn emit(&self) -> ProgramResult {
+        let data = borsh::to_vec(self)?;
+        sol_log_data(&[&data]);
+        Ok(())
+    }
+}
@@ -0,0 +1,110 @@
+//! `getProgramAccounts` filters for common queries. Accounts carry no type
+//! discriminator, so every filter pins the data size of the account type and
+//! then matches fixed-offset fields with memcmp.
+
+use solana_program::pubkey::Pubkey;
+
+use crate::state::{PlayerScore, QuizSession, QuizStatus};
+
+/// Offset of `QuizSession::host`.
+pub const QUIZ_HOST_OFFSET: usize = 0;
+/// Offset of `QuizSession::status`.
+pub const QUIZ_STATUS_OFFSET: usize = 32 // host
+    + 8 // quiz_id
+    + 1 // question_count
+    + 1; // player_count
+/// Offset of `QuizSession::tournament`.
+pub const QUIZ_TOURNAMENT_OFFSET: usize = QUIZ_STATUS_OFFSET
+    + 1 // status
+    + 1 // allow_resubmission
+    + 1 // refresh_slot_on_update
+    + 8 // deadline_slot
+    + 1 // mode
+    + 1 // revealed_question
+    + 8 // reveal_slot
+    + 8 // answer_window_slots
+    + 1 // rounds_revealed
+    + 8 // entry_fee
+    + 8 // prize_pool
+    + 1; // settled
+/// Offset of `PlayerScore::player`.
+pub const SCORE_PLAYER_OFFSET: usize = 0;
+/// Offset of `PlayerScore::quiz`.
+pub const SCORE_QUIZ_OFFSET: usize = 32 + 4; // player + score
+
+/// A `getProgramAccounts` filter, mirroring the RPC `dataSize` and `memcmp`
+/// filters so this module doesn't depend on an RPC client.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AccountFilter {
+    DataSize(u64),
+    Memcmp { offset: usize, bytes: Vec<u8> },
+}
+
+impl AccountFilter {
+    /// Check the filter against raw account data, as the RPC node would.
+    pub fn matches(&self, data: &[u8]) -> bool {
+        match self {
+            AccountFilter::DataSize(size) => data.len() as u64 == *size,
+            AccountFilter::Memcmp { offset, bytes } => data
+                .get(*offset..offset + bytes.len())
+                .is_some_and(|window| window == bytes.as_slice()),
+        }
+    }
+}
+
+fn pubkey_at(offset: usize, pubkey: &Pubkey) -> AccountFilter {
+    AccountFilter::Memcmp {
+        offset,
+        bytes: pubkey.to_bytes().to_vec(),
+    }
+}
+
+/// Every quiz session.
+pub fn quizzes() -> Vec<AccountFilter> {
+    vec![AccountFilter::DataSize(QuizSession::SIZE as u64)]
+}
+
+/// Every quiz hosted by `host`.
+pub fn quizzes_by_host(host: &Pubkey) -> Vec<AccountFilter> {
+    let mut filters = quizzes();
+    filters.push(pubkey_at(QUIZ_HOST_OFFSET, host));
+    filters
+}
+
+/// Every quiz in `status`.
+pub fn quizzes_with_status(status: QuizStatus) -> Vec<AccountFilter> {
+    let mut filters = quizzes();
+    filters.push(AccountFilter::Memcmp {
+        offset: QUIZ_STATUS_OFFSET,
+        bytes: vec![status as u8],
+    });
+    filters
+}
+
+/// Every quiz open for players.
+pub fn active_quizzes() -> Vec<AccountFilter> {
+    quizzes_with_status(QuizStatus::Active)
+}
+
+/// Every quiz linked to `tournament` as one of its rounds.
+pub fn quizzes_in_tournament(tournament: &Pubkey) -> Vec<AccountFilter> {
+    let mut filters = quizzes();
+    filters.push(pubkey_at(QUIZ_TOURNAMENT_OFFSET, tournament));
+    filters
+}
+
+/// Every score recorded for `quiz`.
+pub fn scores_for_quiz(quiz: &Pubkey) -> Vec<AccountFilter> {
+    vec![
+        AccountFilter::DataSize(PlayerScore::SIZE as u64),
+        pubkey_at(SCORE_QUIZ_OFFSET, quiz),
+    ]
+}
+
+/// Every score `player` has earned, across quizzes.
+pub fn scores_for_player(player: &Pubkey) -> Vec<AccountFilter> {
+    vec![
+        AccountFilter::DataSize(PlayerScore::SIZE as u64),
+        pubkey_at(SCORE_PLAYER_OFFSET, player),
+    ]
+}
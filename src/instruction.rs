@@ -1,77 +1,1804 @@
 use borsh::{BorshDeserialize, BorshSerialize};
-use solana_program::program_error::ProgramError;
+use ephemeral_rollups_sdk::consts::EXTERNAL_UNDELEGATE_DISCRIMINATOR;
+use shank::ShankInstruction;
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
 
-use crate::state::QuizQuestion;
+use crate::state::{
+    CompressedAnswers, DelegationConfig, HostPlay, PostedStanding, PrizeRemainder, QuestionData,
+    QuizMode, QuizSession, ScoringMode, VoidMode,
+};
 
-#[derive(BorshSerialize, BorshDeserialize, Debug)]
-pub struct AddQuestionData {
-    pub question_index: u8,
-    pub question_text: String,
-    pub options: [String; 4],
-    pub correct_answer_index: u8,
-}
+/// Anchor sighash discriminators, `sha256("global:<instruction_name>")[..8]`,
+/// indexed by variant.
+pub const ANCHOR_DISCRIMINATORS: [[u8; 8]; 123] = [
+    [119, 91, 73, 7, 108, 157, 18, 42],       // initialize_quiz
+    [164, 157, 121, 228, 98, 242, 205, 193],  // add_question
+    [26, 247, 82, 180, 175, 38, 4, 22],       // start_quiz
+    [235, 159, 245, 102, 161, 199, 254, 89],  // delegate_player
+    [142, 178, 58, 248, 113, 129, 119, 142],  // submit_answers
+    [162, 175, 44, 59, 113, 46, 207, 166],    // commit_answers
+    [97, 247, 73, 155, 47, 127, 58, 65],      // calculate_scores
+    [230, 242, 176, 199, 120, 26, 119, 243],  // undelegate_player
+    [30, 48, 31, 182, 3, 63, 197, 166],       // set_submission_policy
+    [64, 230, 55, 224, 174, 65, 57, 194],     // update_answers
+    [221, 73, 184, 157, 1, 150, 231, 48],     // submit_answer
+    [118, 150, 167, 186, 220, 242, 208, 138], // set_quiz_mode
+    [87, 159, 25, 236, 84, 189, 206, 30],     // reveal_question
+    [122, 161, 98, 67, 178, 128, 116, 113],   // create_team
+    [244, 30, 215, 53, 96, 145, 4, 206],      // join_team
+    [10, 158, 72, 167, 4, 75, 99, 87],        // leave_team
+    [12, 139, 249, 234, 106, 90, 167, 169],   // finalize_team_score
+    [129, 189, 100, 228, 190, 165, 238, 114], // set_entry_fee
+    [148, 90, 251, 130, 217, 144, 190, 239],  // settle_duel
+    [158, 137, 233, 231, 73, 132, 191, 68],   // create_tournament
+    [61, 246, 243, 194, 253, 1, 46, 151],     // link_tournament_round
+    [56, 61, 181, 195, 208, 101, 198, 134],   // fund_tournament
+    [4, 181, 214, 235, 218, 156, 163, 106],   // advance_bracket
+    [219, 207, 183, 94, 201, 32, 78, 193],    // claim_tournament_prize
+    [131, 234, 220, 27, 62, 34, 42, 169],     // initialize_daily_quiz
+    [67, 220, 184, 223, 48, 154, 121, 69],    // publish_daily_question
+    [250, 105, 203, 34, 215, 214, 221, 210],  // play_daily_quiz
+    [179, 125, 215, 183, 6, 136, 142, 203],   // practice_quiz
+    [218, 246, 171, 22, 232, 191, 118, 246],  // list_quiz
+    [251, 21, 176, 151, 227, 249, 1, 14],     // delist_quiz
+    [116, 67, 54, 19, 202, 10, 211, 231],     // commit_and_undelegate_answers
+    [65, 33, 118, 214, 101, 187, 190, 72],    // delegate_quiz
+    [142, 2, 81, 143, 193, 186, 155, 110],    // commit_and_undelegate_quiz
+    [202, 148, 159, 212, 85, 38, 99, 113],    // delegate_scoreboard
+    [105, 10, 47, 118, 93, 206, 135, 18],     // grade_answers
+    [144, 60, 221, 145, 252, 34, 99, 144],    // commit_scoreboard
+    [44, 209, 235, 189, 250, 36, 163, 147],   // join_quiz
+    [242, 193, 143, 179, 150, 25, 122, 227],  // create_session
+    [136, 90, 184, 124, 197, 224, 140, 241],  // schedule_start
+    [173, 34, 194, 230, 174, 92, 106, 81],    // activate_quiz
+    [253, 222, 230, 146, 81, 77, 0, 45],      // close_submissions
+    [243, 172, 187, 47, 86, 64, 57, 55],      // cancel_quiz
+    [86, 138, 237, 155, 170, 226, 117, 58],   // set_late_policy
+    [75, 49, 195, 89, 198, 131, 137, 151],    // set_confidence_scoring
+    [120, 111, 169, 242, 229, 22, 138, 79],   // set_hint
+    [151, 122, 243, 171, 161, 248, 236, 117], // buy_hint
+    [73, 190, 56, 230, 17, 188, 126, 241],    // set_round_multiplier
+    [189, 252, 255, 1, 110, 73, 135, 33],     // open_question
+    [12, 59, 62, 39, 192, 78, 151, 42],       // compute_analytics
+    [125, 232, 78, 47, 233, 47, 47, 237],     // set_dispute_window
+    [24, 89, 203, 20, 202, 21, 27, 242],      // flag_question
+    [157, 63, 123, 30, 24, 146, 128, 235],    // void_question
+    [131, 239, 121, 183, 16, 158, 138, 40],   // regrade
+    [241, 121, 130, 164, 13, 189, 89, 247],   // correct_score
+    [156, 204, 129, 113, 197, 56, 24, 205],   // init_event_log
+    [141, 99, 232, 52, 244, 154, 81, 193],    // set_prize_split
+    [68, 95, 79, 170, 50, 245, 151, 113],     // post_leaderboard_root
+    [33, 236, 14, 214, 124, 31, 112, 252],    // claim_leaderboard_prize
+    [42, 219, 211, 52, 199, 139, 159, 167],   // appoint_scorer
+    [84, 128, 220, 51, 214, 246, 233, 146],   // challenge_score
+    [15, 50, 71, 19, 101, 191, 81, 142],      // release_bond
+    [44, 61, 94, 211, 130, 84, 43, 117],      // init_answer_tree
+    [231, 71, 82, 159, 253, 20, 90, 235],     // submit_compressed_answers
+    [146, 67, 198, 41, 51, 172, 173, 58],     // grade_compressed_answers
+    [228, 107, 203, 188, 137, 169, 152, 122], // top_up_rent
+    [149, 230, 194, 124, 223, 5, 179, 157],   // close_questions
+    [244, 111, 137, 50, 71, 114, 153, 66],    // clone_quiz
+    [245, 51, 247, 234, 31, 9, 40, 227],      // create_template
+    [98, 187, 66, 57, 119, 104, 69, 217],     // set_template_access
+    [223, 110, 151, 6, 6, 188, 219, 153],     // create_from_template
+    [242, 110, 197, 49, 162, 124, 228, 203],  // import_questions
+    [41, 66, 149, 207, 77, 220, 9, 60],       // add_translation
+    [213, 22, 78, 19, 90, 40, 35, 13],        // set_nickname
+    [206, 27, 243, 16, 134, 70, 22, 127],     // init_live_leaderboard
+    [158, 192, 139, 50, 45, 20, 10, 189],     // commit_live_leaderboard
+    [20, 227, 184, 135, 198, 154, 114, 91],   // init_reactions
+    [223, 26, 220, 231, 87, 33, 138, 107],    // send_reaction
+    [13, 105, 82, 248, 250, 223, 252, 236],   // commit_reactions
+    [181, 9, 52, 120, 197, 221, 42, 142],     // create_series
+    [67, 143, 229, 171, 6, 220, 251, 80],     // link_series_quiz
+    [43, 219, 50, 193, 238, 34, 237, 47],     // fund_series
+    [11, 7, 18, 124, 141, 65, 116, 42],       // record_series_quiz
+    [140, 140, 28, 185, 232, 14, 3, 168],     // claim_series_prize
+    [175, 255, 9, 218, 123, 120, 250, 250],   // set_daily_season_length
+    [180, 102, 43, 20, 82, 28, 18, 132],      // end_daily_season
+    [137, 105, 31, 90, 31, 46, 121, 207],     // end_series_season
+    [11, 232, 212, 206, 65, 247, 237, 208],   // set_referral_cut
+    [223, 35, 124, 185, 198, 19, 229, 163],   // pay_referral
+    [43, 34, 249, 50, 128, 225, 98, 239],     // set_prize_threshold
+    [247, 3, 166, 241, 59, 241, 75, 37],      // forfeit_prize
+    [11, 240, 209, 180, 236, 197, 100, 108],  // set_proportional_prizes
+    [125, 129, 116, 160, 41, 138, 65, 242],   // claim_score_prize
+    [249, 251, 210, 15, 108, 59, 214, 79],    // rollover_jackpot
+    [190, 124, 26, 181, 134, 231, 61, 190],   // set_claim_window
+    [64, 168, 221, 224, 42, 216, 138, 144],   // sweep_unclaimed
+    [145, 148, 73, 23, 250, 242, 72, 108],    // set_prize_vesting
+    [208, 190, 166, 114, 203, 225, 140, 208], // claim_vested
+    [17, 182, 62, 184, 189, 158, 128, 146],   // set_usd_entry_fee
+    [213, 121, 214, 255, 156, 175, 226, 241], // contribute_to_prize_pool
+    [110, 148, 182, 9, 237, 155, 222, 1],     // refund_contribution
+    [40, 161, 255, 116, 38, 19, 3, 213],      // set_charity_split
+    [20, 38, 104, 146, 115, 51, 146, 60],     // pay_charity
+    [3, 216, 215, 228, 174, 212, 105, 33],    // set_host_play
+    [253, 39, 180, 157, 69, 169, 12, 180],    // set_join_stake
+    [237, 113, 219, 76, 6, 246, 223, 84],     // reclaim_stake
+    [255, 248, 158, 12, 76, 33, 88, 62],      // set_scoring_mode
+    [15, 123, 78, 205, 4, 144, 201, 100],     // migrate_player_score
+    [2, 9, 142, 249, 165, 161, 162, 34],      // set_question_difficulty
+    [196, 110, 248, 33, 201, 247, 249, 150],  // set_difficulty_scoring
+    [26, 18, 115, 7, 13, 26, 147, 76],        // init_adaptive_bank
+    [108, 36, 162, 21, 84, 139, 176, 234],    // set_question_subset
+    [111, 248, 46, 122, 195, 37, 105, 109],   // reveal_answers
+    [59, 20, 84, 119, 144, 89, 26, 213],      // set_explanation
+    [15, 95, 49, 74, 71, 153, 88, 170],       // set_question_key
+    [190, 128, 2, 0, 240, 197, 66, 26],       // reveal_questions
+    [147, 12, 200, 212, 191, 228, 4, 12],     // commit_question
+    [152, 114, 99, 77, 119, 70, 59, 189],     // reveal_blind_question
+    [175, 48, 160, 193, 6, 109, 188, 203],    // import_signed_questions
+    [109, 115, 54, 217, 232, 77, 131, 65],    // submit_relayed_answers
+    [102, 85, 18, 136, 100, 103, 76, 189],    // refund_duel
+    [210, 144, 178, 27, 201, 43, 56, 4],      // challenge_missing_sheet
+    [10, 119, 232, 221, 159, 20, 155, 243],   // challenge_ranks
+    [101, 50, 225, 6, 133, 1, 145, 38],       // claim_team_prize
+];
 
-#[derive(BorshSerialize, BorshDeserialize, Debug)]
+/// Program instructions, see `unpack` for the wire format.
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, ShankInstruction)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
 pub enum QuizInstruction {
+    /// Create a quiz session PDA at `[b"quiz_session", host, quiz_id]`.
+    #[account(
+        0,
+        writable,
+        signer,
+        name = "host",
+        desc = "Quiz host, pays for the session"
+    )]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    #[account(2, name = "system_program", desc = "System program")]
     InitializeQuiz {
         question_count: u8,
+        quiz_id: u64,
+        delegation: DelegationConfig,
     },
+    /// Create a question PDA at `[b"quiz_question", quiz, question_index]`.
+    #[account(0, writable, signer, name = "host", desc = "Quiz host")]
+    #[account(1, name = "quiz", desc = "Quiz session PDA")]
+    #[account(2, writable, name = "question", desc = "Question PDA")]
+    #[account(3, name = "system_program", desc = "System program")]
     AddQuestion {
         question_index: u8,
         question_text: String,
         options: [String; 4],
         correct_answer_index: u8,
     },
+    #[account(0, signer, name = "host", desc = "Quiz host")]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
     StartQuiz,
-    DelegatePlayer,
+    /// Join a quiz and delegate the answer sheet to the ephemeral rollup. A
+    /// signing host passed as `sponsor` pays the rent instead of the player.
+    /// `referrer` is owed the quiz's referral cut of the entry fee, and
+    /// quizzes priced in USD also take the Pyth price update to convert it.
+    #[account(0, writable, signer, name = "player", desc = "Joining player")]
+    #[account(1, name = "system_program", desc = "System program")]
+    #[account(
+        2,
+        writable,
+        name = "player_answer",
+        desc = "Answer sheet PDA at [b\"player_answer\", quiz, player]"
+    )]
+    #[account(3, name = "owner_program", desc = "This program")]
+    #[account(
+        4,
+        writable,
+        name = "delegation_buffer",
+        desc = "Delegation buffer PDA"
+    )]
+    #[account(
+        5,
+        writable,
+        name = "delegation_record",
+        desc = "Delegation record PDA"
+    )]
+    #[account(
+        6,
+        writable,
+        name = "delegation_metadata",
+        desc = "Delegation metadata PDA"
+    )]
+    #[account(7, name = "delegation_program", desc = "Delegation program")]
+    #[account(8, writable, name = "quiz", desc = "Quiz session PDA")]
+    #[account(
+        9,
+        optional,
+        writable,
+        name = "vault",
+        desc = "Quiz vault, required when the quiz has an entry fee"
+    )]
+    #[account(
+        10,
+        optional,
+        name = "price_update",
+        desc = "Pyth price update, required when the entry fee is set in USD"
+    )]
+    #[account(
+        11,
+        optional,
+        name = "tournament",
+        desc = "Tournament, required when the quiz is a tournament round"
+    )]
+    #[account(
+        12,
+        optional,
+        writable,
+        signer,
+        name = "sponsor",
+        desc = "Quiz host paying the answer sheet and buffer rent"
+    )]
+    DelegatePlayer {
+        validator: Option<Pubkey>,
+        referrer: Option<Pubkey>,
+    },
+    /// `confidence` holds a 1-3 confidence level per answer in
+    /// confidence-weighted quizzes and is empty otherwise.
+    #[account(
+        0,
+        signer,
+        name = "player",
+        desc = "Answering player, or their session key"
+    )]
+    #[account(1, writable, name = "player_answer", desc = "Answer sheet PDA")]
+    #[account(2, name = "quiz", desc = "Quiz session PDA")]
+    #[account(
+        3,
+        optional,
+        name = "session",
+        desc = "Answer session PDA, required when a session key signs"
+    )]
     SubmitAnswers {
         answers: Vec<u8>,
+        confidence: Vec<u8>,
     },
+    /// Sent to the ephemeral rollup, followed by every answer sheet PDA to
+    /// commit.
+    #[account(
+        0,
+        writable,
+        signer,
+        name = "host",
+        desc = "Quiz host, pays for the commit"
+    )]
+    #[account(1, name = "quiz", desc = "Quiz session PDA")]
+    #[account(2, name = "magic_program", desc = "Ephemeral rollup magic program")]
+    #[account(
+        3,
+        writable,
+        name = "magic_context",
+        desc = "Ephemeral rollup magic context"
+    )]
     CommitAnswers,
+    /// Followed by every question PDA in order, then for each player the
+    /// answer sheet, the score PDA to create and the system program. The
+    /// host's stats PDA is created on the first quiz they score. To
+    /// record answer statistics, the system program and every `QuestionStats`
+    /// PDA in order follow last.
+    #[account(
+        0,
+        writable,
+        signer,
+        name = "host",
+        desc = "Quiz host, pays for score accounts"
+    )]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    #[account(
+        2,
+        writable,
+        name = "host_stats",
+        desc = "Host stats PDA at [b\"host_stats\", host]"
+    )]
+    #[account(3, name = "system_program", desc = "System program")]
     CalculateScores,
-    UndelegatePlayer {
-        pda_seeds: Vec<Vec<u8>>,
+    /// Called back by the delegation program when an account is undelegated,
+    /// with `EXTERNAL_UNDELEGATE_DISCRIMINATOR` instead of a sighash. Answer
+    /// sheets are checked and marked committed on the way back.
+    #[account(
+        0,
+        writable,
+        name = "delegated_account",
+        desc = "Answer sheet, quiz session or scoreboard PDA"
+    )]
+    #[account(
+        1,
+        writable,
+        name = "delegation_buffer",
+        desc = "Delegation buffer PDA"
+    )]
+    #[account(2, writable, signer, name = "payer", desc = "Rent payer")]
+    #[account(3, name = "system_program", desc = "System program")]
+    UndelegatePlayer { pda_seeds: Vec<Vec<u8>> },
+    /// `join_grace_slots` after the start new players may still join, 0
+    /// keeps joins open while the quiz is active.
+    #[account(0, signer, name = "host", desc = "Quiz host")]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    SetSubmissionPolicy {
+        allow_resubmission: bool,
+        refresh_slot_on_update: bool,
+        deadline_slot: u64,
+        join_grace_slots: u64,
+    },
+    #[account(
+        0,
+        signer,
+        name = "player",
+        desc = "Answering player, or their session key"
+    )]
+    #[account(1, writable, name = "player_answer", desc = "Answer sheet PDA")]
+    #[account(2, name = "quiz", desc = "Quiz session PDA")]
+    #[account(
+        3,
+        optional,
+        name = "session",
+        desc = "Answer session PDA, required when a session key signs"
+    )]
+    UpdateAnswers {
+        answers: Vec<u8>,
+        confidence: Vec<u8>,
+    },
+    #[account(
+        0,
+        signer,
+        name = "player",
+        desc = "Answering player, or their session key"
+    )]
+    #[account(1, writable, name = "player_answer", desc = "Answer sheet PDA")]
+    #[account(2, name = "quiz", desc = "Quiz session PDA")]
+    #[account(
+        3,
+        optional,
+        name = "question",
+        desc = "Revealed question PDA, required in elimination mode. Adaptive quizzes pass the adaptive bank here and the served question after it"
+    )]
+    #[account(
+        4,
+        optional,
+        name = "session",
+        desc = "Answer session PDA, last account when a session key signs"
+    )]
+    SubmitAnswer { question_index: u8, answer: u8 },
+    #[account(0, signer, name = "host", desc = "Quiz host")]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    SetQuizMode {
+        mode: QuizMode,
+        answer_window_slots: u64,
+    },
+    /// Open the answer window for one question in a live quiz, from now for
+    /// the quiz's `answer_window_slots`.
+    #[account(0, signer, name = "host", desc = "Quiz host")]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    RevealQuestion { index: u8 },
+    #[account(
+        0,
+        writable,
+        signer,
+        name = "captain",
+        desc = "Team captain, pays for the team"
+    )]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    #[account(
+        2,
+        writable,
+        name = "team",
+        desc = "Team PDA at [b\"quiz_team\", quiz, team_id]"
+    )]
+    #[account(3, writable, name = "member", desc = "Captain's membership PDA")]
+    #[account(4, name = "system_program", desc = "System program")]
+    CreateTeam { team_id: u8, name: String },
+    #[account(0, writable, signer, name = "player", desc = "Joining player")]
+    #[account(1, name = "quiz", desc = "Quiz session PDA")]
+    #[account(2, writable, name = "team", desc = "Team PDA")]
+    #[account(
+        3,
+        writable,
+        name = "member",
+        desc = "Membership PDA at [b\"team_member\", quiz, player]"
+    )]
+    #[account(4, name = "system_program", desc = "System program")]
+    JoinTeam { team_id: u8 },
+    #[account(
+        0,
+        writable,
+        signer,
+        name = "player",
+        desc = "Leaving player, receives the membership rent"
+    )]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    #[account(
+        2,
+        writable,
+        name = "team",
+        desc = "Team PDA, closed when its last member leaves"
+    )]
+    #[account(3, writable, name = "member", desc = "Membership PDA")]
+    LeaveTeam { team_id: u8 },
+    /// Followed by the score PDA of every team member, in member order.
+    #[account(0, name = "quiz", desc = "Quiz session PDA")]
+    #[account(1, writable, name = "team", desc = "Team PDA")]
+    FinalizeTeamScore { team_id: u8 },
+    #[account(
+        0,
+        writable,
+        signer,
+        name = "host",
+        desc = "Quiz host, pays for the vault"
+    )]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    #[account(
+        2,
+        writable,
+        name = "vault",
+        desc = "Quiz vault PDA at [b\"quiz_vault\", quiz]"
+    )]
+    #[account(3, name = "system_program", desc = "System program")]
+    SetEntryFee { lamports: u64 },
+    #[account(0, writable, name = "quiz", desc = "Quiz session PDA")]
+    #[account(1, writable, name = "vault", desc = "Quiz vault PDA")]
+    #[account(2, name = "first_score", desc = "First player's score PDA")]
+    #[account(3, name = "second_score", desc = "Second player's score PDA")]
+    #[account(4, writable, name = "first_player", desc = "First player's wallet")]
+    #[account(5, writable, name = "second_player", desc = "Second player's wallet")]
+    SettleDuel,
+    #[account(0, writable, signer, name = "organizer", desc = "Tournament organizer")]
+    #[account(
+        1,
+        writable,
+        name = "tournament",
+        desc = "Tournament PDA at [b\"tournament\", organizer, tournament_id]"
+    )]
+    #[account(2, name = "system_program", desc = "System program")]
+    CreateTournament {
+        tournament_id: u64,
+        round_count: u8,
+        advance_count: u8,
+    },
+    #[account(0, signer, name = "organizer", desc = "Tournament organizer")]
+    #[account(1, signer, name = "host", desc = "Quiz host")]
+    #[account(2, writable, name = "tournament", desc = "Tournament PDA")]
+    #[account(3, writable, name = "quiz", desc = "Quiz session PDA")]
+    LinkTournamentRound { round: u8 },
+    #[account(0, writable, signer, name = "funder", desc = "Prize contributor")]
+    #[account(1, writable, name = "tournament", desc = "Tournament PDA")]
+    #[account(
+        2,
+        writable,
+        name = "vault",
+        desc = "Tournament vault PDA at [b\"tournament_vault\", tournament]"
+    )]
+    #[account(3, name = "system_program", desc = "System program")]
+    FundTournament { lamports: u64 },
+    /// Followed by the score PDA of every player in the round, of which
+    /// there must be at least one.
+    #[account(0, writable, name = "tournament", desc = "Tournament PDA")]
+    #[account(1, name = "quiz", desc = "Quiz session of the current round")]
+    AdvanceBracket,
+    #[account(0, writable, signer, name = "champion", desc = "Tournament champion")]
+    #[account(1, writable, name = "tournament", desc = "Tournament PDA")]
+    #[account(2, writable, name = "vault", desc = "Tournament vault PDA")]
+    ClaimTournamentPrize,
+    #[account(
+        0,
+        writable,
+        signer,
+        name = "authority",
+        desc = "Program upgrade authority, becomes the daily quiz authority"
+    )]
+    #[account(
+        1,
+        writable,
+        name = "config",
+        desc = "Daily config PDA at [b\"daily_config\"]"
+    )]
+    #[account(
+        2,
+        writable,
+        name = "leaderboard",
+        desc = "Leaderboard PDA at [b\"daily_leaderboard\"]"
+    )]
+    #[account(3, name = "system_program", desc = "System program")]
+    #[account(
+        4,
+        name = "program_data",
+        desc = "ProgramData account naming the program's upgrade authority"
+    )]
+    InitializeDailyQuiz { curator: Pubkey },
+    #[account(0, writable, signer, name = "curator", desc = "Daily quiz curator")]
+    #[account(1, name = "config", desc = "Daily config PDA")]
+    #[account(
+        2,
+        writable,
+        name = "daily_quiz",
+        desc = "Daily session PDA at [b\"daily_quiz\", day_index]"
+    )]
+    #[account(3, writable, name = "question", desc = "Question PDA")]
+    #[account(4, name = "system_program", desc = "System program")]
+    PublishDailyQuestion {
+        day_index: u64,
+        question_index: u8,
+        question_text: String,
+        options: [String; 4],
+        correct_answer_index: u8,
+    },
+    /// Followed by every question PDA of the day, in order.
+    #[account(0, writable, signer, name = "player", desc = "Player")]
+    #[account(1, writable, name = "daily_quiz", desc = "Today's daily session PDA")]
+    #[account(
+        2,
+        writable,
+        name = "entry",
+        desc = "Entry PDA at [b\"daily_entry\", daily_quiz, player]"
+    )]
+    #[account(
+        3,
+        writable,
+        name = "streak",
+        desc = "Streak PDA at [b\"daily_streak\", player]"
+    )]
+    #[account(4, writable, name = "leaderboard", desc = "Leaderboard PDA")]
+    #[account(5, name = "system_program", desc = "System program")]
+    PlayDailyQuiz { answers: Vec<u8> },
+    /// Followed by every question PDA of the quiz, in order.
+    #[account(0, writable, signer, name = "player", desc = "Player")]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    #[account(2, writable, name = "player_answer", desc = "Answer sheet PDA")]
+    #[account(
+        3,
+        writable,
+        name = "player_score",
+        desc = "Score PDA at [b\"player_score\", quiz, player]"
+    )]
+    #[account(4, name = "system_program", desc = "System program")]
+    PracticeQuiz { answers: Vec<u8> },
+    #[account(0, writable, signer, name = "host", desc = "Quiz host")]
+    #[account(1, name = "quiz", desc = "Quiz session PDA")]
+    #[account(
+        2,
+        writable,
+        name = "registry",
+        desc = "Registry shard PDA at [b\"quiz_registry\", shard_index]"
+    )]
+    #[account(3, name = "system_program", desc = "System program")]
+    ListQuiz { shard_index: u32, start_slot: u64 },
+    #[account(0, signer, name = "host", desc = "Host that listed the quiz")]
+    #[account(1, name = "quiz", desc = "Quiz session PDA")]
+    #[account(2, writable, name = "registry", desc = "Registry shard PDA")]
+    DelistQuiz { shard_index: u32 },
+    /// Sent to the ephemeral rollup at the end of a quiz, followed by every
+    /// answer sheet PDA to commit and hand back to this program on chain.
+    /// Open to anyone once the quiz deadline has passed.
+    #[account(
+        0,
+        writable,
+        signer,
+        name = "payer",
+        desc = "Quiz host, or anyone after the deadline; pays for the commit"
+    )]
+    #[account(1, name = "quiz", desc = "Quiz session PDA")]
+    #[account(2, name = "magic_program", desc = "Ephemeral rollup magic program")]
+    #[account(
+        3,
+        writable,
+        name = "magic_context",
+        desc = "Ephemeral rollup magic context"
+    )]
+    CommitAndUndelegateAnswers,
+    /// Delegate a started quiz session to the ephemeral rollup so reveals and
+    /// other live updates run there. Players can no longer join on chain.
+    #[account(0, writable, signer, name = "host", desc = "Quiz host")]
+    #[account(1, name = "system_program", desc = "System program")]
+    #[account(2, writable, name = "quiz", desc = "Quiz session PDA")]
+    #[account(3, name = "owner_program", desc = "This program")]
+    #[account(
+        4,
+        writable,
+        name = "delegation_buffer",
+        desc = "Delegation buffer PDA"
+    )]
+    #[account(
+        5,
+        writable,
+        name = "delegation_record",
+        desc = "Delegation record PDA"
+    )]
+    #[account(
+        6,
+        writable,
+        name = "delegation_metadata",
+        desc = "Delegation metadata PDA"
+    )]
+    #[account(7, name = "delegation_program", desc = "Delegation program")]
+    DelegateQuiz { validator: Option<Pubkey> },
+    /// Sent to the ephemeral rollup to commit a delegated quiz session and
+    /// hand it back to this program on chain, before scoring.
+    #[account(0, writable, signer, name = "host", desc = "Quiz host")]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    #[account(2, name = "magic_program", desc = "Ephemeral rollup magic program")]
+    #[account(
+        3,
+        writable,
+        name = "magic_context",
+        desc = "Ephemeral rollup magic context"
+    )]
+    CommitAndUndelegateQuiz,
+    /// Create the scoreboard PDA with a slot for every joined player and
+    /// delegate it to the ephemeral rollup for `GradeAnswers`. The quiz moves
+    /// to `Scoring` and takes no more players or answers.
+    #[account(0, writable, signer, name = "host", desc = "Quiz host")]
+    #[account(1, name = "system_program", desc = "System program")]
+    #[account(2, writable, name = "quiz", desc = "Quiz session PDA")]
+    #[account(
+        3,
+        writable,
+        name = "scoreboard",
+        desc = "Scoreboard PDA at [b\"quiz_scoreboard\", quiz]"
+    )]
+    #[account(4, name = "owner_program", desc = "This program")]
+    #[account(
+        5,
+        writable,
+        name = "delegation_buffer",
+        desc = "Delegation buffer PDA"
+    )]
+    #[account(
+        6,
+        writable,
+        name = "delegation_record",
+        desc = "Delegation record PDA"
+    )]
+    #[account(
+        7,
+        writable,
+        name = "delegation_metadata",
+        desc = "Delegation metadata PDA"
+    )]
+    #[account(8, name = "delegation_program", desc = "Delegation program")]
+    DelegateScoreboard { validator: Option<Pubkey> },
+    /// Sent to the ephemeral rollup to grade answer sheets into the
+    /// scoreboard, followed by every question PDA in order and the answer
+    /// sheet PDAs to grade. Sheets can be graded as they come in, grading one
+    /// again replaces its score. Buzzer quizzes need every sheet at once.
+    #[account(0, signer, name = "host", desc = "Quiz host")]
+    #[account(1, name = "quiz", desc = "Quiz session PDA")]
+    #[account(2, writable, name = "scoreboard", desc = "Scoreboard PDA")]
+    GradeAnswers,
+    /// Sent to the ephemeral rollup to finalize the scoreboard, commit it and
+    /// hand it back to this program on chain.
+    #[account(0, writable, signer, name = "host", desc = "Quiz host")]
+    #[account(1, name = "quiz", desc = "Quiz session PDA")]
+    #[account(2, writable, name = "scoreboard", desc = "Scoreboard PDA")]
+    #[account(3, name = "magic_program", desc = "Ephemeral rollup magic program")]
+    #[account(
+        4,
+        writable,
+        name = "magic_context",
+        desc = "Ephemeral rollup magic context"
+    )]
+    CommitScoreboard,
+    /// Join a base layer quiz, the answer sheet stays on chain and answers
+    /// are written to it directly. A signing host passed as `sponsor` pays
+    /// the rent instead of the player, and `referrer` works as in
+    /// `DelegatePlayer`.
+    #[account(0, writable, signer, name = "player", desc = "Joining player")]
+    #[account(1, name = "system_program", desc = "System program")]
+    #[account(
+        2,
+        writable,
+        name = "player_answer",
+        desc = "Answer sheet PDA at [b\"player_answer\", quiz, player]"
+    )]
+    #[account(3, writable, name = "quiz", desc = "Quiz session PDA")]
+    #[account(
+        4,
+        optional,
+        writable,
+        name = "vault",
+        desc = "Quiz vault, required when the quiz has an entry fee"
+    )]
+    #[account(
+        5,
+        optional,
+        name = "price_update",
+        desc = "Pyth price update, required when the entry fee is set in USD"
+    )]
+    #[account(
+        6,
+        optional,
+        name = "tournament",
+        desc = "Tournament, required when the quiz is a tournament round"
+    )]
+    #[account(
+        7,
+        optional,
+        writable,
+        signer,
+        name = "sponsor",
+        desc = "Quiz host paying the answer sheet rent"
+    )]
+    JoinQuiz { referrer: Option<Pubkey> },
+    /// Let the session key sign answers for the player in this quiz until
+    /// `expires_slot`, capped at the quiz deadline. Sending it again replaces
+    /// the key. `lamports` are moved to the key to pay its transaction fees.
+    #[account(0, writable, signer, name = "player", desc = "Player")]
+    #[account(1, name = "quiz", desc = "Quiz session PDA")]
+    #[account(
+        2,
+        writable,
+        name = "session",
+        desc = "Answer session PDA at [b\"answer_session\", quiz, player]"
+    )]
+    #[account(3, writable, name = "session_key", desc = "Session key")]
+    #[account(4, name = "system_program", desc = "System program")]
+    CreateSession { expires_slot: u64, lamports: u64 },
+    /// Schedule the quiz to open at `start_slot` through `ActivateQuiz`, 0
+    /// cancels the schedule.
+    #[account(0, signer, name = "host", desc = "Quiz host")]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    ScheduleStart { start_slot: u64 },
+    /// Crank that opens a scheduled quiz once its start slot is reached,
+    /// callable by anyone.
+    #[account(0, writable, name = "quiz", desc = "Quiz session PDA")]
+    ActivateQuiz,
+    /// Stop taking joins and answers so the quiz can be scored. The host can
+    /// close any time, anyone else once the deadline slot has passed.
+    #[account(
+        0,
+        signer,
+        name = "caller",
+        desc = "Quiz host, or anyone after the deadline"
+    )]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    CloseSubmissions,
+    /// Call the quiz off before it is scored. Rejected while the vault holds
+    /// entry fees, contributions are refunded with `RefundContribution`.
+    /// Cancelling a started quiz counts against the host's
+    /// reputation.
+    #[account(0, writable, signer, name = "host", desc = "Quiz host")]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    #[account(
+        2,
+        writable,
+        name = "host_stats",
+        desc = "Host stats PDA at [b\"host_stats\", host]"
+    )]
+    #[account(3, name = "system_program", desc = "System program")]
+    CancelQuiz,
+    /// Keep taking answers of an async quiz for `late_window_slots` after
+    /// the deadline, taking a point off a late sheet per
+    /// `late_slots_per_point` slots past it.
+    #[account(0, signer, name = "host", desc = "Quiz host")]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    SetLatePolicy {
+        late_window_slots: u64,
+        late_slots_per_point: u64,
+    },
+    /// Weigh graded answers by the confidence players submit with them: a
+    /// correct answer earns its confidence, a wrong one loses it.
+    #[account(0, signer, name = "host", desc = "Quiz host")]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    SetConfidenceScoring { enabled: bool },
+    /// Attach a hint to a question before the quiz starts. Buying it costs
+    /// the player `point_cost` points at grading and `lamport_cost` lamports
+    /// into the prize pool, lamport prices need a base layer quiz.
+    #[account(0, writable, signer, name = "host", desc = "Quiz host")]
+    #[account(1, name = "quiz", desc = "Quiz session PDA")]
+    #[account(2, name = "question", desc = "Question PDA")]
+    #[account(
+        3,
+        writable,
+        name = "hint",
+        desc = "Hint PDA at [b\"question_hint\", quiz, question_index]"
+    )]
+    #[account(4, name = "system_program", desc = "System program")]
+    #[account(
+        5,
+        optional,
+        writable,
+        name = "vault",
+        desc = "Quiz vault PDA, created for hints with a lamport price"
+    )]
+    SetHint {
+        question_index: u8,
+        hint: String,
+        point_cost: u8,
+        lamport_cost: u64,
     },
+    /// Unlock a question's hint on the player's answer sheet. Hints with a
+    /// lamport price also take the quiz vault and the system program.
+    #[account(0, writable, signer, name = "player", desc = "Player buying the hint")]
+    #[account(1, writable, name = "player_answer", desc = "Player answer PDA")]
+    #[account(
+        2,
+        writable,
+        name = "quiz",
+        desc = "Quiz session PDA, only written for paid hints"
+    )]
+    #[account(3, name = "hint", desc = "Hint PDA")]
+    #[account(4, optional, writable, name = "vault", desc = "Quiz vault PDA")]
+    #[account(5, optional, name = "system_program", desc = "System program")]
+    BuyHint { question_index: u8 },
+    /// Make questions `first_question..=last_question` worth `multiplier`
+    /// points each before the quiz starts, e.g. a double-points final round.
+    /// The question PDAs follow the quiz in order.
+    #[account(0, signer, name = "host", desc = "Quiz host")]
+    #[account(1, name = "quiz", desc = "Quiz session PDA")]
+    #[account(2, writable, name = "questions", desc = "Question PDAs in the range")]
+    SetRoundMultiplier {
+        first_question: u8,
+        last_question: u8,
+        multiplier: u8,
+    },
+    /// Reveal a question in a live quiz with its own answer window. An
+    /// `open_slot` in the past opens it now, a `close_slot` of 0 keeps it
+    /// open until the next reveal.
+    #[account(0, signer, name = "host", desc = "Quiz host")]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    OpenQuestion {
+        index: u8,
+        open_slot: u64,
+        close_slot: u64,
+    },
+    /// Write the `QuizAnalytics` PDA of a completed quiz whose scores were
+    /// calculated with question stats. Followed by every player's score PDA,
+    /// then each question PDA and its `QuestionStats` PDA in order. Anyone
+    /// can call it and pays the rent.
+    #[account(
+        0,
+        writable,
+        signer,
+        name = "payer",
+        desc = "Pays for the analytics account"
+    )]
+    #[account(1, name = "quiz", desc = "Quiz session PDA")]
+    #[account(
+        2,
+        writable,
+        name = "analytics",
+        desc = "Analytics PDA at [b\"quiz_analytics\", quiz]"
+    )]
+    #[account(3, name = "system_program", desc = "System program")]
+    ComputeAnalytics,
+    /// Let players flag questions for `slots` after the quiz is scored.
+    #[account(0, signer, name = "host", desc = "Quiz host")]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    SetDisputeWindow { slots: u64 },
+    /// Flag a question of a scored quiz during its dispute window. Only
+    /// players with a score can flag, once per question.
+    #[account(0, writable, signer, name = "player", desc = "Flagging player")]
+    #[account(1, name = "quiz", desc = "Quiz session PDA")]
+    #[account(2, name = "player_score", desc = "Player score PDA")]
+    #[account(
+        3,
+        writable,
+        name = "dispute",
+        desc = "Dispute PDA at [b\"question_dispute\", quiz, question_index]"
+    )]
+    #[account(
+        4,
+        writable,
+        name = "flag",
+        desc = "Flag PDA at [b\"dispute_flag\", quiz, question_index, player]"
+    )]
+    #[account(5, name = "system_program", desc = "System program")]
+    FlagQuestion { question_index: u8, reason_code: u8 },
+    /// Void a question of a scored quiz, before prizes are paid out. Run
+    /// `Regrade` afterwards to apply it to the scores. Counts as an upheld
+    /// dispute in the host's stats.
+    #[account(0, writable, signer, name = "host", desc = "Quiz host")]
+    #[account(1, name = "quiz", desc = "Quiz session PDA")]
+    #[account(2, writable, name = "question", desc = "Question PDA")]
+    #[account(3, writable, name = "host_stats", desc = "Host stats PDA")]
+    #[account(4, name = "system_program", desc = "System program")]
+    VoidQuestion { question_index: u8, mode: VoidMode },
+    /// Recompute every player's score of a scored quiz, before prizes are
+    /// paid out. Followed by every question PDA in order, then for each
+    /// player their answer sheet and their writable score PDA, then the
+    /// writable `ScoreAudit` pages the changed scores are recorded on.
+    #[account(0, writable, signer, name = "host", desc = "Quiz host")]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    #[account(2, name = "system_program", desc = "System program")]
+    Regrade,
+    /// Overwrite one player's score of a scored quiz by hand, before prizes
+    /// are paid out. `reason` is a `ScoreCorrection::REASON_*` code other
+    /// than `REASON_REGRADE`.
+    #[account(0, writable, signer, name = "host", desc = "Quiz host")]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    #[account(2, writable, name = "player_score", desc = "Player score PDA")]
+    #[account(3, name = "system_program", desc = "System program")]
+    #[account(
+        4,
+        writable,
+        name = "score_audit",
+        desc = "Score audit page the correction falls on, at [b\"score_audit\", quiz, page]"
+    )]
+    CorrectScore { new_score: u32, reason: u8 },
+    /// Give a base layer quiz a `QuizEventLog` before it starts. From then
+    /// on `JoinQuiz`, `PracticeQuiz`, `SubmitAnswers`, `UpdateAnswers`,
+    /// `SubmitAnswer`, `RevealQuestion`, `OpenQuestion` and `CalculateScores`
+    /// take the writable log right after their fixed accounts.
+    #[account(0, writable, signer, name = "host", desc = "Quiz host")]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    #[account(
+        2,
+        writable,
+        name = "event_log",
+        desc = "Event log PDA at [b\"event_log\", quiz]"
+    )]
+    #[account(3, name = "system_program", desc = "System program")]
+    InitEventLog,
+    /// Split the prize pool between ranks 1, 2 and 3 in basis points before
+    /// the quiz starts, paid out by `ClaimLeaderboardPrize`.
+    #[account(0, signer, name = "host", desc = "Quiz host")]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    SetPrizeSplit {
+        split_bps: [u16; QuizSession::PRIZE_RANKS],
+    },
+    /// Finalize a quiz too large for `CalculateScores` with the Merkle root
+    /// of every player's `(player, score, rank)` standing, see `merkle`.
+    /// An appointed scorer also passes the writable bond PDA
+    /// (`[b"scorer_bond", quiz]`) and the system program, and deposits the
+    /// bond.
+    #[account(
+        0,
+        writable,
+        signer,
+        name = "scorer",
+        desc = "Appointed scorer, or the quiz host"
+    )]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    PostLeaderboardRoot { root: [u8; 32] },
+    /// Claim the prize of a rank by proving the standing against the
+    /// quiz's leaderboard root, once the dispute window has closed.
+    #[account(0, writable, signer, name = "player", desc = "Ranked player")]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    #[account(2, writable, name = "vault", desc = "Quiz vault PDA")]
+    #[account(
+        3,
+        writable,
+        name = "claim",
+        desc = "Claim PDA at [b\"leaderboard_claim\", quiz, player]"
+    )]
+    #[account(4, name = "system_program", desc = "System program")]
+    ClaimLeaderboardPrize {
+        score: u32,
+        rank: u32,
+        proof: Vec<[u8; 32]>,
+    },
+    /// Let `scorer` finalize the quiz with `PostLeaderboardRoot` from scores
+    /// computed off chain, putting up `bond_lamports` that a successful
+    /// `ChallengeScore` takes. Challenges run during the dispute window.
+    #[account(0, signer, name = "host", desc = "Quiz host")]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    AppointScorer { scorer: Pubkey, bond_lamports: u64 },
+    /// During the dispute window, prove the appointed scorer posted a wrong
+    /// score: the standing's Merkle proof and the player's sheet, regraded
+    /// against every question PDA passed in order after the fixed accounts.
+    /// The challenger takes the bond, the quiz goes back to `Scoring` and the
+    /// host's stats count an upheld dispute.
+    #[account(0, writable, signer, name = "challenger", desc = "Challenger")]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    #[account(2, name = "player_answer", desc = "Challenged player's answer sheet")]
+    #[account(3, writable, name = "bond", desc = "Scorer bond PDA")]
+    #[account(4, writable, name = "host_stats", desc = "Quiz host's stats PDA")]
+    #[account(5, name = "system_program", desc = "System program")]
+    ChallengeScore {
+        score: u32,
+        rank: u32,
+        proof: Vec<[u8; 32]>,
+    },
+    /// Return the bond to the scorer once the dispute window has closed.
+    #[account(0, writable, signer, name = "scorer", desc = "Appointed scorer")]
+    #[account(1, name = "quiz", desc = "Quiz session PDA")]
+    #[account(2, writable, name = "bond", desc = "Scorer bond PDA")]
+    ReleaseBond,
+    /// Let players of a free, self-paced base layer quiz submit compressed
+    /// sheets into an `AnswerTree` at `[b"answer_tree", quiz]` instead of
+    /// joining with a `PlayerAnswer` each. Set up before the quiz starts.
+    #[account(0, writable, signer, name = "host", desc = "Quiz host")]
+    #[account(1, name = "quiz", desc = "Quiz session PDA")]
+    #[account(2, writable, name = "answer_tree", desc = "Answer tree PDA")]
+    #[account(3, name = "system_program", desc = "System program")]
+    InitAnswerTree,
+    /// Append the player's sheet to the quiz's answer tree, no join needed.
+    /// The sheet is logged in a `CompressedAnswersSubmitted` event for
+    /// indexers to rebuild the tree from. Each player submits once.
+    #[account(0, writable, signer, name = "player", desc = "Answering player")]
+    #[account(1, name = "quiz", desc = "Quiz session PDA")]
+    #[account(2, writable, name = "answer_tree", desc = "Answer tree PDA")]
+    #[account(
+        3,
+        writable,
+        name = "compressed_sheet",
+        desc = "Compressed sheet PDA at [b\"compressed_sheet\", quiz, player]"
+    )]
+    #[account(4, name = "system_program", desc = "System program")]
+    SubmitCompressedAnswers {
+        answers: Vec<u8>,
+        confidence: Vec<u8>,
+    },
+    /// Once the quiz is scored, grade a compressed sheet into the player's
+    /// `PlayerScore`, proving it against the answer tree root. Every
+    /// question PDA follows the fixed accounts in order. Anyone may grade
+    /// a sheet, which must be the one recorded for its player.
+    #[account(0, writable, signer, name = "payer", desc = "Pays the score PDA rent")]
+    #[account(1, name = "quiz", desc = "Quiz session PDA")]
+    #[account(2, writable, name = "answer_tree", desc = "Answer tree PDA")]
+    #[account(3, writable, name = "player_score", desc = "Player score PDA")]
+    #[account(4, name = "system_program", desc = "System program")]
+    #[account(5, name = "compressed_sheet", desc = "Player's compressed sheet PDA")]
+    GradeCompressedAnswers {
+        sheet: CompressedAnswers,
+        proof: Vec<[u8; 32]>,
+    },
+    /// Add `lamports` to any account the program owns, e.g. one grown past
+    /// its rent reserve. Fails unless the account ends up rent-exempt.
+    #[account(0, writable, signer, name = "payer", desc = "Pays the lamports")]
+    #[account(1, writable, name = "account", desc = "Program-owned account")]
+    #[account(2, name = "system_program", desc = "System program")]
+    TopUpRent { lamports: u64 },
+    /// Close every question PDA of a completed or cancelled quiz, passed in
+    /// order after the fixed accounts, and refund their rent to the host.
+    /// Completed quizzes wait until the dispute window has closed and every
+    /// compressed sheet has been graded.
+    #[account(0, writable, signer, name = "host", desc = "Quiz host")]
+    #[account(1, name = "quiz", desc = "Quiz session PDA")]
+    #[account(
+        2,
+        name = "answer_tree",
+        desc = "Answer tree PDA, checked when the quiz has one"
+    )]
+    CloseQuestions,
+    /// Start a new draft quiz at `[b"quiz_session", host, quiz_id]` with the
+    /// settings and questions of one of the host's completed quizzes. Each
+    /// source question PDA is followed by the new one at the same index,
+    /// in order after the fixed accounts. Schedules, tournaments and the
+    /// scorer aren't copied.
+    #[account(0, writable, signer, name = "host", desc = "Host of both quizzes")]
+    #[account(1, name = "source_quiz", desc = "Completed quiz to copy")]
+    #[account(2, writable, name = "quiz", desc = "New quiz session PDA")]
+    #[account(3, name = "system_program", desc = "System program")]
+    CloneQuiz { quiz_id: u64 },
+    /// Publish the questions and settings of one of the author's quizzes as
+    /// a template at `[b"quiz_template", author, template_id]`. Each source
+    /// question PDA is followed by the template question PDA
+    /// (`[b"template_question", template, question_index]`) at the same
+    /// index, in order after the fixed accounts.
+    #[account(0, writable, signer, name = "author", desc = "Template author")]
+    #[account(1, name = "source_quiz", desc = "Author's quiz to copy")]
+    #[account(2, writable, name = "template", desc = "Template PDA")]
+    #[account(3, name = "system_program", desc = "System program")]
+    CreateTemplate {
+        template_id: u64,
+        title: String,
+        public: bool,
+    },
+    /// Open the template to everyone, or only to the author and up to 8
+    /// `allowed` hosts.
+    #[account(0, signer, name = "author", desc = "Template author")]
+    #[account(1, writable, name = "template", desc = "Template PDA")]
+    SetTemplateAccess { public: bool, allowed: Vec<Pubkey> },
+    /// Create a draft quiz at `[b"quiz_session", host, quiz_id]` from a
+    /// template the host may use. Each template question PDA is followed by
+    /// the new question PDA at the same index, in order after the fixed
+    /// accounts.
+    #[account(0, writable, signer, name = "host", desc = "Host of the new quiz")]
+    #[account(1, writable, name = "template", desc = "Template PDA")]
+    #[account(2, writable, name = "quiz", desc = "New quiz session PDA")]
+    #[account(3, name = "system_program", desc = "System program")]
+    CreateFromTemplate {
+        quiz_id: u64,
+        delegation: DelegationConfig,
+    },
+    /// Create question PDAs for `questions` at consecutive indices from
+    /// `first_index`, in one transaction. The batch is bounded by the
+    /// transaction size limit, so long quizzes are imported in several calls.
+    #[account(0, writable, signer, name = "host", desc = "Quiz host")]
+    #[account(1, name = "quiz", desc = "Quiz session PDA")]
+    #[account(2, name = "system_program", desc = "System program")]
+    #[account(
+        3,
+        writable,
+        name = "questions",
+        desc = "Question PDAs, one per imported question in index order"
+    )]
+    ImportQuestions {
+        first_index: u8,
+        questions: Vec<QuestionData>,
+    },
+    /// Add a translation of a question before the quiz starts. Options are
+    /// given in the question's own order, so answer indices mean the same
+    /// thing in every language.
+    #[account(0, writable, signer, name = "host", desc = "Quiz host")]
+    #[account(1, name = "quiz", desc = "Quiz session PDA")]
+    #[account(2, name = "question", desc = "Question PDA")]
+    #[account(
+        3,
+        writable,
+        name = "translation",
+        desc = "Translation PDA at [b\"question_translation\", quiz, question_index, language]"
+    )]
+    #[account(4, name = "system_program", desc = "System program")]
+    AddTranslation {
+        question_index: u8,
+        language: [u8; 2],
+        question_text: String,
+        options: [String; 4],
+    },
+    /// Set the player's nickname, creating their profile on first use. With
+    /// `reserve` the name is also claimed so no other player can reserve it;
+    /// changing a reserved name releases the old claim.
+    #[account(0, writable, signer, name = "player", desc = "Player")]
+    #[account(
+        1,
+        writable,
+        name = "profile",
+        desc = "Profile PDA at [b\"player_profile\", player]"
+    )]
+    #[account(2, name = "system_program", desc = "System program")]
+    #[account(
+        3,
+        optional,
+        writable,
+        name = "claim",
+        desc = "Claim PDA at [b\"nickname\", lowercased nickname], when reserving"
+    )]
+    #[account(
+        4,
+        optional,
+        writable,
+        name = "previous_claim",
+        desc = "Claim PDA of the reserved nickname being replaced"
+    )]
+    SetNickname { nickname: String, reserve: bool },
+    /// Create the live leaderboard of a live quiz before it starts, and
+    /// delegate it to the ephemeral rollup unless the quiz plays on the base
+    /// layer. `SubmitAnswer` then takes the writable leaderboard and the
+    /// answered question's PDA after its other accounts, before any session.
+    #[account(0, writable, signer, name = "host", desc = "Quiz host")]
+    #[account(1, name = "system_program", desc = "System program")]
+    #[account(2, writable, name = "quiz", desc = "Quiz session PDA")]
+    #[account(
+        3,
+        writable,
+        name = "leaderboard",
+        desc = "Live leaderboard PDA at [b\"live_leaderboard\", quiz]"
+    )]
+    #[account(4, optional, name = "owner_program", desc = "This program")]
+    #[account(
+        5,
+        optional,
+        writable,
+        name = "delegation_buffer",
+        desc = "Delegation buffer PDA"
+    )]
+    #[account(
+        6,
+        optional,
+        writable,
+        name = "delegation_record",
+        desc = "Delegation record PDA"
+    )]
+    #[account(
+        7,
+        optional,
+        writable,
+        name = "delegation_metadata",
+        desc = "Delegation metadata PDA"
+    )]
+    #[account(8, optional, name = "delegation_program", desc = "Delegation program")]
+    InitLiveLeaderboard { validator: Option<Pubkey> },
+    /// Sent to the ephemeral rollup to commit the live leaderboard and hand
+    /// it back to this program on chain.
+    #[account(0, writable, signer, name = "host", desc = "Quiz host")]
+    #[account(1, name = "quiz", desc = "Quiz session PDA")]
+    #[account(2, writable, name = "leaderboard", desc = "Live leaderboard PDA")]
+    #[account(3, name = "magic_program", desc = "Ephemeral rollup magic program")]
+    #[account(
+        4,
+        writable,
+        name = "magic_context",
+        desc = "Ephemeral rollup magic context"
+    )]
+    CommitLiveLeaderboard,
+    /// Create the reaction counters of a quiz that hasn't finished, delegated
+    /// to the ephemeral rollup unless the quiz plays on the base layer.
+    /// Players then react at most once every `cooldown_slots`.
+    #[account(0, writable, signer, name = "host", desc = "Quiz host")]
+    #[account(1, name = "system_program", desc = "System program")]
+    #[account(2, name = "quiz", desc = "Quiz session PDA")]
+    #[account(
+        3,
+        writable,
+        name = "reactions",
+        desc = "Reactions PDA at [b\"quiz_reactions\", quiz]"
+    )]
+    #[account(4, optional, name = "owner_program", desc = "This program")]
+    #[account(
+        5,
+        optional,
+        writable,
+        name = "delegation_buffer",
+        desc = "Delegation buffer PDA"
+    )]
+    #[account(
+        6,
+        optional,
+        writable,
+        name = "delegation_record",
+        desc = "Delegation record PDA"
+    )]
+    #[account(
+        7,
+        optional,
+        writable,
+        name = "delegation_metadata",
+        desc = "Delegation metadata PDA"
+    )]
+    #[account(8, optional, name = "delegation_program", desc = "Delegation program")]
+    InitReactions {
+        cooldown_slots: u64,
+        validator: Option<Pubkey>,
+    },
+    /// A joined player reacts while the quiz runs, counted in the quiz's
+    /// reactions and emitted as a `ReactionSent` event.
+    #[account(0, signer, name = "player", desc = "Reacting player")]
+    #[account(1, writable, name = "player_answer", desc = "Answer sheet PDA")]
+    #[account(2, name = "quiz", desc = "Quiz session PDA")]
+    #[account(3, writable, name = "reactions", desc = "Reactions PDA")]
+    SendReaction { emoji_code: u8 },
+    /// Sent to the ephemeral rollup to commit the reaction counters and hand
+    /// them back to this program on chain.
+    #[account(0, writable, signer, name = "host", desc = "Quiz host")]
+    #[account(1, name = "quiz", desc = "Quiz session PDA")]
+    #[account(2, writable, name = "reactions", desc = "Reactions PDA")]
+    #[account(3, name = "magic_program", desc = "Ephemeral rollup magic program")]
+    #[account(
+        4,
+        writable,
+        name = "magic_context",
+        desc = "Ephemeral rollup magic context"
+    )]
+    CommitReactions,
+    #[account(0, writable, signer, name = "organizer", desc = "Series organizer")]
+    #[account(
+        1,
+        writable,
+        name = "series",
+        desc = "Series PDA at [b\"series\", organizer, series_id]"
+    )]
+    #[account(2, name = "system_program", desc = "System program")]
+    CreateSeries { series_id: u64, quiz_count: u8 },
+    /// Put a quiz at `position` of the series before it starts. The organizer
+    /// and the quiz host both sign.
+    #[account(0, signer, name = "organizer", desc = "Series organizer")]
+    #[account(1, signer, name = "host", desc = "Quiz host")]
+    #[account(2, writable, name = "series", desc = "Series PDA")]
+    #[account(3, name = "quiz", desc = "Quiz session PDA")]
+    LinkSeriesQuiz { position: u8 },
+    #[account(0, writable, signer, name = "funder", desc = "Prize contributor")]
+    #[account(1, writable, name = "series", desc = "Series PDA")]
+    #[account(
+        2,
+        writable,
+        name = "vault",
+        desc = "Series vault PDA at [b\"series_vault\", series]"
+    )]
+    #[account(3, name = "system_program", desc = "System program")]
+    FundSeries { lamports: u64 },
+    /// Add a scored quiz of the series to the standings once its dispute
+    /// window closes, followed by the score PDA of every player in it.
+    #[account(0, writable, name = "series", desc = "Series PDA")]
+    #[account(1, name = "quiz", desc = "Quiz session PDA")]
+    RecordSeriesQuiz,
+    /// The series leader takes the prize once every quiz is recorded.
+    #[account(0, writable, signer, name = "leader", desc = "Series leader")]
+    #[account(1, writable, name = "series", desc = "Series PDA")]
+    #[account(2, writable, name = "vault", desc = "Series vault PDA")]
+    ClaimSeriesPrize,
+    /// Let anyone end a daily leaderboard season after `season_days`, 0
+    /// leaves season ends to the authority.
+    #[account(0, signer, name = "authority", desc = "Daily quiz authority")]
+    #[account(1, writable, name = "config", desc = "Daily config PDA")]
+    SetDailySeasonLength { season_days: u64 },
+    /// Archive the daily leaderboard and start a new season with an empty
+    /// board. Player streaks carry over.
+    #[account(
+        0,
+        writable,
+        signer,
+        name = "caller",
+        desc = "Authority, or anyone once the season has run its length"
+    )]
+    #[account(1, name = "config", desc = "Daily config PDA")]
+    #[account(2, writable, name = "leaderboard", desc = "Daily leaderboard PDA")]
+    #[account(
+        3,
+        writable,
+        name = "archive",
+        desc = "Archive PDA at [b\"season_archive\", leaderboard, season]"
+    )]
+    #[account(4, name = "system_program", desc = "System program")]
+    EndDailySeason,
+    /// Archive the standings of a decided series and start its next season
+    /// of `quiz_count` quizzes.
+    #[account(0, writable, signer, name = "organizer", desc = "Series organizer")]
+    #[account(1, writable, name = "series", desc = "Series PDA")]
+    #[account(
+        2,
+        writable,
+        name = "archive",
+        desc = "Archive PDA at [b\"season_archive\", series, season]"
+    )]
+    #[account(3, name = "system_program", desc = "System program")]
+    EndSeriesSeason { quiz_count: u8 },
+    /// Set the share of each referred player's entry fee, in basis points,
+    /// kept aside for their referrer before the quiz starts.
+    #[account(0, signer, name = "host", desc = "Quiz host")]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    SetReferralCut { bps: u16 },
+    /// Pay a referrer the cut of a referred player's entry fee once the quiz
+    /// is scored. Anyone may send it.
+    #[account(0, writable, name = "referrer", desc = "Referrer on the answer sheet")]
+    #[account(1, name = "quiz", desc = "Quiz session PDA")]
+    #[account(
+        2,
+        writable,
+        name = "player_answer",
+        desc = "Referred player's answer sheet"
+    )]
+    #[account(3, writable, name = "vault", desc = "Quiz vault PDA")]
+    PayReferral,
+    /// Require a minimum score for ranked prizes before the quiz starts:
+    /// `min_score` points or `min_score_bps` of the question count,
+    /// whichever is higher. Shares of ranks that miss it go where
+    /// `remainder` says, through `ForfeitPrize`.
+    #[account(0, signer, name = "host", desc = "Quiz host")]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    SetPrizeThreshold {
+        min_score: u8,
+        min_score_bps: u16,
+        remainder: PrizeRemainder,
+    },
+    /// Hand the share of a prize rank to the quiz's `PrizeRemainder` once the
+    /// dispute window has closed, by proving the `(player, score, rank)`
+    /// standing misses the prize threshold. Ranks beyond the player count
+    /// need no proof. Anyone may send it.
+    #[account(0, writable, name = "quiz", desc = "Quiz session PDA")]
+    #[account(1, writable, name = "vault", desc = "Quiz vault PDA")]
+    #[account(2, writable, name = "host", desc = "Quiz host")]
+    ForfeitPrize {
+        rank: u32,
+        player: Pubkey,
+        score: u32,
+        proof: Vec<[u8; 32]>,
+    },
+    /// Share the prize pool by score instead of by rank before the quiz
+    /// starts, for quizzes finalized with `CalculateScores`. Every player at
+    /// or above the prize threshold is paid `score / total score` of the
+    /// pool by `ClaimScorePrize`.
+    #[account(0, signer, name = "host", desc = "Quiz host")]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    SetProportionalPrizes { enabled: bool },
+    /// Claim a player's share of a proportional prize pool once the dispute
+    /// window has closed. The first claim settles the quiz, so scores can no
+    /// longer be corrected.
+    #[account(0, writable, signer, name = "player", desc = "Scored player")]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    #[account(2, writable, name = "vault", desc = "Quiz vault PDA")]
+    #[account(3, name = "player_score", desc = "Player's score PDA")]
+    #[account(
+        4,
+        writable,
+        name = "claim",
+        desc = "Claim PDA at [b\"leaderboard_claim\", quiz, player]"
+    )]
+    #[account(5, name = "system_program", desc = "System program")]
+    ClaimScorePrize,
+    /// Move the jackpot of a scored `PrizeRemainder::RollOver` quiz into the
+    /// vault of the quiz at the next position of its series, once the
+    /// dispute window has closed. The jackpot is the forfeited rank shares,
+    /// or the whole pool of a proportional quiz where nobody qualified.
+    #[account(0, writable, signer, name = "host", desc = "Host of the scored quiz")]
+    #[account(1, name = "series", desc = "Series PDA both quizzes are linked to")]
+    #[account(2, writable, name = "quiz", desc = "Scored quiz session PDA")]
+    #[account(3, writable, name = "vault", desc = "Scored quiz vault PDA")]
+    #[account(4, writable, name = "next_quiz", desc = "Next quiz session PDA")]
+    #[account(5, writable, name = "next_vault", desc = "Next quiz vault PDA")]
+    #[account(6, name = "system_program", desc = "System program")]
+    RolloverJackpot,
+    /// Close prize claims, duel settlement and referral payments `slots`
+    /// after the dispute window, 0 to leave them open for ever.
+    #[account(0, signer, name = "host", desc = "Quiz host")]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    SetClaimWindow { slots: u64 },
+    /// Once claims have closed, send everything left in the quiz vault above
+    /// rent and still vesting prizes to an account of the host's choosing and
+    /// settle the quiz.
+    #[account(0, signer, name = "host", desc = "Quiz host")]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    #[account(2, writable, name = "vault", desc = "Quiz vault PDA")]
+    #[account(3, writable, name = "recipient", desc = "Host or treasury account")]
+    SweepUnclaimed,
+    /// Vest prizes of at least `min_lamports` linearly over `slots` instead
+    /// of paying them at claim, 0 slots to pay every prize at once. Set
+    /// before the quiz starts.
+    #[account(0, signer, name = "host", desc = "Quiz host")]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    SetPrizeVesting { min_lamports: u64, slots: u64 },
+    /// Release the part of a vesting prize unlocked so far.
+    #[account(0, writable, signer, name = "player", desc = "Prize winner")]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    #[account(2, writable, name = "vault", desc = "Quiz vault PDA")]
+    #[account(
+        3,
+        writable,
+        name = "claim",
+        desc = "Claim PDA at [b\"leaderboard_claim\", quiz, player]"
+    )]
+    ClaimVested,
+    /// Charge an entry fee of `cents` USD instead of a fixed lamport amount,
+    /// converted at join from a Pyth price update of `feed_id`. Replaces
+    /// the lamport fee, 0 cents turns the fee off.
+    #[account(
+        0,
+        writable,
+        signer,
+        name = "host",
+        desc = "Quiz host, pays for the vault"
+    )]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    #[account(
+        2,
+        writable,
+        name = "vault",
+        desc = "Quiz vault PDA at [b\"quiz_vault\", quiz]"
+    )]
+    #[account(3, name = "system_program", desc = "System program")]
+    SetUsdEntryFee { cents: u64, feed_id: [u8; 32] },
+    /// Add lamports to the prize pool of a quiz that hasn't finished, from
+    /// anyone. Creates the vault of a free quiz, and the contributor's record
+    /// on their first contribution.
+    #[account(0, writable, signer, name = "contributor", desc = "Prize contributor")]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    #[account(
+        2,
+        writable,
+        name = "vault",
+        desc = "Quiz vault PDA at [b\"quiz_vault\", quiz]"
+    )]
+    #[account(3, name = "system_program", desc = "System program")]
+    #[account(
+        4,
+        writable,
+        name = "contribution",
+        desc = "Contribution PDA at [b\"prize_contribution\", quiz, contributor]"
+    )]
+    ContributeToPrizePool { lamports: u64 },
+    /// Pay a contributor back their share of a cancelled quiz's pool and
+    /// close their record. Anyone can send it.
+    #[account(0, writable, name = "contributor", desc = "Prize contributor")]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    #[account(2, writable, name = "vault", desc = "Quiz vault PDA")]
+    #[account(3, writable, name = "contribution", desc = "Contribution PDA")]
+    RefundContribution,
+    /// Route `bps` of the pot to `charity` once the quiz is scored, ahead of
+    /// every prize. Set before the quiz starts, 0 bps for no charity.
+    #[account(0, signer, name = "host", desc = "Quiz host")]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    SetCharitySplit { charity: Pubkey, bps: u16 },
+    /// Pay the charity its share of a scored quiz's pot. Prizes, duel
+    /// settlement, forfeits, jackpot rollovers and sweeps wait for it.
+    /// Anyone can send it.
+    #[account(0, writable, name = "quiz", desc = "Quiz session PDA")]
+    #[account(1, writable, name = "vault", desc = "Quiz vault PDA")]
+    #[account(2, writable, name = "charity", desc = "The quiz's charity")]
+    PayCharity,
+    /// Choose whether the host and scorer may join and answer the quiz
+    /// before it starts. By default they can't when there are prizes.
+    #[account(0, signer, name = "host", desc = "Quiz host")]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    SetHostPlay { policy: HostPlay },
+    /// Make every joining player lock `lamports` in the quiz vault, so
+    /// entering with many wallets ties up funds in each. Stakes stay out of
+    /// the prize pool and are reclaimed with `ReclaimStake`.
+    #[account(
+        0,
+        writable,
+        signer,
+        name = "host",
+        desc = "Quiz host, pays for the vault"
+    )]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    #[account(
+        2,
+        writable,
+        name = "vault",
+        desc = "Quiz vault PDA at [b\"quiz_vault\", quiz]"
+    )]
+    #[account(3, name = "system_program", desc = "System program")]
+    SetJoinStake { lamports: u64 },
+    /// Return a player's stake once the quiz is scored or cancelled and
+    /// their sheet is back on Solana.
+    #[account(0, writable, signer, name = "player", desc = "Staking player")]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    #[account(2, writable, name = "player_answer", desc = "Player's answer sheet")]
+    #[account(3, writable, name = "vault", desc = "Quiz vault PDA")]
+    ReclaimStake,
+    /// Choose how sheets are turned into scores before the quiz starts.
+    #[account(0, signer, name = "host", desc = "Quiz host")]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    SetScoringMode { mode: ScoringMode },
+    /// Bring a `PlayerScore` written while scores were a u8 up to the
+    /// current layout, the payer covering the extra rent. Anyone can send it.
+    #[account(0, writable, signer, name = "payer", desc = "Pays the extra rent")]
+    #[account(1, writable, name = "player_score", desc = "Player score PDA")]
+    #[account(2, name = "system_program", desc = "System program")]
+    MigratePlayerScore,
+    /// Rate questions `first_question..=last_question` from 1 to 5 before
+    /// the quiz starts. The question PDAs follow the quiz in order.
+    #[account(0, signer, name = "host", desc = "Quiz host")]
+    #[account(1, name = "quiz", desc = "Quiz session PDA")]
+    #[account(2, writable, name = "questions", desc = "Question PDAs in the range")]
+    SetQuestionDifficulty {
+        first_question: u8,
+        last_question: u8,
+        difficulty: u8,
+    },
+    /// Multiply the points of every question by its difficulty.
+    #[account(0, signer, name = "host", desc = "Quiz host")]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    SetDifficultyScoring { enabled: bool },
+    /// Set up serving for an adaptive quiz before it starts: snapshot the
+    /// difficulty of every question, passed in order after the fixed
+    /// accounts, and serve each player `questions_per_player` of them.
+    #[account(0, writable, signer, name = "host", desc = "Quiz host")]
+    #[account(1, name = "quiz", desc = "Quiz session PDA")]
+    #[account(
+        2,
+        writable,
+        name = "adaptive_bank",
+        desc = "Adaptive bank PDA at [b\"adaptive_bank\", quiz]"
+    )]
+    #[account(3, name = "system_program", desc = "System program")]
+    InitAdaptiveBank { questions_per_player: u8 },
+    /// Serve each player their own `questions_per_player` of the questions
+    /// before the quiz starts, drawn from `seed` and the player's pubkey
+    /// (see `QuizSession::question_subset`). Answer sheets then hold one
+    /// answer per served question in that order. 0 serves every question.
+    #[account(0, signer, name = "host", desc = "Quiz host")]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    SetQuestionSubset {
+        questions_per_player: u8,
+        seed: [u8; 32],
+    },
+    /// Crank that marks a completed quiz's answers as revealed, so clients
+    /// know they may show them, and publishes the answer key in an
+    /// `AnswersRevealed` event. Callable by anyone, once. Every question PDA
+    /// follows in order.
+    #[account(0, writable, name = "quiz", desc = "Quiz session PDA")]
+    RevealAnswers,
+    /// Attach an explanation of up to 512 bytes to a question before the
+    /// quiz starts, for clients to show once its answers are revealed.
+    #[account(0, writable, signer, name = "host", desc = "Quiz host")]
+    #[account(1, name = "quiz", desc = "Quiz session PDA")]
+    #[account(2, name = "question", desc = "Question PDA")]
+    #[account(
+        3,
+        writable,
+        name = "explanation",
+        desc = "Explanation PDA at [b\"question_explanation\", quiz, question_index]"
+    )]
+    #[account(4, name = "system_program", desc = "System program")]
+    SetExplanation {
+        question_index: u8,
+        explanation: String,
+    },
+    /// Commit to the key question text and options are uploaded encrypted
+    /// under (`cipher::key_hash`) before the quiz starts. No answers are
+    /// taken until `RevealQuestions` publishes the key. A zero hash means
+    /// plaintext questions.
+    #[account(0, signer, name = "host", desc = "Quiz host")]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    SetQuestionKey { key_hash: [u8; 32] },
+    /// Publish the key the questions are encrypted under, checked against
+    /// the committed hash, so clients can decrypt them. Sent at the start.
+    #[account(0, signer, name = "host", desc = "Quiz host")]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    RevealQuestions { key: [u8; 32] },
+    /// Commit to a live quiz question by its hash
+    /// (`QuestionCommitment::hash_question`) before the quiz starts, instead
+    /// of adding it. Round multipliers, difficulties, hints and other
+    /// per-question settings need the question account, so blind questions
+    /// keep the defaults.
+    #[account(0, writable, signer, name = "host", desc = "Quiz host")]
+    #[account(1, name = "quiz", desc = "Quiz session PDA")]
+    #[account(
+        2,
+        writable,
+        name = "commitment",
+        desc = "Commitment PDA at [b\"question_commitment\", quiz, question_index]"
+    )]
+    #[account(3, name = "system_program", desc = "System program")]
+    CommitQuestion {
+        question_index: u8,
+        question_hash: [u8; 32],
+    },
+    /// `RevealQuestion` for a committed question: publish its content,
+    /// checked against the commitment, as the question account and open its
+    /// answer window. The commitment's rent goes back to the host. Sent to
+    /// Solana, the rollup can't create the question account.
+    #[account(0, writable, signer, name = "host", desc = "Quiz host")]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    #[account(2, writable, name = "commitment", desc = "Commitment PDA")]
+    #[account(3, writable, name = "question", desc = "Question PDA")]
+    #[account(4, name = "system_program", desc = "System program")]
+    RevealBlindQuestion { index: u8, question: QuestionData },
+    /// `ImportQuestions` for a pack signed off-chain by `curator`, the host
+    /// or a third-party question provider. An ed25519 program instruction
+    /// earlier in the transaction must verify the curator's signature over
+    /// `QuestionData::pack_message(first_index, questions)`. The quiz records
+    /// the curator and refuses packs from any other.
+    #[account(0, writable, signer, name = "host", desc = "Quiz host")]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    #[account(2, name = "system_program", desc = "System program")]
+    #[account(3, name = "instructions", desc = "Instructions sysvar")]
+    #[account(
+        4,
+        writable,
+        name = "questions",
+        desc = "Question PDAs, one per imported question in index order"
+    )]
+    ImportSignedQuestions {
+        first_index: u8,
+        curator: Pubkey,
+        questions: Vec<QuestionData>,
+    },
+    /// `SubmitAnswers` sent and paid for by a relayer, for players without
+    /// SOL for fees. Instead of signing the transaction the player signs
+    /// `PlayerAnswer::relay_message(quiz, answers, confidence, nonce)`
+    /// off-chain, verified by an ed25519 program instruction earlier in the
+    /// transaction. `nonce` must be higher than the sheet's last, so a
+    /// signed payload can't be replayed.
+    #[account(
+        0,
+        signer,
+        name = "relayer",
+        desc = "Wallet sending the answers for the player"
+    )]
+    #[account(1, writable, name = "player_answer", desc = "Answer sheet PDA")]
+    #[account(2, name = "quiz", desc = "Quiz session PDA")]
+    #[account(3, name = "instructions", desc = "Instructions sysvar")]
+    SubmitRelayedAnswers {
+        answers: Vec<u8>,
+        confidence: Vec<u8>,
+        nonce: u64,
+    },
+    /// Return the entry fee of a duel nobody else joined, once the second
+    /// seat can no longer be taken: after the deadline or the join grace
+    /// window, or once submissions closed. An unscored duel is cancelled, so
+    /// stakes and contributions come back through `ReclaimStake` and
+    /// `RefundContribution`. A scored one pays the whole pot, as the lone
+    /// player wins uncontested.
+    #[account(0, writable, signer, name = "player", desc = "The duel's only player")]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    #[account(2, name = "player_answer", desc = "Player's answer sheet")]
+    #[account(3, writable, name = "vault", desc = "Quiz vault PDA")]
+    RefundDuel,
+    /// During the dispute window, prove the appointed scorer posted a
+    /// standing for a player with neither an answer sheet nor a compressed
+    /// sheet. Slashes the scorer like `ChallengeScore`.
+    #[account(0, writable, signer, name = "challenger", desc = "Challenger")]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    #[account(
+        2,
+        name = "player_answer",
+        desc = "The player's answer sheet PDA, which must not exist"
+    )]
+    #[account(
+        3,
+        name = "compressed_sheet",
+        desc = "The player's compressed sheet PDA, which must not exist"
+    )]
+    #[account(4, writable, name = "bond", desc = "Scorer bond PDA")]
+    #[account(5, writable, name = "host_stats", desc = "Quiz host's stats PDA")]
+    #[account(6, name = "system_program", desc = "System program")]
+    ChallengeMissingSheet { standing: PostedStanding },
+    /// During the dispute window, prove two standings of the appointed
+    /// scorer's root conflict: one player ranked twice, one rank held
+    /// twice, a rank of 0, or a higher rank with a lower score. Slashes the
+    /// scorer like `ChallengeScore`.
+    #[account(0, writable, signer, name = "challenger", desc = "Challenger")]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    #[account(2, writable, name = "bond", desc = "Scorer bond PDA")]
+    #[account(3, writable, name = "host_stats", desc = "Quiz host's stats PDA")]
+    #[account(4, name = "system_program", desc = "System program")]
+    ChallengeRanks {
+        first: PostedStanding,
+        second: PostedStanding,
+    },
+    /// Claim a member's part of a team's ranked prize once every team of the
+    /// quiz is finalized. Teams rank by score, ties to the lower team id, and
+    /// each member takes an equal part of the rank's share.
+    ///
+    /// Followed by every team PDA of the quiz, in any order.
+    #[account(0, writable, signer, name = "player", desc = "Team member")]
+    #[account(1, writable, name = "quiz", desc = "Quiz session PDA")]
+    #[account(2, writable, name = "vault", desc = "Quiz vault PDA")]
+    #[account(
+        3,
+        writable,
+        name = "claim",
+        desc = "Claim PDA at [b\"leaderboard_claim\", quiz, player]"
+    )]
+    #[account(4, name = "system_program", desc = "System program")]
+    ClaimTeamPrize { team_id: u8 },
 }
 
 impl QuizInstruction {
+    /// Instruction data comes in three formats, all followed by the
+    /// Borsh-encoded fields of the variant:
+    ///
+    /// - the Anchor sighash of the instruction name, 8 bytes
+    /// - the compact one-byte variant index, which is exactly how Borsh
+    ///   encodes the enum
+    /// - the legacy variant index as a little-endian u64, accepted for one
+    ///   more release
+    ///
+    /// They are tried in that order. A sighash can't start a compact
+    /// encoding, but zero-heavy compact data can look like a legacy
+    /// discriminator, so the compact form wins over the legacy one.
+    ///
+    /// The delegation program's undelegation callback is recognized by
+    /// `EXTERNAL_UNDELEGATE_DISCRIMINATOR` and decodes to `UndelegatePlayer`.
     pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
-        if input.len() < 8 {
-            return Err(ProgramError::InvalidInstructionData);
+        if let Some((ix_discriminator, rest)) = input.split_first_chunk::<8>() {
+            // The delegation program's callback carries only the PDA seeds
+            if *ix_discriminator == EXTERNAL_UNDELEGATE_DISCRIMINATOR {
+                return Vec::<Vec<u8>>::try_from_slice(rest)
+                    .map(|pda_seeds| Self::UndelegatePlayer { pda_seeds })
+                    .map_err(|_| ProgramError::InvalidInstructionData);
+            }
+            if let Some(index) = ANCHOR_DISCRIMINATORS
+                .iter()
+                .position(|sighash| sighash == ix_discriminator)
+            {
+                return Self::unpack_fields(index as u8, rest);
+            }
         }
 
-        let (ix_discriminator, rest) = input.split_at(8);
+        if let Ok(instruction) = Self::try_from_slice(input) {
+            return Ok(instruction);
+        }
 
-        Ok(match ix_discriminator {
-            [0, 0, 0, 0, 0, 0, 0, 0] => {
-                if rest.is_empty() {
-                    return Err(ProgramError::InvalidInstructionData);
-                }
-                let question_count = rest[0];
-                Self::InitializeQuiz { question_count }
-            }
-            [1, 0, 0, 0, 0, 0, 0, 0] => {
-                let question_data = AddQuestionData::try_from_slice(rest)?;
-                Self::AddQuestion {
-                    question_index: question_data.question_index,
-                    question_text: question_data.question_text,
-                    options: question_data.options,
-                    correct_answer_index: question_data.correct_answer_index,
-                }
+        match input.split_first_chunk::<8>() {
+            Some(([index, padding @ ..], rest)) if *padding == [0; 7] => {
+                Self::unpack_fields(*index, rest)
             }
-            [2, 0, 0, 0, 0, 0, 0, 0] => Self::StartQuiz,
-            [3, 0, 0, 0, 0, 0, 0, 0] => Self::DelegatePlayer,
-            [4, 0, 0, 0, 0, 0, 0, 0] => {
-                let answers = Vec::<u8>::try_from_slice(rest)?;
-                Self::SubmitAnswers { answers }
-            }
-            [5, 0, 0, 0, 0, 0, 0, 0] => Self::CommitAnswers,
-            [6, 0, 0, 0, 0, 0, 0, 0] => Self::CalculateScores,
-            [7, 0, 0, 0, 0, 0, 0, 0] => {
-                let pda_seeds = Vec::<Vec<u8>>::try_from_slice(rest)?;
-                Self::UndelegatePlayer { pda_seeds }
-            }
-            _ => return Err(ProgramError::InvalidInstructionData),
-        })
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+
+    /// Encode the instruction with the Anchor sighash discriminator, for
+    /// transactions that Anchor tooling and explorers should decode.
+    pub fn pack(&self) -> Vec<u8> {
+        let mut encoded = self.pack_compact();
+        let tag = encoded[0] as usize;
+        encoded.splice(..1, ANCHOR_DISCRIMINATORS[tag]);
+        encoded
+    }
+
+    /// Encode the instruction with the one-byte variant tag, the smallest
+    /// format `unpack` accepts.
+    pub fn pack_compact(&self) -> Vec<u8> {
+        borsh::to_vec(self).expect("instruction serializes")
+    }
+
+    fn unpack_fields(index: u8, fields: &[u8]) -> Result<Self, ProgramError> {
+        // Borsh encodes a variant as its one-byte index ahead of the fields
+        let mut encoded = Vec::with_capacity(1 + fields.len());
+        encoded.push(index);
+        encoded.extend_from_slice(fields);
+        Self::try_from_slice(&encoded).map_err(|_| ProgramError::InvalidInstructionData)
     }
 }
@@ -1,4 +1,22 @@
+pub mod cipher;
+#[cfg(feature = "client")]
+pub mod client;
+pub mod ed25519;
+#[cfg(not(feature = "no-entrypoint"))]
 pub mod entrypoint;
+pub mod error;
+pub mod events;
+#[cfg(feature = "client")]
+pub mod filters;
 pub mod instruction;
+pub mod merkle;
 pub mod processor;
+pub mod pyth;
+#[cfg(feature = "rpc")]
+pub mod rpc;
+#[cfg(feature = "schema")]
+pub mod schema;
 pub mod state;
+#[cfg(feature = "rpc")]
+pub mod subscribe;
+pub mod validation;
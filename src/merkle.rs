@@ -0,0 +1,132 @@
+//! Merkle trees over leaderboard standings, for quizzes too large to store a
+//! score PDA per player. Leaves and inner nodes are domain separated and
+//! pairs are hashed in sorted order, so a proof is just the sibling hashes
+//! from the leaf up.
+//!
+//! Compressed answer sheets go in fixed-depth trees instead, padded with
+//! empty subtrees so `AnswerTree` can append to them on chain.
+
+use solana_program::{hash::hashv, pubkey::Pubkey};
+
+use crate::state::CompressedAnswers;
+
+/// Depth of answer trees, room for about a million sheets.
+pub const TREE_DEPTH: usize = 20;
+
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+/// Leaf of a player's standing, `rank` counts from 1.
+pub fn leaderboard_leaf(player: &Pubkey, score: u32, rank: u32) -> [u8; 32] {
+    hashv(&[
+        LEAF_PREFIX,
+        player.as_ref(),
+        &score.to_le_bytes(),
+        &rank.to_le_bytes(),
+    ])
+    .to_bytes()
+}
+
+/// Leaf of a compressed answer sheet.
+pub fn answer_leaf(sheet: &CompressedAnswers) -> [u8; 32] {
+    hashv(&[
+        LEAF_PREFIX,
+        sheet.player.as_ref(),
+        &[sheet.answers.len() as u8],
+        &sheet.answers,
+        &sheet.confidence,
+        &sheet.submitted_slot.to_le_bytes(),
+    ])
+    .to_bytes()
+}
+
+pub(crate) fn hash_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (low, high) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[NODE_PREFIX, low, high]).to_bytes()
+}
+
+/// Hash each pair of nodes, an odd last node moves up as it is.
+fn parent_level(level: &[[u8; 32]]) -> Vec<[u8; 32]> {
+    level
+        .chunks(2)
+        .map(|pair| {
+            pair[1..]
+                .iter()
+                .fold(pair[0], |node, sibling| hash_pair(&node, sibling))
+        })
+        .collect()
+}
+
+/// Root of the tree over `leaves`, zeroes for no leaves.
+pub fn root(leaves: &[[u8; 32]]) -> [u8; 32] {
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        level = parent_level(&level);
+    }
+    level.first().copied().unwrap_or_default()
+}
+
+/// Sibling hashes from `leaves[index]` up to the root.
+pub fn proof(leaves: &[[u8; 32]], mut index: usize) -> Vec<[u8; 32]> {
+    let mut proof = Vec::new();
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        if let Some(sibling) = level.get(index ^ 1) {
+            proof.push(*sibling);
+        }
+        index /= 2;
+        level = parent_level(&level);
+    }
+    proof
+}
+
+pub fn verify(root: &[u8; 32], leaf: [u8; 32], proof: &[[u8; 32]]) -> bool {
+    proof
+        .iter()
+        .fold(leaf, |node, sibling| hash_pair(&node, sibling))
+        == *root
+}
+
+/// Root of an empty subtree `height` levels high.
+pub fn empty_root(height: usize) -> [u8; 32] {
+    (0..height).fold([0; 32], |node, _| hash_pair(&node, &node))
+}
+
+/// Hash each pair of nodes at `height`, a missing right node is empty.
+fn padded_parent_level(level: &[[u8; 32]], height: usize) -> Vec<[u8; 32]> {
+    let empty = empty_root(height);
+    level
+        .chunks(2)
+        .map(|pair| hash_pair(&pair[0], pair.get(1).unwrap_or(&empty)))
+        .collect()
+}
+
+/// Root of the `TREE_DEPTH` tree over `leaves`, as `AnswerTree` keeps it.
+pub fn padded_root(leaves: &[[u8; 32]]) -> [u8; 32] {
+    let mut level = leaves.to_vec();
+    for height in 0..TREE_DEPTH {
+        level = padded_parent_level(&level, height);
+    }
+    level
+        .first()
+        .copied()
+        .unwrap_or_else(|| empty_root(TREE_DEPTH))
+}
+
+/// The `TREE_DEPTH` sibling hashes from `leaves[index]` up to the padded
+/// root.
+pub fn padded_proof(leaves: &[[u8; 32]], mut index: usize) -> Vec<[u8; 32]> {
+    let mut proof = Vec::with_capacity(TREE_DEPTH);
+    let mut level = leaves.to_vec();
+    for height in 0..TREE_DEPTH {
+        proof.push(
+            level
+                .get(index ^ 1)
+                .copied()
+                .unwrap_or_else(|| empty_root(height)),
+        );
+        index /= 2;
+        level = padded_parent_level(&level, height);
+    }
+    proof
+}
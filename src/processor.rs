@@ -1,9 +1,10 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
+    clock::Clock,
     entrypoint::ProgramResult,
     msg,
-    program::invoke_signed,
+    program::{invoke, invoke_signed},
     program_error::ProgramError,
     pubkey::Pubkey,
     system_instruction,
@@ -14,11 +15,32 @@ use solana_program::{
 use ephemeral_rollups_sdk::cpi::{
     delegate_account, undelegate_account, DelegateAccounts, DelegateConfig,
 };
-use ephemeral_rollups_sdk::ephem::commit_accounts;
+use ephemeral_rollups_sdk::ephem::{commit_accounts, commit_and_undelegate_accounts};
 
 use crate::{
+    cipher, ed25519,
+    error::QuizError,
+    events::{
+        AnswersRevealed, AnswersSubmitted, CompressedAnswersSubmitted, PlayerJoined, PrizeClaimed,
+        PrizePoolContributed, QuestionAdded, QuestionRevealed, QuizEvent, QuizInitialized,
+        ReactionSent, ScoresFinalized,
+    },
     instruction::QuizInstruction,
-    state::{PlayerAnswer, PlayerScore, QuizQuestion, QuizSession},
+    merkle, pyth,
+    state::{
+        AdaptiveBank, AnswerSession, AnswerTree, CompressedAnswers, CompressedSheet, DailyConfig,
+        DailyEntry, DailyLeaderboard, DailyQuiz, DailyStreak, DelegationConfig, DisputeFlag,
+        HostPlay, HostStats, LeaderboardClaim, LeaderboardEntry, LiveLeaderboard, LoggedEvent,
+        NicknameClaim, PlayerAnswer, PlayerProfile, PlayerScore, PostedStanding, PrizeContribution,
+        PrizeRemainder, QuestionCommitment, QuestionData, QuestionDispute, QuestionExplanation,
+        QuestionHint, QuestionStats, QuestionTranslation, QuizAnalytics, QuizEventLog, QuizMode,
+        QuizQuestion, QuizReactions, QuizRegistry, QuizScoreboard, QuizSession, QuizStatus,
+        QuizTemplate, RegistryEntry, ScoreAudit, ScoreCorrection, ScoringMode, SeasonArchive,
+        Series, Team, TeamMember, TemplateConfig, Tournament, VoidMode,
+    },
+    validation::{
+        assert_owned_by, assert_owned_by_program_or_delegated, assert_pda, assert_upgrade_authority,
+    },
 };
 
 pub fn process_instruction(
@@ -29,9 +51,11 @@ pub fn process_instruction(
     let instruction = QuizInstruction::unpack(instruction_data)?;
 
     match instruction {
-        QuizInstruction::InitializeQuiz { question_count } => {
-            process_initialize_quiz(program_id, accounts, question_count)
-        }
+        QuizInstruction::InitializeQuiz {
+            question_count,
+            quiz_id,
+            delegation,
+        } => process_initialize_quiz(program_id, accounts, question_count, quiz_id, delegation),
         QuizInstruction::AddQuestion {
             question_index,
             question_text,
@@ -46,15 +70,408 @@ pub fn process_instruction(
             correct_answer_index,
         ),
         QuizInstruction::StartQuiz => process_start_quiz(program_id, accounts),
-        QuizInstruction::DelegatePlayer => process_delegate_player(program_id, accounts),
-        QuizInstruction::SubmitAnswers { answers } => {
-            process_submit_answers(program_id, accounts, answers)
-        }
+        QuizInstruction::DelegatePlayer {
+            validator,
+            referrer,
+        } => process_delegate_player(program_id, accounts, validator, referrer),
+        QuizInstruction::SubmitAnswers {
+            answers,
+            confidence,
+        } => process_submit_answers(program_id, accounts, answers, confidence, None),
         QuizInstruction::CommitAnswers => process_commit_answers(program_id, accounts),
         QuizInstruction::CalculateScores => process_calculate_scores(program_id, accounts),
         QuizInstruction::UndelegatePlayer { pda_seeds } => {
             process_undelegate_player(program_id, accounts, pda_seeds)
         }
+        QuizInstruction::SetSubmissionPolicy {
+            allow_resubmission,
+            refresh_slot_on_update,
+            deadline_slot,
+            join_grace_slots,
+        } => process_set_submission_policy(
+            program_id,
+            accounts,
+            allow_resubmission,
+            refresh_slot_on_update,
+            deadline_slot,
+            join_grace_slots,
+        ),
+        QuizInstruction::UpdateAnswers {
+            answers,
+            confidence,
+        } => process_update_answers(program_id, accounts, answers, confidence),
+        QuizInstruction::SubmitAnswer {
+            question_index,
+            answer,
+        } => process_submit_answer(program_id, accounts, question_index, answer),
+        QuizInstruction::SetQuizMode {
+            mode,
+            answer_window_slots,
+        } => process_set_quiz_mode(program_id, accounts, mode, answer_window_slots),
+        QuizInstruction::RevealQuestion { index } => {
+            process_reveal_question(program_id, accounts, index, None, None)
+        }
+        QuizInstruction::CreateTeam { team_id, name } => {
+            process_create_team(program_id, accounts, team_id, name)
+        }
+        QuizInstruction::JoinTeam { team_id } => process_join_team(program_id, accounts, team_id),
+        QuizInstruction::LeaveTeam { team_id } => process_leave_team(program_id, accounts, team_id),
+        QuizInstruction::FinalizeTeamScore { team_id } => {
+            process_finalize_team_score(program_id, accounts, team_id)
+        }
+        QuizInstruction::SetEntryFee { lamports } => {
+            process_set_entry_fee(program_id, accounts, lamports)
+        }
+        QuizInstruction::SettleDuel => process_settle_duel(program_id, accounts),
+        QuizInstruction::CreateTournament {
+            tournament_id,
+            round_count,
+            advance_count,
+        } => process_create_tournament(
+            program_id,
+            accounts,
+            tournament_id,
+            round_count,
+            advance_count,
+        ),
+        QuizInstruction::LinkTournamentRound { round } => {
+            process_link_tournament_round(program_id, accounts, round)
+        }
+        QuizInstruction::FundTournament { lamports } => {
+            process_fund_tournament(program_id, accounts, lamports)
+        }
+        QuizInstruction::AdvanceBracket => process_advance_bracket(program_id, accounts),
+        QuizInstruction::ClaimTournamentPrize => {
+            process_claim_tournament_prize(program_id, accounts)
+        }
+        QuizInstruction::InitializeDailyQuiz { curator } => {
+            process_initialize_daily_quiz(program_id, accounts, curator)
+        }
+        QuizInstruction::PublishDailyQuestion {
+            day_index,
+            question_index,
+            question_text,
+            options,
+            correct_answer_index,
+        } => process_publish_daily_question(
+            program_id,
+            accounts,
+            day_index,
+            question_index,
+            question_text,
+            options,
+            correct_answer_index,
+        ),
+        QuizInstruction::PlayDailyQuiz { answers } => {
+            process_play_daily_quiz(program_id, accounts, answers)
+        }
+        QuizInstruction::PracticeQuiz { answers } => {
+            process_practice_quiz(program_id, accounts, answers)
+        }
+        QuizInstruction::ListQuiz {
+            shard_index,
+            start_slot,
+        } => process_list_quiz(program_id, accounts, shard_index, start_slot),
+        QuizInstruction::DelistQuiz { shard_index } => {
+            process_delist_quiz(program_id, accounts, shard_index)
+        }
+        QuizInstruction::CommitAndUndelegateAnswers => {
+            process_commit_and_undelegate_answers(program_id, accounts)
+        }
+        QuizInstruction::DelegateQuiz { validator } => {
+            process_delegate_quiz(program_id, accounts, validator)
+        }
+        QuizInstruction::CommitAndUndelegateQuiz => {
+            process_commit_and_undelegate_quiz(program_id, accounts)
+        }
+        QuizInstruction::DelegateScoreboard { validator } => {
+            process_delegate_scoreboard(program_id, accounts, validator)
+        }
+        QuizInstruction::GradeAnswers => process_grade_answers(program_id, accounts),
+        QuizInstruction::CommitScoreboard => process_commit_scoreboard(program_id, accounts),
+        QuizInstruction::JoinQuiz { referrer } => process_join_quiz(program_id, accounts, referrer),
+        QuizInstruction::CreateSession {
+            expires_slot,
+            lamports,
+        } => process_create_session(program_id, accounts, expires_slot, lamports),
+        QuizInstruction::ScheduleStart { start_slot } => {
+            process_schedule_start(program_id, accounts, start_slot)
+        }
+        QuizInstruction::ActivateQuiz => process_activate_quiz(program_id, accounts),
+        QuizInstruction::CloseSubmissions => process_close_submissions(program_id, accounts),
+        QuizInstruction::CancelQuiz => process_cancel_quiz(program_id, accounts),
+        QuizInstruction::SetLatePolicy {
+            late_window_slots,
+            late_slots_per_point,
+        } => process_set_late_policy(
+            program_id,
+            accounts,
+            late_window_slots,
+            late_slots_per_point,
+        ),
+        QuizInstruction::SetConfidenceScoring { enabled } => {
+            process_set_confidence_scoring(program_id, accounts, enabled)
+        }
+        QuizInstruction::SetHint {
+            question_index,
+            hint,
+            point_cost,
+            lamport_cost,
+        } => process_set_hint(
+            program_id,
+            accounts,
+            question_index,
+            hint,
+            point_cost,
+            lamport_cost,
+        ),
+        QuizInstruction::BuyHint { question_index } => {
+            process_buy_hint(program_id, accounts, question_index)
+        }
+        QuizInstruction::SetRoundMultiplier {
+            first_question,
+            last_question,
+            multiplier,
+        } => process_set_round_multiplier(
+            program_id,
+            accounts,
+            first_question,
+            last_question,
+            multiplier,
+        ),
+        QuizInstruction::OpenQuestion {
+            index,
+            open_slot,
+            close_slot,
+        } => process_reveal_question(
+            program_id,
+            accounts,
+            index,
+            Some((open_slot, close_slot)),
+            None,
+        ),
+        QuizInstruction::ComputeAnalytics => process_compute_analytics(program_id, accounts),
+        QuizInstruction::SetDisputeWindow { slots } => {
+            process_set_dispute_window(program_id, accounts, slots)
+        }
+        QuizInstruction::FlagQuestion {
+            question_index,
+            reason_code,
+        } => process_flag_question(program_id, accounts, question_index, reason_code),
+        QuizInstruction::VoidQuestion {
+            question_index,
+            mode,
+        } => process_void_question(program_id, accounts, question_index, mode),
+        QuizInstruction::Regrade => process_regrade(program_id, accounts),
+        QuizInstruction::CorrectScore { new_score, reason } => {
+            process_correct_score(program_id, accounts, new_score, reason)
+        }
+        QuizInstruction::InitEventLog => process_init_event_log(program_id, accounts),
+        QuizInstruction::SetPrizeSplit { split_bps } => {
+            process_set_prize_split(program_id, accounts, split_bps)
+        }
+        QuizInstruction::PostLeaderboardRoot { root } => {
+            process_post_leaderboard_root(program_id, accounts, root)
+        }
+        QuizInstruction::ClaimLeaderboardPrize { score, rank, proof } => {
+            process_claim_leaderboard_prize(program_id, accounts, score, rank, proof)
+        }
+        QuizInstruction::AppointScorer {
+            scorer,
+            bond_lamports,
+        } => process_appoint_scorer(program_id, accounts, scorer, bond_lamports),
+        QuizInstruction::ChallengeScore { score, rank, proof } => {
+            process_challenge_score(program_id, accounts, score, rank, proof)
+        }
+        QuizInstruction::ReleaseBond => process_release_bond(program_id, accounts),
+        QuizInstruction::InitAnswerTree => process_init_answer_tree(program_id, accounts),
+        QuizInstruction::SubmitCompressedAnswers {
+            answers,
+            confidence,
+        } => process_submit_compressed_answers(program_id, accounts, answers, confidence),
+        QuizInstruction::GradeCompressedAnswers { sheet, proof } => {
+            process_grade_compressed_answers(program_id, accounts, sheet, proof)
+        }
+        QuizInstruction::TopUpRent { lamports } => {
+            process_top_up_rent(program_id, accounts, lamports)
+        }
+        QuizInstruction::CloseQuestions => process_close_questions(program_id, accounts),
+        QuizInstruction::CloneQuiz { quiz_id } => process_clone_quiz(program_id, accounts, quiz_id),
+        QuizInstruction::CreateTemplate {
+            template_id,
+            title,
+            public,
+        } => process_create_template(program_id, accounts, template_id, title, public),
+        QuizInstruction::SetTemplateAccess { public, allowed } => {
+            process_set_template_access(program_id, accounts, public, allowed)
+        }
+        QuizInstruction::CreateFromTemplate {
+            quiz_id,
+            delegation,
+        } => process_create_from_template(program_id, accounts, quiz_id, delegation),
+        QuizInstruction::ImportQuestions {
+            first_index,
+            questions,
+        } => process_import_questions(program_id, accounts, first_index, questions, None),
+        QuizInstruction::AddTranslation {
+            question_index,
+            language,
+            question_text,
+            options,
+        } => process_add_translation(
+            program_id,
+            accounts,
+            question_index,
+            language,
+            question_text,
+            options,
+        ),
+        QuizInstruction::SetNickname { nickname, reserve } => {
+            process_set_nickname(program_id, accounts, nickname, reserve)
+        }
+        QuizInstruction::InitLiveLeaderboard { validator } => {
+            process_init_live_leaderboard(program_id, accounts, validator)
+        }
+        QuizInstruction::CommitLiveLeaderboard => {
+            process_commit_live_leaderboard(program_id, accounts)
+        }
+        QuizInstruction::InitReactions {
+            cooldown_slots,
+            validator,
+        } => process_init_reactions(program_id, accounts, cooldown_slots, validator),
+        QuizInstruction::SendReaction { emoji_code } => {
+            process_send_reaction(program_id, accounts, emoji_code)
+        }
+        QuizInstruction::CommitReactions => process_commit_reactions(program_id, accounts),
+        QuizInstruction::CreateSeries {
+            series_id,
+            quiz_count,
+        } => process_create_series(program_id, accounts, series_id, quiz_count),
+        QuizInstruction::LinkSeriesQuiz { position } => {
+            process_link_series_quiz(program_id, accounts, position)
+        }
+        QuizInstruction::FundSeries { lamports } => {
+            process_fund_series(program_id, accounts, lamports)
+        }
+        QuizInstruction::RecordSeriesQuiz => process_record_series_quiz(program_id, accounts),
+        QuizInstruction::ClaimSeriesPrize => process_claim_series_prize(program_id, accounts),
+        QuizInstruction::SetDailySeasonLength { season_days } => {
+            process_set_daily_season_length(program_id, accounts, season_days)
+        }
+        QuizInstruction::EndDailySeason => process_end_daily_season(program_id, accounts),
+        QuizInstruction::EndSeriesSeason { quiz_count } => {
+            process_end_series_season(program_id, accounts, quiz_count)
+        }
+        QuizInstruction::SetReferralCut { bps } => {
+            process_set_referral_cut(program_id, accounts, bps)
+        }
+        QuizInstruction::PayReferral => process_pay_referral(program_id, accounts),
+        QuizInstruction::SetPrizeThreshold {
+            min_score,
+            min_score_bps,
+            remainder,
+        } => process_set_prize_threshold(program_id, accounts, min_score, min_score_bps, remainder),
+        QuizInstruction::ForfeitPrize {
+            rank,
+            player,
+            score,
+            proof,
+        } => process_forfeit_prize(program_id, accounts, rank, player, score, proof),
+        QuizInstruction::SetProportionalPrizes { enabled } => {
+            process_set_proportional_prizes(program_id, accounts, enabled)
+        }
+        QuizInstruction::ClaimScorePrize => process_claim_score_prize(program_id, accounts),
+        QuizInstruction::RolloverJackpot => process_rollover_jackpot(program_id, accounts),
+        QuizInstruction::SetClaimWindow { slots } => {
+            process_set_claim_window(program_id, accounts, slots)
+        }
+        QuizInstruction::SweepUnclaimed => process_sweep_unclaimed(program_id, accounts),
+        QuizInstruction::SetPrizeVesting {
+            min_lamports,
+            slots,
+        } => process_set_prize_vesting(program_id, accounts, min_lamports, slots),
+        QuizInstruction::ClaimVested => process_claim_vested(program_id, accounts),
+        QuizInstruction::SetUsdEntryFee { cents, feed_id } => {
+            process_set_usd_entry_fee(program_id, accounts, cents, feed_id)
+        }
+        QuizInstruction::ContributeToPrizePool { lamports } => {
+            process_contribute_to_prize_pool(program_id, accounts, lamports)
+        }
+        QuizInstruction::RefundContribution => process_refund_contribution(program_id, accounts),
+        QuizInstruction::SetCharitySplit { charity, bps } => {
+            process_set_charity_split(program_id, accounts, charity, bps)
+        }
+        QuizInstruction::PayCharity => process_pay_charity(program_id, accounts),
+        QuizInstruction::SetHostPlay { policy } => {
+            process_set_host_play(program_id, accounts, policy)
+        }
+        QuizInstruction::SetJoinStake { lamports } => {
+            process_set_join_stake(program_id, accounts, lamports)
+        }
+        QuizInstruction::ReclaimStake => process_reclaim_stake(program_id, accounts),
+        QuizInstruction::SetScoringMode { mode } => {
+            process_set_scoring_mode(program_id, accounts, mode)
+        }
+        QuizInstruction::MigratePlayerScore => process_migrate_player_score(program_id, accounts),
+        QuizInstruction::SetQuestionDifficulty {
+            first_question,
+            last_question,
+            difficulty,
+        } => process_set_question_difficulty(
+            program_id,
+            accounts,
+            first_question,
+            last_question,
+            difficulty,
+        ),
+        QuizInstruction::SetDifficultyScoring { enabled } => {
+            process_set_difficulty_scoring(program_id, accounts, enabled)
+        }
+        QuizInstruction::InitAdaptiveBank {
+            questions_per_player,
+        } => process_init_adaptive_bank(program_id, accounts, questions_per_player),
+        QuizInstruction::SetQuestionSubset {
+            questions_per_player,
+            seed,
+        } => process_set_question_subset(program_id, accounts, questions_per_player, seed),
+        QuizInstruction::RevealAnswers => process_reveal_answers(program_id, accounts),
+        QuizInstruction::SetExplanation {
+            question_index,
+            explanation,
+        } => process_set_explanation(program_id, accounts, question_index, explanation),
+        QuizInstruction::SetQuestionKey { key_hash } => {
+            process_set_question_key(program_id, accounts, key_hash)
+        }
+        QuizInstruction::RevealQuestions { key } => {
+            process_reveal_questions(program_id, accounts, key)
+        }
+        QuizInstruction::CommitQuestion {
+            question_index,
+            question_hash,
+        } => process_commit_question(program_id, accounts, question_index, question_hash),
+        QuizInstruction::RevealBlindQuestion { index, question } => {
+            process_reveal_question(program_id, accounts, index, None, Some(question))
+        }
+        QuizInstruction::ImportSignedQuestions {
+            first_index,
+            curator,
+            questions,
+        } => process_import_questions(program_id, accounts, first_index, questions, Some(curator)),
+        QuizInstruction::SubmitRelayedAnswers {
+            answers,
+            confidence,
+            nonce,
+        } => process_submit_answers(program_id, accounts, answers, confidence, Some(nonce)),
+        QuizInstruction::RefundDuel => process_refund_duel(program_id, accounts),
+        QuizInstruction::ChallengeMissingSheet { standing } => {
+            process_challenge_missing_sheet(program_id, accounts, standing)
+        }
+        QuizInstruction::ChallengeRanks { first, second } => {
+            process_challenge_ranks(program_id, accounts, first, second)
+        }
+        QuizInstruction::ClaimTeamPrize { team_id } => {
+            process_claim_team_prize(program_id, accounts, team_id)
+        }
     }
 }
 
@@ -62,6 +479,8 @@ pub fn process_initialize_quiz(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
     question_count: u8,
+    quiz_id: u64,
+    delegation: DelegationConfig,
 ) -> ProgramResult {
     let accounts_iter = &mut accounts.iter();
 
@@ -74,63 +493,54 @@ pub fn process_initialize_quiz(
         return Err(ProgramError::MissingRequiredSignature);
     }
 
-    // Derive quiz session PDA
-    let (quiz_pda, bump_seed) =
-        Pubkey::find_program_address(&[b"quiz_session", host_account.key.as_ref()], program_id);
-
-    // Verify PDA matches provided account
-    if quiz_pda != *quiz_account.key {
-        return Err(ProgramError::InvalidArgument);
-    }
+    // Verify quiz session PDA
+    let quiz_id_bytes = quiz_id.to_le_bytes();
+    let bump_seed = assert_pda(
+        quiz_account,
+        &[QuizSession::SEED, host_account.key.as_ref(), &quiz_id_bytes],
+        program_id,
+    )?;
 
     // Create quiz session account
-    let rent = Rent::get()?;
-    let rent_lamports = rent.minimum_balance(QuizSession::SIZE);
-
-    invoke_signed(
-        &system_instruction::create_account(
-            host_account.key,
-            quiz_account.key,
-            rent_lamports,
-            QuizSession::SIZE as u64,
-            program_id,
-        ),
+    create_pda_account(
+        program_id,
+        host_account,
+        quiz_account,
+        system_program,
+        QuizSession::SIZE,
         &[
-            host_account.clone(),
-            quiz_account.clone(),
-            system_program.clone(),
+            QuizSession::SEED,
+            host_account.key.as_ref(),
+            &quiz_id_bytes,
+            &[bump_seed],
         ],
-        &[&[b"quiz_session", host_account.key.as_ref(), &[bump_seed]]],
     )?;
 
     // Initialize quiz session data
-    let quiz_data = QuizSession {
-        host: *host_account.key,
-        question_count,
-        player_count: 0,
-        active: false,
-        completed: false,
-    };
+    let quiz_data = QuizSession::new(*host_account.key, quiz_id, question_count, delegation);
 
     quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
-    msg!("Quiz session initialized with {} questions", question_count);
+    QuizEvent::QuizInitialized(QuizInitialized {
+        quiz: *quiz_account.key,
+        host: *host_account.key,
+        quiz_id,
+        question_count,
+    })
+    .emit()?;
 
     Ok(())
 }
 
-pub fn process_add_question(
+pub fn process_clone_quiz(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
-    question_index: u8,
-    question_text: String,
-    options: [String; 4],
-    correct_answer_index: u8,
+    quiz_id: u64,
 ) -> ProgramResult {
     let accounts_iter = &mut accounts.iter();
 
     let host_account = next_account_info(accounts_iter)?;
+    let source_account = next_account_info(accounts_iter)?;
     let quiz_account = next_account_info(accounts_iter)?;
-    let question_account = next_account_info(accounts_iter)?;
     let system_program = next_account_info(accounts_iter)?;
 
     // Verify host is signer
@@ -138,204 +548,526 @@ pub fn process_add_question(
         return Err(ProgramError::MissingRequiredSignature);
     }
 
-    // Verify host is the quiz creator
-    let quiz_data = QuizSession::try_from_slice(&quiz_account.data.borrow())?;
-    if quiz_data.host != *host_account.key {
+    // Only a finished run of the host's own quiz is copied
+    let source = load_host_quiz(program_id, host_account, source_account)?;
+    if source.status != QuizStatus::Completed {
         return Err(ProgramError::InvalidAccountData);
     }
 
-    // Verify quiz is not active yet
-    if quiz_data.active {
-        return Err(ProgramError::InvalidAccountData);
+    let quiz_id_bytes = quiz_id.to_le_bytes();
+    create_program_account(
+        program_id,
+        host_account,
+        quiz_account,
+        system_program,
+        QuizSession::SIZE,
+        &[QuizSession::SEED, host_account.key.as_ref(), &quiz_id_bytes],
+    )?;
+
+    // Keep the settings, everything tied to the source's run starts afresh.
+    // Encrypted questions stay hidden until the key is revealed again.
+    let mut quiz_data = QuizSession::new(
+        *host_account.key,
+        quiz_id,
+        source.question_count,
+        source.delegation,
+    );
+    quiz_data.allow_resubmission = source.allow_resubmission;
+    quiz_data.refresh_slot_on_update = source.refresh_slot_on_update;
+    quiz_data.mode = source.mode;
+    quiz_data.answer_window_slots = source.answer_window_slots;
+    quiz_data.entry_fee = source.entry_fee;
+    quiz_data.join_grace_slots = source.join_grace_slots;
+    quiz_data.late_window_slots = source.late_window_slots;
+    quiz_data.late_slots_per_point = source.late_slots_per_point;
+    quiz_data.confidence_weighted = source.confidence_weighted;
+    quiz_data.dispute_window_slots = source.dispute_window_slots;
+    quiz_data.prize_split_bps = source.prize_split_bps;
+    quiz_data.referral_bps = source.referral_bps;
+    quiz_data.min_prize_score = source.min_prize_score;
+    quiz_data.min_prize_score_bps = source.min_prize_score_bps;
+    quiz_data.prize_remainder = source.prize_remainder;
+    quiz_data.proportional_prizes = source.proportional_prizes;
+    quiz_data.claim_window_slots = source.claim_window_slots;
+    quiz_data.vesting_min_lamports = source.vesting_min_lamports;
+    quiz_data.vesting_slots = source.vesting_slots;
+    quiz_data.entry_fee_usd_cents = source.entry_fee_usd_cents;
+    quiz_data.price_feed_id = source.price_feed_id;
+    quiz_data.charity = source.charity;
+    quiz_data.charity_bps = source.charity_bps;
+    quiz_data.host_play = source.host_play;
+    quiz_data.join_stake = source.join_stake;
+    quiz_data.scoring_mode = source.scoring_mode;
+    quiz_data.difficulty_weighted = source.difficulty_weighted;
+    quiz_data.subset_size = source.subset_size;
+    quiz_data.subset_seed = source.subset_seed;
+    quiz_data.question_key_hash = source.question_key_hash;
+    quiz_data.curator = source.curator;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+
+    for question_index in 0..quiz_data.question_count {
+        let source_question_account = next_account_info(accounts_iter)?;
+        let question_account = next_account_info(accounts_iter)?;
+        let source_question = load_question(
+            program_id,
+            source_account,
+            source_question_account,
+            question_index,
+        )?;
+        copy_question(
+            program_id,
+            host_account,
+            question_account,
+            system_program,
+            &[
+                QuizQuestion::SEED,
+                quiz_account.key.as_ref(),
+                &[question_index],
+            ],
+            source_question,
+        )?;
     }
 
-    // Extract question index from instruction
-    // let question_index = question_account.key.to_bytes()[0]; // Old buggy code
-    // Use question_index from instruction parameter instead
+    QuizEvent::QuizInitialized(QuizInitialized {
+        quiz: *quiz_account.key,
+        host: *host_account.key,
+        quiz_id,
+        question_count: quiz_data.question_count,
+    })
+    .emit()?;
 
-    // Derive question PDA
-    let (question_pda, bump_seed) = Pubkey::find_program_address(
-        &[
-            b"quiz_question",
-            quiz_account.key.as_ref(),
-            &[question_index],
-        ],
-        program_id,
+    msg!(
+        "Quiz {} cloned from {}",
+        quiz_account.key,
+        source_account.key
     );
+    Ok(())
+}
 
-    // Verify PDA matches provided account
-    if question_pda != *question_account.key {
-        return Err(ProgramError::InvalidArgument);
+pub fn process_create_template(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    template_id: u64,
+    title: String,
+    public: bool,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let author_account = next_account_info(accounts_iter)?;
+    let source_account = next_account_info(accounts_iter)?;
+    let template_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    // Verify author is signer
+    if !author_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
     }
 
-    // Create question account
-    let account_size = QuizQuestion::get_size(&question_text, &options);
-    let rent = Rent::get()?;
-    let rent_lamports = rent.minimum_balance(account_size);
+    // Only the author's own quizzes are published
+    let source = load_host_quiz(program_id, author_account, source_account)?;
 
-    invoke_signed(
-        &system_instruction::create_account(
-            host_account.key,
-            question_account.key,
-            rent_lamports,
-            account_size as u64,
-            program_id,
-        ),
+    if title.len() > QuizTemplate::MAX_TITLE_LEN {
+        return Err(QuizError::TemplateTitleTooLong.into());
+    }
+
+    let template_id_bytes = template_id.to_le_bytes();
+    create_program_account(
+        program_id,
+        author_account,
+        template_account,
+        system_program,
+        QuizTemplate::SIZE,
         &[
-            host_account.clone(),
-            question_account.clone(),
-            system_program.clone(),
+            QuizTemplate::SEED,
+            author_account.key.as_ref(),
+            &template_id_bytes,
         ],
-        &[&[
-            b"quiz_question",
-            quiz_account.key.as_ref(),
-            &[question_index],
-            &[bump_seed],
-        ]],
     )?;
-
-    // Initialize question data
-    let question_data = QuizQuestion {
-        question_text,
-        options,
-        correct_answer_index,
+    let mut template_title = [0u8; QuizTemplate::MAX_TITLE_LEN];
+    template_title[..title.len()].copy_from_slice(title.as_bytes());
+    let template_data = QuizTemplate {
+        author: *author_account.key,
+        template_id,
+        title: template_title,
+        question_count: source.question_count,
+        config: TemplateConfig::of(&source),
+        public,
+        allowed: [Pubkey::default(); QuizTemplate::MAX_ALLOWED],
+        allowed_count: 0,
+        instance_count: 0,
     };
+    template_data.serialize(&mut &mut template_account.data.borrow_mut()[..])?;
 
-    question_data.serialize(&mut &mut question_account.data.borrow_mut()[..])?;
-    msg!("Quiz question {} added", question_index);
+    for question_index in 0..source.question_count {
+        let source_question_account = next_account_info(accounts_iter)?;
+        let template_question_account = next_account_info(accounts_iter)?;
+        let source_question = load_question(
+            program_id,
+            source_account,
+            source_question_account,
+            question_index,
+        )?;
+        copy_question(
+            program_id,
+            author_account,
+            template_question_account,
+            system_program,
+            &[
+                QuizTemplate::QUESTION_SEED,
+                template_account.key.as_ref(),
+                &[question_index],
+            ],
+            source_question,
+        )?;
+    }
 
+    msg!(
+        "Template {} published with {} questions",
+        template_account.key,
+        source.question_count
+    );
     Ok(())
 }
 
-pub fn process_start_quiz(_program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+pub fn process_set_template_access(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    public: bool,
+    allowed: Vec<Pubkey>,
+) -> ProgramResult {
     let accounts_iter = &mut accounts.iter();
 
-    let host_account = next_account_info(accounts_iter)?;
-    let quiz_account = next_account_info(accounts_iter)?;
+    let author_account = next_account_info(accounts_iter)?;
+    let template_account = next_account_info(accounts_iter)?;
 
-    // Verify host is signer
-    if !host_account.is_signer {
+    // Verify author is signer
+    if !author_account.is_signer {
         return Err(ProgramError::MissingRequiredSignature);
     }
 
-    // Verify host is the quiz creator
-    let mut quiz_data = QuizSession::try_from_slice(&quiz_account.data.borrow())?;
-    if quiz_data.host != *host_account.key {
+    let mut template_data = load_template(program_id, template_account)?;
+    if template_data.author != *author_account.key {
         return Err(ProgramError::InvalidAccountData);
     }
+    if allowed.len() > QuizTemplate::MAX_ALLOWED {
+        return Err(ProgramError::InvalidArgument);
+    }
 
-    // Set quiz as active
-    quiz_data.active = true;
-    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
-
-    msg!("Quiz started and open for players");
+    template_data.public = public;
+    template_data.allowed = [Pubkey::default(); QuizTemplate::MAX_ALLOWED];
+    template_data.allowed[..allowed.len()].copy_from_slice(&allowed);
+    template_data.allowed_count = allowed.len() as u8;
+    template_data.serialize(&mut &mut template_account.data.borrow_mut()[..])?;
     Ok(())
 }
 
-pub fn process_delegate_player(_program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
-    let account_iter = &mut accounts.iter();
+pub fn process_create_from_template(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    quiz_id: u64,
+    delegation: DelegationConfig,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
 
-    let player = next_account_info(account_iter)?;
-    let system_program = next_account_info(account_iter)?;
-    let player_answer_account = next_account_info(account_iter)?;
-    let owner_program = next_account_info(account_iter)?;
-    let delegation_buffer = next_account_info(account_iter)?;
-    let delegation_record = next_account_info(account_iter)?;
-    let delegation_metadata = next_account_info(account_iter)?;
-    let delegation_program = next_account_info(account_iter)?;
-    let quiz_account = next_account_info(account_iter)?;
+    let host_account = next_account_info(accounts_iter)?;
+    let template_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
 
-    // Verify player is signer
-    if !player.is_signer {
+    // Verify host is signer
+    if !host_account.is_signer {
         return Err(ProgramError::MissingRequiredSignature);
     }
 
-    // Verify quiz is active
-    let mut quiz_data = QuizSession::try_from_slice(&quiz_account.data.borrow())?;
-    if !quiz_data.active || quiz_data.completed {
-        return Err(ProgramError::InvalidAccountData);
+    let mut template_data = load_template(program_id, template_account)?;
+    if !template_data.may_use(host_account.key) {
+        return Err(QuizError::TemplateAccessDenied.into());
     }
 
-    // Increment player count
-    quiz_data.player_count += 1;
+    let quiz_id_bytes = quiz_id.to_le_bytes();
+    create_program_account(
+        program_id,
+        host_account,
+        quiz_account,
+        system_program,
+        QuizSession::SIZE,
+        &[QuizSession::SEED, host_account.key.as_ref(), &quiz_id_bytes],
+    )?;
+    let mut quiz_data = QuizSession::new(
+        *host_account.key,
+        quiz_id,
+        template_data.question_count,
+        delegation,
+    );
+    template_data.config.apply(&mut quiz_data);
     quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
 
-    // Prepare player answer PDA seeds
-    let seed_1 = b"player_answer";
-    let seed_2 = quiz_account.key.as_ref();
-    let seed_3 = player.key.as_ref();
-    let pda_seeds: &[&[u8]] = &[seed_1, seed_2, seed_3];
+    for question_index in 0..template_data.question_count {
+        let template_question_account = next_account_info(accounts_iter)?;
+        let question_account = next_account_info(accounts_iter)?;
+        assert_owned_by(template_question_account, program_id)?;
+        assert_pda(
+            template_question_account,
+            &[
+                QuizTemplate::QUESTION_SEED,
+                template_account.key.as_ref(),
+                &[question_index],
+            ],
+            program_id,
+        )?;
+        let template_question =
+            QuizQuestion::try_from_slice(&template_question_account.data.borrow())?;
+        copy_question(
+            program_id,
+            host_account,
+            question_account,
+            system_program,
+            &[
+                QuizQuestion::SEED,
+                quiz_account.key.as_ref(),
+                &[question_index],
+            ],
+            template_question,
+        )?;
+    }
 
-    // Set up delegation
-    let delegate_accounts = DelegateAccounts {
-        payer: player,
-        pda: player_answer_account,
-        owner_program,
-        buffer: delegation_buffer,
-        delegation_record,
-        delegation_metadata,
-        delegation_program,
+    template_data.instance_count += 1;
+    template_data.serialize(&mut &mut template_account.data.borrow_mut()[..])?;
+
+    QuizEvent::QuizInitialized(QuizInitialized {
+        quiz: *quiz_account.key,
+        host: *host_account.key,
+        quiz_id,
+        question_count: quiz_data.question_count,
+    })
+    .emit()?;
+    Ok(())
+}
+
+fn load_template(
+    program_id: &Pubkey,
+    template_account: &AccountInfo,
+) -> Result<QuizTemplate, ProgramError> {
+    assert_owned_by(template_account, program_id)?;
+    let template_data = QuizTemplate::try_from_slice(&template_account.data.borrow())?;
+    assert_pda(
+        template_account,
+        &[
+            QuizTemplate::SEED,
+            template_data.author.as_ref(),
+            &template_data.template_id.to_le_bytes(),
+        ],
+        program_id,
+    )?;
+    Ok(template_data)
+}
+
+/// Create a question PDA at `seeds` holding `source`, as asked before any
+/// play: no buzzer winner and not voided.
+fn copy_question<'a>(
+    program_id: &Pubkey,
+    payer: &AccountInfo<'a>,
+    question_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    seeds: &[&[u8]],
+    source: QuizQuestion,
+) -> ProgramResult {
+    create_program_account(
+        program_id,
+        payer,
+        question_account,
         system_program,
+        QuizQuestion::get_size(&source.question_text, &source.options),
+        seeds,
+    )?;
+    let question_data = QuizQuestion {
+        buzzer_winner: Pubkey::default(),
+        void: VoidMode::NotVoided,
+        ..source
     };
+    question_data.serialize(&mut &mut question_account.data.borrow_mut()[..])?;
+    Ok(())
+}
 
-    let delegate_config = DelegateConfig {
-        commit_frequency_ms: 1000,          // Commit every 1 second
-        validator: Some(Pubkey::default()), // Use default pubkey for now
+pub fn process_add_question(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    question_index: u8,
+    question_text: String,
+    options: [String; 4],
+    correct_answer_index: u8,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let question_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    // Verify host is signer
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify host is the quiz creator
+    let quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+
+    // Verify quiz is not active yet
+    if !quiz_data.status.is_setup() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Verify question index is in range
+    if question_index >= quiz_data.question_count {
+        return Err(QuizError::InvalidQuestionIndex.into());
+    }
+
+    create_question(
+        program_id,
+        host_account,
+        quiz_account,
+        question_account,
+        system_program,
+        question_index,
+        QuestionData {
+            question_text,
+            options,
+            correct_answer_index,
+        },
+    )
+}
+
+/// Create and initialize the question PDA at `question_index`. Callers check
+/// the quiz phase and that the index is within the quiz's question count.
+fn create_question<'a>(
+    program_id: &Pubkey,
+    payer: &AccountInfo<'a>,
+    quiz_account: &AccountInfo<'a>,
+    question_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    question_index: u8,
+    question: QuestionData,
+) -> ProgramResult {
+    if question.correct_answer_index >= QuizQuestion::OPTION_COUNT {
+        return Err(QuizError::InvalidCorrectAnswerIndex.into());
+    }
+
+    // Verify question PDA
+    let bump_seed = assert_pda(
+        question_account,
+        &[
+            QuizQuestion::SEED,
+            quiz_account.key.as_ref(),
+            &[question_index],
+        ],
+        program_id,
+    )?;
+
+    // Create question account
+    let account_size = QuizQuestion::get_size(&question.question_text, &question.options);
+    create_pda_account(
+        program_id,
+        payer,
+        question_account,
+        system_program,
+        account_size,
+        &[
+            QuizQuestion::SEED,
+            quiz_account.key.as_ref(),
+            &[question_index],
+            &[bump_seed],
+        ],
+    )?;
+
+    // Initialize question data
+    let question_data = QuizQuestion {
+        question_text: question.question_text,
+        options: question.options,
+        correct_answer_index: question.correct_answer_index,
+        buzzer_winner: Pubkey::default(),
+        multiplier: 1,
+        void: VoidMode::NotVoided,
+        difficulty: 1,
     };
 
-    delegate_account(delegate_accounts, pda_seeds, delegate_config)?;
-    msg!("Player {} delegated to participate in quiz", player.key);
+    question_data.serialize(&mut &mut question_account.data.borrow_mut()[..])?;
+    QuizEvent::QuestionAdded(QuestionAdded {
+        quiz: *quiz_account.key,
+        question_index,
+    })
+    .emit()?;
 
     Ok(())
 }
 
-pub fn process_submit_answers(
-    _program_id: &Pubkey,
+pub fn process_import_questions(
+    program_id: &Pubkey,
     accounts: &[AccountInfo],
-    answers: Vec<u8>,
+    first_index: u8,
+    questions: Vec<QuestionData>,
+    curator: Option<Pubkey>,
 ) -> ProgramResult {
     let accounts_iter = &mut accounts.iter();
 
-    let player_account = next_account_info(accounts_iter)?;
-    let player_answer_account = next_account_info(accounts_iter)?;
+    let host_account = next_account_info(accounts_iter)?;
     let quiz_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+    // Signed packs are checked against the ed25519 instruction before them
+    let instructions_sysvar = match curator {
+        Some(_) => Some(next_account_info(accounts_iter)?),
+        None => None,
+    };
 
-    // Verify player is signer
-    if !player_account.is_signer {
+    if !host_account.is_signer {
         return Err(ProgramError::MissingRequiredSignature);
     }
 
-    // Verify quiz is active
-    let quiz_data = QuizSession::try_from_slice(&quiz_account.data.borrow())?;
-    if !quiz_data.active || quiz_data.completed {
+    let mut quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+    if !quiz_data.status.is_setup() {
         return Err(ProgramError::InvalidAccountData);
     }
 
-    // Verify answer count matches question count
-    if answers.len() != quiz_data.question_count as usize {
-        return Err(ProgramError::InvalidInstructionData);
+    // The whole batch must fit within the quiz's question count
+    if questions.is_empty()
+        || first_index as usize + questions.len() > quiz_data.question_count as usize
+    {
+        return Err(QuizError::InvalidQuestionIndex.into());
     }
 
-    // Create player answers data
-    let player_answers = PlayerAnswer {
-        player: *player_account.key,
-        answers,
-    };
+    if let (Some(curator), Some(instructions_sysvar)) = (curator, instructions_sysvar) {
+        if quiz_data.curator != Pubkey::default() && quiz_data.curator != curator {
+            return Err(QuizError::CuratorMismatch.into());
+        }
+        ed25519::assert_signed(
+            instructions_sysvar,
+            &curator,
+            &QuestionData::pack_message(first_index, &questions),
+        )?;
+        quiz_data.curator = curator;
+        quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+    }
 
-    // Save answers to delegated account
-    player_answers.serialize(&mut &mut player_answer_account.data.borrow_mut()[..])?;
+    for (question_index, question) in (first_index..).zip(questions) {
+        let question_account = next_account_info(accounts_iter)?;
+        create_question(
+            program_id,
+            host_account,
+            quiz_account,
+            question_account,
+            system_program,
+            question_index,
+            question,
+        )?;
+    }
 
-    msg!("Player {} submitted answers", player_account.key);
     Ok(())
 }
 
-pub fn process_commit_answers(_program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+pub fn process_start_quiz(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
     let accounts_iter = &mut accounts.iter();
 
     let host_account = next_account_info(accounts_iter)?;
     let quiz_account = next_account_info(accounts_iter)?;
-    let magic_program = next_account_info(accounts_iter)?;
-    let magic_context = next_account_info(accounts_iter)?;
 
     // Verify host is signer
     if !host_account.is_signer {
@@ -343,19 +1075,22 @@ pub fn process_commit_answers(_program_id: &Pubkey, accounts: &[AccountInfo]) ->
     }
 
     // Verify host is the quiz creator
-    let quiz_data = QuizSession::try_from_slice(&quiz_account.data.borrow())?;
-    if quiz_data.host != *host_account.key {
-        return Err(ProgramError::InvalidAccountData);
-    }
+    let mut quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
 
-    // Commit answers from ephemeral rollup to Solana
-    commit_accounts(magic_program, vec![], magic_context, magic_context)?;
+    // Set quiz as active, the join window counts from here
+    set_status(&mut quiz_data, QuizStatus::Active)?;
+    quiz_data.start_slot = Clock::get()?.slot;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
 
-    msg!("Player answers committed to chain");
+    msg!("Quiz started and open for players");
     Ok(())
 }
 
-pub fn process_calculate_scores(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+pub fn process_schedule_start(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    start_slot: u64,
+) -> ProgramResult {
     let accounts_iter = &mut accounts.iter();
 
     let host_account = next_account_info(accounts_iter)?;
@@ -367,126 +1102,7754 @@ pub fn process_calculate_scores(program_id: &Pubkey, accounts: &[AccountInfo]) -
     }
 
     // Verify host is the quiz creator
-    let mut quiz_data = QuizSession::try_from_slice(&quiz_account.data.borrow())?;
-    if quiz_data.host != *host_account.key {
-        return Err(ProgramError::InvalidAccountData);
+    let mut quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+
+    // A start that is already due should go through StartQuiz
+    if start_slot != 0 && start_slot <= Clock::get()?.slot {
+        return Err(QuizError::InvalidStartSlot.into());
     }
 
-    // Get question accounts
-    let mut questions = Vec::with_capacity(quiz_data.question_count as usize);
-    for _ in 0..quiz_data.question_count {
-        let question_account = next_account_info(accounts_iter)?;
-        let question = QuizQuestion::try_from_slice(&question_account.data.borrow())?;
-        questions.push(question);
+    let status = match start_slot {
+        0 => QuizStatus::Draft,
+        _ => QuizStatus::Lobby,
+    };
+    if quiz_data.status != status {
+        set_status(&mut quiz_data, status)?;
     }
+    quiz_data.start_slot = start_slot;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
 
-    // Process each player's answers
-    for _ in 0..quiz_data.player_count {
-        let player_answer_account = next_account_info(accounts_iter)?;
-        let player_score_account = next_account_info(accounts_iter)?;
-        let system_program = next_account_info(accounts_iter)?;
+    msg!("Quiz scheduled to start at slot {}", start_slot);
+    Ok(())
+}
 
-        // Get player answers
-        let player_answer = PlayerAnswer::try_from_slice(&player_answer_account.data.borrow())?;
+pub fn process_activate_quiz(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
 
-        // Calculate score
-        let mut score: u8 = 0;
-        for (i, &answer_idx) in player_answer.answers.iter().enumerate() {
-            if i < questions.len() && answer_idx == questions[i].correct_answer_index {
-                score += 1;
-            }
-        }
+    let quiz_account = next_account_info(accounts_iter)?;
 
-        // Create score account
-        let (score_pda, bump_seed) = Pubkey::find_program_address(
-            &[
-                b"player_score",
-                quiz_account.key.as_ref(),
-                player_answer.player.as_ref(),
-            ],
-            program_id,
-        );
+    let mut quiz_data = load_quiz(program_id, quiz_account)?;
+    if quiz_data.status != QuizStatus::Lobby {
+        return Err(QuizError::QuizNotScheduled.into());
+    }
+    let slot = Clock::get()?.slot;
+    if slot < quiz_data.start_slot {
+        return Err(QuizError::StartSlotNotReached.into());
+    }
 
-        // Verify PDA matches provided account
-        if score_pda != *player_score_account.key {
-            return Err(ProgramError::InvalidArgument);
-        }
+    // Set quiz as active, the join window counts from here
+    set_status(&mut quiz_data, QuizStatus::Active)?;
+    quiz_data.start_slot = slot;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
 
-        // Create score account
-        let rent = Rent::get()?;
-        let rent_lamports = rent.minimum_balance(PlayerScore::SIZE);
+    msg!("Scheduled quiz started and open for players");
+    Ok(())
+}
 
-        invoke_signed(
-            &system_instruction::create_account(
-                host_account.key,
-                player_score_account.key,
-                rent_lamports,
-                PlayerScore::SIZE as u64,
-                program_id,
-            ),
-            &[
-                host_account.clone(),
-                player_score_account.clone(),
-                system_program.clone(),
-            ],
-            &[&[
-                b"player_score",
-                quiz_account.key.as_ref(),
-                player_answer.player.as_ref(),
-                &[bump_seed],
-            ]],
-        )?;
+pub fn process_close_submissions(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
 
-        // Save player score
-        let player_score = PlayerScore {
-            player: player_answer.player,
-            score,
-        };
-        player_score.serialize(&mut &mut player_score_account.data.borrow_mut()[..])?;
+    let caller = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
 
-        msg!(
-            "Player {} scored {} out of {}",
-            player_answer.player,
-            score,
-            questions.len()
-        );
+    // Verify caller is signer
+    if !caller.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut quiz_data = load_quiz(program_id, quiz_account)?;
+
+    // The host can close early, anyone else once the deadline passed so
+    // scoring never waits on the host
+    if quiz_data.host != *caller.key && !quiz_data.deadline_passed(Clock::get()?.slot) {
+        return Err(ProgramError::InvalidAccountData);
     }
 
-    // Mark quiz as completed
-    quiz_data.completed = true;
+    set_status(&mut quiz_data, QuizStatus::SubmissionsClosed)?;
     quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
 
-    msg!("Quiz completed and scores calculated");
+    msg!("Submissions closed, quiz is ready for scoring");
     Ok(())
 }
 
-pub fn process_undelegate_player(
-    _program_id: &Pubkey,
+pub fn process_cancel_quiz(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let host_stats_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    // Verify host is signer
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify host is the quiz creator
+    let mut quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+
+    // Escrowed entry fees have no way back to players yet, contributions
+    // are refunded once cancelled
+    if quiz_data.prize_pool > quiz_data.contributed_lamports {
+        return Err(QuizError::PrizePoolNotEmpty.into());
+    }
+
+    // Calling off a quiz players could join counts against the host
+    if !quiz_data.status.is_setup() {
+        let mut host_stats = load_host_stats(
+            program_id,
+            host_account.key,
+            host_account,
+            host_stats_account,
+            system_program,
+        )?;
+        host_stats.quizzes_cancelled = host_stats.quizzes_cancelled.saturating_add(1);
+        host_stats.serialize(&mut &mut host_stats_account.data.borrow_mut()[..])?;
+    }
+
+    set_status(&mut quiz_data, QuizStatus::Cancelled)?;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+
+    msg!("Quiz cancelled");
+    Ok(())
+}
+
+pub fn process_join_quiz(
+    program_id: &Pubkey,
     accounts: &[AccountInfo],
-    pda_seeds: Vec<Vec<u8>>,
+    referrer: Option<Pubkey>,
 ) -> ProgramResult {
     let account_iter = &mut accounts.iter();
 
-    let player_answer_account = next_account_info(account_iter)?;
-    let delegation_buffer = next_account_info(account_iter)?;
     let player = next_account_info(account_iter)?;
     let system_program = next_account_info(account_iter)?;
+    let player_answer_account = next_account_info(account_iter)?;
+    let quiz_account = next_account_info(account_iter)?;
 
     // Verify player is signer
     if !player.is_signer {
         return Err(ProgramError::MissingRequiredSignature);
     }
 
-    // Undelegate player account
-    undelegate_account(
-        player_answer_account,
-        player_answer_account.key,
+    register_player(
+        program_id,
         player,
         system_program,
-        delegation_buffer,
-        pda_seeds,
+        player_answer_account,
+        quiz_account,
+        account_iter,
+        true,
+        referrer,
     )?;
 
-    msg!("Player {} undelegated from quiz", player.key);
+    msg!("Player {} joined the quiz", player.key);
     Ok(())
 }
+
+pub fn process_delegate_player(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    validator: Option<Pubkey>,
+    referrer: Option<Pubkey>,
+) -> ProgramResult {
+    let account_iter = &mut accounts.iter();
+
+    let player = next_account_info(account_iter)?;
+    let system_program = next_account_info(account_iter)?;
+    let player_answer_account = next_account_info(account_iter)?;
+    let owner_program = next_account_info(account_iter)?;
+    let delegation_buffer = next_account_info(account_iter)?;
+    let delegation_record = next_account_info(account_iter)?;
+    let delegation_metadata = next_account_info(account_iter)?;
+    let delegation_program = next_account_info(account_iter)?;
+    let quiz_account = next_account_info(account_iter)?;
+
+    // Verify player is signer
+    if !player.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let (quiz_data, payer) = register_player(
+        program_id,
+        player,
+        system_program,
+        player_answer_account,
+        quiz_account,
+        account_iter,
+        false,
+        referrer,
+    )?;
+    let validator = delegation_validator(&quiz_data, validator)?;
+
+    // Set up delegation
+    let pda_seeds: &[&[u8]] = &[
+        PlayerAnswer::SEED,
+        quiz_account.key.as_ref(),
+        player.key.as_ref(),
+    ];
+    let delegate_accounts = DelegateAccounts {
+        payer,
+        pda: player_answer_account,
+        owner_program,
+        buffer: delegation_buffer,
+        delegation_record,
+        delegation_metadata,
+        delegation_program,
+        system_program,
+    };
+
+    let delegate_config = DelegateConfig {
+        commit_frequency_ms: quiz_data.delegation.commit_frequency_ms,
+        validator,
+    };
+
+    delegate_account(delegate_accounts, pda_seeds, delegate_config)?;
+
+    Ok(())
+}
+
+/// Checks and bookkeeping shared by `JoinQuiz` and `DelegatePlayer`: escrow
+/// the entry fee, admit tournament entrants and create the answer sheet
+/// crediting the referrer.
+#[allow(clippy::too_many_arguments)]
+fn register_player<'a, 'info>(
+    program_id: &Pubkey,
+    player: &'a AccountInfo<'info>,
+    system_program: &'a AccountInfo<'info>,
+    player_answer_account: &'a AccountInfo<'info>,
+    quiz_account: &'a AccountInfo<'info>,
+    optional_accounts: &mut impl Iterator<Item = &'a AccountInfo<'info>>,
+    base_layer: bool,
+    referrer: Option<Pubkey>,
+) -> Result<(QuizSession, &'a AccountInfo<'info>), ProgramError> {
+    // Verify quiz is active
+    let mut quiz_data = load_quiz(program_id, quiz_account)?;
+    assert_accepting_answers(&quiz_data)?;
+
+    // Late joiners only get in within the grace window after the start
+    if !quiz_data.join_window_open(Clock::get()?.slot) {
+        return Err(QuizError::JoinWindowClosed.into());
+    }
+
+    // Practice quizzes are played on chain through PracticeQuiz, base layer
+    // quizzes join through JoinQuiz and rollup quizzes through DelegatePlayer
+    if quiz_data.mode == QuizMode::Practice || quiz_data.delegation.base_layer != base_layer {
+        return Err(QuizError::WrongQuizMode.into());
+    }
+
+    // Duels are strictly two players
+    if quiz_data.mode == QuizMode::Duel && quiz_data.player_count >= QuizSession::DUEL_PLAYERS {
+        return Err(QuizError::QuizFull.into());
+    }
+
+    // The host and scorer know the answers
+    if quiz_data.barred_from_playing(player.key) {
+        return Err(QuizError::HostCannotPlay.into());
+    }
+
+    let event_log = next_event_log(program_id, quiz_account, &quiz_data, optional_accounts)?;
+
+    if referrer == Some(*player.key) {
+        return Err(QuizError::InvalidReferrer.into());
+    }
+
+    // Escrow the entry fee and stake in the quiz vault, the referrer's cut
+    // stays out of the prize pool until it is paid
+    let mut entry_fee = quiz_data.entry_fee;
+    let mut referral_cut = 0;
+    let stake = quiz_data.join_stake;
+    if quiz_data.pays_to_join() {
+        let vault_account = next_account_info(optional_accounts)?;
+        assert_owned_by(vault_account, program_id)?;
+        assert_pda(
+            vault_account,
+            &[QuizSession::VAULT_SEED, quiz_account.key.as_ref()],
+            program_id,
+        )?;
+        // Fees set in USD are converted at the current SOL price
+        if quiz_data.entry_fee_usd_cents > 0 {
+            let price = pyth::PriceUpdate::load(next_account_info(optional_accounts)?)?;
+            entry_fee = price.usd_cents_to_lamports(
+                quiz_data.entry_fee_usd_cents,
+                &quiz_data.price_feed_id,
+                Clock::get()?.unix_timestamp,
+            )?;
+        }
+        invoke(
+            &system_instruction::transfer(player.key, vault_account.key, entry_fee + stake),
+            &[
+                player.clone(),
+                vault_account.clone(),
+                system_program.clone(),
+            ],
+        )?;
+        if referrer.is_some() {
+            referral_cut = (entry_fee as u128 * quiz_data.referral_bps as u128 / 10_000) as u64;
+        }
+        quiz_data.prize_pool = quiz_data
+            .prize_pool
+            .checked_add(entry_fee - referral_cut)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        quiz_data.stakes_held = quiz_data
+            .stakes_held
+            .checked_add(stake)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+    }
+
+    // Tournament rounds after the first only admit players who advanced
+    if quiz_data.tournament != Pubkey::default() {
+        let tournament_account = next_account_info(optional_accounts)?;
+        if *tournament_account.key != quiz_data.tournament {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let tournament_data = load_tournament(program_id, tournament_account)?;
+        if tournament_data.current_round != quiz_data.tournament_round {
+            return Err(QuizError::WrongTournamentRound.into());
+        }
+        if tournament_data.entrant_count > 0 && !tournament_data.entrants().contains(player.key) {
+            return Err(QuizError::NotTournamentEntrant.into());
+        }
+    }
+
+    // The host can sponsor the rent so players with empty wallets can join
+    let payer = match optional_accounts.next() {
+        Some(sponsor) => {
+            if !sponsor.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if *sponsor.key != quiz_data.host {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            sponsor
+        }
+        None => player,
+    };
+
+    // Prepare player answer PDA seeds
+    let seed_1 = PlayerAnswer::SEED;
+    let seed_2 = quiz_account.key.as_ref();
+    let seed_3 = player.key.as_ref();
+    let pda_seeds: &[&[u8]] = &[seed_1, seed_2, seed_3];
+    let bump_seed = assert_pda(player_answer_account, pda_seeds, program_id)?;
+
+    // An existing answer account means this player already joined
+    if !player_answer_account.data_is_empty() {
+        return Err(QuizError::PlayerAlreadyJoined.into());
+    }
+
+    // Create player answer account
+    let account_size = PlayerAnswer::get_size(quiz_data.question_count as usize);
+    create_pda_account(
+        program_id,
+        payer,
+        player_answer_account,
+        system_program,
+        account_size,
+        &[seed_1, seed_2, seed_3, &[bump_seed]],
+    )?;
+
+    let player_answers = PlayerAnswer {
+        referrer: referrer.unwrap_or_default(),
+        referral_cut,
+        stake,
+        ..PlayerAnswer::new(*player.key, quiz_data.question_count as usize)
+    };
+    player_answers.serialize(&mut &mut player_answer_account.data.borrow_mut()[..])?;
+
+    // Increment player count
+    quiz_data.player_count += 1;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+
+    QuizEvent::PlayerJoined(PlayerJoined {
+        quiz: *quiz_account.key,
+        player: *player.key,
+        entry_fee,
+    })
+    .emit()?;
+    log_event(event_log, LoggedEvent::KIND_JOINED, *player.key, 0)?;
+
+    Ok((quiz_data, payer))
+}
+
+pub fn process_submit_answers(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    answers: Vec<u8>,
+    confidence: Vec<u8>,
+    relay_nonce: Option<u64>,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    // The relayer paying for the transaction when relayed
+    let player_account = next_account_info(accounts_iter)?;
+    let player_answer_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+
+    let (quiz_data, mut player_answers) = match relay_nonce {
+        None => load_answer_sheet(
+            program_id,
+            player_account,
+            player_answer_account,
+            quiz_account,
+            session_account(accounts),
+        )?,
+        Some(nonce) => {
+            let instructions_sysvar = next_account_info(accounts_iter)?;
+            let (quiz_data, mut player_answers) =
+                load_open_answer_sheet(program_id, player_answer_account, quiz_account)?;
+            // Each signed payload goes through once
+            if nonce <= player_answers.relay_nonce {
+                return Err(QuizError::StaleRelayNonce.into());
+            }
+            ed25519::assert_signed(
+                instructions_sysvar,
+                &player_answers.player,
+                &PlayerAnswer::relay_message(quiz_account.key, &answers, &confidence, nonce),
+            )?;
+            player_answers.relay_nonce = nonce;
+            (quiz_data, player_answers)
+        }
+    };
+    let event_log = next_event_log(program_id, quiz_account, &quiz_data, accounts_iter)?;
+    validate_answers(quiz_data.served_question_count(), &answers)?;
+
+    // Live and adaptive quizzes collect answers one question at a time
+    if quiz_data.mode.answers_one_at_a_time() {
+        return Err(QuizError::WrongQuizMode.into());
+    }
+
+    // Reject repeated submissions unless the host allows resubmission
+    if player_answers.answered_count() > 0 && !quiz_data.allow_resubmission {
+        return Err(QuizError::AnswersAlreadySubmitted.into());
+    }
+
+    // Update player answers data
+    let slot = Clock::get()?.slot;
+    let confidence = validate_confidence(&quiz_data, &answers, confidence)?;
+    for index in place_answers(&quiz_data, &mut player_answers, answers, confidence) {
+        player_answers.mark_answered(index);
+        player_answers.answer_slots[index] = slot;
+    }
+    player_answers.submitted = true;
+    player_answers.submitted_slot = slot;
+
+    // Save answers to delegated account
+    player_answers.serialize(&mut &mut player_answer_account.data.borrow_mut()[..])?;
+
+    emit_answers_submitted(quiz_account, event_log, &player_answers)?;
+    Ok(())
+}
+
+pub fn process_update_answers(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    answers: Vec<u8>,
+    confidence: Vec<u8>,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let player_account = next_account_info(accounts_iter)?;
+    let player_answer_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+
+    let (quiz_data, mut player_answers) = load_answer_sheet(
+        program_id,
+        player_account,
+        player_answer_account,
+        quiz_account,
+        session_account(accounts),
+    )?;
+    let event_log = next_event_log(program_id, quiz_account, &quiz_data, accounts_iter)?;
+    validate_answers(quiz_data.served_question_count(), &answers)?;
+
+    // Live and adaptive quizzes collect answers one question at a time
+    if quiz_data.mode.answers_one_at_a_time() {
+        return Err(QuizError::WrongQuizMode.into());
+    }
+
+    // Updates overwrite an earlier submission and need the host's opt-in
+    if !player_answers.submitted {
+        return Err(QuizError::AnswersNotSubmitted.into());
+    }
+    if !quiz_data.allow_resubmission {
+        return Err(QuizError::AnswerUpdatesDisabled.into());
+    }
+
+    // Overwrite the previous submission
+    let confidence = validate_confidence(&quiz_data, &answers, confidence)?;
+    place_answers(&quiz_data, &mut player_answers, answers, confidence);
+    if quiz_data.refresh_slot_on_update {
+        let slot = Clock::get()?.slot;
+        player_answers.answer_slots.fill(slot);
+        player_answers.submitted_slot = slot;
+    }
+
+    player_answers.serialize(&mut &mut player_answer_account.data.borrow_mut()[..])?;
+
+    emit_answers_submitted(quiz_account, event_log, &player_answers)?;
+    Ok(())
+}
+
+pub fn process_submit_answer(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    question_index: u8,
+    answer: u8,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let player_account = next_account_info(accounts_iter)?;
+    let player_answer_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+
+    let (quiz_data, mut player_answers) = load_answer_sheet(
+        program_id,
+        player_account,
+        player_answer_account,
+        quiz_account,
+        session_account(accounts),
+    )?;
+    let event_log = next_event_log(program_id, quiz_account, &quiz_data, accounts_iter)?;
+
+    // Verify question and answer indices are in range
+    if question_index >= quiz_data.question_count {
+        return Err(QuizError::InvalidQuestionIndex.into());
+    }
+    if !QuizQuestion::is_valid_answer(answer) {
+        return Err(QuizError::InvalidAnswerIndex.into());
+    }
+
+    // Players served a subset only answer the questions in it
+    if !quiz_data
+        .question_subset(&player_answers.player)
+        .contains(&question_index)
+    {
+        return Err(QuizError::QuestionNotServed.into());
+    }
+
+    // Live quizzes only accept the revealed question within its window
+    let clock = Clock::get()?;
+    if quiz_data.mode.is_live() && !quiz_data.answer_window_open(question_index, clock.slot) {
+        return Err(QuizError::AnswerWindowClosed.into());
+    }
+
+    // Reject repeated answers unless the host allows resubmission, elimination
+    // rounds and adaptive questions are always answered once
+    let index = question_index as usize;
+    let elimination = quiz_data.mode == QuizMode::Elimination;
+    let adaptive = quiz_data.mode == QuizMode::Adaptive;
+    if player_answers.is_answered(index)
+        && (!quiz_data.allow_resubmission || elimination || adaptive)
+    {
+        return Err(QuizError::QuestionAlreadyAnswered.into());
+    }
+
+    if elimination {
+        apply_elimination_round(
+            program_id,
+            quiz_account,
+            next_account_info(accounts_iter)?,
+            &quiz_data,
+            &mut player_answers,
+            question_index,
+            answer,
+        )?;
+    }
+
+    // Adaptive quizzes only take the question the player was served, and
+    // its answer picks the level of the next one
+    let adaptive_serving = if adaptive {
+        let bank = load_adaptive_bank(program_id, quiz_account, next_account_info(accounts_iter)?)?;
+        if bank.next_question(&player_answers) != Some(question_index) {
+            return Err(QuizError::QuestionNotServed.into());
+        }
+        let question = load_question(
+            program_id,
+            quiz_account,
+            next_account_info(accounts_iter)?,
+            question_index,
+        )?;
+        Some((bank, question))
+    } else {
+        None
+    };
+
+    // Quizzes with a live leaderboard grade the answer against its question,
+    // replacing what a changed answer earned before
+    let live_board =
+        match next_live_leaderboard(program_id, quiz_account, &quiz_data, accounts_iter)? {
+            Some(board_account) => {
+                let question = load_question(
+                    program_id,
+                    quiz_account,
+                    next_account_info(accounts_iter)?,
+                    question_index,
+                )?;
+                let previous = player_answers.is_answered(index).then(|| LiveGrade {
+                    points: answer_points(&quiz_data, &question, &player_answers, index),
+                    correct: player_answers.answers[index] == question.correct_answer_index,
+                });
+                Some((board_account, question, previous))
+            }
+            None => None,
+        };
+
+    // Record the answer, the sheet counts as submitted once every question
+    // served, or an adaptive player's share of them, is answered
+    player_answers.answers[index] = answer;
+    player_answers.answer_slots[index] = clock.slot;
+    player_answers.mark_answered(index);
+    player_answers.submitted =
+        player_answers.answered_count() >= quiz_data.served_question_count() as usize;
+    player_answers.submitted_slot = clock.slot;
+    if let Some((bank, question)) = adaptive_serving {
+        player_answers.adjust_adaptive_level(answer, question.correct_answer_index);
+        player_answers.submitted =
+            player_answers.answered_count() >= bank.questions_per_player as usize;
+    }
+
+    if let Some((board_account, question, previous)) = live_board {
+        grade_live_answer(
+            board_account,
+            &quiz_data,
+            &question,
+            &mut player_answers,
+            index,
+            previous,
+        )?;
+    }
+
+    player_answers.serialize(&mut &mut player_answer_account.data.borrow_mut()[..])?;
+
+    emit_answers_submitted(quiz_account, event_log, &player_answers)?;
+    Ok(())
+}
+
+/// What an answer counted for on the live leaderboard.
+struct LiveGrade {
+    points: i32,
+    correct: bool,
+}
+
+fn next_live_leaderboard<'a, 'info>(
+    program_id: &Pubkey,
+    quiz_account: &AccountInfo,
+    quiz_data: &QuizSession,
+    accounts_iter: &mut impl Iterator<Item = &'a AccountInfo<'info>>,
+) -> Result<Option<&'a AccountInfo<'info>>, ProgramError> {
+    if !quiz_data.live_leaderboard {
+        return Ok(None);
+    }
+    let board_account = next_account_info(accounts_iter)?;
+    assert_owned_by(board_account, program_id)?;
+    assert_pda(
+        board_account,
+        &[LiveLeaderboard::SEED, quiz_account.key.as_ref()],
+        program_id,
+    )?;
+    Ok(Some(board_account))
+}
+
+/// Add the answer at `index` to the player's running score and move them on
+/// the live leaderboard. Elimination quizzes rank by rounds survived.
+fn grade_live_answer(
+    board_account: &AccountInfo,
+    quiz_data: &QuizSession,
+    question: &QuizQuestion,
+    player_answers: &mut PlayerAnswer,
+    index: usize,
+    previous: Option<LiveGrade>,
+) -> ProgramResult {
+    let mut board = LiveLeaderboard::try_from_slice(&board_account.data.borrow())?;
+    let points = answer_points(quiz_data, question, player_answers, index);
+    let correct = player_answers.answers[index] == question.correct_answer_index;
+
+    player_answers.live_score = if quiz_data.mode == QuizMode::Elimination {
+        player_answers.rounds_survived as u32
+    } else {
+        let previous_points = previous.as_ref().map_or(0, |grade| grade.points);
+        (player_answers.live_score as i64 - previous_points as i64 + points as i64)
+            .clamp(0, u32::MAX as i64) as u32
+    };
+
+    match previous {
+        Some(grade) => {
+            board.correct_count = board.correct_count - grade.correct as u32 + correct as u32
+        }
+        None => {
+            board.graded_count += 1;
+            board.correct_count += correct as u32;
+        }
+    }
+    board.record(
+        player_answers.player,
+        player_answers
+            .live_score
+            .saturating_sub(player_answers.hint_penalty as u32),
+    );
+    board.serialize(&mut &mut board_account.data.borrow_mut()[..])?;
+    Ok(())
+}
+
+/// Knock the player out on a wrong answer or a missed earlier round, otherwise
+/// count the round as survived. Rejects players who are already out.
+fn apply_elimination_round(
+    program_id: &Pubkey,
+    quiz_account: &AccountInfo,
+    question_account: &AccountInfo,
+    quiz_data: &QuizSession,
+    player_answers: &mut PlayerAnswer,
+    question_index: u8,
+    answer: u8,
+) -> ProgramResult {
+    if player_answers.eliminated {
+        return Err(QuizError::PlayerEliminated.into());
+    }
+
+    // Verify question account
+    assert_owned_by(question_account, program_id)?;
+    assert_pda(
+        question_account,
+        &[
+            QuizQuestion::SEED,
+            quiz_account.key.as_ref(),
+            &[question_index],
+        ],
+        program_id,
+    )?;
+    let question = QuizQuestion::try_from_slice(&question_account.data.borrow())?;
+
+    // Players must have survived every earlier round to play this one
+    let missed_round =
+        player_answers.rounds_survived.saturating_add(1) != quiz_data.rounds_revealed;
+    if missed_round || answer != question.correct_answer_index {
+        player_answers.eliminated = true;
+        msg!(
+            "Player {} eliminated in round {}",
+            player_answers.player,
+            quiz_data.rounds_revealed
+        );
+    } else {
+        player_answers.rounds_survived += 1;
+    }
+
+    Ok(())
+}
+
+/// Shared checks for instructions that write a player's answer sheet: the
+/// player or their session key signs, the quiz and answer accounts are this
+/// player's PDAs, and the quiz is still accepting answers.
+fn load_answer_sheet(
+    program_id: &Pubkey,
+    signer_account: &AccountInfo,
+    player_answer_account: &AccountInfo,
+    quiz_account: &AccountInfo,
+    session_account: Option<&AccountInfo>,
+) -> Result<(QuizSession, PlayerAnswer), ProgramError> {
+    // Verify player or session key is signer
+    if !signer_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let (quiz_data, player_answers) =
+        load_open_answer_sheet(program_id, player_answer_account, quiz_account)?;
+
+    // Anyone but the player needs a live session for them
+    if player_answers.player != *signer_account.key {
+        let session_account = session_account.ok_or(ProgramError::NotEnoughAccountKeys)?;
+        let session = load_answer_session(
+            program_id,
+            quiz_account,
+            session_account,
+            &player_answers.player,
+        )?;
+        if session.session_key != *signer_account.key || Clock::get()?.slot > session.expires_slot {
+            return Err(QuizError::InvalidSession.into());
+        }
+    }
+    Ok((quiz_data, player_answers))
+}
+
+/// `load_answer_sheet` short of checking who sends the answers, for
+/// answers the player signed off-chain.
+fn load_open_answer_sheet(
+    program_id: &Pubkey,
+    player_answer_account: &AccountInfo,
+    quiz_account: &AccountInfo,
+) -> Result<(QuizSession, PlayerAnswer), ProgramError> {
+    let quiz_data = load_quiz(program_id, quiz_account)?;
+    assert_owned_by(player_answer_account, program_id)?;
+    let player_answers = PlayerAnswer::try_from_slice(&player_answer_account.data.borrow())?;
+    let slot = Clock::get()?.slot;
+
+    // Verify the answer account is the PDA of the player it records
+    assert_pda(
+        player_answer_account,
+        &[
+            PlayerAnswer::SEED,
+            quiz_account.key.as_ref(),
+            player_answers.player.as_ref(),
+        ],
+        program_id,
+    )?;
+
+    // Verify quiz is active and its questions can be read
+    assert_accepting_answers(&quiz_data)?;
+    assert_questions_revealed(&quiz_data)?;
+
+    // Reject answers after the deadline
+    if quiz_data.deadline_passed(slot) {
+        return Err(QuizError::SubmissionDeadlinePassed.into());
+    }
+
+    if player_answers.committed {
+        return Err(QuizError::AnswersCommitted.into());
+    }
+    if quiz_data.barred_from_playing(&player_answers.player) {
+        return Err(QuizError::HostCannotPlay.into());
+    }
+    Ok((quiz_data, player_answers))
+}
+
+/// The answer session PDA comes after every other account of an answer
+/// instruction.
+fn session_account<'a, 'info>(
+    accounts: &'a [AccountInfo<'info>],
+) -> Option<&'a AccountInfo<'info>> {
+    accounts.get(3..).and_then(<[_]>::last)
+}
+
+fn load_answer_session(
+    program_id: &Pubkey,
+    quiz_account: &AccountInfo,
+    session_account: &AccountInfo,
+    player: &Pubkey,
+) -> Result<AnswerSession, ProgramError> {
+    assert_owned_by(session_account, program_id)?;
+    assert_pda(
+        session_account,
+        &[
+            AnswerSession::SEED,
+            quiz_account.key.as_ref(),
+            player.as_ref(),
+        ],
+        program_id,
+    )?;
+    Ok(AnswerSession::try_from_slice(
+        &session_account.data.borrow(),
+    )?)
+}
+
+pub fn process_create_session(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    expires_slot: u64,
+    lamports: u64,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let player = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let session_account = next_account_info(accounts_iter)?;
+    let session_key = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    // Verify player is signer
+    if !player.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let quiz_data = load_quiz(program_id, quiz_account)?;
+    if !quiz_data.status.is_setup() && quiz_data.status != QuizStatus::Active {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Sessions never outlive the quiz deadline
+    let expires_slot = match quiz_data.final_deadline_slot() {
+        0 => expires_slot,
+        deadline_slot => expires_slot.min(deadline_slot),
+    };
+    if expires_slot < Clock::get()?.slot {
+        return Err(QuizError::InvalidSession.into());
+    }
+
+    // Create the session on first use, later calls rotate the key
+    if session_account.data_is_empty() {
+        create_program_account(
+            program_id,
+            player,
+            session_account,
+            system_program,
+            AnswerSession::SIZE,
+            &[
+                AnswerSession::SEED,
+                quiz_account.key.as_ref(),
+                player.key.as_ref(),
+            ],
+        )?;
+    } else {
+        load_answer_session(program_id, quiz_account, session_account, player.key)?;
+    }
+
+    AnswerSession {
+        player: *player.key,
+        quiz: *quiz_account.key,
+        session_key: *session_key.key,
+        expires_slot,
+    }
+    .serialize(&mut &mut session_account.data.borrow_mut()[..])?;
+
+    // Fund the key so it can pay for its own transactions
+    if lamports > 0 {
+        invoke(
+            &system_instruction::transfer(player.key, session_key.key, lamports),
+            &[player.clone(), session_key.clone(), system_program.clone()],
+        )?;
+    }
+
+    msg!(
+        "Session key {} can answer until slot {}",
+        session_key.key,
+        expires_slot
+    );
+    Ok(())
+}
+
+/// Load any player's answer sheet for the quiz, delegated or not, checking
+/// it is the PDA of the player it records.
+fn load_answer_sheet_of(
+    program_id: &Pubkey,
+    quiz_account: &AccountInfo,
+    player_answer_account: &AccountInfo,
+) -> Result<PlayerAnswer, ProgramError> {
+    assert_owned_by_program_or_delegated(player_answer_account, program_id)?;
+    let player_answer = PlayerAnswer::try_from_slice(&player_answer_account.data.borrow())?;
+    assert_pda(
+        player_answer_account,
+        &[
+            PlayerAnswer::SEED,
+            quiz_account.key.as_ref(),
+            player_answer.player.as_ref(),
+        ],
+        program_id,
+    )?;
+    Ok(player_answer)
+}
+
+fn emit_answers_submitted(
+    quiz_account: &AccountInfo,
+    event_log: Option<&AccountInfo>,
+    player_answers: &PlayerAnswer,
+) -> ProgramResult {
+    let answered_count = player_answers.answered_count() as u8;
+    QuizEvent::AnswersSubmitted(AnswersSubmitted {
+        quiz: *quiz_account.key,
+        player: player_answers.player,
+        answered_count,
+        slot: player_answers.submitted_slot,
+    })
+    .emit()?;
+    log_event(
+        event_log,
+        LoggedEvent::KIND_SUBMITTED,
+        player_answers.player,
+        answered_count,
+    )
+}
+
+/// Quizzes with an event log take it right after the fixed accounts of
+/// every instruction that logs.
+fn next_event_log<'a, 'info>(
+    program_id: &Pubkey,
+    quiz_account: &AccountInfo,
+    quiz_data: &QuizSession,
+    accounts_iter: &mut impl Iterator<Item = &'a AccountInfo<'info>>,
+) -> Result<Option<&'a AccountInfo<'info>>, ProgramError> {
+    if !quiz_data.event_log {
+        return Ok(None);
+    }
+    let log_account = next_account_info(accounts_iter)?;
+    assert_owned_by(log_account, program_id)?;
+    assert_pda(
+        log_account,
+        &[QuizEventLog::SEED, quiz_account.key.as_ref()],
+        program_id,
+    )?;
+    Ok(Some(log_account))
+}
+
+fn log_event(event_log: Option<&AccountInfo>, kind: u8, actor: Pubkey, value: u8) -> ProgramResult {
+    let Some(log_account) = event_log else {
+        return Ok(());
+    };
+    let mut log = QuizEventLog::try_from_slice(&log_account.data.borrow())?;
+    log.push(LoggedEvent {
+        kind,
+        slot: Clock::get()?.slot,
+        actor,
+        value,
+    });
+    log.serialize(&mut &mut log_account.data.borrow_mut()[..])?;
+    Ok(())
+}
+
+fn validate_answers(question_count: u8, answers: &[u8]) -> ProgramResult {
+    // Verify answer count matches the questions served
+    if answers.len() != question_count as usize {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    // Verify every answer points at an existing option or is a skip
+    if !answers
+        .iter()
+        .all(|&answer| QuizQuestion::is_valid_answer(answer))
+    {
+        return Err(QuizError::InvalidAnswerIndex.into());
+    }
+
+    Ok(())
+}
+
+/// Write a sheet's answers, in the order the player was served their
+/// questions, to the questions they answer and return those questions'
+/// indexes. That is every question in order unless the quiz serves each
+/// player a subset.
+fn place_answers(
+    quiz_data: &QuizSession,
+    player_answers: &mut PlayerAnswer,
+    answers: Vec<u8>,
+    confidence: Vec<u8>,
+) -> Vec<usize> {
+    let subset = quiz_data.question_subset(&player_answers.player);
+    subset
+        .into_iter()
+        .zip(answers.into_iter().zip(confidence))
+        .map(|(question_index, (answer, level))| {
+            let index = question_index as usize;
+            player_answers.answers[index] = answer;
+            player_answers.confidence[index] = level;
+            index
+        })
+        .collect()
+}
+
+/// Confidence-weighted quizzes need a 1-3 level for every answer, other
+/// quizzes take none and grade every answer at 1.
+fn validate_confidence(
+    quiz_data: &QuizSession,
+    answers: &[u8],
+    confidence: Vec<u8>,
+) -> Result<Vec<u8>, ProgramError> {
+    if !quiz_data.confidence_weighted {
+        if !confidence.is_empty() {
+            return Err(QuizError::InvalidConfidence.into());
+        }
+        return Ok(vec![1; answers.len()]);
+    }
+    if confidence.len() != answers.len()
+        || !confidence
+            .iter()
+            .all(|level| (1..=PlayerAnswer::MAX_CONFIDENCE).contains(level))
+    {
+        return Err(QuizError::InvalidConfidence.into());
+    }
+    Ok(confidence)
+}
+
+pub fn process_commit_answers(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let magic_program = next_account_info(accounts_iter)?;
+    let magic_context = next_account_info(accounts_iter)?;
+
+    // Verify host is signer
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify host is the quiz creator
+    let quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+    assert_rollup_quiz(&quiz_data)?;
+
+    let answer_accounts = load_sheets_to_commit(program_id, quiz_account, accounts_iter)?;
+
+    // Commit answers from ephemeral rollup to Solana
+    commit_accounts(
+        host_account,
+        answer_accounts.clone(),
+        magic_context,
+        magic_program,
+    )?;
+
+    msg!("Committed {} answer sheets to chain", answer_accounts.len());
+    Ok(())
+}
+
+pub fn process_commit_and_undelegate_answers(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let payer = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let magic_program = next_account_info(accounts_iter)?;
+    let magic_context = next_account_info(accounts_iter)?;
+
+    // Verify payer is signer
+    if !payer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // The host can end delegation any time, anyone else once the deadline
+    // passed so sheets of players who walked away don't stay stuck
+    let quiz_data = load_quiz(program_id, quiz_account)?;
+    assert_rollup_quiz(&quiz_data)?;
+    if quiz_data.host != *payer.key && !quiz_data.deadline_passed(Clock::get()?.slot) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let answer_accounts = load_sheets_to_commit(program_id, quiz_account, accounts_iter)?;
+
+    // Commit the final answers and return the sheets to this program on chain
+    commit_and_undelegate_accounts(payer, answer_accounts.clone(), magic_context, magic_program)?;
+
+    msg!(
+        "Committed and undelegated {} answer sheets",
+        answer_accounts.len()
+    );
+    Ok(())
+}
+
+pub fn process_delegate_quiz(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    validator: Option<Pubkey>,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let owner_program = next_account_info(accounts_iter)?;
+    let delegation_buffer = next_account_info(accounts_iter)?;
+    let delegation_record = next_account_info(accounts_iter)?;
+    let delegation_metadata = next_account_info(accounts_iter)?;
+    let delegation_program = next_account_info(accounts_iter)?;
+
+    // Verify host is signer
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify host is the quiz creator
+    let quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+
+    // Only a running quiz has live state worth moving to the rollup
+    if !quiz_data.status.is_running() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let validator = delegation_validator(&quiz_data, validator)?;
+
+    let quiz_id_bytes = quiz_data.quiz_id.to_le_bytes();
+    let pda_seeds: &[&[u8]] = &[QuizSession::SEED, quiz_data.host.as_ref(), &quiz_id_bytes];
+
+    let delegate_accounts = DelegateAccounts {
+        payer: host_account,
+        pda: quiz_account,
+        owner_program,
+        buffer: delegation_buffer,
+        delegation_record,
+        delegation_metadata,
+        delegation_program,
+        system_program,
+    };
+
+    let delegate_config = DelegateConfig {
+        commit_frequency_ms: quiz_data.delegation.commit_frequency_ms,
+        validator,
+    };
+
+    delegate_account(delegate_accounts, pda_seeds, delegate_config)?;
+
+    msg!(
+        "Quiz {} delegated to the ephemeral rollup",
+        quiz_account.key
+    );
+    Ok(())
+}
+
+pub fn process_commit_and_undelegate_quiz(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let magic_program = next_account_info(accounts_iter)?;
+    let magic_context = next_account_info(accounts_iter)?;
+
+    // Verify host is signer
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify host is the quiz creator
+    let quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+    assert_rollup_quiz(&quiz_data)?;
+
+    // Commit the live state and return the session to this program on chain
+    commit_and_undelegate_accounts(
+        host_account,
+        vec![quiz_account],
+        magic_context,
+        magic_program,
+    )?;
+
+    msg!("Quiz {} committed and undelegated", quiz_account.key);
+    Ok(())
+}
+
+pub fn process_delegate_scoreboard(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    validator: Option<Pubkey>,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let scoreboard_account = next_account_info(accounts_iter)?;
+    let owner_program = next_account_info(accounts_iter)?;
+    let delegation_buffer = next_account_info(accounts_iter)?;
+    let delegation_record = next_account_info(accounts_iter)?;
+    let delegation_metadata = next_account_info(accounts_iter)?;
+    let delegation_program = next_account_info(accounts_iter)?;
+
+    // Verify host is signer
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify host is the quiz creator, grading closes the quiz to new
+    // players so every one of them gets a slot
+    let mut quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+    set_status(&mut quiz_data, QuizStatus::Scoring)?;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+
+    let validator = delegation_validator(&quiz_data, validator)?;
+
+    // One slot per player who has joined so far
+    let capacity = quiz_data.player_count as usize;
+    let pda_seeds: &[&[u8]] = &[QuizScoreboard::SEED, quiz_account.key.as_ref()];
+    create_program_account(
+        program_id,
+        host_account,
+        scoreboard_account,
+        system_program,
+        QuizScoreboard::get_size(capacity),
+        pda_seeds,
+    )?;
+    QuizScoreboard::new(*quiz_account.key, capacity)
+        .serialize(&mut &mut scoreboard_account.data.borrow_mut()[..])?;
+
+    let delegate_accounts = DelegateAccounts {
+        payer: host_account,
+        pda: scoreboard_account,
+        owner_program,
+        buffer: delegation_buffer,
+        delegation_record,
+        delegation_metadata,
+        delegation_program,
+        system_program,
+    };
+
+    let delegate_config = DelegateConfig {
+        commit_frequency_ms: quiz_data.delegation.commit_frequency_ms,
+        validator,
+    };
+
+    delegate_account(delegate_accounts, pda_seeds, delegate_config)?;
+
+    msg!("Scoreboard created for {} players and delegated", capacity);
+    Ok(())
+}
+
+pub fn process_grade_answers(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let scoreboard_account = next_account_info(accounts_iter)?;
+
+    // Verify host is signer
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify host is the quiz creator
+    let quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+
+    let mut scoreboard = load_scoreboard(program_id, quiz_account, scoreboard_account)?;
+    if scoreboard.finalized {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let mut questions = Vec::with_capacity(quiz_data.question_count as usize);
+    for question_index in 0..quiz_data.question_count {
+        let question_account = next_account_info(accounts_iter)?;
+        questions.push(load_question(
+            program_id,
+            quiz_account,
+            question_account,
+            question_index,
+        )?);
+    }
+
+    let sheets = accounts_iter
+        .map(|player_answer_account| {
+            load_answer_sheet_of(program_id, quiz_account, player_answer_account)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if sheets.is_empty() {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    // Buzzer points depend on who answered first, so every sheet is needed
+    let buzzer_winners = if quiz_data.mode == QuizMode::Buzzer {
+        if sheets.len() != quiz_data.player_count as usize {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+        let sheet_refs: Vec<&PlayerAnswer> = sheets.iter().collect();
+        Some(find_buzzer_winners(&questions, &sheet_refs))
+    } else {
+        None
+    };
+
+    for (player_index, player_answer) in sheets.iter().enumerate() {
+        let score = score_sheet(
+            &quiz_data,
+            &questions,
+            player_answer,
+            player_index,
+            buzzer_winners.as_deref(),
+        )
+        .saturating_sub(quiz_data.late_penalty(player_answer.submitted_slot));
+        if !scoreboard.record(player_answer.player, score) {
+            return Err(QuizError::ScoreboardFull.into());
+        }
+    }
+    scoreboard.serialize(&mut &mut scoreboard_account.data.borrow_mut()[..])?;
+
+    msg!("Graded {} answer sheets", sheets.len());
+    Ok(())
+}
+
+pub fn process_commit_scoreboard(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let scoreboard_account = next_account_info(accounts_iter)?;
+    let magic_program = next_account_info(accounts_iter)?;
+    let magic_context = next_account_info(accounts_iter)?;
+
+    // Verify host is signer
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify host is the quiz creator
+    let quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+    assert_rollup_quiz(&quiz_data)?;
+
+    let mut scoreboard = load_scoreboard(program_id, quiz_account, scoreboard_account)?;
+    scoreboard.finalized = true;
+    scoreboard.serialize(&mut &mut scoreboard_account.data.borrow_mut()[..])?;
+
+    // Only the final scores reach the chain, not the answer sheets
+    commit_and_undelegate_accounts(
+        host_account,
+        vec![scoreboard_account],
+        magic_context,
+        magic_program,
+    )?;
+
+    msg!(
+        "Scoreboard committed with {} scores",
+        scoreboard.entry_count
+    );
+    Ok(())
+}
+
+pub fn process_init_live_leaderboard(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    validator: Option<Pubkey>,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let board_account = next_account_info(accounts_iter)?;
+
+    // Verify host is signer
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify host is the quiz creator, the board has to see every answer so
+    // it is set up before the quiz starts
+    let mut quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+    if !quiz_data.status.is_setup() || quiz_data.live_leaderboard {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if !quiz_data.mode.answers_one_at_a_time() {
+        return Err(QuizError::WrongQuizMode.into());
+    }
+
+    let pda_seeds: &[&[u8]] = &[LiveLeaderboard::SEED, quiz_account.key.as_ref()];
+    create_program_account(
+        program_id,
+        host_account,
+        board_account,
+        system_program,
+        LiveLeaderboard::SIZE,
+        pda_seeds,
+    )?;
+    LiveLeaderboard::new(*quiz_account.key)
+        .serialize(&mut &mut board_account.data.borrow_mut()[..])?;
+
+    quiz_data.live_leaderboard = true;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+
+    // Rollup answer sheets grade into it on the rollup, where spectators
+    // subscribe to it
+    if !quiz_data.delegation.base_layer {
+        delegate_quiz_pda(
+            &quiz_data,
+            host_account,
+            board_account,
+            system_program,
+            accounts_iter,
+            pda_seeds,
+            validator,
+        )?;
+    }
+
+    msg!("Live leaderboard created for quiz {}", quiz_account.key);
+    Ok(())
+}
+
+/// Delegate a PDA shared by the whole quiz with the quiz's commit frequency.
+/// The owner program and the delegation accounts come next in
+/// `accounts_iter`.
+fn delegate_quiz_pda<'a, 'info>(
+    quiz_data: &QuizSession,
+    payer: &'a AccountInfo<'info>,
+    pda: &'a AccountInfo<'info>,
+    system_program: &'a AccountInfo<'info>,
+    accounts_iter: &mut impl Iterator<Item = &'a AccountInfo<'info>>,
+    pda_seeds: &[&[u8]],
+    validator: Option<Pubkey>,
+) -> ProgramResult {
+    let validator = delegation_validator(quiz_data, validator)?;
+    let delegate_accounts = DelegateAccounts {
+        payer,
+        pda,
+        owner_program: next_account_info(accounts_iter)?,
+        buffer: next_account_info(accounts_iter)?,
+        delegation_record: next_account_info(accounts_iter)?,
+        delegation_metadata: next_account_info(accounts_iter)?,
+        delegation_program: next_account_info(accounts_iter)?,
+        system_program,
+    };
+    let delegate_config = DelegateConfig {
+        commit_frequency_ms: quiz_data.delegation.commit_frequency_ms,
+        validator,
+    };
+    delegate_account(delegate_accounts, pda_seeds, delegate_config)
+}
+
+pub fn process_commit_live_leaderboard(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let board_account = next_account_info(accounts_iter)?;
+    let magic_program = next_account_info(accounts_iter)?;
+    let magic_context = next_account_info(accounts_iter)?;
+
+    // Verify host is signer
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify host is the quiz creator
+    let quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+    assert_rollup_quiz(&quiz_data)?;
+    if !quiz_data.live_leaderboard {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    assert_owned_by(board_account, program_id)?;
+    assert_pda(
+        board_account,
+        &[LiveLeaderboard::SEED, quiz_account.key.as_ref()],
+        program_id,
+    )?;
+
+    commit_and_undelegate_accounts(
+        host_account,
+        vec![board_account],
+        magic_context,
+        magic_program,
+    )?;
+
+    msg!("Live leaderboard of quiz {} committed", quiz_account.key);
+    Ok(())
+}
+
+pub fn process_init_reactions(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    cooldown_slots: u64,
+    validator: Option<Pubkey>,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let reactions_account = next_account_info(accounts_iter)?;
+
+    // Verify host is signer
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify host is the quiz creator
+    let quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+    if quiz_data.status.is_finished() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let pda_seeds: &[&[u8]] = &[QuizReactions::SEED, quiz_account.key.as_ref()];
+    create_program_account(
+        program_id,
+        host_account,
+        reactions_account,
+        system_program,
+        QuizReactions::SIZE,
+        pda_seeds,
+    )?;
+    let reactions = QuizReactions {
+        quiz: *quiz_account.key,
+        cooldown_slots,
+        counts: [0; QuizReactions::KINDS],
+        total: 0,
+    };
+    reactions.serialize(&mut &mut reactions_account.data.borrow_mut()[..])?;
+
+    // Rollup players react next to their delegated answer sheets
+    if !quiz_data.delegation.base_layer {
+        delegate_quiz_pda(
+            &quiz_data,
+            host_account,
+            reactions_account,
+            system_program,
+            accounts_iter,
+            pda_seeds,
+            validator,
+        )?;
+    }
+
+    msg!("Reactions opened for quiz {}", quiz_account.key);
+    Ok(())
+}
+
+pub fn process_send_reaction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    emoji_code: u8,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let player_account = next_account_info(accounts_iter)?;
+    let player_answer_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let reactions_account = next_account_info(accounts_iter)?;
+
+    // Verify player is signer
+    if !player_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Only players in a running quiz react
+    let quiz_data = load_quiz(program_id, quiz_account)?;
+    if !quiz_data.status.is_running() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    assert_owned_by(player_answer_account, program_id)?;
+    let mut player_answers = load_answer_sheet_of(program_id, quiz_account, player_answer_account)?;
+    if player_answers.player != *player_account.key || !player_answers.joined {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    assert_owned_by(reactions_account, program_id)?;
+    assert_pda(
+        reactions_account,
+        &[QuizReactions::SEED, quiz_account.key.as_ref()],
+        program_id,
+    )?;
+    let mut reactions = QuizReactions::try_from_slice(&reactions_account.data.borrow())?;
+
+    if emoji_code as usize >= QuizReactions::KINDS {
+        return Err(QuizError::InvalidReaction.into());
+    }
+    let slot = Clock::get()?.slot;
+    if player_answers.last_reaction_slot != 0
+        && slot
+            < player_answers
+                .last_reaction_slot
+                .saturating_add(reactions.cooldown_slots)
+    {
+        return Err(QuizError::ReactionCooldown.into());
+    }
+
+    reactions.counts[emoji_code as usize] += 1;
+    reactions.total += 1;
+    reactions.serialize(&mut &mut reactions_account.data.borrow_mut()[..])?;
+    player_answers.last_reaction_slot = slot;
+    player_answers.serialize(&mut &mut player_answer_account.data.borrow_mut()[..])?;
+
+    QuizEvent::ReactionSent(ReactionSent {
+        quiz: *quiz_account.key,
+        player: *player_account.key,
+        emoji_code,
+    })
+    .emit()
+}
+
+pub fn process_commit_reactions(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let reactions_account = next_account_info(accounts_iter)?;
+    let magic_program = next_account_info(accounts_iter)?;
+    let magic_context = next_account_info(accounts_iter)?;
+
+    // Verify host is signer
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify host is the quiz creator
+    let quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+    assert_rollup_quiz(&quiz_data)?;
+    assert_owned_by(reactions_account, program_id)?;
+    assert_pda(
+        reactions_account,
+        &[QuizReactions::SEED, quiz_account.key.as_ref()],
+        program_id,
+    )?;
+
+    commit_and_undelegate_accounts(
+        host_account,
+        vec![reactions_account],
+        magic_context,
+        magic_program,
+    )?;
+
+    msg!("Reactions of quiz {} committed", quiz_account.key);
+    Ok(())
+}
+
+fn load_scoreboard(
+    program_id: &Pubkey,
+    quiz_account: &AccountInfo,
+    scoreboard_account: &AccountInfo,
+) -> Result<QuizScoreboard, ProgramError> {
+    assert_owned_by(scoreboard_account, program_id)?;
+    assert_pda(
+        scoreboard_account,
+        &[QuizScoreboard::SEED, quiz_account.key.as_ref()],
+        program_id,
+    )?;
+    Ok(QuizScoreboard::try_from_slice(
+        &scoreboard_account.data.borrow(),
+    )?)
+}
+
+/// Rollup instructions have nothing to act on in a base layer quiz.
+fn assert_rollup_quiz(quiz_data: &QuizSession) -> ProgramResult {
+    if quiz_data.delegation.base_layer {
+        return Err(QuizError::WrongQuizMode.into());
+    }
+    Ok(())
+}
+
+/// Route to the requested validator, unless the host pinned another one.
+fn delegation_validator(
+    quiz_data: &QuizSession,
+    requested: Option<Pubkey>,
+) -> Result<Option<Pubkey>, ProgramError> {
+    assert_rollup_quiz(quiz_data)?;
+    match (quiz_data.delegation.validator(), requested) {
+        (Some(allowed), Some(requested)) if requested != allowed => {
+            Err(QuizError::ValidatorNotAllowed.into())
+        }
+        (allowed, requested) => Ok(allowed.or(requested)),
+    }
+}
+
+/// Collect the remaining accounts, each of which must be an answer sheet of
+/// the quiz.
+fn load_sheets_to_commit<'a, 'info>(
+    program_id: &Pubkey,
+    quiz_account: &AccountInfo,
+    remaining_accounts: impl Iterator<Item = &'a AccountInfo<'info>>,
+) -> Result<Vec<&'a AccountInfo<'info>>, ProgramError> {
+    let answer_accounts: Vec<&AccountInfo> = remaining_accounts.collect();
+    if answer_accounts.is_empty() {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    for player_answer_account in &answer_accounts {
+        load_answer_sheet_of(program_id, quiz_account, player_answer_account)?;
+    }
+    Ok(answer_accounts)
+}
+
+pub fn process_calculate_scores(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let host_stats_account = next_account_info(accounts_iter)?;
+    let stats_system_program = next_account_info(accounts_iter)?;
+
+    // Verify host is signer
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify host is the quiz creator
+    let mut quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+    let event_log = next_event_log(program_id, quiz_account, &quiz_data, accounts_iter)?;
+
+    // Get question accounts
+    let mut question_accounts = Vec::with_capacity(quiz_data.question_count as usize);
+    let mut questions = Vec::with_capacity(quiz_data.question_count as usize);
+    for question_index in 0..quiz_data.question_count {
+        let question_account = next_account_info(accounts_iter)?;
+        let question = load_question(program_id, quiz_account, question_account, question_index)?;
+        question_accounts.push(question_account);
+        questions.push(question);
+    }
+
+    // Read every player's answers first, buzzer scoring compares players
+    let mut players = Vec::with_capacity(quiz_data.player_count as usize);
+    for _ in 0..quiz_data.player_count {
+        let player_answer_account = next_account_info(accounts_iter)?;
+        let player_score_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+
+        // Get player answers, which may still be delegated after a commit
+        let player_answer = load_answer_sheet_of(program_id, quiz_account, player_answer_account)?;
+
+        players.push((player_answer, player_score_account, system_program));
+    }
+
+    // In buzzer mode record the winner of each question on its account
+    let buzzer_winners = if quiz_data.mode == QuizMode::Buzzer {
+        let sheets: Vec<&PlayerAnswer> = players.iter().map(|(answer, _, _)| answer).collect();
+        let winners = find_buzzer_winners(&questions, &sheets);
+        for ((question, question_account), winner) in questions
+            .iter_mut()
+            .zip(question_accounts.iter())
+            .zip(winners.iter())
+        {
+            if let Some(player_index) = winner {
+                question.buzzer_winner = players[*player_index].0.player;
+                question.serialize(&mut &mut question_account.data.borrow_mut()[..])?;
+            }
+        }
+        Some(winners)
+    } else {
+        None
+    };
+
+    // Add the quiz to the host's running totals
+    let active_players = players
+        .iter()
+        .filter(|(answer, _, _)| answer.answered_count() > 0)
+        .count();
+    record_host_stats(
+        program_id,
+        host_account,
+        host_stats_account,
+        stats_system_program,
+        &quiz_data,
+        players.len(),
+        active_players,
+    )?;
+
+    // Tally the answer distribution when the stats PDAs are passed
+    if let Some(system_program) = accounts_iter.next() {
+        let sheets: Vec<&PlayerAnswer> = players.iter().map(|(answer, _, _)| answer).collect();
+        for question_index in 0..quiz_data.question_count {
+            let stats_account = next_account_info(accounts_iter)?;
+            record_question_stats(
+                program_id,
+                host_account,
+                quiz_account,
+                system_program,
+                stats_account,
+                question_index,
+                &sheets,
+            )?;
+        }
+    }
+
+    // Process each player's answers
+    for (player_index, (player_answer, player_score_account, system_program)) in
+        players.into_iter().enumerate()
+    {
+        // Calculate score, minus any late submission penalty
+        let late_penalty = quiz_data.late_penalty(player_answer.submitted_slot);
+        let score = score_sheet(
+            &quiz_data,
+            &questions,
+            &player_answer,
+            player_index,
+            buzzer_winners.as_deref(),
+        )
+        .saturating_sub(late_penalty);
+
+        // Verify score PDA
+        let bump_seed = assert_pda(
+            player_score_account,
+            &[
+                PlayerScore::SEED,
+                quiz_account.key.as_ref(),
+                player_answer.player.as_ref(),
+            ],
+            program_id,
+        )?;
+
+        // Create score account
+        create_pda_account(
+            program_id,
+            host_account,
+            player_score_account,
+            system_program,
+            PlayerScore::SIZE,
+            &[
+                PlayerScore::SEED,
+                quiz_account.key.as_ref(),
+                player_answer.player.as_ref(),
+                &[bump_seed],
+            ],
+        )?;
+
+        // Save player score
+        let player_score = PlayerScore {
+            player: player_answer.player,
+            score,
+            quiz: *quiz_account.key,
+            late_penalty,
+            skipped: player_answer.skipped_count() as u8,
+        };
+        player_score.serialize(&mut &mut player_score_account.data.borrow_mut()[..])?;
+        quiz_data.total_prize_score += quiz_data.prize_points(score);
+
+        msg!(
+            "Player {} scored {} out of {}",
+            player_answer.player,
+            score,
+            questions.len()
+        );
+    }
+
+    // Mark quiz as completed, opening the dispute window
+    set_status(&mut quiz_data, QuizStatus::Completed)?;
+    quiz_data.scored_slot = Clock::get()?.slot;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+
+    QuizEvent::ScoresFinalized(ScoresFinalized {
+        quiz: *quiz_account.key,
+        player_count: quiz_data.player_count,
+        question_count: quiz_data.question_count,
+    })
+    .emit()?;
+    log_event(
+        event_log,
+        LoggedEvent::KIND_FINALIZED,
+        *host_account.key,
+        quiz_data.player_count,
+    )?;
+    Ok(())
+}
+
+pub fn process_post_leaderboard_root(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    root: [u8; 32],
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let scorer_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+
+    // Verify scorer is signer
+    if !scorer_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // The appointed scorer posts the root, otherwise the host
+    let mut quiz_data = load_quiz(program_id, quiz_account)?;
+    let scorer = if quiz_data.scorer == Pubkey::default() {
+        quiz_data.host
+    } else {
+        quiz_data.scorer
+    };
+    if *scorer_account.key != scorer {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let event_log = next_event_log(program_id, quiz_account, &quiz_data, accounts_iter)?;
+
+    // An all-zero root marks quizzes scored on chain
+    if root == [0; 32] {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // An appointed scorer puts up the bond challengers can claim
+    if quiz_data.scorer != Pubkey::default() {
+        let bond_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+        create_vault_if_needed(
+            program_id,
+            scorer_account,
+            bond_account,
+            system_program,
+            &[QuizSession::SCORER_BOND_SEED, quiz_account.key.as_ref()],
+        )?;
+        invoke(
+            &system_instruction::transfer(
+                scorer_account.key,
+                bond_account.key,
+                quiz_data.scorer_bond,
+            ),
+            &[
+                scorer_account.clone(),
+                bond_account.clone(),
+                system_program.clone(),
+            ],
+        )?;
+    }
+
+    // Finalize in place of CalculateScores, opening the dispute window
+    set_status(&mut quiz_data, QuizStatus::Completed)?;
+    quiz_data.leaderboard_root = root;
+    quiz_data.scored_slot = Clock::get()?.slot;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+
+    QuizEvent::ScoresFinalized(ScoresFinalized {
+        quiz: *quiz_account.key,
+        player_count: quiz_data.player_count,
+        question_count: quiz_data.question_count,
+    })
+    .emit()?;
+    log_event(
+        event_log,
+        LoggedEvent::KIND_FINALIZED,
+        *scorer_account.key,
+        quiz_data.player_count,
+    )?;
+    Ok(())
+}
+
+pub fn process_appoint_scorer(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    scorer: Pubkey,
+    bond_lamports: u64,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+
+    // Verify host is signer
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify host is the quiz creator
+    let mut quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+
+    // Scoring arrangements can only change before the quiz starts
+    if !quiz_data.status.is_setup() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if scorer == Pubkey::default() {
+        return Err(ProgramError::InvalidArgument);
+    }
+    // The bond means nothing if nobody gets the chance to challenge
+    if quiz_data.dispute_window_slots == 0 {
+        return Err(QuizError::NoDisputeWindow.into());
+    }
+
+    quiz_data.scorer = scorer;
+    quiz_data.scorer_bond = bond_lamports;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+
+    msg!(
+        "Scorer {} appointed with a bond of {} lamports",
+        scorer,
+        bond_lamports
+    );
+    Ok(())
+}
+
+pub fn process_challenge_score(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    score: u32,
+    rank: u32,
+    proof: Vec<[u8; 32]>,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let challenger = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let player_answer_account = next_account_info(accounts_iter)?;
+    let bond_account = next_account_info(accounts_iter)?;
+    let host_stats_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    // Verify challenger is signer
+    if !challenger.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut quiz_data = load_quiz(program_id, quiz_account)?;
+    assert_challengeable(&quiz_data)?;
+
+    // A buzzer score depends on every other sheet, so only sheets graded on
+    // their own can be checked
+    if quiz_data.mode == QuizMode::Buzzer {
+        return Err(QuizError::WrongQuizMode.into());
+    }
+
+    // The challenged standing must be in the posted tree
+    let player_answer = load_answer_sheet_of(program_id, quiz_account, player_answer_account)?;
+    let leaf = merkle::leaderboard_leaf(&player_answer.player, score, rank);
+    if !merkle::verify(&quiz_data.leaderboard_root, leaf, &proof) {
+        return Err(QuizError::InvalidMerkleProof.into());
+    }
+
+    // Regrade the sheet against the questions as CalculateScores would
+    let mut questions = Vec::with_capacity(quiz_data.question_count as usize);
+    for question_index in 0..quiz_data.question_count {
+        let question_account = next_account_info(accounts_iter)?;
+        questions.push(load_question(
+            program_id,
+            quiz_account,
+            question_account,
+            question_index,
+        )?);
+    }
+    let regraded = score_sheet(&quiz_data, &questions, &player_answer, 0, None)
+        .saturating_sub(quiz_data.late_penalty(player_answer.submitted_slot));
+    if regraded == score {
+        return Err(QuizError::ScoreNotFraudulent.into());
+    }
+
+    slash_scorer(
+        program_id,
+        quiz_account,
+        &mut quiz_data,
+        bond_account,
+        challenger,
+        host_stats_account,
+        system_program,
+    )?;
+
+    msg!(
+        "Score of {} posted as {} regrades to {}, scorer slashed",
+        player_answer.player,
+        score,
+        regraded
+    );
+    Ok(())
+}
+
+pub fn process_challenge_missing_sheet(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    standing: PostedStanding,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let challenger = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let player_answer_account = next_account_info(accounts_iter)?;
+    let sheet_account = next_account_info(accounts_iter)?;
+    let bond_account = next_account_info(accounts_iter)?;
+    let host_stats_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    // Verify challenger is signer
+    if !challenger.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut quiz_data = load_quiz(program_id, quiz_account)?;
+    assert_challengeable(&quiz_data)?;
+    assert_posted(&quiz_data, &standing)?;
+
+    // Players who never joined nor submitted a compressed sheet have no
+    // standing to be in the tree
+    assert_pda(
+        player_answer_account,
+        &[
+            PlayerAnswer::SEED,
+            quiz_account.key.as_ref(),
+            standing.player.as_ref(),
+        ],
+        program_id,
+    )?;
+    assert_pda(
+        sheet_account,
+        &[
+            CompressedSheet::SEED,
+            quiz_account.key.as_ref(),
+            standing.player.as_ref(),
+        ],
+        program_id,
+    )?;
+    if !player_answer_account.data_is_empty() || !sheet_account.data_is_empty() {
+        return Err(QuizError::ScoreNotFraudulent.into());
+    }
+
+    slash_scorer(
+        program_id,
+        quiz_account,
+        &mut quiz_data,
+        bond_account,
+        challenger,
+        host_stats_account,
+        system_program,
+    )?;
+
+    msg!(
+        "Standing of {} who never played, scorer slashed",
+        standing.player
+    );
+    Ok(())
+}
+
+pub fn process_challenge_ranks(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    first: PostedStanding,
+    second: PostedStanding,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let challenger = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let bond_account = next_account_info(accounts_iter)?;
+    let host_stats_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    // Verify challenger is signer
+    if !challenger.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut quiz_data = load_quiz(program_id, quiz_account)?;
+    assert_challengeable(&quiz_data)?;
+    assert_posted(&quiz_data, &first)?;
+    assert_posted(&quiz_data, &second)?;
+
+    // Two leaves of the same standing contradict nothing
+    if (first.player, first.score, first.rank) == (second.player, second.score, second.rank) {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Each player holds one rank and each rank one player, counting from 1
+    // with scores never rising down the table
+    let (higher, lower) = if first.rank <= second.rank {
+        (&first, &second)
+    } else {
+        (&second, &first)
+    };
+    let conflicting = higher.rank == 0
+        || higher.player == lower.player
+        || higher.rank == lower.rank
+        || higher.score < lower.score;
+    if !conflicting {
+        return Err(QuizError::ScoreNotFraudulent.into());
+    }
+
+    slash_scorer(
+        program_id,
+        quiz_account,
+        &mut quiz_data,
+        bond_account,
+        challenger,
+        host_stats_account,
+        system_program,
+    )?;
+
+    msg!(
+        "Standings of {} and {} conflict, scorer slashed",
+        first.player,
+        second.player
+    );
+    Ok(())
+}
+
+/// Only bonded roots can be challenged, during the dispute window.
+fn assert_challengeable(quiz_data: &QuizSession) -> ProgramResult {
+    if quiz_data.scorer == Pubkey::default() || quiz_data.leaderboard_root == [0; 32] {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if !quiz_data.dispute_window_open(Clock::get()?.slot) {
+        return Err(QuizError::DisputeWindowClosed.into());
+    }
+    Ok(())
+}
+
+/// Reject a standing that isn't a leaf of the posted root.
+fn assert_posted(quiz_data: &QuizSession, standing: &PostedStanding) -> ProgramResult {
+    let leaf = merkle::leaderboard_leaf(&standing.player, standing.score, standing.rank);
+    if !merkle::verify(&quiz_data.leaderboard_root, leaf, &standing.proof) {
+        return Err(QuizError::InvalidMerkleProof.into());
+    }
+    Ok(())
+}
+
+/// Pay the bond to a successful challenger, count the dispute against the
+/// host and throw out the root so the host scores again.
+fn slash_scorer<'a>(
+    program_id: &Pubkey,
+    quiz_account: &AccountInfo<'a>,
+    quiz_data: &mut QuizSession,
+    bond_account: &AccountInfo<'a>,
+    challenger: &AccountInfo<'a>,
+    host_stats_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+) -> ProgramResult {
+    assert_owned_by(bond_account, program_id)?;
+    assert_pda(
+        bond_account,
+        &[QuizSession::SCORER_BOND_SEED, quiz_account.key.as_ref()],
+        program_id,
+    )?;
+    close_account(bond_account, challenger)?;
+    record_upheld_dispute(
+        program_id,
+        &quiz_data.host,
+        challenger,
+        host_stats_account,
+        system_program,
+    )?;
+
+    quiz_data.leaderboard_root = [0; 32];
+    quiz_data.scorer = Pubkey::default();
+    quiz_data.scorer_bond = 0;
+    // Completed can't go back through set_status, a root proven wrong never
+    // finalized the quiz
+    quiz_data.status = QuizStatus::Scoring;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+    Ok(())
+}
+
+pub fn process_release_bond(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let scorer_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let bond_account = next_account_info(accounts_iter)?;
+
+    // Verify scorer is signer
+    if !scorer_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // The bond is returned once the root can no longer be challenged
+    let quiz_data = load_quiz(program_id, quiz_account)?;
+    if quiz_data.scorer != *scorer_account.key
+        || quiz_data.status != QuizStatus::Completed
+        || quiz_data.leaderboard_root == [0; 32]
+    {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if quiz_data.dispute_window_open(Clock::get()?.slot) {
+        return Err(QuizError::DisputeWindowOpen.into());
+    }
+
+    assert_owned_by(bond_account, program_id)?;
+    assert_pda(
+        bond_account,
+        &[QuizSession::SCORER_BOND_SEED, quiz_account.key.as_ref()],
+        program_id,
+    )?;
+    close_account(bond_account, scorer_account)?;
+
+    msg!("Bond returned to scorer {}", scorer_account.key);
+    Ok(())
+}
+
+pub fn process_init_answer_tree(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let tree_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    // Verify host is signer
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify host is the quiz creator
+    let quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+    if !quiz_data.status.is_setup() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    assert_compressible(&quiz_data)?;
+
+    create_program_account(
+        program_id,
+        host_account,
+        tree_account,
+        system_program,
+        AnswerTree::SIZE,
+        &[AnswerTree::SEED, quiz_account.key.as_ref()],
+    )?;
+    AnswerTree::new(*quiz_account.key).serialize(&mut &mut tree_account.data.borrow_mut()[..])?;
+
+    msg!("Answer tree created for quiz {}", quiz_account.key);
+    Ok(())
+}
+
+pub fn process_submit_compressed_answers(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    answers: Vec<u8>,
+    confidence: Vec<u8>,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let player = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let tree_account = next_account_info(accounts_iter)?;
+    let sheet_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    // Verify player is signer
+    if !player.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // A compressed sheet joins and submits at once, so both windows apply
+    let quiz_data = load_quiz(program_id, quiz_account)?;
+    assert_accepting_answers(&quiz_data)?;
+    let slot = Clock::get()?.slot;
+    if !quiz_data.join_window_open(slot) {
+        return Err(QuizError::JoinWindowClosed.into());
+    }
+    if quiz_data.deadline_passed(slot) {
+        return Err(QuizError::SubmissionDeadlinePassed.into());
+    }
+    // Fees and settings may have changed since the tree was created
+    assert_compressible(&quiz_data)?;
+    assert_questions_revealed(&quiz_data)?;
+    if quiz_data.barred_from_playing(player.key) {
+        return Err(QuizError::HostCannotPlay.into());
+    }
+    validate_answers(quiz_data.served_question_count(), &answers)?;
+    // One sheet per player, so nobody can pick which of theirs is graded
+    if sheet_account.owner == program_id {
+        return Err(QuizError::AnswersAlreadySubmitted.into());
+    }
+
+    let sheet = CompressedAnswers {
+        player: *player.key,
+        confidence: validate_confidence(&quiz_data, &answers, confidence)?,
+        answers,
+        submitted_slot: slot,
+    };
+    let mut tree = load_answer_tree(program_id, quiz_account, tree_account)?;
+    let leaf = merkle::answer_leaf(&sheet);
+    let leaf_index = tree.append(leaf).ok_or(QuizError::AnswerTreeFull)?;
+    tree.serialize(&mut &mut tree_account.data.borrow_mut()[..])?;
+
+    create_program_account(
+        program_id,
+        player,
+        sheet_account,
+        system_program,
+        CompressedSheet::SIZE,
+        &[
+            CompressedSheet::SEED,
+            quiz_account.key.as_ref(),
+            player.key.as_ref(),
+        ],
+    )?;
+    CompressedSheet {
+        player: *player.key,
+        leaf_index,
+        leaf,
+    }
+    .serialize(&mut &mut sheet_account.data.borrow_mut()[..])?;
+
+    QuizEvent::CompressedAnswersSubmitted(CompressedAnswersSubmitted {
+        quiz: *quiz_account.key,
+        leaf_index,
+        sheet,
+    })
+    .emit()?;
+    Ok(())
+}
+
+pub fn process_grade_compressed_answers(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    sheet: CompressedAnswers,
+    proof: Vec<[u8; 32]>,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let payer = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let tree_account = next_account_info(accounts_iter)?;
+    let player_score_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+    let sheet_account = next_account_info(accounts_iter)?;
+
+    // Verify payer is signer
+    if !payer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // The tree is frozen once answers close, grading waits for the scores
+    // of joined players so it never takes one of their score PDAs
+    let quiz_data = load_quiz(program_id, quiz_account)?;
+    if quiz_data.status != QuizStatus::Completed {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let mut tree = load_answer_tree(program_id, quiz_account, tree_account)?;
+    let leaf = merkle::answer_leaf(&sheet);
+    if !merkle::verify(&tree.root, leaf, &proof) {
+        return Err(QuizError::InvalidMerkleProof.into());
+    }
+
+    // Only the leaf recorded for the player when they submitted
+    assert_owned_by(sheet_account, program_id)?;
+    assert_pda(
+        sheet_account,
+        &[
+            CompressedSheet::SEED,
+            quiz_account.key.as_ref(),
+            sheet.player.as_ref(),
+        ],
+        program_id,
+    )?;
+    let recorded = CompressedSheet::try_from_slice(&sheet_account.data.borrow())?;
+    if recorded.leaf != leaf {
+        return Err(QuizError::InvalidMerkleProof.into());
+    }
+
+    let mut questions = Vec::with_capacity(quiz_data.question_count as usize);
+    for question_index in 0..quiz_data.question_count {
+        let question_account = next_account_info(accounts_iter)?;
+        questions.push(load_question(
+            program_id,
+            quiz_account,
+            question_account,
+            question_index,
+        )?);
+    }
+
+    // Grade it like a joined player's sheet answered in one go
+    let mut player_answer = PlayerAnswer::new(sheet.player, quiz_data.question_count as usize);
+    for index in place_answers(
+        &quiz_data,
+        &mut player_answer,
+        sheet.answers,
+        sheet.confidence,
+    ) {
+        player_answer.mark_answered(index);
+        player_answer.answer_slots[index] = sheet.submitted_slot;
+    }
+    player_answer.submitted = true;
+    player_answer.submitted_slot = sheet.submitted_slot;
+    let late_penalty = quiz_data.late_penalty(sheet.submitted_slot);
+    let score =
+        score_sheet(&quiz_data, &questions, &player_answer, 0, None).saturating_sub(late_penalty);
+
+    // Creating the score PDA fails if the player was already graded
+    create_program_account(
+        program_id,
+        payer,
+        player_score_account,
+        system_program,
+        PlayerScore::SIZE,
+        &[
+            PlayerScore::SEED,
+            quiz_account.key.as_ref(),
+            sheet.player.as_ref(),
+        ],
+    )?;
+    let player_score = PlayerScore {
+        player: sheet.player,
+        score,
+        quiz: *quiz_account.key,
+        late_penalty,
+        skipped: player_answer.skipped_count() as u8,
+    };
+    player_score.serialize(&mut &mut player_score_account.data.borrow_mut()[..])?;
+    tree.graded_count += 1;
+    tree.serialize(&mut &mut tree_account.data.borrow_mut()[..])?;
+
+    msg!(
+        "Player {} scored {} out of {}",
+        sheet.player,
+        score,
+        questions.len()
+    );
+    Ok(())
+}
+
+/// Compressed sheets skip `JoinQuiz`, so only free, self-paced base layer
+/// quizzes outside tournaments take them.
+fn assert_compressible(quiz_data: &QuizSession) -> ProgramResult {
+    if quiz_data.mode != QuizMode::Async
+        || !quiz_data.delegation.base_layer
+        || quiz_data.pays_to_join()
+        || quiz_data.tournament != Pubkey::default()
+    {
+        return Err(QuizError::WrongQuizMode.into());
+    }
+    Ok(())
+}
+
+fn load_answer_tree(
+    program_id: &Pubkey,
+    quiz_account: &AccountInfo,
+    tree_account: &AccountInfo,
+) -> Result<AnswerTree, ProgramError> {
+    assert_owned_by(tree_account, program_id)?;
+    assert_pda(
+        tree_account,
+        &[AnswerTree::SEED, quiz_account.key.as_ref()],
+        program_id,
+    )?;
+    Ok(AnswerTree::try_from_slice(&tree_account.data.borrow())?)
+}
+
+pub fn process_close_questions(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let tree_account = next_account_info(accounts_iter)?;
+
+    // Verify host is signer
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Regrades and challenges read the questions until the dispute window
+    // closes
+    let quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+    if !quiz_data.status.is_finished() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if quiz_data.dispute_window_open(Clock::get()?.slot) {
+        return Err(QuizError::DisputeWindowOpen.into());
+    }
+
+    // So does grading compressed sheets, of quizzes that take them
+    if tree_account.owner == program_id {
+        let tree = load_answer_tree(program_id, quiz_account, tree_account)?;
+        if tree.graded_count < tree.leaf_count {
+            return Err(QuizError::SheetsUngraded.into());
+        }
+    } else {
+        assert_pda(
+            tree_account,
+            &[AnswerTree::SEED, quiz_account.key.as_ref()],
+            program_id,
+        )?;
+    }
+
+    let mut closed = 0;
+    for question_index in 0..quiz_data.question_count {
+        let question_account = next_account_info(accounts_iter)?;
+        assert_pda(
+            question_account,
+            &[
+                QuizQuestion::SEED,
+                quiz_account.key.as_ref(),
+                &[question_index],
+            ],
+            program_id,
+        )?;
+        // Cancelled quizzes may not have every question added
+        if question_account.owner != program_id {
+            continue;
+        }
+        close_account(question_account, host_account)?;
+        closed += 1;
+    }
+
+    msg!("Closed {} questions of quiz {}", closed, quiz_account.key);
+    Ok(())
+}
+
+pub fn process_top_up_rent(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    lamports: u64,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let payer = next_account_info(accounts_iter)?;
+    let target_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    // Verify payer is signer
+    if !payer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Anyone may fund any of the program's accounts, nothing else
+    assert_owned_by(target_account, program_id)?;
+
+    if lamports > 0 {
+        invoke(
+            &system_instruction::transfer(payer.key, target_account.key, lamports),
+            &[
+                payer.clone(),
+                target_account.clone(),
+                system_program.clone(),
+            ],
+        )?;
+    }
+
+    if !Rent::get()?.is_exempt(target_account.lamports(), target_account.data_len()) {
+        return Err(ProgramError::AccountNotRentExempt);
+    }
+
+    msg!(
+        "Account {} topped up by {} lamports",
+        target_account.key,
+        lamports
+    );
+    Ok(())
+}
+
+pub fn process_migrate_player_score(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let payer = next_account_info(accounts_iter)?;
+    let player_score_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    // Verify payer is signer
+    if !payer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Only scores still in the u8 layout are migrated
+    assert_owned_by(player_score_account, program_id)?;
+    let player_score = PlayerScore::unpack_legacy(&player_score_account.data.borrow())
+        .ok_or(ProgramError::InvalidAccountData)?;
+    assert_pda(
+        player_score_account,
+        &[
+            PlayerScore::SEED,
+            player_score.quiz.as_ref(),
+            player_score.player.as_ref(),
+        ],
+        program_id,
+    )?;
+
+    let shortfall = Rent::get()?
+        .minimum_balance(PlayerScore::SIZE)
+        .saturating_sub(player_score_account.lamports());
+    if shortfall > 0 {
+        invoke(
+            &system_instruction::transfer(payer.key, player_score_account.key, shortfall),
+            &[
+                payer.clone(),
+                player_score_account.clone(),
+                system_program.clone(),
+            ],
+        )?;
+    }
+    player_score_account.resize(PlayerScore::SIZE)?;
+    player_score.serialize(&mut &mut player_score_account.data.borrow_mut()[..])?;
+
+    msg!("Migrated the score of {}", player_score.player);
+    Ok(())
+}
+
+pub fn process_claim_leaderboard_prize(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    score: u32,
+    rank: u32,
+    proof: Vec<[u8; 32]>,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let player = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let vault_account = next_account_info(accounts_iter)?;
+    let claim_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    // Verify player is signer
+    if !player.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Only quizzes scored into a Merkle root pay out this way
+    let mut quiz_data = load_quiz(program_id, quiz_account)?;
+    if quiz_data.status != QuizStatus::Completed || quiz_data.leaderboard_root == [0; 32] {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let slot = Clock::get()?.slot;
+    if quiz_data.dispute_window_open(slot) {
+        return Err(QuizError::DisputeWindowOpen.into());
+    }
+    if !quiz_data.claim_window_open(slot) {
+        return Err(QuizError::ClaimWindowClosed.into());
+    }
+    if quiz_data.charity_unpaid() {
+        return Err(QuizError::CharityUnpaid.into());
+    }
+
+    let leaf = merkle::leaderboard_leaf(player.key, score, rank);
+    if !merkle::verify(&quiz_data.leaderboard_root, leaf, &proof) {
+        return Err(QuizError::InvalidMerkleProof.into());
+    }
+
+    if score < quiz_data.prize_threshold() {
+        return Err(QuizError::BelowPrizeThreshold.into());
+    }
+    if quiz_data.proportional_prizes || quiz_data.rank_forfeited(rank) {
+        return Err(QuizError::NoPrizeForRank.into());
+    }
+
+    // Ranks count from 1, each paid its share of the pool
+    let split_bps = (rank as usize)
+        .checked_sub(1)
+        .and_then(|index| quiz_data.prize_split_bps.get(index))
+        .copied()
+        .unwrap_or(0);
+    let lamports = (quiz_data.prize_pool as u128 * split_bps as u128 / 10_000) as u64;
+    if lamports == 0 {
+        return Err(QuizError::NoPrizeForRank.into());
+    }
+
+    record_prize_claim(
+        program_id,
+        player,
+        quiz_account,
+        &mut quiz_data,
+        vault_account,
+        claim_account,
+        system_program,
+        rank,
+        lamports,
+    )?;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+    Ok(())
+}
+
+/// Create the player's claim PDA for a prize and pay it, or start vesting it
+/// when it's at least the quiz's `vesting_min_lamports`.
+#[allow(clippy::too_many_arguments)]
+fn record_prize_claim<'a>(
+    program_id: &Pubkey,
+    player: &AccountInfo<'a>,
+    quiz_account: &AccountInfo<'a>,
+    quiz_data: &mut QuizSession,
+    vault_account: &AccountInfo<'a>,
+    claim_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    rank: u32,
+    lamports: u64,
+) -> ProgramResult {
+    assert_owned_by(vault_account, program_id)?;
+    assert_pda(
+        vault_account,
+        &[QuizSession::VAULT_SEED, quiz_account.key.as_ref()],
+        program_id,
+    )?;
+
+    // The claim PDA can only be created once per player
+    create_program_account(
+        program_id,
+        player,
+        claim_account,
+        system_program,
+        LeaderboardClaim::SIZE,
+        &[
+            LeaderboardClaim::SEED,
+            quiz_account.key.as_ref(),
+            player.key.as_ref(),
+        ],
+    )?;
+    let vests = quiz_data.vests(lamports);
+    let claim = LeaderboardClaim {
+        player: *player.key,
+        rank,
+        lamports,
+        vesting_start_slot: Clock::get()?.slot,
+        vesting_slots: if vests { quiz_data.vesting_slots } else { 0 },
+        released: if vests { 0 } else { lamports },
+    };
+    claim.serialize(&mut &mut claim_account.data.borrow_mut()[..])?;
+
+    // Vesting prizes stay in the vault, out of reach of a sweep
+    if vests {
+        quiz_data.vesting_owed += lamports;
+        msg!(
+            "{} lamports vesting over {} slots",
+            lamports,
+            quiz_data.vesting_slots
+        );
+        return Ok(());
+    }
+    transfer_from_vault(vault_account, player, lamports)?;
+    QuizEvent::PrizeClaimed(PrizeClaimed {
+        source: *quiz_account.key,
+        recipient: *player.key,
+        lamports,
+    })
+    .emit()?;
+    Ok(())
+}
+
+pub fn process_compute_analytics(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let payer = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let analytics_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    // Verify payer is signer
+    if !payer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Only final scores are summarized
+    let quiz_data = load_quiz(program_id, quiz_account)?;
+    if quiz_data.status != QuizStatus::Completed {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Every player's score, each counted once
+    let mut players = Vec::with_capacity(quiz_data.player_count as usize);
+    let mut scores = Vec::with_capacity(quiz_data.player_count as usize);
+    for _ in 0..quiz_data.player_count {
+        let score_account = next_account_info(accounts_iter)?;
+        assert_owned_by(score_account, program_id)?;
+        let score = PlayerScore::try_from_slice(&score_account.data.borrow())?;
+        assert_pda(
+            score_account,
+            &[
+                PlayerScore::SEED,
+                quiz_account.key.as_ref(),
+                score.player.as_ref(),
+            ],
+            program_id,
+        )?;
+        if players.contains(&score.player) {
+            return Err(ProgramError::InvalidArgument);
+        }
+        players.push(score.player);
+        scores.push(score.score);
+    }
+
+    // Each question's correct answer and how the room answered it
+    let mut questions = Vec::with_capacity(quiz_data.question_count as usize);
+    for question_index in 0..quiz_data.question_count {
+        let question_account = next_account_info(accounts_iter)?;
+        let stats_account = next_account_info(accounts_iter)?;
+        let question = load_question(program_id, quiz_account, question_account, question_index)?;
+        assert_owned_by(stats_account, program_id)?;
+        assert_pda(
+            stats_account,
+            &[
+                QuestionStats::SEED,
+                quiz_account.key.as_ref(),
+                &[question_index],
+            ],
+            program_id,
+        )?;
+        let stats = QuestionStats::try_from_slice(&stats_account.data.borrow())?;
+        questions.push((question.correct_answer_index, question.difficulty, stats));
+    }
+
+    create_program_account(
+        program_id,
+        payer,
+        analytics_account,
+        system_program,
+        QuizAnalytics::SIZE,
+        &[QuizAnalytics::SEED, quiz_account.key.as_ref()],
+    )?;
+    let analytics = QuizAnalytics::compute(*quiz_account.key, &scores, &questions);
+    analytics.serialize(&mut &mut analytics_account.data.borrow_mut()[..])?;
+
+    msg!(
+        "Quiz analytics: average {} median {} (hundredths)",
+        analytics.average_score_x100,
+        analytics.median_score_x100
+    );
+    Ok(())
+}
+
+/// Load `host`'s stats PDA, creating it with `payer` paying the rent on
+/// first use.
+fn load_host_stats<'a>(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    payer: &AccountInfo<'a>,
+    host_stats_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+) -> Result<HostStats, ProgramError> {
+    let seeds: &[&[u8]] = &[HostStats::SEED, host.as_ref()];
+    if host_stats_account.owner == program_id {
+        assert_pda(host_stats_account, seeds, program_id)?;
+        return Ok(HostStats::try_from_slice(
+            &host_stats_account.data.borrow(),
+        )?);
+    }
+    create_program_account(
+        program_id,
+        payer,
+        host_stats_account,
+        system_program,
+        HostStats::SIZE,
+        seeds,
+    )?;
+    Ok(HostStats {
+        host: *host,
+        ..HostStats::default()
+    })
+}
+
+/// Add one finalized quiz to the host's stats PDA, creating it on first use.
+fn record_host_stats<'a>(
+    program_id: &Pubkey,
+    host_account: &AccountInfo<'a>,
+    host_stats_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    quiz_data: &QuizSession,
+    players: usize,
+    active_players: usize,
+) -> ProgramResult {
+    let mut host_stats = load_host_stats(
+        program_id,
+        host_account.key,
+        host_account,
+        host_stats_account,
+        system_program,
+    )?;
+
+    host_stats.quizzes_hosted = host_stats.quizzes_hosted.saturating_add(1);
+    host_stats.players_served += players as u64;
+    host_stats.active_players += active_players as u64;
+
+    // Prizes wait on scoring, so a long wait after the deadline counts
+    // against the host
+    if quiz_data.deadline_slot != 0 {
+        let delay = Clock::get()?
+            .slot
+            .saturating_sub(quiz_data.final_deadline_slot());
+        host_stats.scoring_delay_slots += delay;
+        if delay > HostStats::LATE_SCORING_SLOTS {
+            host_stats.late_scorings = host_stats.late_scorings.saturating_add(1);
+        }
+    }
+    host_stats.serialize(&mut &mut host_stats_account.data.borrow_mut()[..])?;
+    Ok(())
+}
+
+/// Count a dispute decided against the host of a quiz.
+fn record_upheld_dispute<'a>(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    payer: &AccountInfo<'a>,
+    host_stats_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+) -> ProgramResult {
+    let mut host_stats =
+        load_host_stats(program_id, host, payer, host_stats_account, system_program)?;
+    host_stats.disputes_upheld = host_stats.disputes_upheld.saturating_add(1);
+    host_stats.serialize(&mut &mut host_stats_account.data.borrow_mut()[..])?;
+    Ok(())
+}
+
+/// Create the stats PDA of one question and count every sheet's answer to it.
+fn record_question_stats<'a>(
+    program_id: &Pubkey,
+    host_account: &AccountInfo<'a>,
+    quiz_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    stats_account: &AccountInfo<'a>,
+    question_index: u8,
+    sheets: &[&PlayerAnswer],
+) -> ProgramResult {
+    create_program_account(
+        program_id,
+        host_account,
+        stats_account,
+        system_program,
+        QuestionStats::SIZE,
+        &[
+            QuestionStats::SEED,
+            quiz_account.key.as_ref(),
+            &[question_index],
+        ],
+    )?;
+
+    let mut stats = QuestionStats {
+        quiz: *quiz_account.key,
+        question_index,
+        ..QuestionStats::default()
+    };
+    for sheet in sheets {
+        stats.record(sheet);
+    }
+    stats.serialize(&mut &mut stats_account.data.borrow_mut()[..])?;
+    Ok(())
+}
+
+/// Load a question, verifying it is this program's PDA for the quiz and index.
+fn load_question(
+    program_id: &Pubkey,
+    quiz_account: &AccountInfo,
+    question_account: &AccountInfo,
+    question_index: u8,
+) -> Result<QuizQuestion, ProgramError> {
+    assert_owned_by(question_account, program_id)?;
+    assert_pda(
+        question_account,
+        &[
+            QuizQuestion::SEED,
+            quiz_account.key.as_ref(),
+            &[question_index],
+        ],
+        program_id,
+    )?;
+    Ok(QuizQuestion::try_from_slice(
+        &question_account.data.borrow(),
+    )?)
+}
+
+/// Load the quiz's adaptive bank, verifying it is this program's PDA.
+fn load_adaptive_bank(
+    program_id: &Pubkey,
+    quiz_account: &AccountInfo,
+    bank_account: &AccountInfo,
+) -> Result<AdaptiveBank, ProgramError> {
+    assert_owned_by(bank_account, program_id)?;
+    assert_pda(
+        bank_account,
+        &[AdaptiveBank::SEED, quiz_account.key.as_ref()],
+        program_id,
+    )?;
+    Ok(AdaptiveBank::try_from_slice(&bank_account.data.borrow())?)
+}
+
+/// Score one sheet the way the quiz mode asks for, minus bought hints.
+/// `player_index` is the sheet's position in the list `buzzer_winners` was
+/// computed from.
+fn score_sheet(
+    quiz_data: &QuizSession,
+    questions: &[QuizQuestion],
+    player_answer: &PlayerAnswer,
+    player_index: usize,
+    buzzer_winners: Option<&[Option<usize>]>,
+) -> u32 {
+    match buzzer_winners {
+        Some(winners) => winners
+            .iter()
+            .zip(questions)
+            .map(|(winner, question)| {
+                let points = match question.void_points() {
+                    Some(points) => points as u32,
+                    None if *winner == Some(player_index) => question.multiplier as u32,
+                    None => 0,
+                };
+                points * quiz_data.difficulty_factor(question)
+            })
+            .sum(),
+        None if quiz_data.mode == QuizMode::Elimination => player_answer.rounds_survived as u32,
+        None => grade_answers(quiz_data, questions, player_answer),
+    }
+    .saturating_sub(player_answer.hint_penalty as u32)
+}
+
+/// Count correct answers at their question's multiplier, times its difficulty
+/// in difficulty-weighted quizzes. Unanswered and skipped questions earn
+/// nothing and voided ones the same for everyone. In confidence-weighted
+/// quizzes answers also count their confidence and wrong ones take it off.
+/// The quiz's `ScoringMode` adds streak bonuses or squares the total, which
+/// never goes below zero.
+fn grade_answers(
+    quiz_data: &QuizSession,
+    questions: &[QuizQuestion],
+    player_answer: &PlayerAnswer,
+) -> u32 {
+    let mut score: i64 = 0;
+    let mut streak = 0;
+    for (i, question) in questions.iter().enumerate() {
+        if let Some(points) = question.void_points() {
+            score += points as i64 * quiz_data.difficulty_factor(question) as i64;
+            continue;
+        }
+        if i >= player_answer.answers.len() || !player_answer.is_answered(i) {
+            streak = 0;
+            continue;
+        }
+        let points = answer_points(quiz_data, question, player_answer, i);
+        if points > 0 && quiz_data.scoring_mode == ScoringMode::Streak {
+            score += streak;
+        }
+        streak = if points > 0 { streak + 1 } else { 0 };
+        score += points as i64;
+    }
+    if quiz_data.scoring_mode == ScoringMode::Quadratic {
+        score = score.max(0).pow(2);
+    }
+    score.clamp(0, u32::MAX as i64) as u32
+}
+
+/// Points the player's answer to question `i` adds to the score, negative
+/// for wrong answers in confidence-weighted and negative-marking quizzes.
+/// Streak bonuses and quadratic totals are left to `grade_answers`, so live
+/// leaderboards show them only once the quiz is graded.
+fn answer_points(
+    quiz_data: &QuizSession,
+    question: &QuizQuestion,
+    player_answer: &PlayerAnswer,
+    i: usize,
+) -> i32 {
+    let answer = player_answer.answers[i];
+    if answer == QuizQuestion::SKIPPED {
+        return 0;
+    }
+    let correct = answer == question.correct_answer_index;
+    let mut points = question.multiplier as i32 * quiz_data.difficulty_factor(question) as i32;
+    if quiz_data.confidence_weighted {
+        points *= player_answer.confidence[i] as i32;
+    }
+    match (correct, quiz_data.scoring_mode) {
+        (true, ScoringMode::SpeedWeighted) => {
+            points + quiz_data.speed_bonus(points, player_answer.answer_slots[i])
+        }
+        (true, _) => points,
+        (false, ScoringMode::NegativeMarking) => -points,
+        (false, _) if quiz_data.confidence_weighted => -points,
+        (false, _) => 0,
+    }
+}
+
+/// For each question pick the player whose correct answer landed in the
+/// earliest slot. Answers in the same slot go to the player listed first.
+fn find_buzzer_winners(questions: &[QuizQuestion], sheets: &[&PlayerAnswer]) -> Vec<Option<usize>> {
+    questions
+        .iter()
+        .enumerate()
+        .map(|(question_index, question)| {
+            sheets
+                .iter()
+                .enumerate()
+                .filter(|(_, sheet)| {
+                    question_index < sheet.answers.len()
+                        && sheet.is_answered(question_index)
+                        && sheet.answers[question_index] == question.correct_answer_index
+                })
+                .min_by_key(|(player_index, sheet)| {
+                    (sheet.answer_slots[question_index], *player_index)
+                })
+                .map(|(player_index, _)| player_index)
+        })
+        .collect()
+}
+
+pub fn process_undelegate_player(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    pda_seeds: Vec<Vec<u8>>,
+) -> ProgramResult {
+    let account_iter = &mut accounts.iter();
+
+    let delegated_account = next_account_info(account_iter)?;
+    let delegation_buffer = next_account_info(account_iter)?;
+    let payer = next_account_info(account_iter)?;
+    let system_program = next_account_info(account_iter)?;
+
+    // Verify payer is signer
+    if !payer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Recreate the account from the committed data. Only the delegation
+    // program can sign for the buffer, so nobody else gets this far
+    undelegate_account(
+        delegated_account,
+        program_id,
+        delegation_buffer,
+        payer,
+        system_program,
+        pda_seeds.clone(),
+    )?;
+
+    // Answer sheets must still be the sheet of the player they were derived
+    // for, and are final from here on
+    if pda_seeds.first().map(Vec::as_slice) == Some(PlayerAnswer::SEED) {
+        let mut player_answer = PlayerAnswer::try_from_slice(&delegated_account.data.borrow())?;
+        let recorded_player = pda_seeds.get(2).map(Vec::as_slice);
+        if recorded_player != Some(player_answer.player.as_ref()) || !player_answer.is_well_formed()
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        player_answer.committed = true;
+        player_answer.serialize(&mut &mut delegated_account.data.borrow_mut()[..])?;
+    }
+
+    msg!("Account {} undelegated", delegated_account.key);
+    Ok(())
+}
+
+pub fn process_set_submission_policy(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    allow_resubmission: bool,
+    refresh_slot_on_update: bool,
+    deadline_slot: u64,
+    join_grace_slots: u64,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+
+    // Verify host is signer
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify host is the quiz creator
+    let mut quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+
+    // Policy can only change before the quiz starts
+    if !quiz_data.status.is_setup() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    quiz_data.allow_resubmission = allow_resubmission;
+    quiz_data.refresh_slot_on_update = refresh_slot_on_update;
+    quiz_data.deadline_slot = deadline_slot;
+    quiz_data.join_grace_slots = join_grace_slots;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+
+    msg!(
+        "Submission policy set: resubmission {}, deadline slot {}, join grace {} slots",
+        allow_resubmission,
+        deadline_slot,
+        join_grace_slots
+    );
+    Ok(())
+}
+
+pub fn process_set_late_policy(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    late_window_slots: u64,
+    late_slots_per_point: u64,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+
+    // Verify host is signer
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify host is the quiz creator
+    let mut quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+
+    // Policy can only change before the quiz starts
+    if !quiz_data.status.is_setup() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Live quizzes pace answers themselves, only async sheets can be late
+    if quiz_data.mode != QuizMode::Async {
+        return Err(QuizError::WrongQuizMode.into());
+    }
+
+    quiz_data.late_window_slots = late_window_slots;
+    quiz_data.late_slots_per_point = late_slots_per_point;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+
+    msg!(
+        "Late policy set: {} slots after the deadline, a point per {} slots late",
+        late_window_slots,
+        late_slots_per_point
+    );
+    Ok(())
+}
+
+pub fn process_set_confidence_scoring(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    enabled: bool,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+
+    // Verify host is signer
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify host is the quiz creator
+    let mut quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+
+    // Scoring rules can only change before the quiz starts
+    if !quiz_data.status.is_setup() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    quiz_data.confidence_weighted = enabled;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+
+    msg!("Confidence-weighted scoring: {}", enabled);
+    Ok(())
+}
+
+pub fn process_set_difficulty_scoring(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    enabled: bool,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+
+    // Verify host is signer
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify host is the quiz creator
+    let mut quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+
+    // Scoring rules can only change before the quiz starts
+    if !quiz_data.status.is_setup() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    quiz_data.difficulty_weighted = enabled;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+
+    msg!("Difficulty-weighted scoring: {}", enabled);
+    Ok(())
+}
+
+pub fn process_init_adaptive_bank(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    questions_per_player: u8,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let bank_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    // Verify host is signer
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify host is the quiz creator, questions are served from the bank
+    // from the first answer on
+    let quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+    if !quiz_data.status.is_setup() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if quiz_data.mode != QuizMode::Adaptive {
+        return Err(QuizError::WrongQuizMode.into());
+    }
+    if questions_per_player == 0 || questions_per_player > quiz_data.question_count {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Snapshot every question's difficulty
+    let mut difficulties = Vec::with_capacity(quiz_data.question_count as usize);
+    for question_index in 0..quiz_data.question_count {
+        let question = load_question(
+            program_id,
+            quiz_account,
+            next_account_info(accounts_iter)?,
+            question_index,
+        )?;
+        difficulties.push(question.difficulty);
+    }
+
+    create_program_account(
+        program_id,
+        host_account,
+        bank_account,
+        system_program,
+        AdaptiveBank::get_size(difficulties.len()),
+        &[AdaptiveBank::SEED, quiz_account.key.as_ref()],
+    )?;
+    let bank = AdaptiveBank {
+        quiz: *quiz_account.key,
+        questions_per_player,
+        difficulties,
+    };
+    bank.serialize(&mut &mut bank_account.data.borrow_mut()[..])?;
+
+    msg!(
+        "Adaptive bank serves {} of {} questions",
+        questions_per_player,
+        quiz_data.question_count
+    );
+    Ok(())
+}
+
+pub fn process_set_question_subset(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    questions_per_player: u8,
+    seed: [u8; 32],
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+
+    // Subsets are fixed before anyone answers, and only whole sheets are
+    // mapped onto them
+    if !quiz_data.status.is_setup() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if !quiz_data.mode.serves_question_subsets() {
+        return Err(QuizError::WrongQuizMode.into());
+    }
+    if questions_per_player > quiz_data.question_count {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    quiz_data.subset_size = questions_per_player;
+    quiz_data.subset_seed = seed;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+
+    msg!(
+        "Each player is served {} of {} questions",
+        quiz_data.served_question_count(),
+        quiz_data.question_count
+    );
+    Ok(())
+}
+
+pub fn process_reveal_answers(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let quiz_account = next_account_info(accounts_iter)?;
+
+    // Anyone may reveal once scores are final, nobody can answer any more
+    let mut quiz_data = load_quiz(program_id, quiz_account)?;
+    if quiz_data.status != QuizStatus::Completed {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if quiz_data.answers_revealed {
+        return Err(QuizError::AnswersAlreadyRevealed.into());
+    }
+
+    // Publish the answer key so it can be checked against the scores
+    let mut correct_answers = Vec::with_capacity(quiz_data.question_count as usize);
+    for question_index in 0..quiz_data.question_count {
+        let question = load_question(
+            program_id,
+            quiz_account,
+            next_account_info(accounts_iter)?,
+            question_index,
+        )?;
+        correct_answers.push(question.correct_answer_index);
+    }
+
+    quiz_data.answers_revealed = true;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+
+    QuizEvent::AnswersRevealed(AnswersRevealed {
+        quiz: *quiz_account.key,
+        correct_answers,
+    })
+    .emit()?;
+    msg!("Answers revealed for quiz {}", quiz_account.key);
+    Ok(())
+}
+
+pub fn process_set_explanation(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    question_index: u8,
+    explanation: String,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let question_account = next_account_info(accounts_iter)?;
+    let explanation_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+
+    // Explanations are fixed along with the questions when the quiz starts
+    if !quiz_data.status.is_setup() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if explanation.len() > QuestionExplanation::MAX_LEN {
+        return Err(QuizError::ExplanationTooLong.into());
+    }
+
+    // Only questions that exist get explanations
+    load_question(program_id, quiz_account, question_account, question_index)?;
+
+    create_program_account(
+        program_id,
+        host_account,
+        explanation_account,
+        system_program,
+        QuestionExplanation::get_size(&explanation),
+        &[
+            QuestionExplanation::SEED,
+            quiz_account.key.as_ref(),
+            &[question_index],
+        ],
+    )?;
+
+    let explanation_data = QuestionExplanation {
+        question_index,
+        explanation,
+    };
+    explanation_data.serialize(&mut &mut explanation_account.data.borrow_mut()[..])?;
+
+    msg!("Explanation added to question {}", question_index);
+    Ok(())
+}
+
+pub fn process_set_question_key(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    key_hash: [u8; 32],
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+
+    // The key is committed to while questions are still being loaded
+    if !quiz_data.status.is_setup() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    quiz_data.question_key_hash = key_hash;
+    quiz_data.question_key = [0; 32];
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+
+    msg!("Questions encrypted: {}", key_hash != [0; 32]);
+    Ok(())
+}
+
+pub fn process_commit_question(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    question_index: u8,
+    question_hash: [u8; 32],
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let commitment_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+
+    // Questions are committed to while the quiz is set up and revealed one
+    // round at a time, which only live quizzes do
+    if !quiz_data.status.is_setup() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if !quiz_data.mode.is_live() {
+        return Err(QuizError::WrongQuizMode.into());
+    }
+    if question_index >= quiz_data.question_count {
+        return Err(QuizError::InvalidQuestionIndex.into());
+    }
+
+    create_program_account(
+        program_id,
+        host_account,
+        commitment_account,
+        system_program,
+        QuestionCommitment::SIZE,
+        &[
+            QuestionCommitment::SEED,
+            quiz_account.key.as_ref(),
+            &[question_index],
+        ],
+    )?;
+    let commitment = QuestionCommitment {
+        hash: question_hash,
+    };
+    commitment.serialize(&mut &mut commitment_account.data.borrow_mut()[..])?;
+
+    msg!("Question {} committed", question_index);
+    Ok(())
+}
+
+pub fn process_reveal_questions(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    key: [u8; 32],
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+    if quiz_data.status.is_finished() || !quiz_data.questions_hidden() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if cipher::key_hash(&key) != quiz_data.question_key_hash {
+        return Err(QuizError::WrongQuestionKey.into());
+    }
+
+    quiz_data.question_key = key;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+
+    msg!("Question key revealed for quiz {}", quiz_account.key);
+    Ok(())
+}
+
+pub fn process_set_hint(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    question_index: u8,
+    hint: String,
+    point_cost: u8,
+    lamport_cost: u64,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let question_account = next_account_info(accounts_iter)?;
+    let hint_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    // Verify host is signer
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify host is the quiz creator
+    let quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+
+    // Hints can only be added before the quiz starts
+    if !quiz_data.status.is_setup() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Lamports can't be paid into the pool from the rollup
+    if lamport_cost > 0 {
+        if !quiz_data.delegation.base_layer {
+            return Err(QuizError::WrongQuizMode.into());
+        }
+        let vault_account = next_account_info(accounts_iter)?;
+        create_vault_if_needed(
+            program_id,
+            host_account,
+            vault_account,
+            system_program,
+            &[QuizSession::VAULT_SEED, quiz_account.key.as_ref()],
+        )?;
+    }
+
+    // Only questions that exist get hints
+    load_question(program_id, quiz_account, question_account, question_index)?;
+
+    create_program_account(
+        program_id,
+        host_account,
+        hint_account,
+        system_program,
+        QuestionHint::get_size(&hint),
+        &[
+            QuestionHint::SEED,
+            quiz_account.key.as_ref(),
+            &[question_index],
+        ],
+    )?;
+
+    let hint_data = QuestionHint {
+        point_cost,
+        lamport_cost,
+        hint,
+    };
+    hint_data.serialize(&mut &mut hint_account.data.borrow_mut()[..])?;
+
+    msg!("Hint added to question {}", question_index);
+    Ok(())
+}
+
+pub fn process_add_translation(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    question_index: u8,
+    language: [u8; 2],
+    question_text: String,
+    options: [String; 4],
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let question_account = next_account_info(accounts_iter)?;
+    let translation_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+
+    // Translations are fixed along with the questions when the quiz starts
+    if !quiz_data.status.is_setup() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if !QuestionTranslation::is_valid_language(&language) {
+        return Err(QuizError::InvalidLanguageCode.into());
+    }
+
+    // Only questions that exist get translations
+    load_question(program_id, quiz_account, question_account, question_index)?;
+
+    create_program_account(
+        program_id,
+        host_account,
+        translation_account,
+        system_program,
+        QuestionTranslation::get_size(&question_text, &options),
+        &[
+            QuestionTranslation::SEED,
+            quiz_account.key.as_ref(),
+            &[question_index],
+            &language,
+        ],
+    )?;
+
+    let translation = QuestionTranslation {
+        question_index,
+        language,
+        question_text,
+        options,
+    };
+    translation.serialize(&mut &mut translation_account.data.borrow_mut()[..])?;
+
+    msg!(
+        "Translation {} added to question {}",
+        String::from_utf8_lossy(&language),
+        question_index
+    );
+    Ok(())
+}
+
+pub fn process_set_nickname(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    nickname: String,
+    reserve: bool,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let player_account = next_account_info(accounts_iter)?;
+    let profile_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    if !player_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    // NUL pads the stored name, so it can't be part of it
+    if nickname.is_empty()
+        || nickname.len() > PlayerProfile::MAX_NICKNAME_LEN
+        || nickname.contains('\0')
+    {
+        return Err(QuizError::InvalidNickname.into());
+    }
+
+    let profile_seeds: &[&[u8]] = &[PlayerProfile::SEED, player_account.key.as_ref()];
+    let mut profile = if profile_account.owner == program_id {
+        assert_pda(profile_account, profile_seeds, program_id)?;
+        PlayerProfile::try_from_slice(&profile_account.data.borrow())?
+    } else {
+        create_program_account(
+            program_id,
+            player_account,
+            profile_account,
+            system_program,
+            PlayerProfile::SIZE,
+            profile_seeds,
+        )?;
+        PlayerProfile {
+            player: *player_account.key,
+            nickname: [0; PlayerProfile::MAX_NICKNAME_LEN],
+            reserved: false,
+        }
+    };
+
+    let claim_seed = NicknameClaim::seed(&nickname);
+    let previous_seed = NicknameClaim::seed(profile.nickname());
+    let keeps_claim = profile.reserved && reserve && previous_seed == claim_seed;
+
+    if reserve && !keeps_claim {
+        let claim_account = next_account_info(accounts_iter)?;
+        if claim_account.owner == program_id {
+            return Err(QuizError::NicknameTaken.into());
+        }
+        create_program_account(
+            program_id,
+            player_account,
+            claim_account,
+            system_program,
+            NicknameClaim::SIZE,
+            &[NicknameClaim::SEED, &claim_seed],
+        )?;
+        NicknameClaim {
+            player: *player_account.key,
+        }
+        .serialize(&mut &mut claim_account.data.borrow_mut()[..])?;
+    }
+
+    // Release the name the player held before
+    if profile.reserved && !keeps_claim {
+        let previous_claim = next_account_info(accounts_iter)?;
+        assert_owned_by(previous_claim, program_id)?;
+        assert_pda(
+            previous_claim,
+            &[NicknameClaim::SEED, &previous_seed],
+            program_id,
+        )?;
+        close_account(previous_claim, player_account)?;
+    }
+
+    profile.nickname = [0; PlayerProfile::MAX_NICKNAME_LEN];
+    profile.nickname[..nickname.len()].copy_from_slice(nickname.as_bytes());
+    profile.reserved = reserve;
+    profile.serialize(&mut &mut profile_account.data.borrow_mut()[..])?;
+
+    msg!("Nickname set to {}", nickname);
+    Ok(())
+}
+
+pub fn process_buy_hint(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    question_index: u8,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let player_account = next_account_info(accounts_iter)?;
+    let player_answer_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let hint_account = next_account_info(accounts_iter)?;
+
+    // Paying for hints needs the wallet, so session keys can't buy them
+    let (mut quiz_data, mut player_answers) = load_answer_sheet(
+        program_id,
+        player_account,
+        player_answer_account,
+        quiz_account,
+        None,
+    )?;
+
+    // Verify hint PDA
+    assert_owned_by(hint_account, program_id)?;
+    assert_pda(
+        hint_account,
+        &[
+            QuestionHint::SEED,
+            quiz_account.key.as_ref(),
+            &[question_index],
+        ],
+        program_id,
+    )?;
+    let hint_data = QuestionHint::try_from_slice(&hint_account.data.borrow())?;
+
+    let index = question_index as usize;
+    if index >= player_answers.answers.len() {
+        return Err(QuizError::InvalidQuestionIndex.into());
+    }
+    if player_answers.has_hint(index) {
+        return Err(QuizError::HintAlreadyBought.into());
+    }
+
+    // Paid hints go into the prize pool like entry fees
+    if hint_data.lamport_cost > 0 {
+        let vault_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+        assert_owned_by(vault_account, program_id)?;
+        assert_pda(
+            vault_account,
+            &[QuizSession::VAULT_SEED, quiz_account.key.as_ref()],
+            program_id,
+        )?;
+        invoke(
+            &system_instruction::transfer(
+                player_account.key,
+                vault_account.key,
+                hint_data.lamport_cost,
+            ),
+            &[
+                player_account.clone(),
+                vault_account.clone(),
+                system_program.clone(),
+            ],
+        )?;
+        quiz_data.prize_pool += hint_data.lamport_cost;
+        quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+    }
+
+    player_answers.unlock_hint(index);
+    player_answers.hint_penalty = player_answers
+        .hint_penalty
+        .saturating_add(hint_data.point_cost);
+    player_answers.serialize(&mut &mut player_answer_account.data.borrow_mut()[..])?;
+
+    msg!(
+        "Player {} bought the hint for question {}",
+        player_answers.player,
+        question_index
+    );
+    Ok(())
+}
+
+pub fn process_set_round_multiplier(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    first_question: u8,
+    last_question: u8,
+    multiplier: u8,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+
+    // Verify host is signer
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify host is the quiz creator
+    let quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+
+    // Scoring rules can only change before the quiz starts
+    if !quiz_data.status.is_setup() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if first_question > last_question || last_question >= quiz_data.question_count {
+        return Err(QuizError::InvalidQuestionIndex.into());
+    }
+    if !(1..=QuizQuestion::MAX_MULTIPLIER).contains(&multiplier) {
+        return Err(QuizError::InvalidMultiplier.into());
+    }
+
+    for question_index in first_question..=last_question {
+        let question_account = next_account_info(accounts_iter)?;
+        let mut question =
+            load_question(program_id, quiz_account, question_account, question_index)?;
+        question.multiplier = multiplier;
+        question.serialize(&mut &mut question_account.data.borrow_mut()[..])?;
+    }
+
+    msg!(
+        "Questions {} to {} are worth {} points",
+        first_question,
+        last_question,
+        multiplier
+    );
+    Ok(())
+}
+
+pub fn process_set_question_difficulty(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    first_question: u8,
+    last_question: u8,
+    difficulty: u8,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+
+    // Verify host is signer
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify host is the quiz creator
+    let quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+
+    // Scoring rules can only change before the quiz starts
+    if !quiz_data.status.is_setup() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if first_question > last_question || last_question >= quiz_data.question_count {
+        return Err(QuizError::InvalidQuestionIndex.into());
+    }
+    if !(1..=QuizQuestion::MAX_DIFFICULTY).contains(&difficulty) {
+        return Err(QuizError::InvalidDifficulty.into());
+    }
+
+    for question_index in first_question..=last_question {
+        let question_account = next_account_info(accounts_iter)?;
+        let mut question =
+            load_question(program_id, quiz_account, question_account, question_index)?;
+        question.difficulty = difficulty;
+        question.serialize(&mut &mut question_account.data.borrow_mut()[..])?;
+    }
+
+    msg!(
+        "Questions {} to {} have difficulty {}",
+        first_question,
+        last_question,
+        difficulty
+    );
+    Ok(())
+}
+
+pub fn process_set_prize_split(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    split_bps: [u16; QuizSession::PRIZE_RANKS],
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+
+    // Verify host is signer
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify host is the quiz creator
+    let mut quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+
+    // Prizes can only change before the quiz starts
+    if !quiz_data.status.is_setup() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if split_bps.iter().map(|&bps| bps as u32).sum::<u32>() > 10_000 {
+        return Err(QuizError::InvalidPrizeSplit.into());
+    }
+
+    quiz_data.prize_split_bps = split_bps;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+
+    msg!("Prize split set to {:?} basis points", split_bps);
+    Ok(())
+}
+
+pub fn process_set_referral_cut(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    bps: u16,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+
+    // Verify host is signer
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify host is the quiz creator
+    let mut quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+
+    // The cut can only change before anyone joins
+    if !quiz_data.status.is_setup() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if bps > 10_000 {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    quiz_data.referral_bps = bps;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+
+    msg!("Referral cut set to {} basis points", bps);
+    Ok(())
+}
+
+pub fn process_set_charity_split(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    charity: Pubkey,
+    bps: u16,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+
+    // Players join knowing what share goes to charity
+    if !quiz_data.status.is_setup() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if bps > 10_000 {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    quiz_data.charity = charity;
+    quiz_data.charity_bps = bps;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+
+    msg!("{} basis points of the pot go to {}", bps, charity);
+    Ok(())
+}
+
+pub fn process_pay_charity(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let quiz_account = next_account_info(accounts_iter)?;
+    let vault_account = next_account_info(accounts_iter)?;
+    let charity_account = next_account_info(accounts_iter)?;
+
+    let mut quiz_data = load_quiz(program_id, quiz_account)?;
+    if quiz_data.status != QuizStatus::Completed || !quiz_data.charity_unpaid() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if *charity_account.key != quiz_data.charity {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    assert_owned_by(vault_account, program_id)?;
+    assert_pda(
+        vault_account,
+        &[QuizSession::VAULT_SEED, quiz_account.key.as_ref()],
+        program_id,
+    )?;
+
+    // Prizes are shares of what is left
+    let lamports = (quiz_data.prize_pool as u128 * quiz_data.charity_bps as u128 / 10_000) as u64;
+    transfer_from_vault(vault_account, charity_account, lamports)?;
+    quiz_data.prize_pool -= lamports;
+    quiz_data.charity_paid = true;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+
+    QuizEvent::PrizeClaimed(PrizeClaimed {
+        source: *quiz_account.key,
+        recipient: *charity_account.key,
+        lamports,
+    })
+    .emit()?;
+    Ok(())
+}
+
+pub fn process_pay_referral(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let referrer = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let player_answer_account = next_account_info(accounts_iter)?;
+    let vault_account = next_account_info(accounts_iter)?;
+
+    // Referrals are paid once the quiz is scored
+    let quiz_data = load_quiz(program_id, quiz_account)?;
+    if quiz_data.status != QuizStatus::Completed {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if !quiz_data.claim_window_open(Clock::get()?.slot) {
+        return Err(QuizError::ClaimWindowClosed.into());
+    }
+
+    // The sheet must be back on the base layer to be marked paid
+    assert_owned_by(player_answer_account, program_id)?;
+    let mut player_answers = load_answer_sheet_of(program_id, quiz_account, player_answer_account)?;
+    if player_answers.referrer == Pubkey::default() || player_answers.referrer != *referrer.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if player_answers.referral_cut == 0 {
+        return Err(QuizError::AlreadySettled.into());
+    }
+
+    assert_owned_by(vault_account, program_id)?;
+    assert_pda(
+        vault_account,
+        &[QuizSession::VAULT_SEED, quiz_account.key.as_ref()],
+        program_id,
+    )?;
+    transfer_from_vault(vault_account, referrer, player_answers.referral_cut)?;
+
+    msg!(
+        "Referrer {} paid {} lamports",
+        referrer.key,
+        player_answers.referral_cut
+    );
+    player_answers.referral_cut = 0;
+    player_answers.serialize(&mut &mut player_answer_account.data.borrow_mut()[..])?;
+    Ok(())
+}
+
+pub fn process_set_prize_threshold(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    min_score: u8,
+    min_score_bps: u16,
+    remainder: PrizeRemainder,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+
+    // Verify host is signer
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify host is the quiz creator
+    let mut quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+
+    // Prizes can only change before the quiz starts
+    if !quiz_data.status.is_setup() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if min_score_bps > 10_000 {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    quiz_data.min_prize_score = min_score;
+    quiz_data.min_prize_score_bps = min_score_bps;
+    quiz_data.prize_remainder = remainder;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+
+    msg!(
+        "Prizes need a score of {}, remainder {:?}",
+        quiz_data.prize_threshold(),
+        remainder
+    );
+    Ok(())
+}
+
+pub fn process_forfeit_prize(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    rank: u32,
+    player: Pubkey,
+    score: u32,
+    proof: Vec<[u8; 32]>,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let quiz_account = next_account_info(accounts_iter)?;
+    let vault_account = next_account_info(accounts_iter)?;
+    let host_account = next_account_info(accounts_iter)?;
+
+    // Same window as claims, so a winner can't be raced out of a prize
+    let mut quiz_data = load_quiz(program_id, quiz_account)?;
+    if quiz_data.status != QuizStatus::Completed || quiz_data.leaderboard_root == [0; 32] {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if quiz_data.dispute_window_open(Clock::get()?.slot) {
+        return Err(QuizError::DisputeWindowOpen.into());
+    }
+    if quiz_data.charity_unpaid() {
+        return Err(QuizError::CharityUnpaid.into());
+    }
+    if *host_account.key != quiz_data.host {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let split_bps = (rank as usize)
+        .checked_sub(1)
+        .and_then(|index| quiz_data.prize_split_bps.get(index))
+        .copied()
+        .unwrap_or(0);
+    let lamports = (quiz_data.prize_pool as u128 * split_bps as u128 / 10_000) as u64;
+    if lamports == 0 || quiz_data.rank_forfeited(rank) {
+        return Err(QuizError::NoPrizeForRank.into());
+    }
+
+    // A rank some player reached is only given up if their score misses
+    // the threshold, in which case they could never claim it anyway
+    if rank <= quiz_data.player_count as u32 {
+        let leaf = merkle::leaderboard_leaf(&player, score, rank);
+        if !merkle::verify(&quiz_data.leaderboard_root, leaf, &proof) {
+            return Err(QuizError::InvalidMerkleProof.into());
+        }
+        if score >= quiz_data.prize_threshold() {
+            return Err(QuizError::PrizeRankQualified.into());
+        }
+    }
+
+    assert_owned_by(vault_account, program_id)?;
+    assert_pda(
+        vault_account,
+        &[QuizSession::VAULT_SEED, quiz_account.key.as_ref()],
+        program_id,
+    )?;
+    match quiz_data.prize_remainder {
+        PrizeRemainder::ReturnToHost => transfer_from_vault(vault_account, host_account, lamports)?,
+        PrizeRemainder::RollOver => quiz_data.rollover_lamports += lamports,
+    }
+
+    quiz_data.forfeited_ranks |= 1 << (rank - 1);
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+
+    msg!("Rank {} forfeited {} lamports", rank, lamports);
+    Ok(())
+}
+
+pub fn process_set_proportional_prizes(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    enabled: bool,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+
+    // Verify host is signer
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify host is the quiz creator
+    let mut quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+
+    // Prizes can only change before the quiz starts
+    if !quiz_data.status.is_setup() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    quiz_data.proportional_prizes = enabled;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+
+    msg!("Proportional prizes: {}", enabled);
+    Ok(())
+}
+
+pub fn process_set_host_play(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    policy: HostPlay,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+
+    // Players join knowing whether the host can compete with them
+    if !quiz_data.status.is_setup() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    quiz_data.host_play = policy;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+
+    msg!("Host play: {:?}", policy);
+    Ok(())
+}
+
+pub fn process_set_scoring_mode(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mode: ScoringMode,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+
+    // Players join knowing how they will be scored
+    if !quiz_data.status.is_setup() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    quiz_data.scoring_mode = mode;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+
+    msg!("Scoring mode: {:?}", mode);
+    Ok(())
+}
+
+pub fn process_claim_score_prize(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let player = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let vault_account = next_account_info(accounts_iter)?;
+    let player_score_account = next_account_info(accounts_iter)?;
+    let claim_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    // Verify player is signer
+    if !player.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Only quizzes scored into score PDAs pay out this way
+    let mut quiz_data = load_quiz(program_id, quiz_account)?;
+    if quiz_data.status != QuizStatus::Completed || !quiz_data.proportional_prizes {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let slot = Clock::get()?.slot;
+    if quiz_data.dispute_window_open(slot) {
+        return Err(QuizError::DisputeWindowOpen.into());
+    }
+    if !quiz_data.claim_window_open(slot) {
+        return Err(QuizError::ClaimWindowClosed.into());
+    }
+    if quiz_data.charity_unpaid() {
+        return Err(QuizError::CharityUnpaid.into());
+    }
+
+    assert_owned_by(player_score_account, program_id)?;
+    assert_pda(
+        player_score_account,
+        &[
+            PlayerScore::SEED,
+            quiz_account.key.as_ref(),
+            player.key.as_ref(),
+        ],
+        program_id,
+    )?;
+    let score = PlayerScore::try_from_slice(&player_score_account.data.borrow())?.score;
+    if score < quiz_data.prize_threshold() {
+        return Err(QuizError::BelowPrizeThreshold.into());
+    }
+    let points = quiz_data.prize_points(score);
+    let lamports = quiz_data.proportional_prize(points);
+    if lamports == 0 {
+        return Err(QuizError::NoPrizeForRank.into());
+    }
+
+    record_prize_claim(
+        program_id,
+        player,
+        quiz_account,
+        &mut quiz_data,
+        vault_account,
+        claim_account,
+        system_program,
+        0,
+        lamports,
+    )?;
+    quiz_data.claimed_prize_score += points;
+    quiz_data.settled = true;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+    Ok(())
+}
+
+pub fn process_set_prize_vesting(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    min_lamports: u64,
+    slots: u64,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+
+    // Verify host is signer
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify host is the quiz creator
+    let mut quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+
+    // Prizes can only change before the quiz starts
+    if !quiz_data.status.is_setup() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    quiz_data.vesting_min_lamports = min_lamports;
+    quiz_data.vesting_slots = slots;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+
+    msg!(
+        "Prizes of {} lamports or more vest over {} slots",
+        min_lamports,
+        slots
+    );
+    Ok(())
+}
+
+pub fn process_claim_vested(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let player = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let vault_account = next_account_info(accounts_iter)?;
+    let claim_account = next_account_info(accounts_iter)?;
+
+    // Verify player is signer
+    if !player.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut quiz_data = load_quiz(program_id, quiz_account)?;
+    assert_owned_by(claim_account, program_id)?;
+    assert_pda(
+        claim_account,
+        &[
+            LeaderboardClaim::SEED,
+            quiz_account.key.as_ref(),
+            player.key.as_ref(),
+        ],
+        program_id,
+    )?;
+    let mut claim = LeaderboardClaim::try_from_slice(&claim_account.data.borrow())?;
+
+    // Vested prizes stay claimable after the claim window
+    let lamports = claim.releasable(Clock::get()?.slot);
+    if lamports == 0 {
+        return Err(QuizError::NothingVested.into());
+    }
+
+    assert_owned_by(vault_account, program_id)?;
+    assert_pda(
+        vault_account,
+        &[QuizSession::VAULT_SEED, quiz_account.key.as_ref()],
+        program_id,
+    )?;
+    transfer_from_vault(vault_account, player, lamports)?;
+
+    claim.released += lamports;
+    claim.serialize(&mut &mut claim_account.data.borrow_mut()[..])?;
+    quiz_data.vesting_owed -= lamports;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+
+    QuizEvent::PrizeClaimed(PrizeClaimed {
+        source: *quiz_account.key,
+        recipient: *player.key,
+        lamports,
+    })
+    .emit()?;
+    Ok(())
+}
+
+pub fn process_set_dispute_window(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    slots: u64,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+
+    // Verify host is signer
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify host is the quiz creator
+    let mut quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+
+    // Policy can only change before the quiz starts
+    if !quiz_data.status.is_setup() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if slots == 0 && quiz_data.scorer != Pubkey::default() {
+        return Err(QuizError::NoDisputeWindow.into());
+    }
+
+    quiz_data.dispute_window_slots = slots;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+
+    msg!("Dispute window set to {} slots after scoring", slots);
+    Ok(())
+}
+
+pub fn process_set_claim_window(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    slots: u64,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+
+    // Verify host is signer
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify host is the quiz creator
+    let mut quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+
+    // Policy can only change before the quiz starts
+    if !quiz_data.status.is_setup() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    quiz_data.claim_window_slots = slots;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+
+    msg!("Claim window set to {} slots after disputes", slots);
+    Ok(())
+}
+
+pub fn process_sweep_unclaimed(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let vault_account = next_account_info(accounts_iter)?;
+    let recipient = next_account_info(accounts_iter)?;
+
+    // Verify host is signer
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify host is the quiz creator
+    let mut quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+    if quiz_data.status != QuizStatus::Completed {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if quiz_data.claim_window_open(Clock::get()?.slot) {
+        return Err(QuizError::ClaimWindowOpen.into());
+    }
+    if quiz_data.charity_unpaid() {
+        return Err(QuizError::CharityUnpaid.into());
+    }
+
+    assert_owned_by(vault_account, program_id)?;
+    assert_pda(
+        vault_account,
+        &[QuizSession::VAULT_SEED, quiz_account.key.as_ref()],
+        program_id,
+    )?;
+    let lamports = vault_account
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(vault_account.data_len()))
+        .saturating_sub(quiz_data.vesting_owed)
+        .saturating_sub(quiz_data.stakes_held);
+    transfer_from_vault(vault_account, recipient, lamports)?;
+
+    // Nothing but vesting prizes is left to pay out or roll over
+    quiz_data.prize_pool = 0;
+    quiz_data.rollover_lamports = 0;
+    quiz_data.settled = true;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+
+    msg!("Swept {} unclaimed lamports to {}", lamports, recipient.key);
+    Ok(())
+}
+
+pub fn process_flag_question(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    question_index: u8,
+    reason_code: u8,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let player = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let player_score_account = next_account_info(accounts_iter)?;
+    let dispute_account = next_account_info(accounts_iter)?;
+    let flag_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    // Verify player is signer
+    if !player.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let quiz_data = load_quiz(program_id, quiz_account)?;
+    if !quiz_data.dispute_window_open(Clock::get()?.slot) {
+        return Err(QuizError::DisputeWindowClosed.into());
+    }
+    if question_index >= quiz_data.question_count {
+        return Err(QuizError::InvalidQuestionIndex.into());
+    }
+    if reason_code > QuestionDispute::REASON_OTHER {
+        return Err(QuizError::InvalidReasonCode.into());
+    }
+
+    // Only players who were scored can flag
+    assert_owned_by(player_score_account, program_id)?;
+    assert_pda(
+        player_score_account,
+        &[
+            PlayerScore::SEED,
+            quiz_account.key.as_ref(),
+            player.key.as_ref(),
+        ],
+        program_id,
+    )?;
+
+    // The flag PDA can only be created once per player and question
+    create_program_account(
+        program_id,
+        player,
+        flag_account,
+        system_program,
+        DisputeFlag::SIZE,
+        &[
+            DisputeFlag::SEED,
+            quiz_account.key.as_ref(),
+            &[question_index],
+            player.key.as_ref(),
+        ],
+    )?;
+    let flag = DisputeFlag {
+        player: *player.key,
+        reason_code,
+    };
+    flag.serialize(&mut &mut flag_account.data.borrow_mut()[..])?;
+
+    // The first flag opens the question's dispute
+    let dispute_seeds: &[&[u8]] = &[
+        QuestionDispute::SEED,
+        quiz_account.key.as_ref(),
+        &[question_index],
+    ];
+    let mut dispute = if dispute_account.owner == program_id {
+        assert_pda(dispute_account, dispute_seeds, program_id)?;
+        QuestionDispute::try_from_slice(&dispute_account.data.borrow())?
+    } else {
+        create_program_account(
+            program_id,
+            player,
+            dispute_account,
+            system_program,
+            QuestionDispute::SIZE,
+            dispute_seeds,
+        )?;
+        QuestionDispute {
+            quiz: *quiz_account.key,
+            question_index,
+            ..QuestionDispute::default()
+        }
+    };
+    dispute.reason_counts[reason_code as usize] += 1;
+    dispute.serialize(&mut &mut dispute_account.data.borrow_mut()[..])?;
+
+    msg!(
+        "Question {} flagged by {}, {} flags",
+        question_index,
+        player.key,
+        dispute.flag_count()
+    );
+    Ok(())
+}
+
+pub fn process_void_question(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    question_index: u8,
+    mode: VoidMode,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let question_account = next_account_info(accounts_iter)?;
+    let host_stats_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    // Verify host is signer
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify host is the quiz creator
+    let quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+
+    // Questions can be voided once scored, until prizes are paid out
+    if quiz_data.status != QuizStatus::Completed {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if quiz_data.settled {
+        return Err(QuizError::AlreadySettled.into());
+    }
+
+    let mut question = load_question(program_id, quiz_account, question_account, question_index)?;
+    // A question voided after scoring is a dispute the host conceded
+    if question.void == VoidMode::NotVoided && mode != VoidMode::NotVoided {
+        record_upheld_dispute(
+            program_id,
+            host_account.key,
+            host_account,
+            host_stats_account,
+            system_program,
+        )?;
+    }
+    question.void = mode;
+    question.serialize(&mut &mut question_account.data.borrow_mut()[..])?;
+
+    msg!("Question {} voided as {:?}", question_index, mode);
+    Ok(())
+}
+
+pub fn process_regrade(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    // Verify host is signer
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify host is the quiz creator
+    let mut quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+
+    // Scores can only be rewritten until prizes are paid out
+    if quiz_data.status != QuizStatus::Completed {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if quiz_data.settled {
+        return Err(QuizError::AlreadySettled.into());
+    }
+
+    let mut questions = Vec::with_capacity(quiz_data.question_count as usize);
+    for question_index in 0..quiz_data.question_count {
+        let question_account = next_account_info(accounts_iter)?;
+        questions.push(load_question(
+            program_id,
+            quiz_account,
+            question_account,
+            question_index,
+        )?);
+    }
+
+    // Every player must be regraded so nobody keeps a stale score
+    let mut players: Vec<(PlayerAnswer, &AccountInfo)> =
+        Vec::with_capacity(quiz_data.player_count as usize);
+    for _ in 0..quiz_data.player_count {
+        let player_answer_account = next_account_info(accounts_iter)?;
+        let player_score_account = next_account_info(accounts_iter)?;
+        let player_answer = load_answer_sheet_of(program_id, quiz_account, player_answer_account)?;
+        if players
+            .iter()
+            .any(|(answer, _)| answer.player == player_answer.player)
+        {
+            return Err(ProgramError::InvalidArgument);
+        }
+        assert_owned_by(player_score_account, program_id)?;
+        assert_pda(
+            player_score_account,
+            &[
+                PlayerScore::SEED,
+                quiz_account.key.as_ref(),
+                player_answer.player.as_ref(),
+            ],
+            program_id,
+        )?;
+        players.push((player_answer, player_score_account));
+    }
+
+    let buzzer_winners = if quiz_data.mode == QuizMode::Buzzer {
+        let sheets: Vec<&PlayerAnswer> = players.iter().map(|(answer, _)| answer).collect();
+        Some(find_buzzer_winners(&questions, &sheets))
+    } else {
+        None
+    };
+
+    let slot = Clock::get()?.slot;
+    let mut corrections = Vec::new();
+    quiz_data.total_prize_score = 0;
+    for (player_index, (player_answer, player_score_account)) in players.iter().enumerate() {
+        let mut player_score = PlayerScore::try_from_slice(&player_score_account.data.borrow())?;
+        let old_score = player_score.score;
+        player_score.score = score_sheet(
+            &quiz_data,
+            &questions,
+            player_answer,
+            player_index,
+            buzzer_winners.as_deref(),
+        )
+        .saturating_sub(player_score.late_penalty);
+        player_score.serialize(&mut &mut player_score_account.data.borrow_mut()[..])?;
+        quiz_data.total_prize_score += quiz_data.prize_points(player_score.score);
+
+        if player_score.score != old_score {
+            corrections.push(ScoreCorrection {
+                player: player_answer.player,
+                corrected_by: *host_account.key,
+                slot,
+                old_score,
+                new_score: player_score.score,
+                reason: ScoreCorrection::REASON_REGRADE,
+            });
+        }
+
+        msg!(
+            "Player {} regraded to {}",
+            player_answer.player,
+            player_score.score
+        );
+    }
+
+    // Changed scores go on the audit trail, whose pages come last
+    record_corrections(
+        program_id,
+        host_account,
+        quiz_account,
+        &mut quiz_data,
+        system_program,
+        accounts_iter,
+        &corrections,
+    )?;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+    Ok(())
+}
+
+pub fn process_correct_score(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    new_score: u32,
+    reason: u8,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let player_score_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    // Verify host is signer
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify host is the quiz creator
+    let mut quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+
+    // Scores can only be corrected until prizes are paid out
+    if quiz_data.status != QuizStatus::Completed {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if quiz_data.settled {
+        return Err(QuizError::AlreadySettled.into());
+    }
+
+    // Regrades record themselves, manual corrections say why
+    if !(ScoreCorrection::REASON_SCORING_ERROR..=ScoreCorrection::REASON_OTHER).contains(&reason) {
+        return Err(QuizError::InvalidReasonCode.into());
+    }
+
+    assert_owned_by(player_score_account, program_id)?;
+    let mut player_score = PlayerScore::try_from_slice(&player_score_account.data.borrow())?;
+    assert_pda(
+        player_score_account,
+        &[
+            PlayerScore::SEED,
+            quiz_account.key.as_ref(),
+            player_score.player.as_ref(),
+        ],
+        program_id,
+    )?;
+
+    let correction = ScoreCorrection {
+        player: player_score.player,
+        corrected_by: *host_account.key,
+        slot: Clock::get()?.slot,
+        old_score: player_score.score,
+        new_score,
+        reason,
+    };
+    quiz_data.total_prize_score = quiz_data.total_prize_score
+        - quiz_data.prize_points(player_score.score)
+        + quiz_data.prize_points(new_score);
+    player_score.score = new_score;
+    player_score.serialize(&mut &mut player_score_account.data.borrow_mut()[..])?;
+
+    record_corrections(
+        program_id,
+        host_account,
+        quiz_account,
+        &mut quiz_data,
+        system_program,
+        accounts_iter,
+        &[correction],
+    )?;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+
+    msg!(
+        "Score of {} corrected from {} to {}",
+        correction.player,
+        correction.old_score,
+        new_score
+    );
+    Ok(())
+}
+
+/// Append corrections to the quiz's audit trail. `audit_accounts` yields the
+/// page the next record falls on and then the pages after it, a page is
+/// created when its first record is written.
+fn record_corrections<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    payer: &AccountInfo<'b>,
+    quiz_account: &AccountInfo<'b>,
+    quiz_data: &mut QuizSession,
+    system_program: &AccountInfo<'b>,
+    audit_accounts: &mut impl Iterator<Item = &'a AccountInfo<'b>>,
+    corrections: &[ScoreCorrection],
+) -> ProgramResult {
+    let mut pending = corrections;
+    while !pending.is_empty() {
+        let audit_account = next_account_info(audit_accounts)?;
+        let page = quiz_data.correction_count / ScoreAudit::MAX_RECORDS as u32;
+        let filled = quiz_data.correction_count as usize % ScoreAudit::MAX_RECORDS;
+        let page_bytes = page.to_le_bytes();
+        let seeds: &[&[u8]] = &[ScoreAudit::SEED, quiz_account.key.as_ref(), &page_bytes];
+
+        let mut audit = if filled == 0 {
+            create_program_account(
+                program_id,
+                payer,
+                audit_account,
+                system_program,
+                ScoreAudit::SIZE,
+                seeds,
+            )?;
+            ScoreAudit {
+                quiz: *quiz_account.key,
+                page,
+                records: [ScoreCorrection::default(); ScoreAudit::MAX_RECORDS],
+                record_count: 0,
+            }
+        } else {
+            assert_owned_by(audit_account, program_id)?;
+            assert_pda(audit_account, seeds, program_id)?;
+            ScoreAudit::try_from_slice(&audit_account.data.borrow())?
+        };
+
+        let taken = pending.len().min(ScoreAudit::MAX_RECORDS - filled);
+        for correction in &pending[..taken] {
+            audit.records[audit.record_count as usize] = *correction;
+            audit.record_count += 1;
+        }
+        audit.serialize(&mut &mut audit_account.data.borrow_mut()[..])?;
+
+        quiz_data.correction_count += taken as u32;
+        pending = &pending[taken..];
+    }
+    Ok(())
+}
+
+pub fn process_init_event_log(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let log_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    // Verify host is signer
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify host is the quiz creator
+    let mut quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+
+    // The log has to see every event, so it is set up before the quiz starts
+    if !quiz_data.status.is_setup() || quiz_data.event_log {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Rollup sheets and sessions can't write to a base layer account
+    if !quiz_data.delegation.base_layer {
+        return Err(QuizError::WrongQuizMode.into());
+    }
+
+    create_program_account(
+        program_id,
+        host_account,
+        log_account,
+        system_program,
+        QuizEventLog::SIZE,
+        &[QuizEventLog::SEED, quiz_account.key.as_ref()],
+    )?;
+    let log = QuizEventLog {
+        quiz: *quiz_account.key,
+        total: 0,
+        records: [LoggedEvent::default(); QuizEventLog::CAPACITY],
+    };
+    log.serialize(&mut &mut log_account.data.borrow_mut()[..])?;
+
+    quiz_data.event_log = true;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+
+    msg!("Event log created for quiz {}", quiz_account.key);
+    Ok(())
+}
+
+pub fn process_set_quiz_mode(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mode: QuizMode,
+    answer_window_slots: u64,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+
+    // Verify host is signer
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify host is the quiz creator
+    let mut quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+
+    // Mode can only change before the quiz starts
+    if !quiz_data.status.is_setup() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Practice quizzes carry no prizes
+    if mode == QuizMode::Practice && quiz_data.pays_to_join() {
+        return Err(QuizError::WrongQuizMode.into());
+    }
+    if quiz_data.subset_size > 0 && !mode.serves_question_subsets() {
+        return Err(QuizError::WrongQuizMode.into());
+    }
+
+    quiz_data.mode = mode;
+    quiz_data.answer_window_slots = answer_window_slots;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+
+    msg!(
+        "Quiz mode set to {:?} with a {} slot answer window",
+        mode,
+        answer_window_slots
+    );
+    Ok(())
+}
+
+/// `window` gives the open and close slot for `OpenQuestion`, otherwise the
+/// window opens now and lasts `answer_window_slots`. `blind_question` is the
+/// content `RevealBlindQuestion` publishes.
+pub fn process_reveal_question(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    index: u8,
+    window: Option<(u64, u64)>,
+    blind_question: Option<QuestionData>,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+
+    // Verify host is signer
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify host is the quiz creator
+    let mut quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+
+    // Only running live quizzes reveal questions
+    if quiz_data.status != QuizStatus::Active {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if !quiz_data.mode.is_live() {
+        return Err(QuizError::WrongQuizMode.into());
+    }
+    if index >= quiz_data.question_count {
+        return Err(QuizError::InvalidQuestionIndex.into());
+    }
+
+    // Blind questions only exist once their content matches the commitment
+    if let Some(question) = blind_question {
+        let commitment_account = next_account_info(accounts_iter)?;
+        let question_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+        assert_owned_by(commitment_account, program_id)?;
+        assert_pda(
+            commitment_account,
+            &[
+                QuestionCommitment::SEED,
+                quiz_account.key.as_ref(),
+                &[index],
+            ],
+            program_id,
+        )?;
+        let commitment = QuestionCommitment::try_from_slice(&commitment_account.data.borrow())?;
+        if QuestionCommitment::hash_question(quiz_account.key, index, &question) != commitment.hash
+        {
+            return Err(QuizError::QuestionHashMismatch.into());
+        }
+        create_question(
+            program_id,
+            host_account,
+            quiz_account,
+            question_account,
+            system_program,
+            index,
+            question,
+        )?;
+        close_account(commitment_account, host_account)?;
+    }
+    let event_log = next_event_log(program_id, quiz_account, &quiz_data, accounts_iter)?;
+
+    // Fix the answer window for this question
+    let slot = Clock::get()?.slot;
+    let (open_slot, close_slot) = match window {
+        Some((open_slot, close_slot)) => (open_slot.max(slot), close_slot),
+        None if quiz_data.answer_window_slots == 0 => (slot, 0),
+        None => (slot, slot.saturating_add(quiz_data.answer_window_slots)),
+    };
+    if close_slot != 0 && close_slot < open_slot {
+        return Err(QuizError::InvalidAnswerWindow.into());
+    }
+
+    quiz_data.revealed_question = index;
+    quiz_data.reveal_slot = open_slot;
+    quiz_data.close_slot = close_slot;
+    quiz_data.rounds_revealed = quiz_data.rounds_revealed.saturating_add(1);
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+
+    QuizEvent::QuestionRevealed(QuestionRevealed {
+        quiz: *quiz_account.key,
+        question_index: index,
+        open_slot,
+        close_slot,
+    })
+    .emit()?;
+    log_event(
+        event_log,
+        LoggedEvent::KIND_REVEALED,
+        *host_account.key,
+        index,
+    )?;
+    Ok(())
+}
+
+pub fn process_create_team(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    team_id: u8,
+    name: String,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let captain_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let team_account = next_account_info(accounts_iter)?;
+    let member_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    // Verify captain is signer
+    if !captain_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Teams form before the quiz starts
+    let mut quiz_data = load_quiz(program_id, quiz_account)?;
+    if !quiz_data.status.is_setup() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if name.len() > Team::MAX_NAME_LEN {
+        return Err(QuizError::TeamNameTooLong.into());
+    }
+
+    // Create team account
+    let team_bump = assert_pda(
+        team_account,
+        &[Team::SEED, quiz_account.key.as_ref(), &[team_id]],
+        program_id,
+    )?;
+    create_pda_account(
+        program_id,
+        captain_account,
+        team_account,
+        system_program,
+        Team::SIZE,
+        &[
+            Team::SEED,
+            quiz_account.key.as_ref(),
+            &[team_id],
+            &[team_bump],
+        ],
+    )?;
+
+    let mut team_name = [0u8; Team::MAX_NAME_LEN];
+    team_name[..name.len()].copy_from_slice(name.as_bytes());
+
+    let mut members = [Pubkey::default(); Team::MAX_MEMBERS];
+    members[0] = *captain_account.key;
+
+    let team_data = Team {
+        quiz: *quiz_account.key,
+        team_id,
+        name: team_name,
+        captain: *captain_account.key,
+        members,
+        member_count: 1,
+        score: 0,
+        finalized: false,
+    };
+    team_data.serialize(&mut &mut team_account.data.borrow_mut()[..])?;
+
+    // The captain is the first member
+    create_team_member(
+        program_id,
+        quiz_account,
+        captain_account,
+        member_account,
+        system_program,
+        team_id,
+    )?;
+
+    // Counted so team prizes can require every team to be ranked
+    quiz_data.team_count = quiz_data
+        .team_count
+        .checked_add(1)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+
+    msg!("Team {} created by {}", team_id, captain_account.key);
+    Ok(())
+}
+
+pub fn process_join_team(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    team_id: u8,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let player_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let team_account = next_account_info(accounts_iter)?;
+    let member_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    // Verify player is signer
+    if !player_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Teams form before the quiz starts
+    let quiz_data = load_quiz(program_id, quiz_account)?;
+    if !quiz_data.status.is_setup() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let mut team_data = load_team(program_id, quiz_account, team_account, team_id)?;
+    if team_data.member_count as usize >= Team::MAX_MEMBERS {
+        return Err(QuizError::TeamFull.into());
+    }
+
+    // The membership PDA can only exist once, so players join one team per quiz
+    create_team_member(
+        program_id,
+        quiz_account,
+        player_account,
+        member_account,
+        system_program,
+        team_id,
+    )?;
+
+    team_data.members[team_data.member_count as usize] = *player_account.key;
+    team_data.member_count += 1;
+    team_data.serialize(&mut &mut team_account.data.borrow_mut()[..])?;
+
+    msg!("Player {} joined team {}", player_account.key, team_id);
+    Ok(())
+}
+
+pub fn process_leave_team(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    team_id: u8,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let player_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let team_account = next_account_info(accounts_iter)?;
+    let member_account = next_account_info(accounts_iter)?;
+
+    // Verify player is signer
+    if !player_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Teams are locked once the quiz starts
+    let mut quiz_data = load_quiz(program_id, quiz_account)?;
+    if !quiz_data.status.is_setup() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let mut team_data = load_team(program_id, quiz_account, team_account, team_id)?;
+    let position = team_data
+        .members()
+        .iter()
+        .position(|member| member == player_account.key)
+        .ok_or(QuizError::NotTeamMember)?;
+
+    // Swap the last member into the freed slot
+    let last = team_data.member_count as usize - 1;
+    team_data.members[position] = team_data.members[last];
+    team_data.members[last] = Pubkey::default();
+    team_data.member_count -= 1;
+
+    // The last member out closes the team, so it isn't ranked for team
+    // prizes. Otherwise a leaving captain hands over to the first member left.
+    if team_data.member_count == 0 {
+        close_account(team_account, player_account)?;
+        quiz_data.team_count -= 1;
+        quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+    } else {
+        if team_data.captain == *player_account.key {
+            team_data.captain = team_data.members[0];
+        }
+        team_data.serialize(&mut &mut team_account.data.borrow_mut()[..])?;
+    }
+
+    // Close the membership record and refund its rent to the player
+    assert_owned_by(member_account, program_id)?;
+    assert_pda(
+        member_account,
+        &[
+            TeamMember::SEED,
+            quiz_account.key.as_ref(),
+            player_account.key.as_ref(),
+        ],
+        program_id,
+    )?;
+    close_account(member_account, player_account)?;
+
+    msg!("Player {} left team {}", player_account.key, team_id);
+    Ok(())
+}
+
+pub fn process_finalize_team_score(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    team_id: u8,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let quiz_account = next_account_info(accounts_iter)?;
+    let team_account = next_account_info(accounts_iter)?;
+
+    // Scores must be final before teams are totalled
+    let quiz_data = load_quiz(program_id, quiz_account)?;
+    if quiz_data.status != QuizStatus::Completed {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if quiz_data.dispute_window_open(Clock::get()?.slot) {
+        return Err(QuizError::DisputeWindowOpen.into());
+    }
+
+    let mut team_data = load_team(program_id, quiz_account, team_account, team_id)?;
+    if team_data.finalized {
+        return Err(QuizError::TeamAlreadyFinalized.into());
+    }
+
+    // Sum each member's score, passed in member order. Members who never
+    // played have no score account and contribute nothing.
+    let mut team_score: u32 = 0;
+    for member in team_data.members() {
+        let player_score_account = next_account_info(accounts_iter)?;
+        assert_pda(
+            player_score_account,
+            &[
+                PlayerScore::SEED,
+                quiz_account.key.as_ref(),
+                member.as_ref(),
+            ],
+            program_id,
+        )?;
+        if player_score_account.data_is_empty() {
+            continue;
+        }
+        assert_owned_by(player_score_account, program_id)?;
+        let player_score = PlayerScore::try_from_slice(&player_score_account.data.borrow())?;
+        team_score = team_score
+            .checked_add(player_score.score)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+    }
+
+    team_data.score = team_score;
+    team_data.finalized = true;
+    team_data.serialize(&mut &mut team_account.data.borrow_mut()[..])?;
+
+    msg!("Team {} finalized with score {}", team_id, team_score);
+    Ok(())
+}
+
+pub fn process_claim_team_prize(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    team_id: u8,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let player = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let vault_account = next_account_info(accounts_iter)?;
+    let claim_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    // Verify player is signer
+    if !player.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Teams split the ranked prizes of quizzes scored into score PDAs, which
+    // pay no other way by rank
+    let mut quiz_data = load_quiz(program_id, quiz_account)?;
+    if quiz_data.status != QuizStatus::Completed
+        || quiz_data.leaderboard_root != [0; 32]
+        || quiz_data.proportional_prizes
+        || quiz_data.mode == QuizMode::Duel
+    {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let slot = Clock::get()?.slot;
+    if quiz_data.dispute_window_open(slot) {
+        return Err(QuizError::DisputeWindowOpen.into());
+    }
+    if !quiz_data.claim_window_open(slot) {
+        return Err(QuizError::ClaimWindowClosed.into());
+    }
+    if quiz_data.charity_unpaid() {
+        return Err(QuizError::CharityUnpaid.into());
+    }
+
+    // Every team of the quiz follows, finalized, so the ranking is complete
+    let mut seen = [false; 256];
+    let mut teams = Vec::with_capacity(quiz_data.team_count as usize);
+    for _ in 0..quiz_data.team_count {
+        let team_account = next_account_info(accounts_iter)?;
+        assert_owned_by(team_account, program_id)?;
+        let id = Team::try_from_slice(&team_account.data.borrow())?.team_id;
+        let team_data = load_team(program_id, quiz_account, team_account, id)?;
+        if seen[id as usize] {
+            return Err(ProgramError::InvalidArgument);
+        }
+        seen[id as usize] = true;
+        if !team_data.finalized {
+            return Err(QuizError::TeamNotFinalized.into());
+        }
+        teams.push(team_data);
+    }
+
+    let team_data = teams
+        .iter()
+        .find(|team| team.team_id == team_id)
+        .ok_or(ProgramError::InvalidArgument)?;
+    if !team_data.members().contains(player.key) {
+        return Err(QuizError::NotTeamMember.into());
+    }
+
+    // Ties go to the lower team id
+    let rank = 1 + teams
+        .iter()
+        .filter(|team| {
+            team.score > team_data.score
+                || (team.score == team_data.score && team.team_id < team_id)
+        })
+        .count() as u32;
+
+    // Each member takes an equal part of the team's share
+    let split_bps = quiz_data
+        .prize_split_bps
+        .get(rank as usize - 1)
+        .copied()
+        .unwrap_or(0);
+    let lamports = (quiz_data.prize_pool as u128 * split_bps as u128
+        / 10_000
+        / team_data.member_count as u128) as u64;
+    if lamports == 0 {
+        return Err(QuizError::NoPrizeForRank.into());
+    }
+
+    record_prize_claim(
+        program_id,
+        player,
+        quiz_account,
+        &mut quiz_data,
+        vault_account,
+        claim_account,
+        system_program,
+        rank,
+        lamports,
+    )?;
+    quiz_data.settled = true;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+    Ok(())
+}
+
+/// Load a quiz session, verifying it is this program's PDA for its host and id.
+fn load_quiz(program_id: &Pubkey, quiz_account: &AccountInfo) -> Result<QuizSession, ProgramError> {
+    assert_owned_by(quiz_account, program_id)?;
+    let quiz_data = QuizSession::try_from_slice(&quiz_account.data.borrow())?;
+    assert_pda(
+        quiz_account,
+        &[
+            QuizSession::SEED,
+            quiz_data.host.as_ref(),
+            &quiz_data.quiz_id.to_le_bytes(),
+        ],
+        program_id,
+    )?;
+    Ok(quiz_data)
+}
+
+/// Load a quiz session and verify `host_account` is its host.
+fn load_host_quiz(
+    program_id: &Pubkey,
+    host_account: &AccountInfo,
+    quiz_account: &AccountInfo,
+) -> Result<QuizSession, ProgramError> {
+    let quiz_data = load_quiz(program_id, quiz_account)?;
+    if quiz_data.host != *host_account.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(quiz_data)
+}
+
+/// Move the quiz to `next`, rejecting transitions its phase doesn't allow.
+fn set_status(quiz_data: &mut QuizSession, next: QuizStatus) -> ProgramResult {
+    if !quiz_data.status.can_transition_to(next) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    quiz_data.status = next;
+    Ok(())
+}
+
+/// Joins and answers are only taken while the quiz is active.
+fn assert_accepting_answers(quiz_data: &QuizSession) -> ProgramResult {
+    match quiz_data.status {
+        QuizStatus::Active => Ok(()),
+        QuizStatus::SubmissionsClosed | QuizStatus::Scoring => {
+            Err(QuizError::SubmissionsClosed.into())
+        }
+        _ => Err(ProgramError::InvalidAccountData),
+    }
+}
+
+fn assert_questions_revealed(quiz_data: &QuizSession) -> ProgramResult {
+    if quiz_data.questions_hidden() {
+        return Err(QuizError::QuestionsNotRevealed.into());
+    }
+    Ok(())
+}
+
+fn load_team(
+    program_id: &Pubkey,
+    quiz_account: &AccountInfo,
+    team_account: &AccountInfo,
+    team_id: u8,
+) -> Result<Team, ProgramError> {
+    assert_owned_by(team_account, program_id)?;
+    assert_pda(
+        team_account,
+        &[Team::SEED, quiz_account.key.as_ref(), &[team_id]],
+        program_id,
+    )?;
+    Ok(Team::try_from_slice(&team_account.data.borrow())?)
+}
+
+fn create_team_member<'a>(
+    program_id: &Pubkey,
+    quiz_account: &AccountInfo<'a>,
+    player_account: &AccountInfo<'a>,
+    member_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    team_id: u8,
+) -> ProgramResult {
+    let bump_seed = assert_pda(
+        member_account,
+        &[
+            TeamMember::SEED,
+            quiz_account.key.as_ref(),
+            player_account.key.as_ref(),
+        ],
+        program_id,
+    )?;
+    create_pda_account(
+        program_id,
+        player_account,
+        member_account,
+        system_program,
+        TeamMember::SIZE,
+        &[
+            TeamMember::SEED,
+            quiz_account.key.as_ref(),
+            player_account.key.as_ref(),
+            &[bump_seed],
+        ],
+    )?;
+
+    let member_data = TeamMember {
+        player: *player_account.key,
+        team_id,
+    };
+    member_data.serialize(&mut &mut member_account.data.borrow_mut()[..])?;
+    Ok(())
+}
+
+/// Move all lamports out of a program-owned account and clear its data.
+fn close_account(account: &AccountInfo, destination: &AccountInfo) -> ProgramResult {
+    let lamports = account.lamports();
+    **destination.try_borrow_mut_lamports()? += lamports;
+    **account.try_borrow_mut_lamports()? = 0;
+    account.data.borrow_mut().fill(0);
+    Ok(())
+}
+
+pub fn process_set_entry_fee(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    lamports: u64,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let vault_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    // Verify host is signer
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify host is the quiz creator
+    let mut quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+
+    // Fees can only change before the quiz starts
+    if !quiz_data.status.is_setup() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if quiz_data.mode == QuizMode::Practice {
+        return Err(QuizError::WrongQuizMode.into());
+    }
+
+    create_vault_if_needed(
+        program_id,
+        host_account,
+        vault_account,
+        system_program,
+        &[QuizSession::VAULT_SEED, quiz_account.key.as_ref()],
+    )?;
+
+    quiz_data.entry_fee = lamports;
+    quiz_data.entry_fee_usd_cents = 0;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+
+    msg!("Entry fee set to {} lamports", lamports);
+    Ok(())
+}
+
+pub fn process_set_usd_entry_fee(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    cents: u64,
+    feed_id: [u8; 32],
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let vault_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+
+    // Fees can only change before the quiz starts
+    if !quiz_data.status.is_setup() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if quiz_data.mode == QuizMode::Practice {
+        return Err(QuizError::WrongQuizMode.into());
+    }
+
+    create_vault_if_needed(
+        program_id,
+        host_account,
+        vault_account,
+        system_program,
+        &[QuizSession::VAULT_SEED, quiz_account.key.as_ref()],
+    )?;
+
+    quiz_data.entry_fee = 0;
+    quiz_data.entry_fee_usd_cents = cents;
+    quiz_data.price_feed_id = feed_id;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+
+    msg!("Entry fee set to {} USD cents", cents);
+    Ok(())
+}
+
+pub fn process_set_join_stake(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    lamports: u64,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let vault_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+
+    // Stakes can only change before anyone joins
+    if !quiz_data.status.is_setup() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if quiz_data.mode == QuizMode::Practice {
+        return Err(QuizError::WrongQuizMode.into());
+    }
+
+    create_vault_if_needed(
+        program_id,
+        host_account,
+        vault_account,
+        system_program,
+        &[QuizSession::VAULT_SEED, quiz_account.key.as_ref()],
+    )?;
+
+    quiz_data.join_stake = lamports;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+
+    msg!("Join stake set to {} lamports", lamports);
+    Ok(())
+}
+
+pub fn process_reclaim_stake(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let player = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let player_answer_account = next_account_info(accounts_iter)?;
+    let vault_account = next_account_info(accounts_iter)?;
+
+    if !player.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Stakes stay locked while the quiz runs
+    let mut quiz_data = load_quiz(program_id, quiz_account)?;
+    if !matches!(
+        quiz_data.status,
+        QuizStatus::Completed | QuizStatus::Cancelled
+    ) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // The sheet must be back on the base layer to be marked refunded
+    assert_owned_by(player_answer_account, program_id)?;
+    let mut player_answers = load_answer_sheet_of(program_id, quiz_account, player_answer_account)?;
+    if player_answers.player != *player.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if player_answers.stake == 0 {
+        return Err(QuizError::AlreadySettled.into());
+    }
+
+    assert_owned_by(vault_account, program_id)?;
+    assert_pda(
+        vault_account,
+        &[QuizSession::VAULT_SEED, quiz_account.key.as_ref()],
+        program_id,
+    )?;
+    transfer_from_vault(vault_account, player, player_answers.stake)?;
+
+    let stake = player_answers.stake;
+    quiz_data.stakes_held -= stake;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+    player_answers.stake = 0;
+    player_answers.serialize(&mut &mut player_answer_account.data.borrow_mut()[..])?;
+
+    msg!("Returned {} lamports staked by {}", stake, player.key);
+    Ok(())
+}
+
+pub fn process_contribute_to_prize_pool(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    lamports: u64,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let contributor = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let vault_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+    let contribution_account = next_account_info(accounts_iter)?;
+
+    if !contributor.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut quiz_data = load_quiz(program_id, quiz_account)?;
+
+    // The pool is fixed once the quiz stops taking answers
+    if !quiz_data.status.is_setup() && quiz_data.status != QuizStatus::Active {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    // Practice quizzes carry no prizes
+    if quiz_data.mode == QuizMode::Practice {
+        return Err(QuizError::WrongQuizMode.into());
+    }
+
+    create_vault_if_needed(
+        program_id,
+        contributor,
+        vault_account,
+        system_program,
+        &[QuizSession::VAULT_SEED, quiz_account.key.as_ref()],
+    )?;
+    invoke(
+        &system_instruction::transfer(contributor.key, vault_account.key, lamports),
+        &[
+            contributor.clone(),
+            vault_account.clone(),
+            system_program.clone(),
+        ],
+    )?;
+
+    // Record the contribution so it can be refunded
+    let contribution_seeds: &[&[u8]] = &[
+        PrizeContribution::SEED,
+        quiz_account.key.as_ref(),
+        contributor.key.as_ref(),
+    ];
+    let mut contribution = if contribution_account.data_is_empty() {
+        create_program_account(
+            program_id,
+            contributor,
+            contribution_account,
+            system_program,
+            PrizeContribution::SIZE,
+            contribution_seeds,
+        )?;
+        PrizeContribution {
+            contributor: *contributor.key,
+            lamports: 0,
+        }
+    } else {
+        assert_owned_by(contribution_account, program_id)?;
+        assert_pda(contribution_account, contribution_seeds, program_id)?;
+        PrizeContribution::try_from_slice(&contribution_account.data.borrow())?
+    };
+    contribution.lamports = contribution
+        .lamports
+        .checked_add(lamports)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    contribution.serialize(&mut &mut contribution_account.data.borrow_mut()[..])?;
+
+    quiz_data.prize_pool = quiz_data
+        .prize_pool
+        .checked_add(lamports)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    quiz_data.contributed_lamports = quiz_data
+        .contributed_lamports
+        .checked_add(lamports)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+
+    QuizEvent::PrizePoolContributed(PrizePoolContributed {
+        quiz: *quiz_account.key,
+        contributor: *contributor.key,
+        lamports,
+        prize_pool: quiz_data.prize_pool,
+    })
+    .emit()?;
+    Ok(())
+}
+
+pub fn process_refund_contribution(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let contributor = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let vault_account = next_account_info(accounts_iter)?;
+    let contribution_account = next_account_info(accounts_iter)?;
+
+    let mut quiz_data = load_quiz(program_id, quiz_account)?;
+    if quiz_data.status != QuizStatus::Cancelled {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    assert_owned_by(vault_account, program_id)?;
+    assert_pda(
+        vault_account,
+        &[QuizSession::VAULT_SEED, quiz_account.key.as_ref()],
+        program_id,
+    )?;
+    assert_owned_by(contribution_account, program_id)?;
+    assert_pda(
+        contribution_account,
+        &[
+            PrizeContribution::SEED,
+            quiz_account.key.as_ref(),
+            contributor.key.as_ref(),
+        ],
+        program_id,
+    )?;
+    let contribution = PrizeContribution::try_from_slice(&contribution_account.data.borrow())?;
+
+    let refund = quiz_data.contribution_refund(contribution.lamports);
+    transfer_from_vault(vault_account, contributor, refund)?;
+    quiz_data.prize_pool -= refund;
+    quiz_data.contributed_lamports -= contribution.lamports;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+
+    // The record's rent goes back to the contributor as well
+    close_account(contribution_account, contributor)?;
+
+    msg!("Refunded {} lamports to {}", refund, contributor.key);
+    Ok(())
+}
+
+pub fn process_settle_duel(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let quiz_account = next_account_info(accounts_iter)?;
+    let vault_account = next_account_info(accounts_iter)?;
+    let first_score_account = next_account_info(accounts_iter)?;
+    let second_score_account = next_account_info(accounts_iter)?;
+    let first_player = next_account_info(accounts_iter)?;
+    let second_player = next_account_info(accounts_iter)?;
+
+    // Duels pay out once, after scoring
+    let mut quiz_data = load_quiz(program_id, quiz_account)?;
+    if quiz_data.mode != QuizMode::Duel {
+        return Err(QuizError::WrongQuizMode.into());
+    }
+    if quiz_data.status != QuizStatus::Completed {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if quiz_data.settled {
+        return Err(QuizError::AlreadySettled.into());
+    }
+    let slot = Clock::get()?.slot;
+    if quiz_data.dispute_window_open(slot) {
+        return Err(QuizError::DisputeWindowOpen.into());
+    }
+    if !quiz_data.claim_window_open(slot) {
+        return Err(QuizError::ClaimWindowClosed.into());
+    }
+    if quiz_data.charity_unpaid() {
+        return Err(QuizError::CharityUnpaid.into());
+    }
+
+    assert_owned_by(vault_account, program_id)?;
+    assert_pda(
+        vault_account,
+        &[QuizSession::VAULT_SEED, quiz_account.key.as_ref()],
+        program_id,
+    )?;
+
+    // Both score accounts must belong to the wallets being paid
+    let mut scores = Vec::with_capacity(2);
+    for (score_account, wallet) in [
+        (first_score_account, first_player),
+        (second_score_account, second_player),
+    ] {
+        assert_owned_by(score_account, program_id)?;
+        assert_pda(
+            score_account,
+            &[
+                PlayerScore::SEED,
+                quiz_account.key.as_ref(),
+                wallet.key.as_ref(),
+            ],
+            program_id,
+        )?;
+        scores.push(PlayerScore::try_from_slice(&score_account.data.borrow())?.score);
+    }
+    if first_player.key == second_player.key {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Winner takes the pot, a tie refunds both wagers
+    let pot = quiz_data.prize_pool;
+    let payouts = match scores[0].cmp(&scores[1]) {
+        std::cmp::Ordering::Greater => vec![(first_player, pot)],
+        std::cmp::Ordering::Less => vec![(second_player, pot)],
+        std::cmp::Ordering::Equal => {
+            let refund = pot / 2;
+            vec![(first_player, refund), (second_player, pot - refund)]
+        }
+    };
+    for (wallet, lamports) in payouts {
+        transfer_from_vault(vault_account, wallet, lamports)?;
+        QuizEvent::PrizeClaimed(PrizeClaimed {
+            source: *quiz_account.key,
+            recipient: *wallet.key,
+            lamports,
+        })
+        .emit()?;
+    }
+
+    quiz_data.prize_pool = 0;
+    quiz_data.settled = true;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+
+    msg!("Duel settled with scores {} and {}", scores[0], scores[1]);
+    Ok(())
+}
+
+pub fn process_refund_duel(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let player = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let player_answer_account = next_account_info(accounts_iter)?;
+    let vault_account = next_account_info(accounts_iter)?;
+
+    if !player.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut quiz_data = load_quiz(program_id, quiz_account)?;
+    if quiz_data.mode != QuizMode::Duel {
+        return Err(QuizError::WrongQuizMode.into());
+    }
+    if quiz_data.settled {
+        return Err(QuizError::AlreadySettled.into());
+    }
+    if quiz_data.player_count >= QuizSession::DUEL_PLAYERS {
+        return Err(QuizError::QuizFull.into());
+    }
+
+    // Only once nobody can take the second seat anymore
+    let slot = Clock::get()?.slot;
+    let seat_open = match quiz_data.status {
+        QuizStatus::Active => {
+            quiz_data.join_window_open(slot)
+                && (quiz_data.deadline_slot == 0 || slot <= quiz_data.final_deadline_slot())
+        }
+        QuizStatus::SubmissionsClosed | QuizStatus::Completed => false,
+        _ => return Err(ProgramError::InvalidAccountData),
+    };
+    if seat_open {
+        return Err(QuizError::DuelStillOpen.into());
+    }
+
+    // The sheet must be back on the base layer to prove who joined
+    assert_owned_by(player_answer_account, program_id)?;
+    let player_answers = load_answer_sheet_of(program_id, quiz_account, player_answer_account)?;
+    if player_answers.player != *player.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    assert_owned_by(vault_account, program_id)?;
+    assert_pda(
+        vault_account,
+        &[QuizSession::VAULT_SEED, quiz_account.key.as_ref()],
+        program_id,
+    )?;
+
+    // Contributions are refunded to their contributors unless the duel was
+    // scored, in which case the lone player takes them too
+    let refund = if quiz_data.status == QuizStatus::Completed {
+        quiz_data.prize_pool
+    } else {
+        quiz_data.prize_pool - quiz_data.contributed_lamports
+    };
+    transfer_from_vault(vault_account, player, refund)?;
+
+    quiz_data.prize_pool -= refund;
+    quiz_data.settled = true;
+    if quiz_data.status != QuizStatus::Completed {
+        set_status(&mut quiz_data, QuizStatus::Cancelled)?;
+    }
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+
+    msg!(
+        "Refunded {} lamports to the duel's only player {}",
+        refund,
+        player.key
+    );
+    Ok(())
+}
+
+/// Create a vault PDA, a data-less program-owned account whose lamports
+/// above rent are the prize pool.
+fn create_vault_if_needed<'a>(
+    program_id: &Pubkey,
+    payer: &AccountInfo<'a>,
+    vault_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    vault_seeds: &[&[u8]],
+) -> ProgramResult {
+    let bump_seed = assert_pda(vault_account, vault_seeds, program_id)?;
+    if vault_account.owner == program_id {
+        return Ok(());
+    }
+    let bump = [bump_seed];
+    let mut signer_seeds = vault_seeds.to_vec();
+    signer_seeds.push(&bump);
+
+    create_pda_account(
+        program_id,
+        payer,
+        vault_account,
+        system_program,
+        0,
+        &signer_seeds,
+    )
+}
+
+/// Pay lamports out of a program-owned vault without going below rent.
+fn transfer_from_vault(
+    vault: &AccountInfo,
+    destination: &AccountInfo,
+    lamports: u64,
+) -> ProgramResult {
+    let rent = Rent::get()?;
+    let available = vault
+        .lamports()
+        .saturating_sub(rent.minimum_balance(vault.data_len()));
+    if lamports > available {
+        return Err(ProgramError::InsufficientFunds);
+    }
+    **vault.try_borrow_mut_lamports()? -= lamports;
+    **destination.try_borrow_mut_lamports()? += lamports;
+    Ok(())
+}
+
+pub fn process_create_tournament(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    tournament_id: u64,
+    round_count: u8,
+    advance_count: u8,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let organizer = next_account_info(accounts_iter)?;
+    let tournament_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    // Verify organizer is signer
+    if !organizer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if round_count == 0 || round_count as usize > Tournament::MAX_ROUNDS {
+        return Err(ProgramError::InvalidArgument);
+    }
+    if advance_count == 0 || advance_count as usize > Tournament::MAX_ENTRANTS {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Verify tournament PDA
+    let tournament_id_bytes = tournament_id.to_le_bytes();
+    let bump_seed = assert_pda(
+        tournament_account,
+        &[
+            Tournament::SEED,
+            organizer.key.as_ref(),
+            &tournament_id_bytes,
+        ],
+        program_id,
+    )?;
+
+    create_pda_account(
+        program_id,
+        organizer,
+        tournament_account,
+        system_program,
+        Tournament::SIZE,
+        &[
+            Tournament::SEED,
+            organizer.key.as_ref(),
+            &tournament_id_bytes,
+            &[bump_seed],
+        ],
+    )?;
+
+    let tournament_data = Tournament {
+        organizer: *organizer.key,
+        tournament_id,
+        round_count,
+        current_round: 0,
+        advance_count,
+        rounds: [Pubkey::default(); Tournament::MAX_ROUNDS],
+        entrants: [Pubkey::default(); Tournament::MAX_ENTRANTS],
+        entrant_count: 0,
+        champion: Pubkey::default(),
+        prize_pool: 0,
+        prize_claimed: false,
+    };
+    tournament_data.serialize(&mut &mut tournament_account.data.borrow_mut()[..])?;
+
+    msg!(
+        "Tournament {} created with {} rounds",
+        tournament_id,
+        round_count
+    );
+    Ok(())
+}
+
+pub fn process_link_tournament_round(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    round: u8,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let organizer = next_account_info(accounts_iter)?;
+    let host_account = next_account_info(accounts_iter)?;
+    let tournament_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+
+    // Both the organizer and the quiz host must agree to the link
+    if !organizer.is_signer || !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut tournament_data = load_tournament(program_id, tournament_account)?;
+    if tournament_data.organizer != *organizer.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Verify host is the quiz creator
+    let mut quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+
+    // Rounds are linked before they start, once each
+    if !quiz_data.status.is_setup() || quiz_data.tournament != Pubkey::default() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if round >= tournament_data.round_count || round < tournament_data.current_round {
+        return Err(QuizError::WrongTournamentRound.into());
+    }
+    if tournament_data.rounds[round as usize] != Pubkey::default() {
+        return Err(QuizError::WrongTournamentRound.into());
+    }
+
+    tournament_data.rounds[round as usize] = *quiz_account.key;
+    quiz_data.tournament = *tournament_account.key;
+    quiz_data.tournament_round = round;
+
+    tournament_data.serialize(&mut &mut tournament_account.data.borrow_mut()[..])?;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+
+    msg!("Quiz linked as tournament round {}", round);
+    Ok(())
+}
+
+pub fn process_fund_tournament(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    lamports: u64,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let funder = next_account_info(accounts_iter)?;
+    let tournament_account = next_account_info(accounts_iter)?;
+    let vault_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    // Verify funder is signer
+    if !funder.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut tournament_data = load_tournament(program_id, tournament_account)?;
+    if tournament_data.prize_claimed {
+        return Err(QuizError::AlreadySettled.into());
+    }
+
+    create_vault_if_needed(
+        program_id,
+        funder,
+        vault_account,
+        system_program,
+        &[Tournament::VAULT_SEED, tournament_account.key.as_ref()],
+    )?;
+    invoke(
+        &system_instruction::transfer(funder.key, vault_account.key, lamports),
+        &[
+            funder.clone(),
+            vault_account.clone(),
+            system_program.clone(),
+        ],
+    )?;
+
+    tournament_data.prize_pool += lamports;
+    tournament_data.serialize(&mut &mut tournament_account.data.borrow_mut()[..])?;
+
+    msg!(
+        "Tournament prize pool is now {} lamports",
+        tournament_data.prize_pool
+    );
+    Ok(())
+}
+
+pub fn process_advance_bracket(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let tournament_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+
+    let mut tournament_data = load_tournament(program_id, tournament_account)?;
+    if tournament_data.is_decided() {
+        return Err(QuizError::TournamentDecided.into());
+    }
+
+    // Only the scored session of the current round can advance the bracket
+    let round = tournament_data.current_round;
+    if tournament_data.rounds[round as usize] != *quiz_account.key {
+        return Err(QuizError::WrongTournamentRound.into());
+    }
+    let quiz_data = load_quiz(program_id, quiz_account)?;
+    if quiz_data.status != QuizStatus::Completed {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if quiz_data.dispute_window_open(Clock::get()?.slot) {
+        return Err(QuizError::DisputeWindowOpen.into());
+    }
+
+    let mut standings = load_every_score(program_id, quiz_account, &quiz_data, accounts_iter)?;
+    // An empty round would crown nobody or open the next round to anyone
+    if standings.is_empty() {
+        return Err(QuizError::EmptyRound.into());
+    }
+
+    // Highest score first, ties broken by pubkey so the order is deterministic
+    standings.sort_by(|a, b| b.score.cmp(&a.score).then(a.player.cmp(&b.player)));
+
+    tournament_data.current_round += 1;
+    if tournament_data.is_decided() {
+        tournament_data.champion = standings[0].player;
+        msg!("Tournament champion is {}", tournament_data.champion);
+    } else {
+        let advancing = standings.len().min(tournament_data.advance_count as usize);
+        tournament_data.entrants = [Pubkey::default(); Tournament::MAX_ENTRANTS];
+        for (slot, standing) in tournament_data
+            .entrants
+            .iter_mut()
+            .zip(&standings[..advancing])
+        {
+            *slot = standing.player;
+        }
+        tournament_data.entrant_count = advancing as u8;
+        msg!(
+            "{} players advance to round {}",
+            advancing,
+            tournament_data.current_round
+        );
+    }
+
+    tournament_data.serialize(&mut &mut tournament_account.data.borrow_mut()[..])?;
+    Ok(())
+}
+
+pub fn process_claim_tournament_prize(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let champion = next_account_info(accounts_iter)?;
+    let tournament_account = next_account_info(accounts_iter)?;
+    let vault_account = next_account_info(accounts_iter)?;
+
+    // Verify champion is signer
+    if !champion.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut tournament_data = load_tournament(program_id, tournament_account)?;
+    if !tournament_data.is_decided() || tournament_data.champion != *champion.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if tournament_data.prize_claimed {
+        return Err(QuizError::AlreadySettled.into());
+    }
+
+    assert_owned_by(vault_account, program_id)?;
+    assert_pda(
+        vault_account,
+        &[Tournament::VAULT_SEED, tournament_account.key.as_ref()],
+        program_id,
+    )?;
+    transfer_from_vault(vault_account, champion, tournament_data.prize_pool)?;
+
+    let prize = tournament_data.prize_pool;
+    tournament_data.prize_pool = 0;
+    tournament_data.prize_claimed = true;
+    tournament_data.serialize(&mut &mut tournament_account.data.borrow_mut()[..])?;
+
+    QuizEvent::PrizeClaimed(PrizeClaimed {
+        source: *tournament_account.key,
+        recipient: *champion.key,
+        lamports: prize,
+    })
+    .emit()?;
+    Ok(())
+}
+
+/// Read the score PDA of every player of a quiz from `accounts_iter`. Every
+/// one must be supplied so nobody can be left out.
+fn load_every_score<'a, 'info: 'a>(
+    program_id: &Pubkey,
+    quiz_account: &AccountInfo,
+    quiz_data: &QuizSession,
+    accounts_iter: &mut impl Iterator<Item = &'a AccountInfo<'info>>,
+) -> Result<Vec<PlayerScore>, ProgramError> {
+    let mut scores: Vec<PlayerScore> = Vec::with_capacity(quiz_data.player_count as usize);
+    for _ in 0..quiz_data.player_count {
+        let score_account = next_account_info(accounts_iter)?;
+        assert_owned_by(score_account, program_id)?;
+        let score_data = PlayerScore::try_from_slice(&score_account.data.borrow())?;
+        assert_pda(
+            score_account,
+            &[
+                PlayerScore::SEED,
+                quiz_account.key.as_ref(),
+                score_data.player.as_ref(),
+            ],
+            program_id,
+        )?;
+        if scores.iter().any(|s| s.player == score_data.player) {
+            return Err(ProgramError::InvalidArgument);
+        }
+        scores.push(score_data);
+    }
+    Ok(scores)
+}
+
+/// Load a tournament, verifying it is this program's PDA for its organizer and id.
+fn load_tournament(
+    program_id: &Pubkey,
+    tournament_account: &AccountInfo,
+) -> Result<Tournament, ProgramError> {
+    assert_owned_by(tournament_account, program_id)?;
+    let tournament_data = Tournament::try_from_slice(&tournament_account.data.borrow())?;
+    assert_pda(
+        tournament_account,
+        &[
+            Tournament::SEED,
+            tournament_data.organizer.as_ref(),
+            &tournament_data.tournament_id.to_le_bytes(),
+        ],
+        program_id,
+    )?;
+    Ok(tournament_data)
+}
+
+pub fn process_create_series(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    series_id: u64,
+    quiz_count: u8,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let organizer = next_account_info(accounts_iter)?;
+    let series_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    // Verify organizer is signer
+    if !organizer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if quiz_count == 0 || quiz_count as usize > Series::MAX_QUIZZES {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    create_program_account(
+        program_id,
+        organizer,
+        series_account,
+        system_program,
+        Series::SIZE,
+        &[
+            Series::SEED,
+            organizer.key.as_ref(),
+            &series_id.to_le_bytes(),
+        ],
+    )?;
+
+    let series_data = Series {
+        organizer: *organizer.key,
+        series_id,
+        quiz_count,
+        quizzes: [Pubkey::default(); Series::MAX_QUIZZES],
+        recorded: 0,
+        standings: [LeaderboardEntry::default(); Series::MAX_STANDINGS],
+        standing_count: 0,
+        prize_pool: 0,
+        prize_claimed: false,
+        season: 0,
+    };
+    series_data.serialize(&mut &mut series_account.data.borrow_mut()[..])?;
+
+    msg!("Series {} created with {} quizzes", series_id, quiz_count);
+    Ok(())
+}
+
+pub fn process_link_series_quiz(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    position: u8,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let organizer = next_account_info(accounts_iter)?;
+    let host_account = next_account_info(accounts_iter)?;
+    let series_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+
+    // Both the organizer and the quiz host must agree to the link
+    if !organizer.is_signer || !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut series_data = load_series(program_id, series_account)?;
+    if series_data.organizer != *organizer.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Quizzes join before they start, at one free position each
+    let quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+    if !quiz_data.status.is_setup() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if position >= series_data.quiz_count
+        || series_data.quizzes[position as usize] != Pubkey::default()
+        || series_data.quizzes.contains(quiz_account.key)
+    {
+        return Err(QuizError::WrongSeriesQuiz.into());
+    }
+
+    series_data.quizzes[position as usize] = *quiz_account.key;
+    series_data.serialize(&mut &mut series_account.data.borrow_mut()[..])?;
+
+    msg!("Quiz linked at series position {}", position);
+    Ok(())
+}
+
+pub fn process_fund_series(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    lamports: u64,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let funder = next_account_info(accounts_iter)?;
+    let series_account = next_account_info(accounts_iter)?;
+    let vault_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    // Verify funder is signer
+    if !funder.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut series_data = load_series(program_id, series_account)?;
+    if series_data.prize_claimed {
+        return Err(QuizError::AlreadySettled.into());
+    }
+
+    create_vault_if_needed(
+        program_id,
+        funder,
+        vault_account,
+        system_program,
+        &[Series::VAULT_SEED, series_account.key.as_ref()],
+    )?;
+    invoke(
+        &system_instruction::transfer(funder.key, vault_account.key, lamports),
+        &[
+            funder.clone(),
+            vault_account.clone(),
+            system_program.clone(),
+        ],
+    )?;
+
+    series_data.prize_pool += lamports;
+    series_data.serialize(&mut &mut series_account.data.borrow_mut()[..])?;
+
+    msg!(
+        "Series prize pool is now {} lamports",
+        series_data.prize_pool
+    );
+    Ok(())
+}
+
+pub fn process_record_series_quiz(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let series_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+
+    let mut series_data = load_series(program_id, series_account)?;
+    let position = series_data.quizzes[..series_data.quiz_count as usize]
+        .iter()
+        .position(|quiz| quiz == quiz_account.key)
+        .filter(|&position| !series_data.is_recorded(position))
+        .ok_or(QuizError::WrongSeriesQuiz)?;
+
+    // Scores are final once the quiz is scored and can't be disputed
+    let quiz_data = load_quiz(program_id, quiz_account)?;
+    if quiz_data.status != QuizStatus::Completed {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if quiz_data.dispute_window_open(Clock::get()?.slot) {
+        return Err(QuizError::DisputeWindowOpen.into());
+    }
+
+    let scores = load_every_score(program_id, quiz_account, &quiz_data, accounts_iter)?;
+    for score in &scores {
+        series_data.add_score(score.player, score.score);
+    }
+    series_data.recorded |= 1 << position;
+    series_data.serialize(&mut &mut series_account.data.borrow_mut()[..])?;
+
+    msg!(
+        "Series position {} recorded with {} scores",
+        position,
+        scores.len()
+    );
+    Ok(())
+}
+
+pub fn process_claim_series_prize(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let leader = next_account_info(accounts_iter)?;
+    let series_account = next_account_info(accounts_iter)?;
+    let vault_account = next_account_info(accounts_iter)?;
+
+    // Verify leader is signer
+    if !leader.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut series_data = load_series(program_id, series_account)?;
+    if !series_data.is_complete() {
+        return Err(QuizError::SeriesNotComplete.into());
+    }
+    if series_data.standings().first().map(|e| e.player) != Some(*leader.key) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if series_data.prize_claimed {
+        return Err(QuizError::AlreadySettled.into());
+    }
+
+    assert_owned_by(vault_account, program_id)?;
+    assert_pda(
+        vault_account,
+        &[Series::VAULT_SEED, series_account.key.as_ref()],
+        program_id,
+    )?;
+    transfer_from_vault(vault_account, leader, series_data.prize_pool)?;
+
+    let prize = series_data.prize_pool;
+    series_data.prize_pool = 0;
+    series_data.prize_claimed = true;
+    series_data.serialize(&mut &mut series_account.data.borrow_mut()[..])?;
+
+    QuizEvent::PrizeClaimed(PrizeClaimed {
+        source: *series_account.key,
+        recipient: *leader.key,
+        lamports: prize,
+    })
+    .emit()?;
+    Ok(())
+}
+
+pub fn process_end_series_season(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    quiz_count: u8,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let organizer = next_account_info(accounts_iter)?;
+    let series_account = next_account_info(accounts_iter)?;
+    let archive_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    // Verify organizer is signer
+    if !organizer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut series_data = load_series(program_id, series_account)?;
+    if series_data.organizer != *organizer.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if quiz_count == 0 || quiz_count as usize > Series::MAX_QUIZZES {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // A season ends once it is decided and its leader has been paid
+    if !series_data.is_complete() {
+        return Err(QuizError::SeriesNotComplete.into());
+    }
+    if series_data.prize_pool != 0 && !series_data.prize_claimed {
+        return Err(QuizError::SeasonNotOver.into());
+    }
+
+    archive_season(
+        program_id,
+        organizer,
+        archive_account,
+        system_program,
+        series_account.key,
+        series_data.season,
+        series_data.standings(),
+    )?;
+
+    series_data.start_season(quiz_count);
+    series_data.serialize(&mut &mut series_account.data.borrow_mut()[..])?;
+
+    msg!("Series season {} started", series_data.season);
+    Ok(())
+}
+
+/// Load a series, verifying it is this program's PDA for its organizer and id.
+fn load_series(program_id: &Pubkey, series_account: &AccountInfo) -> Result<Series, ProgramError> {
+    assert_owned_by(series_account, program_id)?;
+    let series_data = Series::try_from_slice(&series_account.data.borrow())?;
+    assert_pda(
+        series_account,
+        &[
+            Series::SEED,
+            series_data.organizer.as_ref(),
+            &series_data.series_id.to_le_bytes(),
+        ],
+        program_id,
+    )?;
+    Ok(series_data)
+}
+
+/// Move a scored quiz's rolled-over prizes, or its whole pool when nobody
+/// qualified, into the vault of the next quiz of its series.
+pub fn process_rollover_jackpot(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let series_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let vault_account = next_account_info(accounts_iter)?;
+    let next_quiz_account = next_account_info(accounts_iter)?;
+    let next_vault_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    // Verify host is signer
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify host is the quiz creator
+    let mut quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+    if quiz_data.status != QuizStatus::Completed
+        || quiz_data.prize_remainder != PrizeRemainder::RollOver
+    {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if quiz_data.dispute_window_open(Clock::get()?.slot) {
+        return Err(QuizError::DisputeWindowOpen.into());
+    }
+    if quiz_data.charity_unpaid() {
+        return Err(QuizError::CharityUnpaid.into());
+    }
+
+    // The jackpot only moves one position along the series
+    let series_data = load_series(program_id, series_account)?;
+    let position = series_data
+        .quizzes
+        .iter()
+        .position(|quiz| quiz == quiz_account.key)
+        .ok_or(QuizError::WrongSeriesQuiz)?;
+    if series_data.quizzes.get(position + 1) != Some(next_quiz_account.key)
+        || *next_quiz_account.key == Pubkey::default()
+    {
+        return Err(QuizError::WrongSeriesQuiz.into());
+    }
+    let mut next_quiz_data = load_quiz(program_id, next_quiz_account)?;
+    if matches!(
+        next_quiz_data.status,
+        QuizStatus::Completed | QuizStatus::Cancelled
+    ) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // A proportional pool nobody qualified for rolls over whole
+    let nobody_qualified = quiz_data.proportional_prizes && quiz_data.total_prize_score == 0;
+    let lamports = if nobody_qualified {
+        quiz_data.prize_pool
+    } else {
+        quiz_data.rollover_lamports
+    };
+    if lamports == 0 {
+        return Err(QuizError::NoJackpot.into());
+    }
+
+    assert_owned_by(vault_account, program_id)?;
+    assert_pda(
+        vault_account,
+        &[QuizSession::VAULT_SEED, quiz_account.key.as_ref()],
+        program_id,
+    )?;
+    create_vault_if_needed(
+        program_id,
+        host_account,
+        next_vault_account,
+        system_program,
+        &[QuizSession::VAULT_SEED, next_quiz_account.key.as_ref()],
+    )?;
+    transfer_from_vault(vault_account, next_vault_account, lamports)?;
+
+    if nobody_qualified {
+        quiz_data.prize_pool = 0;
+        quiz_data.settled = true;
+    }
+    quiz_data.rollover_lamports = 0;
+    quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+    next_quiz_data.prize_pool += lamports;
+    next_quiz_data.serialize(&mut &mut next_quiz_account.data.borrow_mut()[..])?;
+
+    msg!(
+        "Jackpot of {} lamports rolled over to series position {}",
+        lamports,
+        position + 1
+    );
+    Ok(())
+}
+
+pub fn process_initialize_daily_quiz(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    curator: Pubkey,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let authority = next_account_info(accounts_iter)?;
+    let config_account = next_account_info(accounts_iter)?;
+    let leaderboard_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+    let program_data_account = next_account_info(accounts_iter)?;
+
+    // Verify authority is signer
+    if !authority.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // The config PDA is unique, so only the upgrade authority may claim it
+    // before anyone else can
+    assert_upgrade_authority(program_data_account, program_id, authority.key)?;
+    create_program_account(
+        program_id,
+        authority,
+        config_account,
+        system_program,
+        DailyConfig::SIZE,
+        &[DailyConfig::SEED],
+    )?;
+    create_program_account(
+        program_id,
+        authority,
+        leaderboard_account,
+        system_program,
+        DailyLeaderboard::SIZE,
+        &[DailyLeaderboard::SEED],
+    )?;
+
+    let config_data = DailyConfig {
+        authority: *authority.key,
+        curator,
+        season_days: 0,
+    };
+    config_data.serialize(&mut &mut config_account.data.borrow_mut()[..])?;
+
+    let leaderboard_data = DailyLeaderboard {
+        entries: [LeaderboardEntry::default(); DailyLeaderboard::MAX_ENTRIES],
+        entry_count: 0,
+        season: 0,
+        season_start_day: DailyQuiz::day_index(Clock::get()?.unix_timestamp),
+    };
+    leaderboard_data.serialize(&mut &mut leaderboard_account.data.borrow_mut()[..])?;
+
+    msg!("Daily quiz initialized with curator {}", curator);
+    Ok(())
+}
+
+pub fn process_publish_daily_question(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    day_index: u64,
+    question_index: u8,
+    question_text: String,
+    options: [String; 4],
+    correct_answer_index: u8,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let curator = next_account_info(accounts_iter)?;
+    let config_account = next_account_info(accounts_iter)?;
+    let daily_quiz_account = next_account_info(accounts_iter)?;
+    let question_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    // Verify curator is signer
+    if !curator.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify curator is the designated one
+    let config_data = load_daily_config(program_id, config_account)?;
+    if config_data.curator != *curator.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Past days are closed
+    let today = DailyQuiz::day_index(Clock::get()?.unix_timestamp);
+    if day_index < today {
+        return Err(QuizError::SubmissionDeadlinePassed.into());
+    }
+
+    // The first question for a day creates that day's session
+    let day_index_bytes = day_index.to_le_bytes();
+    let mut daily_data = if daily_quiz_account.data_is_empty() {
+        create_program_account(
+            program_id,
+            curator,
+            daily_quiz_account,
+            system_program,
+            DailyQuiz::SIZE,
+            &[DailyQuiz::SEED, &day_index_bytes],
+        )?;
+        DailyQuiz {
+            day_index,
+            question_count: 0,
+            player_count: 0,
+        }
+    } else {
+        load_daily_quiz(program_id, daily_quiz_account)?
+    };
+
+    // Questions are appended in order and frozen once anyone has played
+    if daily_data.player_count > 0 {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if question_index != daily_data.question_count || question_index == u8::MAX {
+        return Err(QuizError::InvalidQuestionIndex.into());
+    }
+    if correct_answer_index >= QuizQuestion::OPTION_COUNT {
+        return Err(QuizError::InvalidCorrectAnswerIndex.into());
+    }
+
+    create_program_account(
+        program_id,
+        curator,
+        question_account,
+        system_program,
+        QuizQuestion::get_size(&question_text, &options),
+        &[
+            QuizQuestion::SEED,
+            daily_quiz_account.key.as_ref(),
+            &[question_index],
+        ],
+    )?;
+
+    let question_data = QuizQuestion {
+        question_text,
+        options,
+        correct_answer_index,
+        buzzer_winner: Pubkey::default(),
+        multiplier: 1,
+        void: VoidMode::NotVoided,
+        difficulty: 1,
+    };
+    question_data.serialize(&mut &mut question_account.data.borrow_mut()[..])?;
+
+    daily_data.question_count += 1;
+    daily_data.serialize(&mut &mut daily_quiz_account.data.borrow_mut()[..])?;
+
+    msg!(
+        "Question {} published for day {}",
+        question_index,
+        day_index
+    );
+    Ok(())
+}
+
+pub fn process_play_daily_quiz(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    answers: Vec<u8>,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let player = next_account_info(accounts_iter)?;
+    let daily_quiz_account = next_account_info(accounts_iter)?;
+    let entry_account = next_account_info(accounts_iter)?;
+    let streak_account = next_account_info(accounts_iter)?;
+    let leaderboard_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    // Verify player is signer
+    if !player.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Only today's session is playable
+    let mut daily_data = load_daily_quiz(program_id, daily_quiz_account)?;
+    let today = DailyQuiz::day_index(Clock::get()?.unix_timestamp);
+    if daily_data.day_index != today {
+        return Err(QuizError::SubmissionDeadlinePassed.into());
+    }
+    validate_answers(daily_data.question_count, &answers)?;
+
+    // One entry per player per day
+    if !entry_account.data_is_empty() {
+        return Err(QuizError::AnswersAlreadySubmitted.into());
+    }
+    create_program_account(
+        program_id,
+        player,
+        entry_account,
+        system_program,
+        DailyEntry::SIZE,
+        &[
+            DailyEntry::SEED,
+            daily_quiz_account.key.as_ref(),
+            player.key.as_ref(),
+        ],
+    )?;
+
+    // Grade right away, daily answers are public once the day starts
+    let mut score: u32 = 0;
+    for (question_index, &answer) in answers.iter().enumerate() {
+        let question_account = next_account_info(accounts_iter)?;
+        let question = load_question(
+            program_id,
+            daily_quiz_account,
+            question_account,
+            question_index as u8,
+        )?;
+        if answer == question.correct_answer_index {
+            score += 1;
+        }
+    }
+
+    let entry_data = DailyEntry {
+        player: *player.key,
+        day_index: today,
+        score,
+    };
+    entry_data.serialize(&mut &mut entry_account.data.borrow_mut()[..])?;
+
+    // Roll the result into the player's streak
+    let streak_seeds: &[&[u8]] = &[DailyStreak::SEED, player.key.as_ref()];
+    let mut streak_data = if streak_account.data_is_empty() {
+        create_program_account(
+            program_id,
+            player,
+            streak_account,
+            system_program,
+            DailyStreak::SIZE,
+            streak_seeds,
+        )?;
+        DailyStreak {
+            player: *player.key,
+            last_day: 0,
+            current_streak: 0,
+            best_streak: 0,
+            days_played: 0,
+            total_score: 0,
+            season: 0,
+            season_score: 0,
+        }
+    } else {
+        assert_owned_by(streak_account, program_id)?;
+        assert_pda(streak_account, streak_seeds, program_id)?;
+        DailyStreak::try_from_slice(&streak_account.data.borrow())?
+    };
+    // The leaderboard ranks this season's totals
+    assert_owned_by(leaderboard_account, program_id)?;
+    assert_pda(leaderboard_account, &[DailyLeaderboard::SEED], program_id)?;
+    let mut leaderboard_data =
+        DailyLeaderboard::try_from_slice(&leaderboard_account.data.borrow())?;
+    streak_data.record_day(today, score, leaderboard_data.season);
+    streak_data.serialize(&mut &mut streak_account.data.borrow_mut()[..])?;
+
+    leaderboard_data.record(*player.key, streak_data.season_score);
+    leaderboard_data.serialize(&mut &mut leaderboard_account.data.borrow_mut()[..])?;
+
+    daily_data.player_count += 1;
+    daily_data.serialize(&mut &mut daily_quiz_account.data.borrow_mut()[..])?;
+
+    msg!(
+        "Daily quiz {} scored {} with a streak of {}",
+        today,
+        score,
+        streak_data.current_streak
+    );
+    Ok(())
+}
+
+pub fn process_set_daily_season_length(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    season_days: u64,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let authority = next_account_info(accounts_iter)?;
+    let config_account = next_account_info(accounts_iter)?;
+
+    // Verify authority is signer
+    if !authority.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut config_data = load_daily_config(program_id, config_account)?;
+    if config_data.authority != *authority.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    config_data.season_days = season_days;
+    config_data.serialize(&mut &mut config_account.data.borrow_mut()[..])?;
+
+    msg!("Daily seasons last {} days", season_days);
+    Ok(())
+}
+
+pub fn process_end_daily_season(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let caller = next_account_info(accounts_iter)?;
+    let config_account = next_account_info(accounts_iter)?;
+    let leaderboard_account = next_account_info(accounts_iter)?;
+    let archive_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    // Verify caller is signer
+    if !caller.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let config_data = load_daily_config(program_id, config_account)?;
+    assert_owned_by(leaderboard_account, program_id)?;
+    assert_pda(leaderboard_account, &[DailyLeaderboard::SEED], program_id)?;
+    let mut leaderboard_data =
+        DailyLeaderboard::try_from_slice(&leaderboard_account.data.borrow())?;
+
+    // The authority ends a season at will, anyone else once it has run its length
+    let today = DailyQuiz::day_index(Clock::get()?.unix_timestamp);
+    if config_data.authority != *caller.key
+        && !leaderboard_data.season_over(config_data.season_days, today)
+    {
+        return Err(QuizError::SeasonNotOver.into());
+    }
+
+    archive_season(
+        program_id,
+        caller,
+        archive_account,
+        system_program,
+        leaderboard_account.key,
+        leaderboard_data.season,
+        leaderboard_data.entries(),
+    )?;
+
+    leaderboard_data.start_season(today);
+    leaderboard_data.serialize(&mut &mut leaderboard_account.data.borrow_mut()[..])?;
+
+    msg!("Daily season {} started", leaderboard_data.season);
+    Ok(())
+}
+
+/// Keep the final standings of `board`'s `season` in a new archive account.
+fn archive_season<'a>(
+    program_id: &Pubkey,
+    payer: &AccountInfo<'a>,
+    archive_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    board: &Pubkey,
+    season: u32,
+    standings: &[LeaderboardEntry],
+) -> ProgramResult {
+    create_program_account(
+        program_id,
+        payer,
+        archive_account,
+        system_program,
+        SeasonArchive::SIZE,
+        &[SeasonArchive::SEED, board.as_ref(), &season.to_le_bytes()],
+    )?;
+    let archive_data = SeasonArchive::new(*board, season, Clock::get()?.unix_timestamp, standings);
+    archive_data.serialize(&mut &mut archive_account.data.borrow_mut()[..])?;
+    Ok(())
+}
+
+fn load_daily_config(
+    program_id: &Pubkey,
+    config_account: &AccountInfo,
+) -> Result<DailyConfig, ProgramError> {
+    assert_owned_by(config_account, program_id)?;
+    assert_pda(config_account, &[DailyConfig::SEED], program_id)?;
+    Ok(DailyConfig::try_from_slice(&config_account.data.borrow())?)
+}
+
+fn load_daily_quiz(
+    program_id: &Pubkey,
+    daily_quiz_account: &AccountInfo,
+) -> Result<DailyQuiz, ProgramError> {
+    assert_owned_by(daily_quiz_account, program_id)?;
+    let daily_data = DailyQuiz::try_from_slice(&daily_quiz_account.data.borrow())?;
+    assert_pda(
+        daily_quiz_account,
+        &[DailyQuiz::SEED, &daily_data.day_index.to_le_bytes()],
+        program_id,
+    )?;
+    Ok(daily_data)
+}
+
+/// Create a program-owned PDA funded for rent by `payer`.
+fn create_program_account<'a>(
+    program_id: &Pubkey,
+    payer: &AccountInfo<'a>,
+    account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    size: usize,
+    seeds: &[&[u8]],
+) -> ProgramResult {
+    let bump_seed = assert_pda(account, seeds, program_id)?;
+    let bump = [bump_seed];
+    let mut signer_seeds = seeds.to_vec();
+    signer_seeds.push(&bump);
+
+    create_pda_account(
+        program_id,
+        payer,
+        account,
+        system_program,
+        size,
+        &signer_seeds,
+    )
+}
+
+/// Create `account` with `size` bytes owned by the program, funded for rent
+/// by `payer`. `create_account` fails on an address that already holds
+/// lamports, so anyone could block a PDA by sending it some first. A
+/// pre-funded PDA is topped up to rent, allocated and assigned instead.
+fn create_pda_account<'a>(
+    program_id: &Pubkey,
+    payer: &AccountInfo<'a>,
+    account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    size: usize,
+    signer_seeds: &[&[u8]],
+) -> ProgramResult {
+    let rent_lamports = Rent::get()?.minimum_balance(size);
+    if account.lamports() == 0 {
+        return invoke_signed(
+            &system_instruction::create_account(
+                payer.key,
+                account.key,
+                rent_lamports,
+                size as u64,
+                program_id,
+            ),
+            &[payer.clone(), account.clone(), system_program.clone()],
+            &[signer_seeds],
+        );
+    }
+
+    let shortfall = rent_lamports.saturating_sub(account.lamports());
+    if shortfall > 0 {
+        invoke(
+            &system_instruction::transfer(payer.key, account.key, shortfall),
+            &[payer.clone(), account.clone(), system_program.clone()],
+        )?;
+    }
+    invoke_signed(
+        &system_instruction::allocate(account.key, size as u64),
+        &[account.clone(), system_program.clone()],
+        &[signer_seeds],
+    )?;
+    invoke_signed(
+        &system_instruction::assign(account.key, program_id),
+        &[account.clone(), system_program.clone()],
+        &[signer_seeds],
+    )
+}
+
+pub fn process_practice_quiz(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    answers: Vec<u8>,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let player = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let player_answer_account = next_account_info(accounts_iter)?;
+    let player_score_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    // Verify player is signer
+    if !player.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut quiz_data = load_quiz(program_id, quiz_account)?;
+    if quiz_data.mode != QuizMode::Practice {
+        return Err(QuizError::WrongQuizMode.into());
+    }
+    if quiz_data.status != QuizStatus::Active {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    assert_questions_revealed(&quiz_data)?;
+    validate_answers(quiz_data.question_count, &answers)?;
+    let event_log = next_event_log(program_id, quiz_account, &quiz_data, accounts_iter)?;
+
+    // The answer sheet stays on chain, no delegation involved
+    let answer_seeds: &[&[u8]] = &[
+        PlayerAnswer::SEED,
+        quiz_account.key.as_ref(),
+        player.key.as_ref(),
+    ];
+    let mut player_answers = if player_answer_account.data_is_empty() {
+        create_program_account(
+            program_id,
+            player,
+            player_answer_account,
+            system_program,
+            PlayerAnswer::get_size(quiz_data.question_count as usize),
+            answer_seeds,
+        )?;
+        quiz_data.player_count = quiz_data.player_count.saturating_add(1);
+        quiz_data.serialize(&mut &mut quiz_account.data.borrow_mut()[..])?;
+        QuizEvent::PlayerJoined(PlayerJoined {
+            quiz: *quiz_account.key,
+            player: *player.key,
+            entry_fee: 0,
+        })
+        .emit()?;
+        log_event(event_log, LoggedEvent::KIND_JOINED, *player.key, 0)?;
+        PlayerAnswer::new(*player.key, quiz_data.question_count as usize)
+    } else {
+        assert_owned_by(player_answer_account, program_id)?;
+        assert_pda(player_answer_account, answer_seeds, program_id)?;
+        PlayerAnswer::try_from_slice(&player_answer_account.data.borrow())?
+    };
+
+    // Every attempt replaces the previous one
+    let slot = Clock::get()?.slot;
+    for (question_index, &answer) in answers.iter().enumerate() {
+        player_answers.answers[question_index] = answer;
+        player_answers.answer_slots[question_index] = slot;
+        player_answers.mark_answered(question_index);
+    }
+    player_answers.submitted = true;
+    player_answers.submitted_slot = slot;
+    player_answers.serialize(&mut &mut player_answer_account.data.borrow_mut()[..])?;
+    emit_answers_submitted(quiz_account, event_log, &player_answers)?;
+
+    // Grade against the question accounts straight away
+    let mut questions = Vec::with_capacity(quiz_data.question_count as usize);
+    for question_index in 0..quiz_data.question_count {
+        let question_account = next_account_info(accounts_iter)?;
+        questions.push(load_question(
+            program_id,
+            quiz_account,
+            question_account,
+            question_index,
+        )?);
+    }
+    let score = grade_answers(&quiz_data, &questions, &player_answers);
+
+    let score_seeds: &[&[u8]] = &[
+        PlayerScore::SEED,
+        quiz_account.key.as_ref(),
+        player.key.as_ref(),
+    ];
+    if player_score_account.data_is_empty() {
+        create_program_account(
+            program_id,
+            player,
+            player_score_account,
+            system_program,
+            PlayerScore::SIZE,
+            score_seeds,
+        )?;
+    } else {
+        assert_owned_by(player_score_account, program_id)?;
+        assert_pda(player_score_account, score_seeds, program_id)?;
+    }
+    let score_data = PlayerScore {
+        player: *player.key,
+        score,
+        quiz: *quiz_account.key,
+        late_penalty: 0,
+        skipped: player_answers.skipped_count() as u8,
+    };
+    score_data.serialize(&mut &mut player_score_account.data.borrow_mut()[..])?;
+
+    msg!("Practice attempt by {} scored {}", player.key, score);
+    Ok(())
+}
+
+pub fn process_list_quiz(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    shard_index: u32,
+    start_slot: u64,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let registry_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    // Verify host is signer
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify host is the quiz creator
+    let quiz_data = load_host_quiz(program_id, host_account, quiz_account)?;
+    if quiz_data.status.is_finished() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // The first listing in a shard creates it
+    let shard_index_bytes = shard_index.to_le_bytes();
+    let mut registry_data = if registry_account.data_is_empty() {
+        create_program_account(
+            program_id,
+            host_account,
+            registry_account,
+            system_program,
+            QuizRegistry::SIZE,
+            &[QuizRegistry::SEED, &shard_index_bytes],
+        )?;
+        QuizRegistry {
+            shard_index,
+            entries: [RegistryEntry::default(); QuizRegistry::MAX_ENTRIES],
+            entry_count: 0,
+        }
+    } else {
+        load_registry(program_id, registry_account)?
+    };
+
+    if registry_data
+        .entries()
+        .iter()
+        .any(|entry| entry.quiz == *quiz_account.key)
+    {
+        return Err(ProgramError::InvalidArgument);
+    }
+    if registry_data.entry_count as usize >= QuizRegistry::MAX_ENTRIES {
+        return Err(QuizError::RegistryFull.into());
+    }
+
+    registry_data.entries[registry_data.entry_count as usize] = RegistryEntry {
+        quiz: *quiz_account.key,
+        host: *host_account.key,
+        start_slot,
+        entry_fee: quiz_data.entry_fee,
+    };
+    registry_data.entry_count += 1;
+    registry_data.serialize(&mut &mut registry_account.data.borrow_mut()[..])?;
+
+    msg!(
+        "Quiz {} listed in registry shard {}",
+        quiz_account.key,
+        shard_index
+    );
+    Ok(())
+}
+
+pub fn process_delist_quiz(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    shard_index: u32,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let host_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let registry_account = next_account_info(accounts_iter)?;
+
+    // Verify host is signer
+    if !host_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut registry_data = load_registry(program_id, registry_account)?;
+    if registry_data.shard_index != shard_index {
+        return Err(QuizError::InvalidPda.into());
+    }
+
+    // Only the host that listed the quiz can remove it
+    let position = registry_data
+        .entries()
+        .iter()
+        .position(|entry| entry.quiz == *quiz_account.key && entry.host == *host_account.key)
+        .ok_or(ProgramError::InvalidArgument)?;
+
+    // Keep the listed entries contiguous
+    let last = registry_data.entry_count as usize - 1;
+    registry_data.entries[position] = registry_data.entries[last];
+    registry_data.entries[last] = RegistryEntry::default();
+    registry_data.entry_count -= 1;
+    registry_data.serialize(&mut &mut registry_account.data.borrow_mut()[..])?;
+
+    msg!(
+        "Quiz {} delisted from registry shard {}",
+        quiz_account.key,
+        shard_index
+    );
+    Ok(())
+}
+
+fn load_registry(
+    program_id: &Pubkey,
+    registry_account: &AccountInfo,
+) -> Result<QuizRegistry, ProgramError> {
+    assert_owned_by(registry_account, program_id)?;
+    let registry_data = QuizRegistry::try_from_slice(&registry_account.data.borrow())?;
+    assert_pda(
+        registry_account,
+        &[QuizRegistry::SEED, &registry_data.shard_index.to_le_bytes()],
+        program_id,
+    )?;
+    Ok(registry_data)
+}
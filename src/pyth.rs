@@ -0,0 +1,111 @@
+//! Just enough of the Pyth pull oracle to price entry fees set in USD.
+//! Price updates are `PriceUpdateV2` accounts posted by the Pyth receiver
+//! program, read by hand here rather than through the Pyth SDK:
+//!
+//! discriminator (8) + write_authority (32) + verification_level (1, plus 1
+//! for `Partial`) + feed_id (32) + price (8) + conf (8) + exponent (4) +
+//! publish_time (8) + ...
+
+use solana_program::{
+    account_info::AccountInfo, program_error::ProgramError, pubkey, pubkey::Pubkey,
+};
+
+use crate::{error::QuizError, validation::assert_owned_by};
+
+/// Owner of every `PriceUpdateV2` account.
+pub const RECEIVER_PROGRAM_ID: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+/// Anchor discriminator of `PriceUpdateV2`.
+pub const PRICE_UPDATE_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+/// Feed id of SOL/USD.
+pub const SOL_USD_FEED_ID: [u8; 32] = [
+    0xef, 0x0d, 0x8b, 0x6f, 0xda, 0x2c, 0xeb, 0xa4, 0x1d, 0xa1, 0x5d, 0x40, 0x95, 0xd1, 0xda, 0x39,
+    0x2a, 0x0d, 0x2f, 0x8e, 0xd0, 0xc6, 0xc7, 0xbc, 0x0f, 0x4c, 0xfa, 0xc8, 0xc2, 0x80, 0xb5, 0x6d,
+];
+/// Prices published longer ago than this are refused.
+pub const MAX_PRICE_AGE_SECS: i64 = 60;
+/// Prices with a confidence interval wider than this share of the price
+/// are refused.
+pub const MAX_CONFIDENCE_BPS: u64 = 200;
+
+/// `verification_level` of an update checked against every guardian
+/// signature, the only level trusted here.
+const VERIFICATION_FULL: u8 = 1;
+const MESSAGE_OFFSET: usize = 8 + 32 + 1;
+
+/// The SOL price of a `PriceUpdateV2`, `price * 10^exponent` USD.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PriceUpdate {
+    pub feed_id: [u8; 32],
+    pub price: i64,
+    pub conf: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
+}
+
+impl PriceUpdate {
+    /// Read a fully verified price update owned by the receiver program.
+    pub fn load(account: &AccountInfo) -> Result<Self, ProgramError> {
+        assert_owned_by(account, &RECEIVER_PROGRAM_ID)?;
+        Self::unpack(&account.data.borrow())
+    }
+
+    pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() < MESSAGE_OFFSET + 32 + 8 + 8 + 4 + 8
+            || data[..8] != PRICE_UPDATE_DISCRIMINATOR
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if data[MESSAGE_OFFSET - 1] != VERIFICATION_FULL {
+            return Err(QuizError::PriceUncertain.into());
+        }
+        let message = &data[MESSAGE_OFFSET..];
+        let field = |at: usize| -> [u8; 8] { message[at..at + 8].try_into().unwrap() };
+        Ok(Self {
+            feed_id: message[..32].try_into().unwrap(),
+            price: i64::from_le_bytes(field(32)),
+            conf: u64::from_le_bytes(field(40)),
+            exponent: i32::from_le_bytes(message[48..52].try_into().unwrap()),
+            publish_time: i64::from_le_bytes(field(52)),
+        })
+    }
+
+    /// Lamports worth `cents` at this price, rounded up. Refuses prices of
+    /// another feed, older than `MAX_PRICE_AGE_SECS` at `now` or less
+    /// certain than `MAX_CONFIDENCE_BPS`.
+    pub fn usd_cents_to_lamports(
+        &self,
+        cents: u64,
+        feed_id: &[u8; 32],
+        now: i64,
+    ) -> Result<u64, ProgramError> {
+        if self.feed_id != *feed_id {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if now.saturating_sub(self.publish_time) > MAX_PRICE_AGE_SECS {
+            return Err(QuizError::StalePrice.into());
+        }
+        if self.price <= 0
+            || self.conf as u128 * 10_000 > self.price as u128 * MAX_CONFIDENCE_BPS as u128
+        {
+            return Err(QuizError::PriceUncertain.into());
+        }
+
+        // cents / 100 USD at price * 10^exponent USD per 10^9 lamports
+        let price = self.price as u128;
+        let scale = 7 - self.exponent as i64;
+        let lamports = if scale >= 0 {
+            10u128
+                .checked_pow(scale as u32)
+                .and_then(|scale| (cents as u128).checked_mul(scale))
+                .map(|value| value.div_ceil(price))
+        } else {
+            10u128
+                .checked_pow(-scale as u32)
+                .and_then(|scale| price.checked_mul(scale))
+                .map(|price| (cents as u128).div_ceil(price))
+        };
+        lamports
+            .and_then(|lamports| u64::try_from(lamports).ok())
+            .ok_or(ProgramError::ArithmeticOverflow)
+    }
+}
@@ -0,0 +1,291 @@
+//! Sending quiz transactions until they land, so SDK users don't each write
+//! their own retry loop. A transaction is re-signed with a fresh blockhash
+//! only once its previous blockhash has expired and none of the earlier
+//! attempts landed, so an instruction never runs twice because its
+//! confirmation got lost. Failed RPC calls are retried with exponential
+//! backoff, failed transactions are returned as is.
+//!
+//! The loop talks to the node through `QuizRpc`, implemented for the
+//! nonblocking `RpcClient` and easy to fake in tests.
+
+use std::time::Duration;
+
+use borsh::BorshDeserialize;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    hash::Hash,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signature, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use thiserror::Error;
+
+use crate::{
+    client,
+    state::{QuizSession, QuizStatus},
+};
+
+#[derive(Debug, Error)]
+pub enum SendError {
+    #[error("RPC request failed: {0}")]
+    Rpc(String),
+    #[error("Transaction failed: {0}")]
+    Transaction(TransactionError),
+    #[error("Transaction did not land after {0} attempts")]
+    Exhausted(u32),
+}
+
+/// What the retry loop needs from an RPC node.
+#[allow(async_fn_in_trait)]
+pub trait QuizRpc {
+    async fn latest_blockhash(&self) -> Result<Hash, SendError>;
+    async fn is_blockhash_valid(&self, blockhash: &Hash) -> Result<bool, SendError>;
+    async fn send_transaction(&self, transaction: &Transaction) -> Result<Signature, SendError>;
+    /// None while the transaction hasn't landed at the client's commitment.
+    async fn signature_status(
+        &self,
+        signature: &Signature,
+    ) -> Result<Option<Result<(), TransactionError>>, SendError>;
+    async fn account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>, SendError>;
+}
+
+impl QuizRpc for RpcClient {
+    async fn latest_blockhash(&self) -> Result<Hash, SendError> {
+        self.get_latest_blockhash().await.map_err(rpc_error)
+    }
+
+    async fn is_blockhash_valid(&self, blockhash: &Hash) -> Result<bool, SendError> {
+        RpcClient::is_blockhash_valid(self, blockhash, self.commitment())
+            .await
+            .map_err(rpc_error)
+    }
+
+    async fn send_transaction(&self, transaction: &Transaction) -> Result<Signature, SendError> {
+        RpcClient::send_transaction(self, transaction)
+            .await
+            .map_err(rpc_error)
+    }
+
+    async fn signature_status(
+        &self,
+        signature: &Signature,
+    ) -> Result<Option<Result<(), TransactionError>>, SendError> {
+        self.get_signature_status(signature)
+            .await
+            .map_err(rpc_error)
+    }
+
+    async fn account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>, SendError> {
+        Ok(self
+            .get_account_with_commitment(address, self.commitment())
+            .await
+            .map_err(rpc_error)?
+            .value
+            .map(|account| account.data))
+    }
+}
+
+fn rpc_error(error: impl std::fmt::Display) -> SendError {
+    SendError::Rpc(error.to_string())
+}
+
+/// How hard `send_and_confirm` tries.
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    /// Blockhashes to sign with before giving up.
+    pub max_attempts: u32,
+    /// Wait after the first failed attempt, doubled after each one.
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    /// Wait between status checks, the transaction is rebroadcast each time.
+    pub poll_interval: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(8),
+            poll_interval: Duration::from_secs(2),
+        }
+    }
+}
+
+/// How one signed attempt ended.
+enum Attempt {
+    Landed(Signature),
+    /// Its blockhash expired, it can no longer land.
+    Expired,
+}
+
+/// Send `instructions` paid by `payer` and signed by `signers` too, until
+/// they land or `policy` runs out.
+pub async fn send_and_confirm(
+    rpc: &impl QuizRpc,
+    payer: &Keypair,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+    policy: &RetryPolicy,
+) -> Result<Signature, SendError> {
+    send_unless_applied(rpc, payer, instructions, signers, policy, None)
+        .await
+        .map(|signature| signature.expect("only skipped when checking a quiz"))
+}
+
+/// `CalculateScores` for every player of `quiz`, safe to call again after
+/// any failure. The quiz is read before each attempt, and None is returned
+/// without sending if it is already scored, e.g. by an earlier call whose
+/// confirmation was lost, as sending again would fail on the existing
+/// score accounts.
+pub async fn calculate_scores(
+    rpc: &impl QuizRpc,
+    program_id: &Pubkey,
+    host: &Keypair,
+    quiz: &Pubkey,
+    players: &[Pubkey],
+    policy: &RetryPolicy,
+) -> Result<Option<Signature>, SendError> {
+    let Some(quiz_data) = load_quiz(rpc, quiz).await? else {
+        return Err(SendError::Rpc(format!("quiz {} not found", quiz)));
+    };
+    let ix = client::calculate_scores_ix(
+        program_id,
+        &host.pubkey(),
+        quiz,
+        quiz_data.question_count,
+        players,
+    );
+    send_unless_applied(rpc, host, &[ix], &[], policy, Some(quiz)).await
+}
+
+async fn load_quiz(rpc: &impl QuizRpc, quiz: &Pubkey) -> Result<Option<QuizSession>, SendError> {
+    rpc.account_data(quiz)
+        .await?
+        .map(|data| QuizSession::try_from_slice(&data).map_err(rpc_error))
+        .transpose()
+}
+
+/// Whether `quiz` was scored, by this call or any other.
+async fn quiz_scored(rpc: &impl QuizRpc, quiz: Option<&Pubkey>) -> Result<bool, SendError> {
+    let Some(quiz) = quiz else {
+        return Ok(false);
+    };
+    Ok(load_quiz(rpc, quiz)
+        .await?
+        .is_some_and(|quiz_data| quiz_data.status == QuizStatus::Completed))
+}
+
+async fn send_unless_applied(
+    rpc: &impl QuizRpc,
+    payer: &Keypair,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+    policy: &RetryPolicy,
+    scored_quiz: Option<&Pubkey>,
+) -> Result<Option<Signature>, SendError> {
+    let mut all_signers = vec![payer];
+    all_signers.extend_from_slice(signers);
+    let mut sent = Vec::new();
+    let mut backoff = policy.initial_backoff;
+
+    for attempt in 1..=policy.max_attempts {
+        if attempt > 1 {
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(policy.max_backoff);
+        }
+
+        // Earlier attempts can't land anymore by now, check none did
+        // before signing another
+        let applied = match landed(rpc, &sent).await {
+            Ok(Some(signature)) => return Ok(Some(signature)),
+            Ok(None) => quiz_scored(rpc, scored_quiz).await,
+            Err(error) => Err(error),
+        };
+        match applied {
+            Ok(true) => return Ok(None),
+            Ok(false) => {}
+            Err(SendError::Transaction(error)) => return Err(SendError::Transaction(error)),
+            Err(_) => continue,
+        }
+
+        let Ok(blockhash) = rpc.latest_blockhash().await else {
+            continue;
+        };
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&payer.pubkey()),
+            &all_signers,
+            blockhash,
+        );
+        sent.push(transaction.signatures[0]);
+        match confirm(rpc, &transaction, policy).await? {
+            Attempt::Landed(signature) => return Ok(Some(signature)),
+            Attempt::Expired => {}
+        }
+    }
+    Err(SendError::Exhausted(policy.max_attempts))
+}
+
+/// The first of `sent` that landed, failing with its error if it failed.
+async fn landed(rpc: &impl QuizRpc, sent: &[Signature]) -> Result<Option<Signature>, SendError> {
+    for signature in sent {
+        match rpc.signature_status(signature).await? {
+            Some(Ok(())) => return Ok(Some(*signature)),
+            Some(Err(error)) => return Err(SendError::Transaction(error)),
+            None => {}
+        }
+    }
+    Ok(None)
+}
+
+/// Broadcast `transaction` until it lands or its blockhash expires. Gives
+/// up with an RPC error once the node fails `max_attempts` checks in a row,
+/// as the transaction may then still land and mustn't be signed again.
+async fn confirm(
+    rpc: &impl QuizRpc,
+    transaction: &Transaction,
+    policy: &RetryPolicy,
+) -> Result<Attempt, SendError> {
+    let mut failures = 0;
+    loop {
+        // A failed send may still have reached a leader, so keep checking
+        // the status either way. Resending the same signature is harmless.
+        let _ = rpc.send_transaction(transaction).await;
+        tokio::time::sleep(policy.poll_interval).await;
+        match check(rpc, transaction).await {
+            Ok(Some(attempt)) => return Ok(attempt),
+            Ok(None) => failures = 0,
+            Err(SendError::Rpc(error)) => {
+                failures += 1;
+                if failures >= policy.max_attempts {
+                    return Err(SendError::Rpc(error));
+                }
+            }
+            Err(error) => return Err(error),
+        }
+    }
+}
+
+/// How `transaction` ended, None while it may still land.
+async fn check(
+    rpc: &impl QuizRpc,
+    transaction: &Transaction,
+) -> Result<Option<Attempt>, SendError> {
+    let signature = transaction.signatures[0];
+    if let Some(landed) = landed(rpc, &[signature]).await? {
+        return Ok(Some(Attempt::Landed(landed)));
+    }
+    if rpc
+        .is_blockhash_valid(&transaction.message.recent_blockhash)
+        .await?
+    {
+        return Ok(None);
+    }
+    // It may have landed between the two checks
+    Ok(Some(match landed(rpc, &[signature]).await? {
+        Some(landed) => Attempt::Landed(landed),
+        None => Attempt::Expired,
+    }))
+}
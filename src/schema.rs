@@ -0,0 +1,82 @@
+//! Borsh schemas of the instruction and every account type, for indexers
+//! and clients in other languages to generate decoders from instead of
+//! reading layouts off `state.rs`. Each container holds the type's
+//! declaration and the definitions of every type it nests, down to
+//! primitives. Pubkeys are `[u8; 32]`.
+//!
+//! `QuizInstruction` is described as a Borsh enum, its one-byte variant tag
+//! being the compact encoding. The Anchor encoding replaces that tag with
+//! `ANCHOR_DISCRIMINATORS[tag]`. Accounts carry no discriminator.
+
+use borsh::{schema::BorshSchemaContainer, schema_container_of};
+
+use crate::{
+    instruction::QuizInstruction,
+    state::{
+        AdaptiveBank, AnswerSession, AnswerTree, CompressedSheet, DailyConfig, DailyEntry,
+        DailyLeaderboard, DailyQuiz, DailyStreak, DisputeFlag, HostStats, LeaderboardClaim,
+        LiveLeaderboard, NicknameClaim, PlayerAnswer, PlayerProfile, PlayerScore,
+        PrizeContribution, QuestionCommitment, QuestionDispute, QuestionExplanation, QuestionHint,
+        QuestionStats, QuestionTranslation, QuizAnalytics, QuizEventLog, QuizQuestion,
+        QuizReactions, QuizRegistry, QuizScoreboard, QuizSession, QuizTemplate, ScoreAudit,
+        SeasonArchive, Series, Team, TeamMember, Tournament,
+    },
+};
+
+/// Schema of `QuizInstruction`.
+pub fn instruction_schema() -> BorshSchemaContainer {
+    schema_container_of::<QuizInstruction>()
+}
+
+/// Schemas of every account the program owns, in `state.rs` order.
+pub fn account_schemas() -> Vec<BorshSchemaContainer> {
+    vec![
+        schema_container_of::<QuizQuestion>(),
+        schema_container_of::<QuizSession>(),
+        schema_container_of::<PlayerAnswer>(),
+        schema_container_of::<QuestionStats>(),
+        schema_container_of::<LeaderboardClaim>(),
+        schema_container_of::<PrizeContribution>(),
+        schema_container_of::<AdaptiveBank>(),
+        schema_container_of::<AnswerTree>(),
+        schema_container_of::<CompressedSheet>(),
+        schema_container_of::<HostStats>(),
+        schema_container_of::<QuizAnalytics>(),
+        schema_container_of::<QuestionDispute>(),
+        schema_container_of::<DisputeFlag>(),
+        schema_container_of::<QuizEventLog>(),
+        schema_container_of::<ScoreAudit>(),
+        schema_container_of::<QuestionHint>(),
+        schema_container_of::<QuestionCommitment>(),
+        schema_container_of::<QuestionExplanation>(),
+        schema_container_of::<QuestionTranslation>(),
+        schema_container_of::<AnswerSession>(),
+        schema_container_of::<PlayerScore>(),
+        schema_container_of::<LiveLeaderboard>(),
+        schema_container_of::<QuizReactions>(),
+        schema_container_of::<QuizScoreboard>(),
+        schema_container_of::<Team>(),
+        schema_container_of::<TeamMember>(),
+        schema_container_of::<QuizTemplate>(),
+        schema_container_of::<Tournament>(),
+        schema_container_of::<Series>(),
+        schema_container_of::<SeasonArchive>(),
+        schema_container_of::<DailyConfig>(),
+        schema_container_of::<DailyQuiz>(),
+        schema_container_of::<DailyEntry>(),
+        schema_container_of::<DailyStreak>(),
+        schema_container_of::<PlayerProfile>(),
+        schema_container_of::<NicknameClaim>(),
+        schema_container_of::<DailyLeaderboard>(),
+        schema_container_of::<QuizRegistry>(),
+    ]
+}
+
+/// The instruction schema followed by the account schemas, Borsh-encoded
+/// as a `Vec<BorshSchemaContainer>`. Borsh publishes the schema of that
+/// type too, so a generator in any language can read the file.
+pub fn export() -> Vec<u8> {
+    let mut schemas = vec![instruction_schema()];
+    schemas.extend(account_schemas());
+    borsh::to_vec(&schemas).expect("schemas serialize")
+}
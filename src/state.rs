@@ -1,54 +1,2256 @@
 use borsh::{BorshDeserialize, BorshSerialize};
-use solana_program::pubkey::Pubkey;
+use shank::{ShankAccount, ShankType};
+use solana_program::{hash::hashv, pubkey::Pubkey};
 
-#[derive(BorshSerialize, BorshDeserialize, Debug)]
+use crate::{cipher, merkle};
+
+/// Serde representation of pubkeys as base58 strings, which is what JSON
+/// consumers expect, rather than 32-element byte arrays.
+#[cfg(feature = "serde")]
+mod pubkey_string {
+    use std::str::FromStr;
+
+    use serde::{Deserialize, Deserializer, Serializer};
+    use solana_program::pubkey::Pubkey;
+
+    pub fn serialize<S: Serializer>(pubkey: &Pubkey, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(pubkey)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Pubkey, D::Error> {
+        let encoded = String::deserialize(deserializer)?;
+        Pubkey::from_str(&encoded).map_err(serde::de::Error::custom)
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct QuizQuestion {
     pub question_text: String,
     pub options: [String; 4],
     pub correct_answer_index: u8,
+    #[cfg_attr(feature = "serde", serde(with = "pubkey_string"))]
+    pub buzzer_winner: Pubkey, // default pubkey until a buzzer quiz is scored
+    pub multiplier: u8, // points a correct answer is worth, 1 to MAX_MULTIPLIER
+    pub void: VoidMode,
+    pub difficulty: u8, // 1 to MAX_DIFFICULTY, scales points in difficulty-weighted quizzes
 }
 
 impl QuizQuestion {
+    pub const SEED: &'static [u8] = b"quiz_question";
+    pub const OPTION_COUNT: u8 = 4;
+    /// Answer value a player sends to skip a question. Skips score nothing
+    /// and are never marked as wrong.
+    pub const SKIPPED: u8 = u8::MAX;
+    pub const MAX_MULTIPLIER: u8 = 3;
+    pub const MAX_DIFFICULTY: u8 = 5;
+
+    pub fn is_valid_answer(answer: u8) -> bool {
+        answer < Self::OPTION_COUNT || answer == Self::SKIPPED
+    }
+
     pub fn get_size(question_text: &str, options: &[String; 4]) -> usize {
         let question_text_size = question_text.len() + 4;
         let options_size: usize = options.iter().map(|s| s.len() + 4).sum();
-        question_text_size + options_size + 1
+        question_text_size + options_size + 1 + 32 + 1 + 1 + 1 // correct_answer_index + buzzer_winner + multiplier + void + difficulty
+    }
+
+    /// Points the question adds for any player, when voided.
+    pub fn void_points(&self) -> Option<u8> {
+        match self.void {
+            VoidMode::NotVoided => None,
+            VoidMode::AwardAll => Some(self.multiplier),
+            VoidMode::Excluded => Some(0),
+        }
+    }
+}
+
+/// The host-supplied content of a question, as imported in bulk.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq, ShankType)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QuestionData {
+    pub question_text: String,
+    pub options: [String; 4],
+    pub correct_answer_index: u8,
+}
+
+impl QuestionData {
+    /// What a curator signs for `ImportSignedQuestions` to accept
+    /// `questions` at consecutive indices from `first_index`. It names no
+    /// quiz, so one signed pack can be imported by any host.
+    pub fn pack_message(first_index: u8, questions: &[QuestionData]) -> [u8; 32] {
+        let encoded = borsh::to_vec(questions).expect("question data encodes");
+        hashv(&[b"question_pack", &[first_index], &encoded]).to_bytes()
+    }
+}
+
+/// How a disputed question counts after the host voids it.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq, ShankType)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VoidMode {
+    NotVoided,
+    /// Every player gets the question's points.
+    AwardAll,
+    /// The question counts for nobody.
+    Excluded,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq, ShankType)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum QuizMode {
+    /// Players answer the whole sheet at their own pace.
+    Async,
+    /// The host reveals questions one at a time and each has an answer window.
+    Live,
+    /// Live play where only the earliest correct answer to a question scores.
+    Buzzer,
+    /// Live play where a wrong or missed answer knocks the player out.
+    Elimination,
+    /// Two players wager the entry fee, the higher score takes the pot.
+    Duel,
+    /// Players answer on chain without delegation or prizes and grade
+    /// themselves straight away, as often as they like.
+    Practice,
+    /// Players answer one question at a time at their own pace, each served
+    /// by the quiz's `AdaptiveBank` at the difficulty their level calls for.
+    Adaptive,
+}
+
+impl QuizMode {
+    pub fn is_live(&self) -> bool {
+        matches!(
+            self,
+            QuizMode::Live | QuizMode::Buzzer | QuizMode::Elimination
+        )
+    }
+
+    /// Whether answers come in one `SubmitAnswer` at a time rather than as
+    /// a whole sheet.
+    pub fn answers_one_at_a_time(&self) -> bool {
+        self.is_live() || *self == QuizMode::Adaptive
+    }
+
+    /// Whether each player can be served their own subset of the questions,
+    /// which needs whole answer sheets graded after the quiz.
+    pub fn serves_question_subsets(&self) -> bool {
+        !self.answers_one_at_a_time() && *self != QuizMode::Practice
+    }
+}
+
+/// Where the share of a prize rank goes when its player scored below the
+/// quiz's prize threshold, or nobody reached the rank.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq, ShankType)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PrizeRemainder {
+    /// Paid back to the host.
+    ReturnToHost,
+    /// Left in the vault and counted in `rollover_lamports`.
+    RollOver,
+}
+
+/// Whether the host and the appointed scorer, who can read every question's
+/// correct answer, may join and answer their own quiz.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq, ShankType)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HostPlay {
+    /// Barred from quizzes with an entry fee or a prize pool.
+    BarredFromPrizes,
+    Barred,
+    Allowed,
+}
+
+/// How `CalculateScores`, `GradeAnswers` and every regrade turn a sheet into
+/// a score. Each mode builds on a correct answer earning its question's
+/// multiplier, scaled by its confidence in confidence-weighted quizzes.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq, ShankType)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ScoringMode {
+    /// Correct answers score, wrong ones earn nothing.
+    Classic,
+    /// Wrong answers take off what a correct one would have earned.
+    NegativeMarking,
+    /// Correct answers earn up to their points again, in proportion to the
+    /// time left between the quiz opening and its deadline. Without a
+    /// deadline this is the same as `Classic`.
+    SpeedWeighted,
+    /// Correct answers earn a point more for each correct answer directly
+    /// before them.
+    Streak,
+    /// The `Classic` total is squared.
+    Quadratic,
+}
+
+/// Phase of a quiz. Handlers only act in the phases they list and move the
+/// quiz forward through `can_transition_to`.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq, ShankType)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum QuizStatus {
+    /// Created, the host is still adding questions and settings.
+    Draft,
+    /// Set up and scheduled to open at `start_slot`.
+    Lobby,
+    /// Open for joins and answers.
+    Active,
+    /// No more joins or answers, waiting to be scored.
+    SubmissionsClosed,
+    /// Being graded into the scoreboard on the rollup.
+    Scoring,
+    /// Scores are final.
+    Completed,
+    /// Called off by the host before scoring.
+    Cancelled,
+}
+
+impl QuizStatus {
+    /// Settings and questions can still change.
+    pub fn is_setup(&self) -> bool {
+        matches!(self, QuizStatus::Draft | QuizStatus::Lobby)
+    }
+
+    /// Started and not finished yet.
+    pub fn is_running(&self) -> bool {
+        matches!(
+            self,
+            QuizStatus::Active | QuizStatus::SubmissionsClosed | QuizStatus::Scoring
+        )
+    }
+
+    pub fn is_finished(&self) -> bool {
+        matches!(self, QuizStatus::Completed | QuizStatus::Cancelled)
+    }
+
+    pub fn can_transition_to(&self, next: QuizStatus) -> bool {
+        use QuizStatus::*;
+        matches!(
+            (self, next),
+            (Draft, Lobby)
+                | (Lobby, Draft)
+                | (Draft | Lobby, Active)
+                | (Active, SubmissionsClosed)
+                | (Active | SubmissionsClosed, Scoring)
+                | (Active | SubmissionsClosed | Scoring, Completed)
+                | (Draft | Lobby | Active | SubmissionsClosed, Cancelled)
+        )
+    }
+}
+
+/// How answer sheets of a quiz are delegated to the ephemeral rollup, chosen
+/// by the host at init.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq, ShankType)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DelegationConfig {
+    pub commit_frequency_ms: u32,
+    #[cfg_attr(feature = "serde", serde(with = "pubkey_string"))]
+    pub validator: Pubkey, // Pubkey::default() lets players pick, otherwise the only one allowed
+    pub base_layer: bool, // play entirely on chain, nothing is delegated
+}
+
+impl DelegationConfig {
+    pub const SIZE: usize = 4 + 32 + 1; // commit_frequency_ms + validator + base_layer
+    pub const DEFAULT_COMMIT_FREQUENCY_MS: u32 = 1000;
+
+    pub fn validator(&self) -> Option<Pubkey> {
+        (self.validator != Pubkey::default()).then_some(self.validator)
     }
 }
 
-#[derive(BorshSerialize, BorshDeserialize, Debug)]
+impl Default for DelegationConfig {
+    fn default() -> Self {
+        Self {
+            commit_frequency_ms: Self::DEFAULT_COMMIT_FREQUENCY_MS,
+            validator: Pubkey::default(),
+            base_layer: false,
+        }
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct QuizSession {
+    #[cfg_attr(feature = "serde", serde(with = "pubkey_string"))]
     pub host: Pubkey,
+    pub quiz_id: u64, // lets one host run many quizzes
     pub question_count: u8,
     pub player_count: u8,
-    pub active: bool,
-    pub completed: bool,
+    pub status: QuizStatus,
+    pub allow_resubmission: bool,
+    pub refresh_slot_on_update: bool,
+    pub deadline_slot: u64, // 0 means no deadline
+    pub mode: QuizMode,
+    pub revealed_question: u8, // NO_QUESTION_REVEALED until the host reveals one
+    pub reveal_slot: u64,      // first slot the revealed question takes answers
+    pub answer_window_slots: u64, // 0 means the window stays open until the next reveal
+    pub rounds_revealed: u8,
+    pub entry_fee: u64,  // lamports paid into the quiz vault at join
+    pub prize_pool: u64, // lamports currently held for payouts in the vault
+    pub settled: bool,
+    #[cfg_attr(feature = "serde", serde(with = "pubkey_string"))]
+    pub tournament: Pubkey, // Pubkey::default() when not part of a tournament
+    pub tournament_round: u8,
+    pub delegation: DelegationConfig,
+    pub start_slot: u64, // scheduled start while in Lobby, then the slot the quiz opened at
+    pub join_grace_slots: u64, // 0 keeps joins open while the quiz is active
+    pub late_window_slots: u64, // async quizzes take answers this long after the deadline
+    pub late_slots_per_point: u64, // a late sheet loses a point per this many slots, 0 for none
+    pub confidence_weighted: bool,
+    pub close_slot: u64, // last slot the revealed question takes answers, 0 until the next reveal
+    pub dispute_window_slots: u64, // players can flag questions this long after scoring, 0 for never
+    pub scored_slot: u64,          // slot CalculateScores completed the quiz at
+    pub correction_count: u32,     // records in the quiz's score audit trail
+    pub event_log: bool,           // lifecycle events go to the quiz's `QuizEventLog`
+    pub leaderboard_root: [u8; 32], // Merkle root of the standings when scored off chain, zeroes otherwise
+    pub prize_split_bps: [u16; QuizSession::PRIZE_RANKS], // share of the prize pool for ranks 1, 2 and 3
+    #[cfg_attr(feature = "serde", serde(with = "pubkey_string"))]
+    pub scorer: Pubkey, // posts the leaderboard root against a bond, Pubkey::default() for the host
+    pub scorer_bond: u64, // lamports the scorer puts up, lost to a successful challenge
+    pub live_leaderboard: bool, // answers are graded into the quiz's `LiveLeaderboard` as they come in
+    pub referral_bps: u16,      // share of a referred player's entry fee owed to the referrer
+    pub min_prize_score: u8,    // score a ranked player needs to claim a prize, 0 for none
+    pub min_prize_score_bps: u16, // same as a share of the question count, the higher threshold applies
+    pub prize_remainder: PrizeRemainder,
+    pub forfeited_ranks: u8, // bitmap of prize ranks whose share went to the remainder
+    pub rollover_lamports: u64, // forfeited shares left in the vault under PrizeRemainder::RollOver
+    pub proportional_prizes: bool, // the pool is shared by score through ClaimScorePrize instead of by rank
+    pub total_prize_score: u32,    // scores at or above the prize threshold, summed when scored
+    pub claimed_prize_score: u32,  // of those, scores already paid out
+    pub claim_window_slots: u64, // prizes can be claimed this long after the dispute window, 0 for ever
+    pub vesting_min_lamports: u64, // prizes this large vest instead of paying out at claim
+    pub vesting_slots: u64,      // slots a vesting prize takes to unlock fully, 0 for no vesting
+    pub vesting_owed: u64,       // lamports of claimed prizes still vesting in the vault
+    pub entry_fee_usd_cents: u64, // entry fee in USD cents, replaces entry_fee when set
+    pub price_feed_id: [u8; 32], // Pyth feed pricing entry_fee_usd_cents in lamports
+    pub contributed_lamports: u64, // of prize_pool, contributions still refundable if cancelled
+    #[cfg_attr(feature = "serde", serde(with = "pubkey_string"))]
+    pub charity: Pubkey, // receives charity_bps of the pot before prizes are paid
+    pub charity_bps: u16,
+    pub charity_paid: bool,
+    pub host_play: HostPlay,
+    pub join_stake: u64, // lamports each player locks in the vault at join, refunded after the quiz
+    pub stakes_held: u64, // stakes in the vault not yet reclaimed
+    pub scoring_mode: ScoringMode,
+    pub difficulty_weighted: bool, // points are multiplied by each question's difficulty
+    pub subset_size: u8,           // questions each player is served, 0 serves every question
+    pub subset_seed: [u8; 32],     // drawn from with the player's pubkey to pick their subset
+    pub answers_revealed: bool,    // set by RevealAnswers, clients may show the correct answers
+    pub question_key_hash: [u8; 32], // commitment to the key question text is encrypted under, zero if plaintext
+    pub question_key: [u8; 32],      // published by RevealQuestions
+    #[cfg_attr(feature = "serde", serde(with = "pubkey_string"))]
+    pub curator: Pubkey, // signer of the questions imported with ImportSignedQuestions, zero if none
+    pub team_count: u8, // teams created, every one is ranked for team prizes
 }
 
 impl QuizSession {
-    pub const SIZE: usize = 32 + 1 + 1 + 1 + 1; // host + question_count + player_count + active + completed
+    pub const SEED: &'static [u8] = b"quiz_session";
+    pub const SIZE: usize = 32 // host
+        + 8 // quiz_id
+        + 1 // question_count
+        + 1 // player_count
+        + 1 // status
+        + 1 // allow_resubmission
+        + 1 // refresh_slot_on_update
+        + 8 // deadline_slot
+        + 1 // mode
+        + 1 // revealed_question
+        + 8 // reveal_slot
+        + 8 // answer_window_slots
+        + 1 // rounds_revealed
+        + 8 // entry_fee
+        + 8 // prize_pool
+        + 1 // settled
+        + 32 // tournament
+        + 1 // tournament_round
+        + DelegationConfig::SIZE // delegation
+        + 8 // start_slot
+        + 8 // join_grace_slots
+        + 8 // late_window_slots
+        + 8 // late_slots_per_point
+        + 1 // confidence_weighted
+        + 8 // close_slot
+        + 8 // dispute_window_slots
+        + 8 // scored_slot
+        + 4 // correction_count
+        + 1 // event_log
+        + 32 // leaderboard_root
+        + 2 * Self::PRIZE_RANKS // prize_split_bps
+        + 32 // scorer
+        + 8 // scorer_bond
+        + 1 // live_leaderboard
+        + 2 // referral_bps
+        + 1 // min_prize_score
+        + 2 // min_prize_score_bps
+        + 1 // prize_remainder
+        + 1 // forfeited_ranks
+        + 8 // rollover_lamports
+        + 1 // proportional_prizes
+        + 4 // total_prize_score
+        + 4 // claimed_prize_score
+        + 8 // claim_window_slots
+        + 8 // vesting_min_lamports
+        + 8 // vesting_slots
+        + 8 // vesting_owed
+        + 8 // entry_fee_usd_cents
+        + 32 // price_feed_id
+        + 8 // contributed_lamports
+        + 32 // charity
+        + 2 // charity_bps
+        + 1 // charity_paid
+        + 1 // host_play
+        + 8 // join_stake
+        + 8 // stakes_held
+        + 1 // scoring_mode
+        + 1 // difficulty_weighted
+        + 1 // subset_size
+        + 32 // subset_seed
+        + 1 // answers_revealed
+        + 32 // question_key_hash
+        + 32 // question_key
+        + 32 // curator
+        + 1; // team_count
+    pub const NO_QUESTION_REVEALED: u8 = u8::MAX;
+    pub const DUEL_PLAYERS: u8 = 2;
+    /// Ranks the prize pool is split between.
+    pub const PRIZE_RANKS: usize = 3;
+    /// Seed for the program-owned, data-less account holding the scorer's bond.
+    pub const SCORER_BOND_SEED: &'static [u8] = b"scorer_bond";
+    /// Seed for the program-owned, data-less vault holding entry fees.
+    pub const VAULT_SEED: &'static [u8] = b"quiz_vault";
+
+    /// A fresh draft quiz with default settings.
+    pub fn new(
+        host: Pubkey,
+        quiz_id: u64,
+        question_count: u8,
+        delegation: DelegationConfig,
+    ) -> Self {
+        Self {
+            host,
+            quiz_id,
+            question_count,
+            player_count: 0,
+            status: QuizStatus::Draft,
+            allow_resubmission: false,
+            refresh_slot_on_update: false,
+            deadline_slot: 0,
+            mode: QuizMode::Async,
+            revealed_question: Self::NO_QUESTION_REVEALED,
+            reveal_slot: 0,
+            answer_window_slots: 0,
+            rounds_revealed: 0,
+            entry_fee: 0,
+            prize_pool: 0,
+            settled: false,
+            tournament: Pubkey::default(),
+            tournament_round: 0,
+            delegation,
+            start_slot: 0,
+            join_grace_slots: 0,
+            late_window_slots: 0,
+            late_slots_per_point: 0,
+            confidence_weighted: false,
+            close_slot: 0,
+            dispute_window_slots: 0,
+            scored_slot: 0,
+            correction_count: 0,
+            event_log: false,
+            leaderboard_root: [0; 32],
+            prize_split_bps: [0; Self::PRIZE_RANKS],
+            scorer: Pubkey::default(),
+            scorer_bond: 0,
+            live_leaderboard: false,
+            referral_bps: 0,
+            min_prize_score: 0,
+            min_prize_score_bps: 0,
+            prize_remainder: PrizeRemainder::ReturnToHost,
+            forfeited_ranks: 0,
+            rollover_lamports: 0,
+            proportional_prizes: false,
+            total_prize_score: 0,
+            claimed_prize_score: 0,
+            claim_window_slots: 0,
+            vesting_min_lamports: 0,
+            vesting_slots: 0,
+            vesting_owed: 0,
+            entry_fee_usd_cents: 0,
+            price_feed_id: [0; 32],
+            contributed_lamports: 0,
+            charity: Pubkey::default(),
+            charity_bps: 0,
+            charity_paid: false,
+            host_play: HostPlay::BarredFromPrizes,
+            join_stake: 0,
+            stakes_held: 0,
+            scoring_mode: ScoringMode::Classic,
+            difficulty_weighted: false,
+            subset_size: 0,
+            subset_seed: [0; 32],
+            answers_revealed: false,
+            question_key_hash: [0; 32],
+            question_key: [0; 32],
+            curator: Pubkey::default(),
+            team_count: 0,
+        }
+    }
+
+    /// Whether answers are no longer taken, counting the late window.
+    pub fn deadline_passed(&self, slot: u64) -> bool {
+        self.deadline_slot != 0 && slot > self.final_deadline_slot()
+    }
+
+    /// Last slot answers are taken at, 0 without a deadline.
+    pub fn final_deadline_slot(&self) -> u64 {
+        match (self.deadline_slot, self.mode) {
+            (0, _) => 0,
+            (deadline_slot, QuizMode::Async) => {
+                deadline_slot.saturating_add(self.late_window_slots)
+            }
+            (deadline_slot, _) => deadline_slot,
+        }
+    }
+
+    /// Points deducted from a sheet last submitted at `submitted_slot`.
+    pub fn late_penalty(&self, submitted_slot: u64) -> u32 {
+        if self.deadline_slot == 0
+            || self.late_slots_per_point == 0
+            || submitted_slot <= self.deadline_slot
+        {
+            return 0;
+        }
+        let late_slots = submitted_slot - self.deadline_slot;
+        late_slots
+            .div_ceil(self.late_slots_per_point)
+            .min(u32::MAX as u64) as u32
+    }
+
+    /// Whether players can still flag questions of the scored quiz.
+    pub fn dispute_window_open(&self, slot: u64) -> bool {
+        self.status == QuizStatus::Completed
+            && self.dispute_window_slots != 0
+            && slot <= self.scored_slot.saturating_add(self.dispute_window_slots)
+    }
+
+    /// Lowest score that still wins a ranked prize.
+    pub fn prize_threshold(&self) -> u32 {
+        let share = (self.served_question_count() as u32 * self.min_prize_score_bps as u32)
+            .div_ceil(10_000);
+        (self.min_prize_score as u32).max(share)
+    }
+
+    /// Points `score` adds to the proportional prize total, 0 below the
+    /// prize threshold.
+    pub fn prize_points(&self, score: u32) -> u32 {
+        if score >= self.prize_threshold() {
+            score
+        } else {
+            0
+        }
+    }
+
+    /// Lamports a claim of `points` pays after `claimed_prize_score` points
+    /// were paid. Shares are cut from the running total, so the rounding
+    /// dust lands on later claims and the pool pays out exactly.
+    pub fn proportional_prize(&self, points: u32) -> u64 {
+        let paid_up_to = |points: u32| {
+            (self.prize_pool as u128 * points as u128)
+                .checked_div(self.total_prize_score as u128)
+                .unwrap_or(0) as u64
+        };
+        paid_up_to(self.claimed_prize_score + points) - paid_up_to(self.claimed_prize_score)
+    }
+
+    pub fn rank_forfeited(&self, rank: u32) -> bool {
+        (1..=Self::PRIZE_RANKS as u32).contains(&rank)
+            && self.forfeited_ranks & (1 << (rank - 1)) != 0
+    }
+
+    /// Whether winners of the scored quiz can still collect. Claims close
+    /// `claim_window_slots` after the dispute window, never when it's 0.
+    pub fn claim_window_open(&self, slot: u64) -> bool {
+        self.claim_window_slots == 0
+            || slot
+                <= self
+                    .scored_slot
+                    .saturating_add(self.dispute_window_slots)
+                    .saturating_add(self.claim_window_slots)
+    }
+
+    /// Whether a prize of `lamports` vests rather than paying out at claim.
+    pub fn vests(&self, lamports: u64) -> bool {
+        self.vesting_slots != 0 && lamports >= self.vesting_min_lamports
+    }
+
+    /// Lamports of a cancelled quiz's pool refunded for a contribution of
+    /// `lamports`, the pool shared pro-rata between what is still owed.
+    /// Cut from the running totals like `proportional_prize`.
+    pub fn contribution_refund(&self, lamports: u64) -> u64 {
+        (self.prize_pool as u128 * lamports as u128)
+            .checked_div(self.contributed_lamports as u128)
+            .unwrap_or(0) as u64
+    }
+
+    /// Whether prizes wait on the charity being paid its share.
+    pub fn charity_unpaid(&self) -> bool {
+        self.charity_bps != 0 && !self.charity_paid
+    }
+
+    /// Whether `player` is the host or scorer and barred from playing.
+    pub fn barred_from_playing(&self, player: &Pubkey) -> bool {
+        let barred = match self.host_play {
+            HostPlay::BarredFromPrizes => self.charges_entry_fee() || self.prize_pool > 0,
+            HostPlay::Barred => true,
+            HostPlay::Allowed => false,
+        };
+        barred && (*player == self.host || *player == self.scorer)
+    }
+
+    /// Whether players pay to join, in lamports or in USD.
+    pub fn charges_entry_fee(&self) -> bool {
+        self.entry_fee > 0 || self.entry_fee_usd_cents > 0
+    }
+
+    /// Whether joining moves lamports into the vault, as a fee or a stake.
+    pub fn pays_to_join(&self) -> bool {
+        self.charges_entry_fee() || self.join_stake > 0
+    }
+
+    pub fn join_window_open(&self, slot: u64) -> bool {
+        self.join_grace_slots == 0 || slot <= self.start_slot.saturating_add(self.join_grace_slots)
+    }
+
+    /// Whether the revealed question is `question_index` and `slot` falls in
+    /// its window. Windows are fixed when the question is revealed.
+    pub fn answer_window_open(&self, question_index: u8, slot: u64) -> bool {
+        self.revealed_question == question_index
+            && slot >= self.reveal_slot
+            && (self.close_slot == 0 || slot <= self.close_slot)
+    }
+
+    /// What the points of `question` are multiplied by for its difficulty.
+    pub fn difficulty_factor(&self, question: &QuizQuestion) -> u32 {
+        if self.difficulty_weighted {
+            question.difficulty as u32
+        } else {
+            1
+        }
+    }
+
+    /// Extra points a correct answer worth `points` earns under
+    /// `ScoringMode::SpeedWeighted` for landing at `slot`, the share of the
+    /// quiz's time still left then.
+    pub fn speed_bonus(&self, points: i32, slot: u64) -> i32 {
+        let span = self.deadline_slot.saturating_sub(self.start_slot);
+        if self.deadline_slot == 0 || span == 0 {
+            return 0;
+        }
+        let left = self.deadline_slot.saturating_sub(slot).min(span);
+        (points as i128 * left as i128 / span as i128) as i32
+    }
+
+    /// Whether question text is still encrypted under a key the host hasn't
+    /// published yet, see `cipher`.
+    pub fn questions_hidden(&self) -> bool {
+        self.question_key_hash != [0; 32]
+            && cipher::key_hash(&self.question_key) != self.question_key_hash
+    }
+
+    /// Questions each player answers, their whole subset if the quiz serves
+    /// one.
+    pub fn served_question_count(&self) -> u8 {
+        if self.subset_size == 0 {
+            self.question_count
+        } else {
+            self.subset_size
+        }
+    }
+
+    /// The questions `player` is served, in the order their sheet answers
+    /// them. Every question in order unless the quiz serves subsets, else
+    /// `subset_size` of them shuffled by hashes of the subset seed and the
+    /// player, so anyone can recompute a player's subset.
+    pub fn question_subset(&self, player: &Pubkey) -> Vec<u8> {
+        let mut order: Vec<u8> = (0..self.question_count).collect();
+        if self.subset_size == 0 {
+            return order;
+        }
+        for draw in 0..self.subset_size.min(self.question_count) {
+            let hash = hashv(&[&self.subset_seed, player.as_ref(), &[draw]]).to_bytes();
+            let left = (self.question_count - draw) as u64;
+            let pick = u64::from_le_bytes(hash[..8].try_into().unwrap()) % left;
+            order.swap(draw as usize, draw as usize + pick as usize);
+        }
+        order.truncate(self.subset_size as usize);
+        order
+    }
 }
 
-#[derive(BorshSerialize, BorshDeserialize, Debug)]
+#[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PlayerAnswer {
+    #[cfg_attr(feature = "serde", serde(with = "pubkey_string"))]
     pub player: Pubkey,
     pub answers: Vec<u8>,
+    pub answered: Vec<u8>,      // bitmap, one bit per question
+    pub answer_slots: Vec<u64>, // slot each answer was recorded at
+    pub eliminated: bool,
+    pub rounds_survived: u8,
+    pub joined: bool,
+    pub submitted: bool,
+    pub submitted_slot: u64,
+    pub committed: bool, // set when the sheet comes back from the rollup, answers are final
+    pub confidence: Vec<u8>, // 1-3 per answer, only weighs in confidence-weighted quizzes
+    pub hints: Vec<u8>,  // bitmap, one bit per question whose hint was bought
+    pub hint_penalty: u8, // points taken off at grading for bought hints
+    pub live_score: u32, // running points shown on the quiz's `LiveLeaderboard`
+    pub last_reaction_slot: u64, // slot of the player's last `SendReaction`, 0 for none
+    #[cfg_attr(feature = "serde", serde(with = "pubkey_string"))]
+    pub referrer: Pubkey, // Pubkey::default() if the player wasn't referred
+    pub referral_cut: u64, // lamports of the entry fee owed to the referrer until paid
+    pub stake: u64,      // lamports locked at join until reclaimed
+    pub adaptive_level: u8, // difficulty the next question is served at in adaptive quizzes
+    pub relay_nonce: u64, // nonce of the last relayed submission, the next must be higher
 }
 
 impl PlayerAnswer {
+    pub const SEED: &'static [u8] = b"player_answer";
+    pub const MAX_CONFIDENCE: u8 = 3;
+
+    pub fn new(player: Pubkey, answer_count: usize) -> Self {
+        Self {
+            player,
+            answers: vec![0; answer_count],
+            answered: vec![0; Self::bitmap_len(answer_count)],
+            answer_slots: vec![0; answer_count],
+            eliminated: false,
+            rounds_survived: 0,
+            joined: true,
+            submitted: false,
+            submitted_slot: 0,
+            committed: false,
+            confidence: vec![1; answer_count],
+            hints: vec![0; Self::bitmap_len(answer_count)],
+            hint_penalty: 0,
+            live_score: 0,
+            last_reaction_slot: 0,
+            referrer: Pubkey::default(),
+            referral_cut: 0,
+            stake: 0,
+            adaptive_level: 1,
+            relay_nonce: 0,
+        }
+    }
+
     pub fn get_size(answer_count: usize) -> usize {
         32 + // player pubkey
         4 + // vec length
-        answer_count // u8 for each answer
+        answer_count + // u8 for each answer
+        4 + // bitmap length
+        Self::bitmap_len(answer_count) + // answered bitmap
+        4 + // answer slots length
+        answer_count * 8 + // u64 slot for each answer
+        1 + // eliminated
+        1 + // rounds_survived
+        1 + // joined
+        1 + // submitted
+        8 + // submitted_slot
+        1 + // committed
+        4 + // confidence length
+        answer_count + // u8 confidence for each answer
+        4 + // hints bitmap length
+        Self::bitmap_len(answer_count) + // hints bitmap
+        1 + // hint_penalty
+        4 + // live_score
+        8 + // last_reaction_slot
+        32 + // referrer
+        8 + // referral_cut
+        8 + // stake
+        1 + // adaptive_level
+        8 // relay_nonce
+    }
+
+    /// What a player signs for a relayer to send `SubmitRelayedAnswers`
+    /// with `answers` and `confidence` to `quiz` on their behalf.
+    pub fn relay_message(quiz: &Pubkey, answers: &[u8], confidence: &[u8], nonce: u64) -> [u8; 32] {
+        hashv(&[
+            b"relayed_answers",
+            quiz.as_ref(),
+            &(answers.len() as u32).to_le_bytes(),
+            answers,
+            confidence,
+            &nonce.to_le_bytes(),
+        ])
+        .to_bytes()
+    }
+
+    pub fn bitmap_len(answer_count: usize) -> usize {
+        answer_count.div_ceil(8)
+    }
+
+    /// Whether the answers, bitmap and slots agree on the answer count.
+    pub fn is_well_formed(&self) -> bool {
+        self.answer_slots.len() == self.answers.len()
+            && self.confidence.len() == self.answers.len()
+            && self.answered.len() == Self::bitmap_len(self.answers.len())
+            && self.hints.len() == Self::bitmap_len(self.answers.len())
+    }
+
+    pub fn has_hint(&self, index: usize) -> bool {
+        self.hints[index / 8] & (1 << (index % 8)) != 0
+    }
+
+    pub fn unlock_hint(&mut self, index: usize) {
+        self.hints[index / 8] |= 1 << (index % 8);
+    }
+
+    pub fn is_answered(&self, index: usize) -> bool {
+        self.answered[index / 8] & (1 << (index % 8)) != 0
+    }
+
+    pub fn mark_answered(&mut self, index: usize) {
+        self.answered[index / 8] |= 1 << (index % 8);
+    }
+
+    /// Move the adaptive level up a difficulty after a right answer and down
+    /// one after a wrong one, skips leave it where it is.
+    pub fn adjust_adaptive_level(&mut self, answer: u8, correct_answer_index: u8) {
+        self.adaptive_level = if answer == correct_answer_index {
+            (self.adaptive_level + 1).min(QuizQuestion::MAX_DIFFICULTY)
+        } else if answer == QuizQuestion::SKIPPED {
+            self.adaptive_level
+        } else {
+            self.adaptive_level.saturating_sub(1).max(1)
+        };
+    }
+
+    pub fn answered_count(&self) -> usize {
+        (0..self.answers.len())
+            .filter(|&index| self.is_answered(index))
+            .count()
+    }
+
+    pub fn skipped_count(&self) -> usize {
+        (0..self.answers.len())
+            .filter(|&index| {
+                self.is_answered(index) && self.answers[index] == QuizQuestion::SKIPPED
+            })
+            .count()
+    }
+}
+
+/// How the room answered one question, at `[b"question_stats", quiz,
+/// question_index]`. Tallied from every answer sheet when scores are
+/// calculated.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default, ShankAccount)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QuestionStats {
+    #[cfg_attr(feature = "serde", serde(with = "pubkey_string"))]
+    pub quiz: Pubkey,
+    pub question_index: u8,
+    pub option_counts: [u32; 4], // players who picked each option
+    pub skipped: u32,
+    pub unanswered: u32,
+}
+
+impl QuestionStats {
+    pub const SEED: &'static [u8] = b"question_stats";
+    pub const SIZE: usize = 32 // quiz
+        + 1 // question_index
+        + 4 * 4 // option_counts
+        + 4 // skipped
+        + 4; // unanswered
+
+    /// Players whose sheet was graded for this question.
+    pub fn player_count(&self) -> u32 {
+        self.option_counts.iter().sum::<u32>() + self.skipped + self.unanswered
+    }
+
+    /// Count one sheet's answer to this question.
+    pub fn record(&mut self, sheet: &PlayerAnswer) {
+        let index = self.question_index as usize;
+        if index >= sheet.answers.len() || !sheet.is_answered(index) {
+            self.unanswered += 1;
+        } else if sheet.answers[index] == QuizQuestion::SKIPPED {
+            self.skipped += 1;
+        } else if let Some(count) = self.option_counts.get_mut(sheet.answers[index] as usize) {
+            *count += 1;
+        }
+    }
+}
+
+/// A `(player, score, rank)` leaf of a posted leaderboard root with its
+/// `merkle::proof`, as challenges cite it.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq, ShankType)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PostedStanding {
+    #[cfg_attr(feature = "serde", serde(with = "pubkey_string"))]
+    pub player: Pubkey,
+    pub score: u32,
+    pub rank: u32,
+    pub proof: Vec<[u8; 32]>,
+}
+
+/// A prize paid out against a Merkle leaderboard, or by score with `rank` 0,
+/// at `[b"leaderboard_claim", quiz, player]`. Its existence stops a second
+/// claim. A vesting prize unlocks linearly from `vesting_start_slot` and is
+/// released with `ClaimVested`.
+#[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LeaderboardClaim {
+    #[cfg_attr(feature = "serde", serde(with = "pubkey_string"))]
+    pub player: Pubkey,
+    pub rank: u32,
+    pub lamports: u64,
+    pub vesting_start_slot: u64,
+    pub vesting_slots: u64, // 0 when the prize was paid at claim
+    pub released: u64,      // lamports paid to the player so far
+}
+
+impl LeaderboardClaim {
+    pub const SEED: &'static [u8] = b"leaderboard_claim";
+    pub const SIZE: usize = 32 // player
+        + 4 // rank
+        + 8 // lamports
+        + 8 // vesting_start_slot
+        + 8 // vesting_slots
+        + 8; // released
+
+    /// Lamports unlocked by `slot` and not yet released.
+    pub fn releasable(&self, slot: u64) -> u64 {
+        let elapsed = slot.saturating_sub(self.vesting_start_slot);
+        let unlocked = if elapsed >= self.vesting_slots {
+            self.lamports
+        } else {
+            (self.lamports as u128 * elapsed as u128 / self.vesting_slots as u128) as u64
+        };
+        unlocked.saturating_sub(self.released)
+    }
+}
+
+/// What a contributor put into a quiz's prize pool, at
+/// `[b"prize_contribution", quiz, contributor]`. Closed when refunded after
+/// the quiz is cancelled.
+#[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PrizeContribution {
+    #[cfg_attr(feature = "serde", serde(with = "pubkey_string"))]
+    pub contributor: Pubkey,
+    pub lamports: u64,
+}
+
+impl PrizeContribution {
+    pub const SEED: &'static [u8] = b"prize_contribution";
+    pub const SIZE: usize = 32 + 8; // contributor + lamports
+}
+
+/// The difficulty of every question of an adaptive quiz, at
+/// `[b"adaptive_bank", quiz]`, so questions are served without loading each
+/// question PDA. A snapshot taken before the quiz starts.
+#[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AdaptiveBank {
+    #[cfg_attr(feature = "serde", serde(with = "pubkey_string"))]
+    pub quiz: Pubkey,
+    pub questions_per_player: u8,
+    pub difficulties: Vec<u8>, // by question index
+}
+
+impl AdaptiveBank {
+    pub const SEED: &'static [u8] = b"adaptive_bank";
+
+    pub fn get_size(question_count: usize) -> usize {
+        32 + // quiz
+        1 + // questions_per_player
+        4 + // vec length
+        question_count // difficulty of each question
+    }
+
+    /// The question `sheet` is served next: the first unanswered one at the
+    /// sheet's adaptive level, else at the nearest level, easier before
+    /// harder. None once the player has answered their share.
+    pub fn next_question(&self, sheet: &PlayerAnswer) -> Option<u8> {
+        if sheet.answered_count() >= self.questions_per_player as usize {
+            return None;
+        }
+        let level = sheet.adaptive_level;
+        (0..self.difficulties.len().min(sheet.answers.len()))
+            .filter(|&index| !sheet.is_answered(index))
+            .min_by_key(|&index| {
+                let difficulty = self.difficulties[index];
+                (difficulty.abs_diff(level), difficulty > level, index)
+            })
+            .map(|index| index as u8)
+    }
+}
+
+/// An answer sheet kept as a leaf of the quiz's `AnswerTree` instead of a
+/// `PlayerAnswer` account.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq, ShankType)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CompressedAnswers {
+    #[cfg_attr(feature = "serde", serde(with = "pubkey_string"))]
+    pub player: Pubkey,
+    pub answers: Vec<u8>,
+    pub confidence: Vec<u8>, // 1 per answer unless the quiz is confidence-weighted
+    pub submitted_slot: u64,
+}
+
+/// Append-only Merkle tree of a quiz's compressed answer sheets, at
+/// `[b"answer_tree", quiz]`. Only the root and the right edge of the tree
+/// live on chain, indexers rebuild the leaves from
+/// `CompressedAnswersSubmitted` events.
+#[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AnswerTree {
+    #[cfg_attr(feature = "serde", serde(with = "pubkey_string"))]
+    pub quiz: Pubkey,
+    pub leaf_count: u32,
+    pub root: [u8; 32],
+    pub frontier: [[u8; 32]; merkle::TREE_DEPTH], // last complete left subtree at each height
+    pub graded_count: u32,                        // leaves graded into a `PlayerScore`
+}
+
+impl AnswerTree {
+    pub const SEED: &'static [u8] = b"answer_tree";
+    pub const SIZE: usize = 32 // quiz
+        + 4 // leaf_count
+        + 32 // root
+        + 32 * merkle::TREE_DEPTH // frontier
+        + 4; // graded_count
+
+    pub fn new(quiz: Pubkey) -> Self {
+        Self {
+            quiz,
+            leaf_count: 0,
+            root: merkle::padded_root(&[]),
+            frontier: [[0; 32]; merkle::TREE_DEPTH],
+            graded_count: 0,
+        }
+    }
+
+    /// Append a leaf and update the root, `None` once the tree is full.
+    pub fn append(&mut self, leaf: [u8; 32]) -> Option<u32> {
+        let index = self.leaf_count;
+        if index as u64 >= 1 << merkle::TREE_DEPTH {
+            return None;
+        }
+        let mut node = leaf;
+        let mut empty = [0; 32];
+        // The subtree holding the new leaf is complete while every lower
+        // bit of its index is set
+        let mut complete = true;
+        for height in 0..merkle::TREE_DEPTH {
+            if index >> height & 1 == 0 {
+                if complete {
+                    self.frontier[height] = node;
+                }
+                node = merkle::hash_pair(&node, &empty);
+                complete = false;
+            } else {
+                node = merkle::hash_pair(&self.frontier[height], &node);
+            }
+            empty = merkle::hash_pair(&empty, &empty);
+        }
+        self.root = node;
+        self.leaf_count += 1;
+        Some(index)
+    }
+}
+
+/// Where a player's compressed sheet sits in the quiz's answer tree, at
+/// `[b"compressed_sheet", quiz, player]`. Its existence stops the player
+/// submitting twice, and only the leaf it records can be graded.
+#[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CompressedSheet {
+    #[cfg_attr(feature = "serde", serde(with = "pubkey_string"))]
+    pub player: Pubkey,
+    pub leaf_index: u32,
+    pub leaf: [u8; 32], // `merkle::answer_leaf` of the submitted sheet
+}
+
+impl CompressedSheet {
+    pub const SEED: &'static [u8] = b"compressed_sheet";
+    pub const SIZE: usize = 32 // player
+        + 4 // leaf_index
+        + 32; // leaf
+}
+
+/// Running totals for everything a host has finalized or called off, at
+/// `[b"host_stats", host]`. Created the first time the host scores or
+/// cancels a quiz, or has a dispute upheld.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default, ShankAccount)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HostStats {
+    #[cfg_attr(feature = "serde", serde(with = "pubkey_string"))]
+    pub host: Pubkey,
+    pub quizzes_hosted: u32,      // quizzes scored with CalculateScores
+    pub players_served: u64,      // players who joined those quizzes
+    pub active_players: u64,      // of those, players who answered at least one question
+    pub quizzes_cancelled: u32,   // cancelled after they started
+    pub disputes_upheld: u32,     // questions voided after scoring and posted scores proven wrong
+    pub late_scorings: u32, // quizzes scored more than LATE_SCORING_SLOTS after their deadline
+    pub scoring_delay_slots: u64, // slots from deadline to scoring, summed over quizzes with a deadline
+}
+
+impl HostStats {
+    pub const SEED: &'static [u8] = b"host_stats";
+    pub const SIZE: usize = 32 // host
+        + 4 // quizzes_hosted
+        + 8 // players_served
+        + 8 // active_players
+        + 4 // quizzes_cancelled
+        + 4 // disputes_upheld
+        + 4 // late_scorings
+        + 8; // scoring_delay_slots
+    /// Scoring later than this after the deadline (about a day) holds up
+    /// payouts and counts against the host.
+    pub const LATE_SCORING_SLOTS: u64 = 216_000;
+
+    /// Average players per quiz, in hundredths.
+    pub fn average_players_x100(&self) -> u64 {
+        (self.players_served * 100)
+            .checked_div(self.quizzes_hosted as u64)
+            .unwrap_or(0)
+    }
+
+    /// Share of joined players who answered anything, in basis points.
+    pub fn participation_bps(&self) -> u64 {
+        (self.active_players * 10_000)
+            .checked_div(self.players_served)
+            .unwrap_or(0)
+    }
+
+    /// Share of the host's track record that went well, in basis points:
+    /// quizzes scored against those plus cancellations, upheld disputes and
+    /// late scorings. 0 for a host without history.
+    pub fn reputation_bps(&self) -> u64 {
+        let good = self.quizzes_hosted as u64;
+        let bad =
+            self.quizzes_cancelled as u64 + self.disputes_upheld as u64 + self.late_scorings as u64;
+        (good * 10_000).checked_div(good + bad).unwrap_or(0)
+    }
+}
+
+/// Aggregate results of a scored quiz, at `[b"quiz_analytics", quiz]`.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default, ShankAccount)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QuizAnalytics {
+    #[cfg_attr(feature = "serde", serde(with = "pubkey_string"))]
+    pub quiz: Pubkey,
+    pub player_count: u8,
+    pub average_score_x100: u32, // hundredths of a point
+    pub median_score_x100: u32,  // hundredths of a point
+    pub hardest_question: u8,    // fewest correct answers, lowest index on ties
+    pub easiest_question: u8,    // most correct answers, lowest index on ties
+    pub participation_bps: u16,  // share of questions answered or skipped, in basis points
+    pub correct_bps_by_difficulty: [u16; QuizQuestion::MAX_DIFFICULTY as usize], // share of sheets right on questions of difficulty 1 to 5, 0 with none
+}
+
+impl QuizAnalytics {
+    pub const SEED: &'static [u8] = b"quiz_analytics";
+    pub const SIZE: usize = 32 // quiz
+        + 1 // player_count
+        + 4 // average_score_x100
+        + 4 // median_score_x100
+        + 1 // hardest_question
+        + 1 // easiest_question
+        + 2 // participation_bps
+        + 2 * QuizQuestion::MAX_DIFFICULTY as usize; // correct_bps_by_difficulty
+
+    /// Summarize final `scores` and, for each question in order, its correct
+    /// answer index, difficulty and answer statistics.
+    pub fn compute(quiz: Pubkey, scores: &[u32], questions: &[(u8, u8, QuestionStats)]) -> Self {
+        let mut sorted = scores.to_vec();
+        sorted.sort_unstable();
+        let count = sorted.len() as u64;
+        let total: u64 = sorted.iter().map(|&score| score as u64).sum();
+        let median_x100 = match sorted.len() {
+            0 => 0,
+            len if len % 2 == 1 => sorted[len / 2] as u64 * 100,
+            len => (sorted[len / 2 - 1] as u64 + sorted[len / 2] as u64) * 50,
+        };
+
+        let correct_counts: Vec<u32> = questions
+            .iter()
+            .map(|(correct, _, stats)| {
+                stats
+                    .option_counts
+                    .get(*correct as usize)
+                    .copied()
+                    .unwrap_or(0)
+            })
+            .collect();
+        let hardest = (0..correct_counts.len()).min_by_key(|&index| correct_counts[index]);
+        let easiest =
+            (0..correct_counts.len()).min_by_key(|&index| std::cmp::Reverse(correct_counts[index]));
+
+        let slots: u64 = questions
+            .iter()
+            .map(|(_, _, stats)| stats.player_count() as u64)
+            .sum();
+        let answered: u64 = questions
+            .iter()
+            .map(|(_, _, stats)| (stats.player_count() - stats.unanswered) as u64)
+            .sum();
+
+        let mut correct_bps_by_difficulty = [0; QuizQuestion::MAX_DIFFICULTY as usize];
+        for (level, bps) in (1..).zip(correct_bps_by_difficulty.iter_mut()) {
+            let (correct, graded) = questions
+                .iter()
+                .zip(&correct_counts)
+                .filter(|((_, difficulty, _), _)| *difficulty == level)
+                .fold(
+                    (0u64, 0u64),
+                    |(correct, graded), ((_, _, stats), &count)| {
+                        (correct + count as u64, graded + stats.player_count() as u64)
+                    },
+                );
+            *bps = (correct * 10_000).checked_div(graded).unwrap_or(0) as u16;
+        }
+
+        Self {
+            quiz,
+            player_count: sorted.len() as u8,
+            average_score_x100: (total * 100).checked_div(count).unwrap_or(0) as u32,
+            median_score_x100: median_x100 as u32,
+            hardest_question: hardest.unwrap_or(0) as u8,
+            easiest_question: easiest.unwrap_or(0) as u8,
+            participation_bps: (answered * 10_000).checked_div(slots).unwrap_or(0) as u16,
+            correct_bps_by_difficulty,
+        }
+    }
+}
+
+/// Flags players raised against one question, at `[b"question_dispute",
+/// quiz, question_index]`.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default, ShankAccount)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QuestionDispute {
+    #[cfg_attr(feature = "serde", serde(with = "pubkey_string"))]
+    pub quiz: Pubkey,
+    pub question_index: u8,
+    pub reason_counts: [u32; 4], // flags per reason code
+}
+
+impl QuestionDispute {
+    pub const SEED: &'static [u8] = b"question_dispute";
+    pub const SIZE: usize = 32 + 1 + 4 * 4; // quiz + question_index + reason_counts
+
+    pub const REASON_WRONG_ANSWER: u8 = 0;
+    pub const REASON_AMBIGUOUS: u8 = 1;
+    pub const REASON_TYPO: u8 = 2;
+    pub const REASON_OTHER: u8 = 3;
+
+    pub fn flag_count(&self) -> u32 {
+        self.reason_counts.iter().sum()
+    }
+}
+
+/// One player's flag on a question, at `[b"dispute_flag", quiz,
+/// question_index, player]`. Its existence stops the player flagging twice.
+#[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DisputeFlag {
+    #[cfg_attr(feature = "serde", serde(with = "pubkey_string"))]
+    pub player: Pubkey,
+    pub reason_code: u8,
+}
+
+impl DisputeFlag {
+    pub const SEED: &'static [u8] = b"dispute_flag";
+    pub const SIZE: usize = 32 + 1; // player + reason_code
+}
+
+/// One entry of a quiz's event log.
+#[derive(
+    BorshSerialize, BorshDeserialize, Debug, Clone, Copy, Default, PartialEq, Eq, ShankType,
+)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LoggedEvent {
+    pub kind: u8, // one of the `KIND_*` codes
+    pub slot: u64,
+    #[cfg_attr(feature = "serde", serde(with = "pubkey_string"))]
+    pub actor: Pubkey, // the player, or the host for reveals and finalization
+    pub value: u8, // depends on the kind, see the `KIND_*` codes
+}
+
+impl LoggedEvent {
+    pub const SIZE: usize = 1 + 8 + 32 + 1; // kind + slot + actor + value
+
+    /// A player joined, `value` is unused.
+    pub const KIND_JOINED: u8 = 0;
+    /// A player submitted answers, `value` is how many questions they have answered.
+    pub const KIND_SUBMITTED: u8 = 1;
+    /// The host revealed a question, `value` is its index.
+    pub const KIND_REVEALED: u8 = 2;
+    /// The host scored the quiz, `value` is the player count.
+    pub const KIND_FINALIZED: u8 = 3;
+}
+
+/// The last `CAPACITY` lifecycle events of a base layer quiz, at
+/// `[b"event_log", quiz]`. Event `n` is written to `records[n % CAPACITY]`,
+/// overwriting the oldest one once the log is full.
+#[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QuizEventLog {
+    #[cfg_attr(feature = "serde", serde(with = "pubkey_string"))]
+    pub quiz: Pubkey,
+    pub total: u32, // events ever logged
+    pub records: [LoggedEvent; QuizEventLog::CAPACITY],
+}
+
+impl QuizEventLog {
+    pub const SEED: &'static [u8] = b"event_log";
+    pub const CAPACITY: usize = 32;
+    pub const SIZE: usize = 32 + 4 + LoggedEvent::SIZE * Self::CAPACITY; // quiz + total + records
+
+    pub fn push(&mut self, event: LoggedEvent) {
+        self.records[self.total as usize % Self::CAPACITY] = event;
+        self.total += 1;
+    }
+
+    /// The events still in the log, oldest first.
+    pub fn events(&self) -> impl Iterator<Item = &LoggedEvent> {
+        let total = self.total as usize;
+        let oldest = if total > Self::CAPACITY {
+            total % Self::CAPACITY
+        } else {
+            0
+        };
+        self.records
+            .iter()
+            .cycle()
+            .skip(oldest)
+            .take(total.min(Self::CAPACITY))
+    }
+
+    /// How many events were overwritten.
+    pub fn dropped(&self) -> u32 {
+        self.total.saturating_sub(Self::CAPACITY as u32)
+    }
+}
+
+/// One change to a recorded score.
+#[derive(
+    BorshSerialize, BorshDeserialize, Debug, Clone, Copy, Default, PartialEq, Eq, ShankType,
+)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScoreCorrection {
+    #[cfg_attr(feature = "serde", serde(with = "pubkey_string"))]
+    pub player: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "pubkey_string"))]
+    pub corrected_by: Pubkey,
+    pub slot: u64,
+    pub old_score: u32,
+    pub new_score: u32,
+    pub reason: u8, // one of the `REASON_*` codes
+}
+
+impl ScoreCorrection {
+    pub const SIZE: usize = 32 // player
+        + 32 // corrected_by
+        + 8 // slot
+        + 4 // old_score
+        + 4 // new_score
+        + 1; // reason
+
+    pub const REASON_REGRADE: u8 = 0;
+    pub const REASON_SCORING_ERROR: u8 = 1;
+    pub const REASON_MISCONDUCT: u8 = 2;
+    pub const REASON_OTHER: u8 = 3;
+}
+
+/// One page of a quiz's append-only score audit trail, at `[b"score_audit",
+/// quiz, page]` with the page as a little-endian u32. Record `n` of the
+/// trail lives on page `n / MAX_RECORDS`, so clients read pages 0, 1, 2, ...
+/// until one is missing.
+#[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScoreAudit {
+    #[cfg_attr(feature = "serde", serde(with = "pubkey_string"))]
+    pub quiz: Pubkey,
+    pub page: u32,
+    pub records: [ScoreCorrection; ScoreAudit::MAX_RECORDS],
+    pub record_count: u8,
+}
+
+impl ScoreAudit {
+    pub const SEED: &'static [u8] = b"score_audit";
+    pub const MAX_RECORDS: usize = 16;
+    pub const SIZE: usize = 32 // quiz
+        + 4 // page
+        + ScoreCorrection::SIZE * Self::MAX_RECORDS // records
+        + 1; // record_count
+
+    pub fn records(&self) -> &[ScoreCorrection] {
+        &self.records[..self.record_count as usize]
+    }
+}
+
+/// A hint the host attached to a question, at `[b"question_hint", quiz,
+/// question_index]`. The text is public account data like the question
+/// itself, buying it only records the cost against the player.
+#[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QuestionHint {
+    pub point_cost: u8,    // taken off the buyer's score at grading
+    pub lamport_cost: u64, // paid into the prize pool, base layer quizzes only
+    pub hint: String,
+}
+
+impl QuestionHint {
+    pub const SEED: &'static [u8] = b"question_hint";
+
+    pub fn get_size(hint: &str) -> usize {
+        1 + 8 + 4 + hint.len() // point_cost + lamport_cost + hint
+    }
+}
+
+/// The hash a blind quiz's host commits to a question with before the quiz
+/// starts, at `[b"question_commitment", quiz, question_index]`. The question
+/// account is only created when `RevealBlindQuestion` publishes content
+/// matching it, so nobody, validators included, can read it before then.
+#[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QuestionCommitment {
+    pub hash: [u8; 32],
+}
+
+impl QuestionCommitment {
+    pub const SEED: &'static [u8] = b"question_commitment";
+    pub const SIZE: usize = 32; // hash
+
+    /// The commitment to `question` at `question_index` of `quiz`, over its
+    /// Borsh encoding.
+    pub fn hash_question(quiz: &Pubkey, question_index: u8, question: &QuestionData) -> [u8; 32] {
+        let encoded = borsh::to_vec(question).expect("question data encodes");
+        hashv(&[quiz.as_ref(), &[question_index], &encoded]).to_bytes()
+    }
+}
+
+/// Why a question's answer is right, e.g. with a source link, at
+/// `[b"question_explanation", quiz, question_index]`. Public account data
+/// like the question, but clients keep it hidden until `RevealAnswers` sets
+/// the quiz's `answers_revealed`.
+#[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QuestionExplanation {
+    pub question_index: u8,
+    pub explanation: String,
+}
+
+impl QuestionExplanation {
+    pub const SEED: &'static [u8] = b"question_explanation";
+    pub const MAX_LEN: usize = 512;
+
+    pub fn get_size(explanation: &str) -> usize {
+        1 + 4 + explanation.len() // question_index + explanation
+    }
+}
+
+/// A question's text and options in another language, at
+/// `[b"question_translation", quiz, question_index, language]`. Options keep
+/// the order of the question account, which stays the only source for the
+/// correct answer and for grading.
+#[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QuestionTranslation {
+    pub question_index: u8,
+    pub language: [u8; 2], // ISO 639-1 code, lowercase ASCII
+    pub question_text: String,
+    pub options: [String; 4],
+}
+
+impl QuestionTranslation {
+    pub const SEED: &'static [u8] = b"question_translation";
+
+    pub fn get_size(question_text: &str, options: &[String; 4]) -> usize {
+        let options_size: usize = options.iter().map(|s| s.len() + 4).sum();
+        1 + 2 + question_text.len() + 4 + options_size // question_index + language + question_text + options
+    }
+
+    pub fn is_valid_language(language: &[u8; 2]) -> bool {
+        language.iter().all(u8::is_ascii_lowercase)
     }
 }
 
-#[derive(BorshSerialize, BorshDeserialize, Debug)]
+/// A temporary key the player lets sign answers for one quiz, so live play
+/// doesn't need the wallet for every answer.
+#[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AnswerSession {
+    #[cfg_attr(feature = "serde", serde(with = "pubkey_string"))]
+    pub player: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "pubkey_string"))]
+    pub quiz: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "pubkey_string"))]
+    pub session_key: Pubkey,
+    pub expires_slot: u64, // last slot the key can sign, capped at the quiz deadline
+}
+
+impl AnswerSession {
+    pub const SEED: &'static [u8] = b"answer_session";
+    pub const SIZE: usize = 32 + 32 + 32 + 8; // player + quiz + session_key + expires_slot
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PlayerScore {
+    #[cfg_attr(feature = "serde", serde(with = "pubkey_string"))]
     pub player: Pubkey,
-    pub score: u8,
+    pub score: u32,
+    #[cfg_attr(feature = "serde", serde(with = "pubkey_string"))]
+    pub quiz: Pubkey, // lets RPC filters find every score of a quiz
+    pub late_penalty: u32, // points already taken off `score` for submitting late
+    pub skipped: u8,
 }
 
 impl PlayerScore {
-    pub const SIZE: usize = 32 + 1; // 33 bytes
+    pub const SEED: &'static [u8] = b"player_score";
+    pub const SIZE: usize = 32 + 4 + 32 + 4 + 1; // player + score + quiz + late_penalty + skipped
+    /// Size of scores written before `score` and `late_penalty` were widened
+    /// from u8s, which `MigratePlayerScore` brings up to the current layout.
+    pub const LEGACY_SIZE: usize = 32 + 1 + 32 + 1 + 1;
+
+    /// Read a score in the layout before it was widened.
+    pub fn unpack_legacy(data: &[u8]) -> Option<Self> {
+        if data.len() != Self::LEGACY_SIZE {
+            return None;
+        }
+        Some(Self {
+            player: Pubkey::new_from_array(data[..32].try_into().ok()?),
+            score: data[32] as u32,
+            quiz: Pubkey::new_from_array(data[33..65].try_into().ok()?),
+            late_penalty: data[65] as u32,
+            skipped: data[66],
+        })
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, Default, ShankType)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScoreEntry {
+    #[cfg_attr(feature = "serde", serde(with = "pubkey_string"))]
+    pub player: Pubkey,
+    pub score: u32,
+}
+
+impl ScoreEntry {
+    pub const SIZE: usize = 32 + 4; // player + score
+}
+
+/// Running standings of a live quiz at `[b"live_leaderboard", quiz]`, for
+/// spectators to poll or subscribe to. Every answer is graded into it as it
+/// comes in. Entries only move on their own player's answers, and buzzer
+/// points and score corrections settle at grading.
+#[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LiveLeaderboard {
+    #[cfg_attr(feature = "serde", serde(with = "pubkey_string"))]
+    pub quiz: Pubkey,
+    pub graded_count: u32,  // answers graded, a changed answer counts once
+    pub correct_count: u32, // graded answers that are currently correct
+    pub top: [ScoreEntry; LiveLeaderboard::TOP], // highest scores first
+    pub top_count: u8,
+}
+
+impl LiveLeaderboard {
+    pub const SEED: &'static [u8] = b"live_leaderboard";
+    pub const TOP: usize = 10;
+    pub const SIZE: usize = 32 // quiz
+        + 4 // graded_count
+        + 4 // correct_count
+        + ScoreEntry::SIZE * Self::TOP // top
+        + 1; // top_count
+
+    pub fn new(quiz: Pubkey) -> Self {
+        Self {
+            quiz,
+            graded_count: 0,
+            correct_count: 0,
+            top: [ScoreEntry::default(); Self::TOP],
+            top_count: 0,
+        }
+    }
+
+    pub fn top(&self) -> &[ScoreEntry] {
+        &self.top[..self.top_count as usize]
+    }
+
+    /// Move the player to `score`, taking the lowest entry's place when the
+    /// board is full and they beat it. Ties keep their current order.
+    pub fn record(&mut self, player: Pubkey, score: u32) {
+        let count = self.top_count as usize;
+        let position = match self.top[..count].iter().position(|e| e.player == player) {
+            Some(position) => position,
+            None if count < Self::TOP => {
+                self.top_count += 1;
+                count
+            }
+            None if score > self.top[count - 1].score => count - 1,
+            None => return,
+        };
+        self.top[position] = ScoreEntry { player, score };
+        self.top[..self.top_count as usize].sort_by_key(|e| std::cmp::Reverse(e.score));
+    }
+}
+
+/// Reaction counters of a quiz at `[b"quiz_reactions", quiz]`, bumped by
+/// players' `SendReaction` during play.
+#[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QuizReactions {
+    #[cfg_attr(feature = "serde", serde(with = "pubkey_string"))]
+    pub quiz: Pubkey,
+    pub cooldown_slots: u64, // slots a player waits between reactions
+    pub counts: [u32; QuizReactions::KINDS], // per emoji code
+    pub total: u32,
+}
+
+impl QuizReactions {
+    pub const SEED: &'static [u8] = b"quiz_reactions";
+    /// Emoji codes run from 0 to `KINDS - 1`, clients map them to emojis.
+    pub const KINDS: usize = 8;
+    pub const SIZE: usize = 32 + 8 + 4 * Self::KINDS + 4; // quiz + cooldown_slots + counts + total
+}
+
+/// Scores graded on the ephemeral rollup, committed to chain in one account
+/// instead of every answer sheet.
+#[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QuizScoreboard {
+    #[cfg_attr(feature = "serde", serde(with = "pubkey_string"))]
+    pub quiz: Pubkey,
+    pub entries: Vec<ScoreEntry>, // one slot per player joined at creation
+    pub entry_count: u8,
+    pub finalized: bool,
+}
+
+impl QuizScoreboard {
+    pub const SEED: &'static [u8] = b"quiz_scoreboard";
+
+    pub fn new(quiz: Pubkey, capacity: usize) -> Self {
+        Self {
+            quiz,
+            entries: vec![ScoreEntry::default(); capacity],
+            entry_count: 0,
+            finalized: false,
+        }
+    }
+
+    pub fn get_size(capacity: usize) -> usize {
+        32 + // quiz
+        4 + // vec length
+        ScoreEntry::SIZE * capacity + // entries
+        1 + // entry_count
+        1 // finalized
+    }
+
+    pub fn entries(&self) -> &[ScoreEntry] {
+        &self.entries[..self.entry_count as usize]
+    }
+
+    /// Insert or update a player's score, false when the board is full.
+    pub fn record(&mut self, player: Pubkey, score: u32) -> bool {
+        let count = self.entry_count as usize;
+        if let Some(entry) = self.entries[..count]
+            .iter_mut()
+            .find(|e| e.player == player)
+        {
+            entry.score = score;
+        } else if count < self.entries.len() {
+            self.entries[count] = ScoreEntry { player, score };
+            self.entry_count += 1;
+        } else {
+            return false;
+        }
+        true
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+pub struct Team {
+    pub quiz: Pubkey,
+    pub team_id: u8,
+    pub name: [u8; Team::MAX_NAME_LEN], // utf-8, zero padded
+    pub captain: Pubkey,
+    pub members: [Pubkey; Team::MAX_MEMBERS],
+    pub member_count: u8,
+    pub score: u32,
+    pub finalized: bool,
+}
+
+impl Team {
+    pub const SEED: &'static [u8] = b"quiz_team";
+    pub const MAX_NAME_LEN: usize = 32;
+    pub const MAX_MEMBERS: usize = 8;
+    pub const SIZE: usize = 32 // quiz
+        + 1 // team_id
+        + Self::MAX_NAME_LEN // name
+        + 32 // captain
+        + 32 * Self::MAX_MEMBERS // members
+        + 1 // member_count
+        + 4 // score
+        + 1; // finalized
+
+    pub fn members(&self) -> &[Pubkey] {
+        &self.members[..self.member_count as usize]
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+pub struct TeamMember {
+    pub player: Pubkey,
+    pub team_id: u8,
+}
+
+impl TeamMember {
+    pub const SEED: &'static [u8] = b"team_member";
+    pub const SIZE: usize = 32 + 1; // player + team_id
+}
+
+/// Scoring and submission settings a template hands to every quiz made
+/// from it.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq, ShankType)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+pub struct TemplateConfig {
+    pub mode: QuizMode,
+    pub answer_window_slots: u64,
+    pub allow_resubmission: bool,
+    pub refresh_slot_on_update: bool,
+    pub entry_fee: u64,
+    pub join_grace_slots: u64,
+    pub late_window_slots: u64,
+    pub late_slots_per_point: u64,
+    pub confidence_weighted: bool,
+    pub dispute_window_slots: u64,
+    pub prize_split_bps: [u16; QuizSession::PRIZE_RANKS],
+}
+
+impl TemplateConfig {
+    pub const SIZE: usize = 1 // mode
+        + 8 // answer_window_slots
+        + 1 // allow_resubmission
+        + 1 // refresh_slot_on_update
+        + 8 // entry_fee
+        + 8 // join_grace_slots
+        + 8 // late_window_slots
+        + 8 // late_slots_per_point
+        + 1 // confidence_weighted
+        + 8 // dispute_window_slots
+        + 2 * QuizSession::PRIZE_RANKS; // prize_split_bps
+
+    pub fn of(quiz: &QuizSession) -> Self {
+        Self {
+            mode: quiz.mode,
+            answer_window_slots: quiz.answer_window_slots,
+            allow_resubmission: quiz.allow_resubmission,
+            refresh_slot_on_update: quiz.refresh_slot_on_update,
+            entry_fee: quiz.entry_fee,
+            join_grace_slots: quiz.join_grace_slots,
+            late_window_slots: quiz.late_window_slots,
+            late_slots_per_point: quiz.late_slots_per_point,
+            confidence_weighted: quiz.confidence_weighted,
+            dispute_window_slots: quiz.dispute_window_slots,
+            prize_split_bps: quiz.prize_split_bps,
+        }
+    }
+
+    pub fn apply(&self, quiz: &mut QuizSession) {
+        quiz.mode = self.mode;
+        quiz.answer_window_slots = self.answer_window_slots;
+        quiz.allow_resubmission = self.allow_resubmission;
+        quiz.refresh_slot_on_update = self.refresh_slot_on_update;
+        quiz.entry_fee = self.entry_fee;
+        quiz.join_grace_slots = self.join_grace_slots;
+        quiz.late_window_slots = self.late_window_slots;
+        quiz.late_slots_per_point = self.late_slots_per_point;
+        quiz.confidence_weighted = self.confidence_weighted;
+        quiz.dispute_window_slots = self.dispute_window_slots;
+        quiz.prize_split_bps = self.prize_split_bps;
+    }
+}
+
+/// A published question set with its settings, at
+/// `[b"quiz_template", author, template_id]`. Its questions live at
+/// `[b"template_question", template, question_index]`.
+#[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+pub struct QuizTemplate {
+    pub author: Pubkey,
+    pub template_id: u64,
+    pub title: [u8; QuizTemplate::MAX_TITLE_LEN], // utf-8, zero padded
+    pub question_count: u8,
+    pub config: TemplateConfig,
+    pub public: bool, // anyone may create quizzes from it
+    pub allowed: [Pubkey; QuizTemplate::MAX_ALLOWED], // hosts besides the author, when not public
+    pub allowed_count: u8,
+    pub instance_count: u32, // quizzes created from it
+}
+
+impl QuizTemplate {
+    pub const SEED: &'static [u8] = b"quiz_template";
+    pub const QUESTION_SEED: &'static [u8] = b"template_question";
+    pub const MAX_TITLE_LEN: usize = 64;
+    pub const MAX_ALLOWED: usize = 8;
+    pub const SIZE: usize = 32 // author
+        + 8 // template_id
+        + Self::MAX_TITLE_LEN // title
+        + 1 // question_count
+        + TemplateConfig::SIZE // config
+        + 1 // public
+        + 32 * Self::MAX_ALLOWED // allowed
+        + 1 // allowed_count
+        + 4; // instance_count
+
+    pub fn allowed(&self) -> &[Pubkey] {
+        &self.allowed[..self.allowed_count as usize]
+    }
+
+    pub fn may_use(&self, host: &Pubkey) -> bool {
+        self.public || self.author == *host || self.allowed().contains(host)
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+pub struct Tournament {
+    pub organizer: Pubkey,
+    pub tournament_id: u64,
+    pub round_count: u8,
+    pub current_round: u8, // equals round_count once the bracket is decided
+    pub advance_count: u8, // players carried into the next round
+    pub rounds: [Pubkey; Tournament::MAX_ROUNDS], // quiz session per round
+    pub entrants: [Pubkey; Tournament::MAX_ENTRANTS], // allowed into current_round, empty for an open round
+    pub entrant_count: u8,
+    pub champion: Pubkey,
+    pub prize_pool: u64,
+    pub prize_claimed: bool,
+}
+
+impl Tournament {
+    pub const SEED: &'static [u8] = b"tournament";
+    /// Seed for the data-less vault holding the grand prize.
+    pub const VAULT_SEED: &'static [u8] = b"tournament_vault";
+    pub const MAX_ROUNDS: usize = 8;
+    pub const MAX_ENTRANTS: usize = 16;
+    pub const SIZE: usize = 32 // organizer
+        + 8 // tournament_id
+        + 1 // round_count
+        + 1 // current_round
+        + 1 // advance_count
+        + 32 * Self::MAX_ROUNDS // rounds
+        + 32 * Self::MAX_ENTRANTS // entrants
+        + 1 // entrant_count
+        + 32 // champion
+        + 8 // prize_pool
+        + 1; // prize_claimed
+
+    pub fn entrants(&self) -> &[Pubkey] {
+        &self.entrants[..self.entrant_count as usize]
+    }
+
+    pub fn is_decided(&self) -> bool {
+        self.current_round >= self.round_count
+    }
+}
+
+/// A league of quizzes with standings summed over them, at
+/// `[b"series", organizer, series_id]`. The leader once every quiz is
+/// recorded takes the series prize, then the organizer may start the next
+/// season with empty standings.
+#[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+pub struct Series {
+    pub organizer: Pubkey,
+    pub series_id: u64,
+    pub quiz_count: u8,
+    pub quizzes: [Pubkey; Series::MAX_QUIZZES], // quiz session per position
+    pub recorded: u16, // bitmap of positions whose scores are in the standings
+    pub standings: [LeaderboardEntry; Series::MAX_STANDINGS], // best total first
+    pub standing_count: u8,
+    pub prize_pool: u64,
+    pub prize_claimed: bool,
+    pub season: u32,
+}
+
+impl Series {
+    pub const SEED: &'static [u8] = b"series";
+    /// Seed for the data-less vault holding the series prize.
+    pub const VAULT_SEED: &'static [u8] = b"series_vault";
+    pub const MAX_QUIZZES: usize = 16;
+    pub const MAX_STANDINGS: usize = 32;
+    pub const SIZE: usize = 32 // organizer
+        + 8 // series_id
+        + 1 // quiz_count
+        + 32 * Self::MAX_QUIZZES // quizzes
+        + 2 // recorded
+        + (32 + 8) * Self::MAX_STANDINGS // standings
+        + 1 // standing_count
+        + 8 // prize_pool
+        + 1 // prize_claimed
+        + 4; // season
+
+    pub fn standings(&self) -> &[LeaderboardEntry] {
+        &self.standings[..self.standing_count as usize]
+    }
+
+    pub fn is_recorded(&self, position: usize) -> bool {
+        self.recorded & (1 << position) != 0
+    }
+
+    /// Whether every quiz of the series is in the standings.
+    pub fn is_complete(&self) -> bool {
+        self.recorded.count_ones() == self.quiz_count as u32
+    }
+
+    /// Add a quiz score to the player's total. A full table drops its lowest
+    /// total for a newcomer who beats it, so only the leading totals are kept.
+    pub fn add_score(&mut self, player: Pubkey, score: u32) {
+        let count = self.standing_count as usize;
+        if let Some(entry) = self.standings[..count]
+            .iter_mut()
+            .find(|e| e.player == player)
+        {
+            entry.total_score += score as u64;
+        } else if count < Self::MAX_STANDINGS {
+            self.standings[count] = LeaderboardEntry {
+                player,
+                total_score: score as u64,
+            };
+            self.standing_count += 1;
+        } else if score as u64 > self.standings[count - 1].total_score {
+            self.standings[count - 1] = LeaderboardEntry {
+                player,
+                total_score: score as u64,
+            };
+        } else {
+            return;
+        }
+        let count = self.standing_count as usize;
+        self.standings[..count].sort_by_key(|e| std::cmp::Reverse(e.total_score));
+    }
+
+    /// Clear the quizzes and standings for the next season of `quiz_count`
+    /// quizzes. The prize pool carries over.
+    pub fn start_season(&mut self, quiz_count: u8) {
+        self.quiz_count = quiz_count;
+        self.quizzes = [Pubkey::default(); Self::MAX_QUIZZES];
+        self.recorded = 0;
+        self.standings = [LeaderboardEntry::default(); Self::MAX_STANDINGS];
+        self.standing_count = 0;
+        self.prize_claimed = false;
+        self.season += 1;
+    }
+}
+
+/// The final standings of one season of a leaderboard, at
+/// `[b"season_archive", board, season]` where `board` is the daily
+/// leaderboard or a series.
+#[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+pub struct SeasonArchive {
+    pub board: Pubkey,
+    pub season: u32,
+    pub ended_at: i64,                                           // unix timestamp
+    pub entries: [LeaderboardEntry; SeasonArchive::MAX_ENTRIES], // best total first
+    pub entry_count: u8,
+}
+
+impl SeasonArchive {
+    pub const SEED: &'static [u8] = b"season_archive";
+    pub const MAX_ENTRIES: usize = Series::MAX_STANDINGS;
+    pub const SIZE: usize = 32 // board
+        + 4 // season
+        + 8 // ended_at
+        + (32 + 8) * Self::MAX_ENTRIES // entries
+        + 1; // entry_count
+
+    pub fn new(board: Pubkey, season: u32, ended_at: i64, standings: &[LeaderboardEntry]) -> Self {
+        let mut entries = [LeaderboardEntry::default(); Self::MAX_ENTRIES];
+        entries[..standings.len()].copy_from_slice(standings);
+        SeasonArchive {
+            board,
+            season,
+            ended_at,
+            entries,
+            entry_count: standings.len() as u8,
+        }
+    }
+
+    pub fn entries(&self) -> &[LeaderboardEntry] {
+        &self.entries[..self.entry_count as usize]
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+pub struct DailyConfig {
+    pub authority: Pubkey,
+    pub curator: Pubkey,  // publishes each day's questions
+    pub season_days: u64, // season length, 0 if only the authority ends seasons
+}
+
+impl DailyConfig {
+    pub const SEED: &'static [u8] = b"daily_config";
+    pub const SIZE: usize = 32 + 32 + 8; // authority + curator + season_days
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+pub struct DailyQuiz {
+    pub day_index: u64,
+    pub question_count: u8,
+    pub player_count: u32,
+}
+
+impl DailyQuiz {
+    pub const SEED: &'static [u8] = b"daily_quiz";
+    pub const SIZE: usize = 8 + 1 + 4; // day_index + question_count + player_count
+    pub const SECONDS_PER_DAY: i64 = 86_400;
+
+    /// Days since the unix epoch, the daily quiz rolls over at midnight UTC.
+    pub fn day_index(unix_timestamp: i64) -> u64 {
+        (unix_timestamp / Self::SECONDS_PER_DAY) as u64
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+pub struct DailyEntry {
+    pub player: Pubkey,
+    pub day_index: u64,
+    pub score: u32,
+}
+
+impl DailyEntry {
+    pub const SEED: &'static [u8] = b"daily_entry";
+    pub const SIZE: usize = 32 + 8 + 4; // player + day_index + score
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+pub struct DailyStreak {
+    pub player: Pubkey,
+    pub last_day: u64,
+    pub current_streak: u32,
+    pub best_streak: u32,
+    pub days_played: u32,
+    pub total_score: u64,
+    pub season: u32,       // daily leaderboard season of season_score
+    pub season_score: u64, // total score in that season
+}
+
+impl DailyStreak {
+    pub const SEED: &'static [u8] = b"daily_streak";
+    pub const SIZE: usize = 32 // player
+        + 8 // last_day
+        + 4 // current_streak
+        + 4 // best_streak
+        + 4 // days_played
+        + 8 // total_score
+        + 4 // season
+        + 8; // season_score
+
+    /// Record a day's score. Streaks run across seasons, the season score
+    /// starts over in a new `season`.
+    pub fn record_day(&mut self, day_index: u64, score: u32, season: u32) {
+        if self.days_played > 0 && self.last_day + 1 == day_index {
+            self.current_streak += 1;
+        } else {
+            self.current_streak = 1;
+        }
+        self.best_streak = self.best_streak.max(self.current_streak);
+        self.last_day = day_index;
+        self.days_played += 1;
+        self.total_score += score as u64;
+        if self.season != season {
+            self.season = season;
+            self.season_score = 0;
+        }
+        self.season_score += score as u64;
+    }
+}
+
+/// A player's display name, at `[b"player_profile", player]`, so
+/// leaderboards can show names instead of addresses.
+#[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlayerProfile {
+    #[cfg_attr(feature = "serde", serde(with = "pubkey_string"))]
+    pub player: Pubkey,
+    pub nickname: [u8; PlayerProfile::MAX_NICKNAME_LEN], // utf-8, zero padded
+    pub reserved: bool, // a NicknameClaim holds the name for this player
+}
+
+impl PlayerProfile {
+    pub const SEED: &'static [u8] = b"player_profile";
+    pub const MAX_NICKNAME_LEN: usize = 32;
+    pub const SIZE: usize = 32 + Self::MAX_NICKNAME_LEN + 1; // player + nickname + reserved
+
+    pub fn nickname(&self) -> &str {
+        let len = self
+            .nickname
+            .iter()
+            .position(|&byte| byte == 0)
+            .unwrap_or(Self::MAX_NICKNAME_LEN);
+        std::str::from_utf8(&self.nickname[..len]).unwrap_or_default()
+    }
+}
+
+/// Reserves a nickname for one player, at `[b"nickname", name]` where
+/// `name` is the nickname with ASCII letters lowercased, so names differing
+/// only in case can't both be reserved.
+#[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NicknameClaim {
+    #[cfg_attr(feature = "serde", serde(with = "pubkey_string"))]
+    pub player: Pubkey,
+}
+
+impl NicknameClaim {
+    pub const SEED: &'static [u8] = b"nickname";
+    pub const SIZE: usize = 32; // player
+
+    pub fn seed(nickname: &str) -> Vec<u8> {
+        nickname.to_ascii_lowercase().into_bytes()
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, Default, ShankType)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+pub struct LeaderboardEntry {
+    pub player: Pubkey,
+    pub total_score: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+pub struct DailyLeaderboard {
+    pub entries: [LeaderboardEntry; DailyLeaderboard::MAX_ENTRIES], // best season total first
+    pub entry_count: u8,
+    pub season: u32,
+    pub season_start_day: u64,
+}
+
+impl DailyLeaderboard {
+    pub const SEED: &'static [u8] = b"daily_leaderboard";
+    pub const MAX_ENTRIES: usize = 10;
+    pub const SIZE: usize = (32 + 8) * Self::MAX_ENTRIES // entries
+        + 1 // entry_count
+        + 4 // season
+        + 8; // season_start_day
+
+    pub fn entries(&self) -> &[LeaderboardEntry] {
+        &self.entries[..self.entry_count as usize]
+    }
+
+    /// Whether a season of `season_days` has run its course by `today`.
+    pub fn season_over(&self, season_days: u64, today: u64) -> bool {
+        season_days != 0 && today >= self.season_start_day.saturating_add(season_days)
+    }
+
+    /// Empty the board for the season starting on `today`.
+    pub fn start_season(&mut self, today: u64) {
+        self.entries = [LeaderboardEntry::default(); Self::MAX_ENTRIES];
+        self.entry_count = 0;
+        self.season += 1;
+        self.season_start_day = today;
+    }
+
+    /// Insert or update a player's running total, keeping the board sorted.
+    pub fn record(&mut self, player: Pubkey, total_score: u64) {
+        let count = self.entry_count as usize;
+        if let Some(entry) = self.entries[..count]
+            .iter_mut()
+            .find(|e| e.player == player)
+        {
+            entry.total_score = total_score;
+        } else if count < Self::MAX_ENTRIES {
+            self.entries[count] = LeaderboardEntry {
+                player,
+                total_score,
+            };
+            self.entry_count += 1;
+        } else if total_score > self.entries[count - 1].total_score {
+            self.entries[count - 1] = LeaderboardEntry {
+                player,
+                total_score,
+            };
+        } else {
+            return;
+        }
+        let count = self.entry_count as usize;
+        self.entries[..count].sort_by_key(|e| std::cmp::Reverse(e.total_score));
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, Default, ShankType)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+pub struct RegistryEntry {
+    pub quiz: Pubkey,
+    pub host: Pubkey,
+    pub start_slot: u64, // advertised by the host, 0 if unscheduled
+    pub entry_fee: u64,
+}
+
+/// One page of publicly listed quizzes, clients read shards 0, 1, 2, ...
+/// until one is missing.
+#[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
+#[cfg_attr(feature = "schema", derive(borsh::BorshSchema))]
+pub struct QuizRegistry {
+    pub shard_index: u32,
+    pub entries: [RegistryEntry; QuizRegistry::MAX_ENTRIES],
+    pub entry_count: u8,
+}
+
+impl QuizRegistry {
+    pub const SEED: &'static [u8] = b"quiz_registry";
+    pub const MAX_ENTRIES: usize = 32;
+    pub const SIZE: usize = 4 // shard_index
+        + (32 + 32 + 8 + 8) * Self::MAX_ENTRIES // entries
+        + 1; // entry_count
+
+    pub fn entries(&self) -> &[RegistryEntry] {
+        &self.entries[..self.entry_count as usize]
+    }
 }
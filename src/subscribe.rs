@@ -0,0 +1,163 @@
+//! Live updates of quiz accounts over the websocket `accountSubscribe`, for
+//! scoreboards that redraw as players answer instead of polling. Every
+//! update is decoded into the account's state struct and sent down a
+//! channel by a background task, which unsubscribes once the receiver is
+//! dropped.
+//!
+//! Updates only arrive when the account changes, read it once first to
+//! draw the current state. The task talks to the node through
+//! `QuizPubsub`, implemented for the nonblocking `PubsubClient` and easy to
+//! fake in tests.
+
+use std::{future::Future, sync::Arc};
+
+use borsh::BorshDeserialize;
+use futures::{future::BoxFuture, stream::BoxStream, StreamExt};
+use solana_account_decoder_client_types::UiAccountEncoding;
+use solana_client::{nonblocking::pubsub_client::PubsubClient, rpc_config::RpcAccountInfoConfig};
+use solana_sdk::{account::Account, pubkey::Pubkey};
+use thiserror::Error;
+use tokio::{
+    sync::mpsc::{self, UnboundedReceiver, UnboundedSender},
+    task::JoinHandle,
+};
+
+use crate::{
+    client,
+    state::{LiveLeaderboard, PlayerScore, QuizSession},
+};
+
+#[derive(Debug, Error)]
+pub enum SubscribeError {
+    #[error("Subscription failed: {0}")]
+    Pubsub(String),
+    #[error("Account {0} doesn't hold the expected state")]
+    Undecodable(Pubkey),
+}
+
+/// An account as of `slot`, None once it was closed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AccountUpdate<T> {
+    pub slot: u64,
+    pub account: Option<T>,
+}
+
+/// Raw data of an account on every change.
+pub type AccountStream<'a> = BoxStream<'a, AccountUpdate<Vec<u8>>>;
+
+/// Ends the subscription on the node.
+pub type Unsubscribe = Box<dyn FnOnce() -> BoxFuture<'static, ()> + Send>;
+
+/// What the subscription task needs from a websocket node.
+pub trait QuizPubsub: Send + Sync + 'static {
+    fn account_updates(
+        &self,
+        address: Pubkey,
+    ) -> impl Future<Output = Result<(AccountStream<'_>, Unsubscribe), SubscribeError>> + Send;
+}
+
+impl QuizPubsub for PubsubClient {
+    async fn account_updates(
+        &self,
+        address: Pubkey,
+    ) -> Result<(AccountStream<'_>, Unsubscribe), SubscribeError> {
+        let config = RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            ..RpcAccountInfoConfig::default()
+        };
+        let (stream, unsubscribe) = self
+            .account_subscribe(&address, Some(config))
+            .await
+            .map_err(|error| SubscribeError::Pubsub(error.to_string()))?;
+        let updates = stream.map(|response| AccountUpdate {
+            slot: response.context.slot,
+            account: response
+                .value
+                .decode::<Account>()
+                .filter(|account| account.lamports > 0)
+                .map(|account| account.data),
+        });
+        Ok((updates.boxed(), unsubscribe))
+    }
+}
+
+/// Updates of one account, decoded. Drop it to unsubscribe.
+pub struct Subscription<T> {
+    pub updates: UnboundedReceiver<AccountUpdate<T>>,
+    /// Ends with the reason updates stopped: an error, or Ok when the
+    /// receiver was dropped or the node closed the stream.
+    pub task: JoinHandle<Result<(), SubscribeError>>,
+}
+
+/// Updates of the quiz session at `quiz`, e.g. to follow its status and
+/// player count.
+pub fn subscribe_quiz(pubsub: Arc<impl QuizPubsub>, quiz: Pubkey) -> Subscription<QuizSession> {
+    subscribe(pubsub, quiz, |data| QuizSession::try_from_slice(data).ok())
+}
+
+/// Updates of the running standings of `quiz`.
+pub fn subscribe_live_leaderboard(
+    pubsub: Arc<impl QuizPubsub>,
+    program_id: &Pubkey,
+    quiz: &Pubkey,
+) -> Subscription<LiveLeaderboard> {
+    let board = client::find_live_leaderboard_address(program_id, quiz);
+    subscribe(pubsub, board, |data| {
+        LiveLeaderboard::try_from_slice(data).ok()
+    })
+}
+
+/// Updates of `player`'s final score in `quiz`, the first one arriving when
+/// the quiz is scored.
+pub fn subscribe_player_score(
+    pubsub: Arc<impl QuizPubsub>,
+    program_id: &Pubkey,
+    quiz: &Pubkey,
+    player: &Pubkey,
+) -> Subscription<PlayerScore> {
+    let score = client::find_player_score_address(program_id, quiz, player);
+    subscribe(pubsub, score, |data| {
+        PlayerScore::try_from_slice(data)
+            .ok()
+            .or_else(|| PlayerScore::unpack_legacy(data))
+    })
+}
+
+fn subscribe<T: Send + 'static>(
+    pubsub: Arc<impl QuizPubsub>,
+    address: Pubkey,
+    decode: fn(&[u8]) -> Option<T>,
+) -> Subscription<T> {
+    let (sender, updates) = mpsc::unbounded_channel();
+    let task = tokio::spawn(async move { forward(&*pubsub, address, decode, sender).await });
+    Subscription { updates, task }
+}
+
+async fn forward<T>(
+    pubsub: &impl QuizPubsub,
+    address: Pubkey,
+    decode: fn(&[u8]) -> Option<T>,
+    sender: UnboundedSender<AccountUpdate<T>>,
+) -> Result<(), SubscribeError> {
+    let (mut stream, unsubscribe) = pubsub.account_updates(address).await?;
+    let result = loop {
+        let update = tokio::select! {
+            _ = sender.closed() => break Ok(()),
+            update = stream.next() => update,
+        };
+        let Some(AccountUpdate { slot, account }) = update else {
+            break Ok(());
+        };
+        let account = match account.map(|data| decode(&data)) {
+            Some(None) => break Err(SubscribeError::Undecodable(address)),
+            Some(Some(decoded)) => Some(decoded),
+            None => None,
+        };
+        if sender.send(AccountUpdate { slot, account }).is_err() {
+            break Ok(());
+        }
+    };
+    drop(stream);
+    unsubscribe().await;
+    result
+}
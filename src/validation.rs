@@ -0,0 +1,64 @@
+use ephemeral_rollups_sdk::consts::DELEGATION_PROGRAM_ID;
+use solana_program::{
+    account_info::AccountInfo, bpf_loader_upgradeable, entrypoint::ProgramResult,
+    program_error::ProgramError, pubkey::Pubkey,
+};
+
+use crate::error::QuizError;
+
+/// Reject accounts that are not owned by `owner`.
+pub fn assert_owned_by(account: &AccountInfo, owner: &Pubkey) -> ProgramResult {
+    if account.owner != owner {
+        return Err(QuizError::InvalidAccountOwner.into());
+    }
+    Ok(())
+}
+
+/// Reject accounts that are neither owned by this program nor currently
+/// delegated to the ephemeral rollup (in which case the delegation program
+/// owns them on the base layer).
+pub fn assert_owned_by_program_or_delegated(
+    account: &AccountInfo,
+    program_id: &Pubkey,
+) -> ProgramResult {
+    if account.owner != program_id && *account.owner != DELEGATION_PROGRAM_ID {
+        return Err(QuizError::InvalidAccountOwner.into());
+    }
+    Ok(())
+}
+
+/// Re-derive the PDA for `seeds` and reject the account if it does not match.
+/// Returns the bump seed so creation paths can sign for the account.
+pub fn assert_pda(
+    account: &AccountInfo,
+    seeds: &[&[u8]],
+    program_id: &Pubkey,
+) -> Result<u8, ProgramError> {
+    let (expected_pda, bump_seed) = Pubkey::find_program_address(seeds, program_id);
+    if expected_pda != *account.key {
+        return Err(QuizError::InvalidPda.into());
+    }
+    Ok(bump_seed)
+}
+
+/// Reject `authority` unless it is the upgrade authority recorded in the
+/// program's ProgramData account.
+pub fn assert_upgrade_authority(
+    program_data: &AccountInfo,
+    program_id: &Pubkey,
+    authority: &Pubkey,
+) -> ProgramResult {
+    assert_owned_by(program_data, &bpf_loader_upgradeable::id())?;
+    if *program_data.key != bpf_loader_upgradeable::get_program_data_address(program_id) {
+        return Err(QuizError::InvalidPda.into());
+    }
+    // Bincode layout: u32 variant 3, u64 slot, then Option<Pubkey>
+    let data = program_data.data.borrow();
+    if data.len() < 45 || data[..4] != 3u32.to_le_bytes() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if data[12] != 1 || data[13..45] != authority.to_bytes() {
+        return Err(QuizError::NotUpgradeAuthority.into());
+    }
+    Ok(())
+}
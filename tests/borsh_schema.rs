@@ -0,0 +1,76 @@
+use borsh::{
+    max_serialized_size,
+    schema::{BorshSchemaContainer, Definition},
+    BorshDeserialize,
+};
+use quiz_game::{instruction::ANCHOR_DISCRIMINATORS, schema, state::*};
+
+#[test]
+fn schemas_are_complete() {
+    let instruction = schema::instruction_schema();
+    instruction.validate().unwrap();
+    match instruction.get_definition(instruction.declaration()) {
+        Some(Definition::Enum { variants, .. }) => {
+            assert_eq!(variants.len(), ANCHOR_DISCRIMINATORS.len())
+        }
+        other => panic!("expected an enum, got {:?}", other),
+    }
+    for account in schema::account_schemas() {
+        account.validate().unwrap();
+    }
+}
+
+#[test]
+fn exported_schemas_decode() {
+    let decoded = Vec::<BorshSchemaContainer>::try_from_slice(&schema::export()).unwrap();
+    assert_eq!(decoded[0], schema::instruction_schema());
+    assert_eq!(decoded[1..], schema::account_schemas());
+}
+
+/// The schema and the SIZE constants each account is allocated with can't
+/// drift apart
+#[test]
+fn fixed_size_accounts_match_their_schema() {
+    macro_rules! assert_sizes {
+        ($($account:ty),* $(,)?) => {
+            $(assert_eq!(
+                max_serialized_size::<$account>().unwrap(),
+                <$account>::SIZE,
+                stringify!($account)
+            );)*
+        };
+    }
+    assert_sizes!(
+        QuizSession,
+        QuestionStats,
+        LeaderboardClaim,
+        PrizeContribution,
+        AnswerTree,
+        CompressedSheet,
+        HostStats,
+        QuizAnalytics,
+        QuestionDispute,
+        DisputeFlag,
+        QuizEventLog,
+        ScoreAudit,
+        QuestionCommitment,
+        AnswerSession,
+        PlayerScore,
+        LiveLeaderboard,
+        QuizReactions,
+        Team,
+        TeamMember,
+        QuizTemplate,
+        Tournament,
+        Series,
+        SeasonArchive,
+        DailyConfig,
+        DailyQuiz,
+        DailyEntry,
+        DailyStreak,
+        PlayerProfile,
+        NicknameClaim,
+        DailyLeaderboard,
+        QuizRegistry,
+    );
+}
@@ -0,0 +1,38 @@
+use quiz_game::cipher;
+
+fn options() -> [String; 4] {
+    [
+        "Paris",
+        "Lyon",
+        "Marseille",
+        "A city with a much longer name than the rest",
+    ]
+    .map(String::from)
+}
+
+#[test]
+fn questions_decrypt_with_the_committed_key() {
+    let key = [7; 32];
+    let (text, options) = cipher::encrypt_question(&key, 3, "Capital of France?", &self::options());
+    assert_ne!(text, "Capital of France?");
+    assert!(text.bytes().all(|byte| byte.is_ascii_hexdigit()));
+
+    let (plain_text, plain_options) = cipher::decrypt_question(&key, 3, &text, &options).unwrap();
+    assert_eq!(plain_text, "Capital of France?");
+    assert_eq!(plain_options, self::options());
+
+    // Each question and field gets its own keystream
+    let (other_text, _) = cipher::encrypt_question(&key, 4, "Capital of France?", &self::options());
+    assert_ne!(other_text, text);
+    assert_ne!(
+        options[0],
+        cipher::encrypt_question(&key, 3, "Paris", &self::options()).0
+    );
+
+    assert_ne!(
+        cipher::decrypt_question(&[8; 32], 3, &text, &options),
+        Some((plain_text, plain_options))
+    );
+    assert_eq!(cipher::decrypt_question(&key, 3, "abc", &options), None);
+    assert_ne!(cipher::key_hash(&key), cipher::key_hash(&[8; 32]));
+}
@@ -0,0 +1,177 @@
+//! Shared setup for the program-test suites: a bank with the quiz program
+//! loaded and a few transaction helpers on top of the client builders.
+
+#![allow(dead_code)]
+
+use quiz_game::{
+    client,
+    state::{DelegationConfig, QuizMode},
+};
+use solana_program_test::{
+    processor, BanksClient, BanksClientError, ProgramTest, ProgramTestContext,
+};
+use solana_sdk::{
+    account::Account,
+    bpf_loader_upgradeable,
+    instruction::{Instruction, InstructionError},
+    pubkey::Pubkey,
+    rent::Rent,
+    signature::{Keypair, Signer},
+    system_instruction,
+    transaction::{Transaction, TransactionError},
+};
+
+pub const QUIZ_ID: u64 = 7;
+
+pub fn program_test() -> (ProgramTest, Pubkey) {
+    let program_id = Pubkey::new_unique();
+    let program_test = ProgramTest::new(
+        "quiz_game",
+        program_id,
+        processor!(quiz_game::processor::process_instruction),
+    );
+    (program_test, program_id)
+}
+
+pub fn question(
+    program_id: &Pubkey,
+    host: &Pubkey,
+    quiz: &Pubkey,
+    index: u8,
+    correct_answer_index: u8,
+) -> Instruction {
+    client::add_question_ix(
+        program_id,
+        host,
+        quiz,
+        index,
+        format!("Question {}", index),
+        ["A", "B", "C", "D"].map(String::from),
+        correct_answer_index,
+    )
+}
+
+pub async fn send(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> Result<(), BanksClientError> {
+    let blockhash = banks_client.get_latest_blockhash().await?;
+    let mut all_signers = vec![payer];
+    all_signers.extend_from_slice(signers);
+    let transaction = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&payer.pubkey()),
+        &all_signers,
+        blockhash,
+    );
+    banks_client.process_transaction(transaction).await
+}
+
+/// Create a wallet holding one SOL.
+pub async fn funded_keypair(banks_client: &mut BanksClient, payer: &Keypair) -> Keypair {
+    let keypair = Keypair::new();
+    send(
+        banks_client,
+        payer,
+        &[system_instruction::transfer(
+            &payer.pubkey(),
+            &keypair.pubkey(),
+            1_000_000_000,
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+    keypair
+}
+
+/// Create and start a quiz hosted by `payer`, one question per entry of
+/// `answers` with that entry as the correct option.
+pub async fn started_quiz(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    program_id: &Pubkey,
+    mode: QuizMode,
+    answers: &[u8],
+) -> Pubkey {
+    started_quiz_with(
+        banks_client,
+        payer,
+        program_id,
+        mode,
+        answers,
+        DelegationConfig::default(),
+    )
+    .await
+}
+
+/// `started_quiz` with a chosen delegation config.
+pub async fn started_quiz_with(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    program_id: &Pubkey,
+    mode: QuizMode,
+    answers: &[u8],
+    delegation: DelegationConfig,
+) -> Pubkey {
+    let host = payer.pubkey();
+    let quiz = client::find_quiz_address(program_id, &host, QUIZ_ID);
+    let initialize = client::initialize_quiz_with_delegation_ix(
+        program_id,
+        &host,
+        QUIZ_ID,
+        answers.len() as u8,
+        delegation,
+    );
+    send(banks_client, payer, &[initialize], &[]).await.unwrap();
+    // One question per transaction keeps long quizzes under the size limit
+    for (index, &answer) in answers.iter().enumerate() {
+        let add = question(program_id, &host, &quiz, index as u8, answer);
+        send(banks_client, payer, &[add], &[]).await.unwrap();
+    }
+    send(
+        banks_client,
+        payer,
+        &[
+            client::set_quiz_mode_ix(program_id, &host, &quiz, mode, 0),
+            client::start_quiz_ix(program_id, &host, &quiz),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    quiz
+}
+
+/// Stand in for the ProgramData account of an upgradeable deploy, naming
+/// `authority` as the program's upgrade authority.
+pub fn set_upgrade_authority(
+    context: &mut ProgramTestContext,
+    program_id: &Pubkey,
+    authority: &Pubkey,
+) {
+    let mut data = vec![0; 45];
+    data[..4].copy_from_slice(&3u32.to_le_bytes());
+    data[12] = 1;
+    data[13..].copy_from_slice(authority.as_ref());
+    context.set_account(
+        &bpf_loader_upgradeable::get_program_data_address(program_id),
+        &Account {
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            owner: bpf_loader_upgradeable::id(),
+            executable: false,
+            rent_epoch: 0,
+        }
+        .into(),
+    );
+}
+
+pub fn instruction_error(result: Result<(), BanksClientError>) -> InstructionError {
+    match result.unwrap_err().unwrap() {
+        TransactionError::InstructionError(_, error) => error,
+        other => panic!("expected an instruction error, got {:?}", other),
+    }
+}
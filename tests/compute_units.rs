@@ -0,0 +1,229 @@
+//! Compute-unit benchmarks against the built SBF program. Each scenario
+//! prints what it consumed and fails if it no longer fits the default
+//! per-instruction budget.
+//!
+//! Run with `cargo test-sbf --test compute_units -- --nocapture`.
+
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use common::*;
+use quiz_game::{
+    client,
+    state::{PlayerAnswer, QuizMode, QuizSession},
+};
+use solana_program_test::{ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::Account,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    rent::Rent,
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+
+/// What a single instruction gets without a compute budget request.
+const DEFAULT_BUDGET: u64 = 200_000;
+
+async fn setup() -> (ProgramTestContext, Pubkey) {
+    let program_id = Pubkey::new_unique();
+    // No native processor, load target/deploy/quiz_game.so so CUs are metered
+    let mut program_test = ProgramTest::new("quiz_game", program_id, None);
+    // Measure past the default so a regression reports its actual cost
+    program_test.set_compute_max_units(1_400_000);
+    (program_test.start_with_context().await, program_id)
+}
+
+async fn measure(
+    context: &mut ProgramTestContext,
+    label: &str,
+    instruction: Instruction,
+    signers: &[&Keypair],
+    question_count: u8,
+    player_count: u8,
+) {
+    let estimate = client::estimate_compute_units(
+        &instruction.program_id,
+        std::slice::from_ref(&instruction),
+        question_count,
+        player_count,
+    );
+    let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend_from_slice(signers);
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&context.payer.pubkey()),
+        &all_signers,
+        blockhash,
+    );
+    let outcome = context
+        .banks_client
+        .process_transaction_with_metadata(transaction)
+        .await
+        .unwrap();
+    outcome.result.unwrap();
+    let consumed = outcome.metadata.unwrap().compute_units_consumed;
+    println!("{:<40} {:>8} CU", label, consumed);
+    assert!(
+        consumed <= DEFAULT_BUDGET,
+        "{} used {} CU, over the {} CU default",
+        label,
+        consumed,
+        DEFAULT_BUDGET
+    );
+    // Priority fee limits must not starve the instruction
+    assert!(
+        consumed <= estimate as u64,
+        "{} used {} CU, over its {} CU estimate",
+        label,
+        consumed,
+        estimate
+    );
+}
+
+/// Write a submitted answer sheet straight into the bank, as if the player
+/// had joined, answered on the rollup and been committed back.
+fn seed_answer_sheet(
+    context: &mut ProgramTestContext,
+    program_id: &Pubkey,
+    quiz: &Pubkey,
+    player: &Pubkey,
+    answers: &[u8],
+) {
+    let mut sheet = PlayerAnswer::new(*player, answers.len());
+    sheet.answers.copy_from_slice(answers);
+    for index in 0..answers.len() {
+        sheet.mark_answered(index);
+    }
+    sheet.submitted = true;
+    let data = borsh::to_vec(&sheet).unwrap();
+    context.set_account(
+        &client::find_player_answer_address(program_id, quiz, player),
+        &Account {
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            owner: *program_id,
+            executable: false,
+            rent_epoch: 0,
+        }
+        .into(),
+    );
+}
+
+async fn set_player_count(context: &mut ProgramTestContext, quiz: &Pubkey, player_count: u8) {
+    let mut account = context
+        .banks_client
+        .get_account(*quiz)
+        .await
+        .unwrap()
+        .unwrap();
+    let mut quiz_data = QuizSession::try_from_slice(&account.data).unwrap();
+    quiz_data.player_count = player_count;
+    quiz_data.serialize(&mut &mut account.data[..]).unwrap();
+    context.set_account(quiz, &account.into());
+}
+
+#[tokio::test]
+async fn initialize_and_add_question() {
+    let (mut context, program_id) = setup().await;
+    let host = context.payer.pubkey();
+    let quiz = client::find_quiz_address(&program_id, &host, QUIZ_ID);
+
+    measure(
+        &mut context,
+        "InitializeQuiz",
+        client::initialize_quiz_ix(&program_id, &host, QUIZ_ID, 16),
+        &[],
+        16,
+        0,
+    )
+    .await;
+    for (index, text_len) in [16usize, 256, 512].into_iter().enumerate() {
+        measure(
+            &mut context,
+            &format!("AddQuestion ({} byte text)", text_len),
+            client::add_question_ix(
+                &program_id,
+                &host,
+                &quiz,
+                index as u8,
+                "?".repeat(text_len),
+                ["A", "B", "C", "D"].map(String::from),
+                0,
+            ),
+            &[],
+            16,
+            0,
+        )
+        .await;
+    }
+}
+
+#[tokio::test]
+async fn practice_quiz_by_question_count() {
+    for question_count in [1u8, 8, 16] {
+        let (mut context, program_id) = setup().await;
+        let answers = vec![1; question_count as usize];
+        let quiz = started_quiz(
+            &mut context.banks_client,
+            &context.payer,
+            &program_id,
+            QuizMode::Practice,
+            &answers,
+        )
+        .await;
+        let player = funded_keypair(&mut context.banks_client, &context.payer).await;
+
+        measure(
+            &mut context,
+            &format!("PracticeQuiz ({} questions)", question_count),
+            client::practice_quiz_ix(&program_id, &player.pubkey(), &quiz, answers),
+            &[&player],
+            question_count,
+            0,
+        )
+        .await;
+    }
+}
+
+#[tokio::test]
+async fn calculate_scores_by_player_count() {
+    const QUESTION_COUNT: usize = 10;
+
+    // Legacy transactions fit about eight players next to ten questions
+    for player_count in [1u8, 4, 8] {
+        let (mut context, program_id) = setup().await;
+        let answers = [2; QUESTION_COUNT];
+        let quiz = started_quiz(
+            &mut context.banks_client,
+            &context.payer,
+            &program_id,
+            QuizMode::Async,
+            &answers,
+        )
+        .await;
+
+        let players: Vec<Pubkey> = (0..player_count).map(|_| Pubkey::new_unique()).collect();
+        for player in &players {
+            seed_answer_sheet(&mut context, &program_id, &quiz, player, &answers);
+        }
+        set_player_count(&mut context, &quiz, player_count).await;
+
+        let host = context.payer.pubkey();
+        measure(
+            &mut context,
+            &format!(
+                "CalculateScores ({} players x {} questions)",
+                player_count, QUESTION_COUNT
+            ),
+            client::calculate_scores_ix(&program_id, &host, &quiz, QUESTION_COUNT as u8, &players),
+            &[],
+            QUESTION_COUNT as u8,
+            player_count,
+        )
+        .await;
+    }
+}
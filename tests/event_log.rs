@@ -0,0 +1,40 @@
+use quiz_game::state::{LoggedEvent, QuizEventLog};
+use solana_program::pubkey::Pubkey;
+
+fn event(slot: u64) -> LoggedEvent {
+    LoggedEvent {
+        kind: LoggedEvent::KIND_SUBMITTED,
+        slot,
+        actor: Pubkey::default(),
+        value: 0,
+    }
+}
+
+fn slots(log: &QuizEventLog) -> Vec<u64> {
+    log.events().map(|event| event.slot).collect()
+}
+
+#[test]
+fn event_log_keeps_the_latest_events_in_order() {
+    let mut log = QuizEventLog {
+        quiz: Pubkey::new_unique(),
+        total: 0,
+        records: [LoggedEvent::default(); QuizEventLog::CAPACITY],
+    };
+    assert_eq!(borsh::to_vec(&log).unwrap().len(), QuizEventLog::SIZE);
+    assert!(slots(&log).is_empty());
+
+    let capacity = QuizEventLog::CAPACITY as u64;
+    for slot in 0..capacity {
+        log.push(event(slot));
+    }
+    assert_eq!(slots(&log), (0..capacity).collect::<Vec<_>>());
+    assert_eq!(log.dropped(), 0);
+
+    // Past capacity the oldest events are overwritten
+    for slot in capacity..capacity + 5 {
+        log.push(event(slot));
+    }
+    assert_eq!(slots(&log), (5..capacity + 5).collect::<Vec<_>>());
+    assert_eq!(log.dropped(), 5);
+}
@@ -0,0 +1,154 @@
+use quiz_game::{
+    filters::{self, AccountFilter},
+    state::{
+        DelegationConfig, HostPlay, PlayerScore, PrizeRemainder, QuizMode, QuizSession, QuizStatus,
+        ScoringMode,
+    },
+};
+use solana_program::pubkey::Pubkey;
+
+fn quiz(host: Pubkey, status: QuizStatus, tournament: Pubkey) -> Vec<u8> {
+    let quiz = QuizSession {
+        host,
+        quiz_id: u64::MAX,
+        question_count: 0xff,
+        player_count: 0xff,
+        status,
+        allow_resubmission: true,
+        refresh_slot_on_update: true,
+        deadline_slot: u64::MAX,
+        mode: QuizMode::Practice,
+        revealed_question: QuizSession::NO_QUESTION_REVEALED,
+        reveal_slot: u64::MAX,
+        answer_window_slots: u64::MAX,
+        rounds_revealed: 0xff,
+        entry_fee: u64::MAX,
+        prize_pool: u64::MAX,
+        settled: true,
+        tournament,
+        tournament_round: 0xff,
+        delegation: DelegationConfig {
+            commit_frequency_ms: u32::MAX,
+            validator: Pubkey::new_unique(),
+            base_layer: true,
+        },
+        start_slot: u64::MAX,
+        join_grace_slots: u64::MAX,
+        late_window_slots: u64::MAX,
+        late_slots_per_point: u64::MAX,
+        confidence_weighted: true,
+        close_slot: u64::MAX,
+        dispute_window_slots: u64::MAX,
+        scored_slot: u64::MAX,
+        correction_count: u32::MAX,
+        event_log: true,
+        leaderboard_root: [0xff; 32],
+        prize_split_bps: [u16::MAX; QuizSession::PRIZE_RANKS],
+        scorer: Pubkey::new_unique(),
+        scorer_bond: u64::MAX,
+        live_leaderboard: true,
+        referral_bps: u16::MAX,
+        min_prize_score: 0xff,
+        min_prize_score_bps: u16::MAX,
+        prize_remainder: PrizeRemainder::RollOver,
+        forfeited_ranks: 0xff,
+        rollover_lamports: u64::MAX,
+        proportional_prizes: true,
+        total_prize_score: u32::MAX,
+        claimed_prize_score: u32::MAX,
+        claim_window_slots: u64::MAX,
+        vesting_min_lamports: u64::MAX,
+        vesting_slots: u64::MAX,
+        vesting_owed: u64::MAX,
+        entry_fee_usd_cents: u64::MAX,
+        price_feed_id: [0xff; 32],
+        contributed_lamports: u64::MAX,
+        charity: Pubkey::new_unique(),
+        charity_bps: u16::MAX,
+        charity_paid: true,
+        host_play: HostPlay::Allowed,
+        join_stake: u64::MAX,
+        stakes_held: u64::MAX,
+        scoring_mode: ScoringMode::Quadratic,
+        difficulty_weighted: true,
+        subset_size: 0xff,
+        subset_seed: [0xff; 32],
+        answers_revealed: true,
+        question_key_hash: [0xff; 32],
+        question_key: [0xff; 32],
+        curator: Pubkey::new_unique(),
+        team_count: 0xff,
+    };
+    borsh::to_vec(&quiz).unwrap()
+}
+
+fn matches_all(filters: &[AccountFilter], data: &[u8]) -> bool {
+    filters.iter().all(|filter| filter.matches(data))
+}
+
+#[test]
+fn quiz_filters_line_up_with_the_layout() {
+    let host = Pubkey::new_unique();
+    let tournament = Pubkey::new_unique();
+    let running = quiz(host, QuizStatus::Active, tournament);
+    let scored = quiz(host, QuizStatus::Completed, Pubkey::default());
+    assert_eq!(running.len(), QuizSession::SIZE);
+
+    assert!(matches_all(&filters::quizzes_by_host(&host), &running));
+    assert!(!matches_all(
+        &filters::quizzes_by_host(&Pubkey::new_unique()),
+        &running
+    ));
+    assert!(matches_all(&filters::active_quizzes(), &running));
+    assert!(!matches_all(&filters::active_quizzes(), &scored));
+    assert!(matches_all(
+        &filters::quizzes_with_status(QuizStatus::Completed),
+        &scored
+    ));
+    assert!(matches_all(
+        &filters::quizzes_in_tournament(&tournament),
+        &running
+    ));
+    assert!(!matches_all(
+        &filters::quizzes_in_tournament(&tournament),
+        &scored
+    ));
+}
+
+#[test]
+fn score_filters_line_up_with_the_layout() {
+    let score = PlayerScore {
+        player: Pubkey::new_unique(),
+        score: 0xff,
+        quiz: Pubkey::new_unique(),
+        late_penalty: 0xff,
+        skipped: 0xff,
+    };
+    let data = borsh::to_vec(&score).unwrap();
+    assert_eq!(data.len(), PlayerScore::SIZE);
+
+    assert!(matches_all(&filters::scores_for_quiz(&score.quiz), &data));
+    assert!(!matches_all(
+        &filters::scores_for_quiz(&score.player),
+        &data
+    ));
+    assert!(matches_all(
+        &filters::scores_for_player(&score.player),
+        &data
+    ));
+    assert!(!matches_all(
+        &filters::scores_for_player(&score.quiz),
+        &data
+    ));
+
+    // Quiz sessions never pass score filters and vice versa
+    let session = quiz(score.player, QuizStatus::Active, Pubkey::default());
+    assert!(!matches_all(
+        &filters::scores_for_player(&score.player),
+        &session
+    ));
+    assert!(!matches_all(
+        &filters::quizzes_by_host(&score.player),
+        &data
+    ));
+}
@@ -0,0 +1,6328 @@
+mod common;
+
+use borsh::BorshDeserialize;
+use common::*;
+use quiz_game::{
+    cipher, client,
+    error::QuizError,
+    merkle, pyth,
+    state::{
+        AnswerTree, CompressedAnswers, DailyLeaderboard, DailyQuiz, DailyStreak, DelegationConfig,
+        HostPlay, HostStats, LeaderboardClaim, LiveLeaderboard, LoggedEvent, NicknameClaim,
+        PlayerAnswer, PlayerProfile, PlayerScore, PostedStanding, PrizeContribution,
+        PrizeRemainder, QuestionData, QuestionDispute, QuestionExplanation, QuestionStats,
+        QuestionTranslation, QuizAnalytics, QuizEventLog, QuizMode, QuizQuestion, QuizReactions,
+        QuizSession, QuizStatus, QuizTemplate, ScoreAudit, ScoreCorrection, ScoringMode,
+        SeasonArchive, Series, Team, VoidMode,
+    },
+};
+use solana_program_test::{BanksClient, ProgramTestContext};
+use solana_sdk::{
+    account::Account,
+    clock::Clock,
+    instruction::InstructionError,
+    pubkey::Pubkey,
+    rent::Rent,
+    signature::{Keypair, Signer},
+    system_instruction,
+    transaction::Transaction,
+};
+
+async fn player_score(banks_client: &mut BanksClient, address: Pubkey) -> PlayerScore {
+    let account = banks_client.get_account(address).await.unwrap().unwrap();
+    PlayerScore::try_from_slice(&account.data).unwrap()
+}
+
+async fn quiz_session(banks_client: &mut BanksClient, address: Pubkey) -> QuizSession {
+    let account = banks_client.get_account(address).await.unwrap().unwrap();
+    QuizSession::try_from_slice(&account.data).unwrap()
+}
+
+#[tokio::test]
+async fn practice_quiz_lifecycle() {
+    let (program_test, program_id) = program_test();
+    let (mut banks_client, payer, _) = program_test.start().await;
+    let quiz = started_quiz(
+        &mut banks_client,
+        &payer,
+        &program_id,
+        QuizMode::Practice,
+        &[2, 1],
+    )
+    .await;
+    let player = funded_keypair(&mut banks_client, &payer).await;
+    let score_address = client::find_player_score_address(&program_id, &quiz, &player.pubkey());
+
+    // One right, one wrong
+    send(
+        &mut banks_client,
+        &payer,
+        &[client::practice_quiz_ix(
+            &program_id,
+            &player.pubkey(),
+            &quiz,
+            vec![2, 0],
+        )],
+        &[&player],
+    )
+    .await
+    .unwrap();
+    let score = player_score(&mut banks_client, score_address).await;
+    assert_eq!(score.player, player.pubkey());
+    assert_eq!(score.score, 1);
+
+    // A retry replaces the previous score
+    send(
+        &mut banks_client,
+        &payer,
+        &[client::practice_quiz_ix(
+            &program_id,
+            &player.pubkey(),
+            &quiz,
+            vec![2, 1],
+        )],
+        &[&player],
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        player_score(&mut banks_client, score_address).await.score,
+        2
+    );
+
+    let quiz_data = quiz_session(&mut banks_client, quiz).await;
+    assert_eq!(quiz_data.status, QuizStatus::Active);
+    assert_eq!(quiz_data.player_count, 1);
+}
+
+#[tokio::test]
+async fn calculate_scores_completes_quiz() {
+    let (program_test, program_id) = program_test();
+    let (mut banks_client, payer, _) = program_test.start().await;
+    let quiz = started_quiz(
+        &mut banks_client,
+        &payer,
+        &program_id,
+        QuizMode::Async,
+        &[0],
+    )
+    .await;
+
+    send(
+        &mut banks_client,
+        &payer,
+        &[client::calculate_scores_ix(
+            &program_id,
+            &payer.pubkey(),
+            &quiz,
+            1,
+            &[],
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        quiz_session(&mut banks_client, quiz).await.status,
+        QuizStatus::Completed
+    );
+}
+
+#[tokio::test]
+async fn answers_are_revealed_once_scored() {
+    let (program_test, program_id) = program_test();
+    let (mut banks_client, payer, _) = program_test.start().await;
+    let host = payer.pubkey();
+    let quiz = client::find_quiz_address(&program_id, &host, QUIZ_ID);
+    let explain =
+        |explanation: String| client::set_explanation_ix(&program_id, &host, &quiz, 0, explanation);
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::initialize_quiz_ix(&program_id, &host, QUIZ_ID, 1),
+            question(&program_id, &host, &quiz, 0, 2),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let result = send(
+        &mut banks_client,
+        &payer,
+        &[explain("x".repeat(QuestionExplanation::MAX_LEN + 1))],
+        &[],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::ExplanationTooLong as u32)
+    );
+
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            explain("Option 2 is the only prime".to_string()),
+            client::start_quiz_ix(&program_id, &host, &quiz),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    // Nothing is revealed before the quiz is scored
+    let result = send(
+        &mut banks_client,
+        &payer,
+        &[client::reveal_answers_ix(&program_id, &quiz, 1)],
+        &[],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::InvalidAccountData
+    );
+
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::calculate_scores_ix(&program_id, &host, &quiz, 1, &[]),
+            client::reveal_answers_ix(&program_id, &quiz, 1),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    assert!(quiz_session(&mut banks_client, quiz).await.answers_revealed);
+    let explanation_address = client::find_explanation_address(&program_id, &quiz, 0);
+    let account = banks_client
+        .get_account(explanation_address)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        QuestionExplanation::try_from_slice(&account.data)
+            .unwrap()
+            .explanation,
+        "Option 2 is the only prime"
+    );
+
+    // Paid by another wallet so it isn't a duplicate of the first reveal
+    let revealer = funded_keypair(&mut banks_client, &payer).await;
+    let result = send(
+        &mut banks_client,
+        &revealer,
+        &[client::reveal_answers_ix(&program_id, &quiz, 1)],
+        &[],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::AnswersAlreadyRevealed as u32)
+    );
+}
+
+#[tokio::test]
+async fn encrypted_questions_open_with_the_committed_key() {
+    let (program_test, program_id) = program_test();
+    let (mut banks_client, payer, _) = program_test.start().await;
+    let host = payer.pubkey();
+    let quiz = client::find_quiz_address(&program_id, &host, QUIZ_ID);
+    let key = [42; 32];
+    let options = ["Two", "Three", "Four", "Five"].map(String::from);
+    let (question_text, encrypted_options) =
+        cipher::encrypt_question(&key, 0, "How many sides has a square?", &options);
+    let base_layer = DelegationConfig {
+        base_layer: true,
+        ..DelegationConfig::default()
+    };
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::initialize_quiz_with_delegation_ix(&program_id, &host, QUIZ_ID, 1, base_layer),
+            client::set_question_key_ix(&program_id, &host, &quiz, cipher::key_hash(&key)),
+            client::add_question_ix(
+                &program_id,
+                &host,
+                &quiz,
+                0,
+                question_text,
+                encrypted_options,
+                2,
+            ),
+            client::start_quiz_ix(&program_id, &host, &quiz),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    // Nobody answers questions they can't read yet
+    let player = funded_keypair(&mut banks_client, &payer).await;
+    let result = send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::join_quiz_ix(&program_id, &player.pubkey(), &quiz, 0, None),
+            client::submit_answers_ix(&program_id, &player.pubkey(), &quiz, vec![2]),
+        ],
+        &[&player],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::QuestionsNotRevealed as u32)
+    );
+
+    let result = send(
+        &mut banks_client,
+        &payer,
+        &[client::reveal_questions_ix(
+            &program_id,
+            &host,
+            &quiz,
+            [41; 32],
+        )],
+        &[],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::WrongQuestionKey as u32)
+    );
+
+    send(
+        &mut banks_client,
+        &payer,
+        &[client::reveal_questions_ix(&program_id, &host, &quiz, key)],
+        &[],
+    )
+    .await
+    .unwrap();
+    let session = quiz_session(&mut banks_client, quiz).await;
+    assert!(!session.questions_hidden());
+    let question_address = client::find_question_address(&program_id, &quiz, 0);
+    let account = banks_client
+        .get_account(question_address)
+        .await
+        .unwrap()
+        .unwrap();
+    let question = QuizQuestion::try_from_slice(&account.data).unwrap();
+    let (text, _) = cipher::decrypt_question(
+        &session.question_key,
+        0,
+        &question.question_text,
+        &question.options,
+    )
+    .unwrap();
+    assert_eq!(text, "How many sides has a square?");
+
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::join_quiz_ix(&program_id, &player.pubkey(), &quiz, 0, None),
+            client::submit_answers_ix(&program_id, &player.pubkey(), &quiz, vec![2]),
+        ],
+        &[&player],
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn base_layer_quiz_lifecycle() {
+    let (program_test, program_id) = program_test();
+    let (mut banks_client, payer, _) = program_test.start().await;
+    let base_layer = DelegationConfig {
+        base_layer: true,
+        ..DelegationConfig::default()
+    };
+    let quiz = started_quiz_with(
+        &mut banks_client,
+        &payer,
+        &program_id,
+        QuizMode::Async,
+        &[3, 1],
+        base_layer,
+    )
+    .await;
+    let player = funded_keypair(&mut banks_client, &payer).await;
+
+    // Base layer quizzes can't be delegated
+    let result = send(
+        &mut banks_client,
+        &payer,
+        &[client::delegate_player_ix(
+            &program_id,
+            &player.pubkey(),
+            &quiz,
+            0,
+            None,
+            None,
+        )],
+        &[&player],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::WrongQuizMode as u32)
+    );
+
+    // Join and answer straight on chain
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::join_quiz_ix(&program_id, &player.pubkey(), &quiz, 0, None),
+            client::submit_answers_ix(&program_id, &player.pubkey(), &quiz, vec![3, 2]),
+        ],
+        &[&player],
+    )
+    .await
+    .unwrap();
+    send(
+        &mut banks_client,
+        &payer,
+        &[client::calculate_scores_ix(
+            &program_id,
+            &payer.pubkey(),
+            &quiz,
+            2,
+            &[player.pubkey()],
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let score_address = client::find_player_score_address(&program_id, &quiz, &player.pubkey());
+    assert_eq!(
+        player_score(&mut banks_client, score_address).await.score,
+        1
+    );
+    assert_eq!(
+        quiz_session(&mut banks_client, quiz).await.status,
+        QuizStatus::Completed
+    );
+}
+
+#[tokio::test]
+async fn repeat_join_is_rejected() {
+    let (program_test, program_id) = program_test();
+    let (mut banks_client, payer, _) = program_test.start().await;
+    let quiz = started_quiz_with(
+        &mut banks_client,
+        &payer,
+        &program_id,
+        QuizMode::Async,
+        &[0],
+        DelegationConfig {
+            base_layer: true,
+            ..DelegationConfig::default()
+        },
+    )
+    .await;
+    let player = funded_keypair(&mut banks_client, &payer).await;
+    let join = || client::join_quiz_ix(&program_id, &player.pubkey(), &quiz, 0, None);
+
+    send(&mut banks_client, &payer, &[join()], &[&player])
+        .await
+        .unwrap();
+    // Paid by the player so it isn't a duplicate of the first transaction
+    let result = send(&mut banks_client, &player, &[join()], &[]).await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::PlayerAlreadyJoined as u32)
+    );
+    assert_eq!(quiz_session(&mut banks_client, quiz).await.player_count, 1);
+}
+
+#[tokio::test]
+async fn wrong_host_cannot_add_question() {
+    let (program_test, program_id) = program_test();
+    let (mut banks_client, payer, _) = program_test.start().await;
+    let quiz = client::find_quiz_address(&program_id, &payer.pubkey(), QUIZ_ID);
+    send(
+        &mut banks_client,
+        &payer,
+        &[client::initialize_quiz_ix(
+            &program_id,
+            &payer.pubkey(),
+            QUIZ_ID,
+            1,
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let intruder = funded_keypair(&mut banks_client, &payer).await;
+    let result = send(
+        &mut banks_client,
+        &payer,
+        &[question(&program_id, &intruder.pubkey(), &quiz, 0, 0)],
+        &[&intruder],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::InvalidAccountData
+    );
+}
+
+#[tokio::test]
+async fn initialize_stores_delegation_config() {
+    let (program_test, program_id) = program_test();
+    let (mut banks_client, payer, _) = program_test.start().await;
+    let quiz = client::find_quiz_address(&program_id, &payer.pubkey(), QUIZ_ID);
+    let delegation = DelegationConfig {
+        commit_frequency_ms: 250,
+        validator: Pubkey::new_unique(),
+        base_layer: false,
+    };
+    send(
+        &mut banks_client,
+        &payer,
+        &[client::initialize_quiz_with_delegation_ix(
+            &program_id,
+            &payer.pubkey(),
+            QUIZ_ID,
+            1,
+            delegation,
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let stored = quiz_session(&mut banks_client, quiz).await.delegation;
+    assert_eq!(stored, delegation);
+    assert_eq!(stored.validator(), Some(delegation.validator));
+    assert_eq!(DelegationConfig::default().validator(), None);
+}
+
+#[tokio::test]
+async fn initialize_rejects_wrong_pda() {
+    let (program_test, program_id) = program_test();
+    let (mut banks_client, payer, _) = program_test.start().await;
+
+    let mut instruction = client::initialize_quiz_ix(&program_id, &payer.pubkey(), QUIZ_ID, 1);
+    instruction.accounts[1].pubkey = Keypair::new().pubkey();
+    let result = send(&mut banks_client, &payer, &[instruction], &[]).await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::InvalidPda as u32)
+    );
+}
+
+#[tokio::test]
+async fn over_length_answers_are_rejected() {
+    let (program_test, program_id) = program_test();
+    let (mut banks_client, payer, _) = program_test.start().await;
+    let quiz = started_quiz(
+        &mut banks_client,
+        &payer,
+        &program_id,
+        QuizMode::Practice,
+        &[0, 1],
+    )
+    .await;
+    let player = funded_keypair(&mut banks_client, &payer).await;
+
+    let result = send(
+        &mut banks_client,
+        &payer,
+        &[client::practice_quiz_ix(
+            &program_id,
+            &player.pubkey(),
+            &quiz,
+            vec![0, 1, 2],
+        )],
+        &[&player],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::InvalidInstructionData
+    );
+}
+
+#[tokio::test]
+async fn out_of_range_answer_is_rejected() {
+    let (program_test, program_id) = program_test();
+    let (mut banks_client, payer, _) = program_test.start().await;
+    let quiz = started_quiz(
+        &mut banks_client,
+        &payer,
+        &program_id,
+        QuizMode::Practice,
+        &[0],
+    )
+    .await;
+    let player = funded_keypair(&mut banks_client, &payer).await;
+
+    let result = send(
+        &mut banks_client,
+        &payer,
+        &[client::practice_quiz_ix(
+            &program_id,
+            &player.pubkey(),
+            &quiz,
+            vec![QuizQuestion::OPTION_COUNT],
+        )],
+        &[&player],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::InvalidAnswerIndex as u32)
+    );
+}
+
+#[tokio::test]
+async fn session_key_answers_for_player() {
+    let (program_test, program_id) = program_test();
+    let (mut banks_client, payer, _) = program_test.start().await;
+    let base_layer = DelegationConfig {
+        base_layer: true,
+        ..DelegationConfig::default()
+    };
+    let quiz = started_quiz_with(
+        &mut banks_client,
+        &payer,
+        &program_id,
+        QuizMode::Async,
+        &[3, 1],
+        base_layer,
+    )
+    .await;
+    let player = funded_keypair(&mut banks_client, &payer).await;
+    let session_key = Keypair::new();
+
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::join_quiz_ix(&program_id, &player.pubkey(), &quiz, 0, None),
+            client::create_session_ix(
+                &program_id,
+                &player.pubkey(),
+                &quiz,
+                &session_key.pubkey(),
+                u64::MAX,
+                1_000_000,
+            ),
+        ],
+        &[&player],
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        banks_client
+            .get_balance(session_key.pubkey())
+            .await
+            .unwrap(),
+        1_000_000
+    );
+
+    // Only the registered key can use the session
+    let stranger = Keypair::new();
+    let result = send(
+        &mut banks_client,
+        &payer,
+        &[client::sign_with_session(
+            &program_id,
+            client::submit_answers_ix(&program_id, &player.pubkey(), &quiz, vec![3, 2]),
+            &stranger.pubkey(),
+        )],
+        &[&stranger],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::InvalidSession as u32)
+    );
+
+    send(
+        &mut banks_client,
+        &payer,
+        &[client::sign_with_session(
+            &program_id,
+            client::submit_answers_ix(&program_id, &player.pubkey(), &quiz, vec![3, 2]),
+            &session_key.pubkey(),
+        )],
+        &[&session_key],
+    )
+    .await
+    .unwrap();
+    send(
+        &mut banks_client,
+        &payer,
+        &[client::calculate_scores_ix(
+            &program_id,
+            &payer.pubkey(),
+            &quiz,
+            2,
+            &[player.pubkey()],
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let score_address = client::find_player_score_address(&program_id, &quiz, &player.pubkey());
+    assert_eq!(
+        player_score(&mut banks_client, score_address).await.score,
+        1
+    );
+}
+
+#[tokio::test]
+async fn host_sponsors_player_with_empty_wallet() {
+    let (program_test, program_id) = program_test();
+    let (mut banks_client, payer, _) = program_test.start().await;
+    let base_layer = DelegationConfig {
+        base_layer: true,
+        ..DelegationConfig::default()
+    };
+    let quiz = started_quiz_with(
+        &mut banks_client,
+        &payer,
+        &program_id,
+        QuizMode::Async,
+        &[3, 1],
+        base_layer,
+    )
+    .await;
+    let player = Keypair::new();
+
+    // Only the quiz host can sponsor
+    let stranger = funded_keypair(&mut banks_client, &payer).await;
+    let result = send(
+        &mut banks_client,
+        &payer,
+        &[client::sponsored_by(
+            client::join_quiz_ix(&program_id, &player.pubkey(), &quiz, 0, None),
+            &stranger.pubkey(),
+        )],
+        &[&player, &stranger],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::InvalidAccountData
+    );
+
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::sponsored_by(
+                client::join_quiz_ix(&program_id, &player.pubkey(), &quiz, 0, None),
+                &payer.pubkey(),
+            ),
+            client::submit_answers_ix(&program_id, &player.pubkey(), &quiz, vec![3, 1]),
+        ],
+        &[&player],
+    )
+    .await
+    .unwrap();
+    assert_eq!(banks_client.get_balance(player.pubkey()).await.unwrap(), 0);
+    assert_eq!(quiz_session(&mut banks_client, quiz).await.player_count, 1);
+}
+
+#[tokio::test]
+async fn relayers_submit_answers_the_player_signed() {
+    let (program_test, program_id) = program_test();
+    let (mut banks_client, payer, _) = program_test.start().await;
+    let base_layer = DelegationConfig {
+        base_layer: true,
+        ..DelegationConfig::default()
+    };
+    let quiz = started_quiz_with(
+        &mut banks_client,
+        &payer,
+        &program_id,
+        QuizMode::Async,
+        &[3, 1],
+        base_layer,
+    )
+    .await;
+    let player = Keypair::new();
+    send(
+        &mut banks_client,
+        &payer,
+        &[client::sponsored_by(
+            client::join_quiz_ix(&program_id, &player.pubkey(), &quiz, 0, None),
+            &payer.pubkey(),
+        )],
+        &[&player],
+    )
+    .await
+    .unwrap();
+    let signed = |answers: &[u8], nonce: u64| {
+        let message = PlayerAnswer::relay_message(&quiz, answers, &[], nonce);
+        let signature: [u8; 64] = player.sign_message(&message).as_ref().try_into().unwrap();
+        client::ed25519_signature_ix(&player.pubkey(), &signature, &message)
+    };
+    let relayed = |answers: Vec<u8>, relayer: &Pubkey, nonce: u64| {
+        client::submit_relayed_answers_ix(
+            &program_id,
+            relayer,
+            &player.pubkey(),
+            &quiz,
+            answers,
+            Vec::new(),
+            nonce,
+        )
+    };
+
+    // The relayer can't change what the player signed
+    let result = send(
+        &mut banks_client,
+        &payer,
+        &[signed(&[3, 2], 1), relayed(vec![3, 1], &payer.pubkey(), 1)],
+        &[],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::SignatureNotVerified as u32)
+    );
+
+    send(
+        &mut banks_client,
+        &payer,
+        &[signed(&[3, 2], 1), relayed(vec![3, 2], &payer.pubkey(), 1)],
+        &[],
+    )
+    .await
+    .unwrap();
+    let address = client::find_player_answer_address(&program_id, &quiz, &player.pubkey());
+    let account = banks_client.get_account(address).await.unwrap().unwrap();
+    let sheet = PlayerAnswer::try_from_slice(&account.data).unwrap();
+    assert!(sheet.submitted);
+    assert_eq!(sheet.answers, vec![3, 2]);
+    assert_eq!(sheet.relay_nonce, 1);
+    assert_eq!(banks_client.get_balance(player.pubkey()).await.unwrap(), 0);
+
+    // Paid by another wallet so it isn't a duplicate of the first relay
+    let relayer = funded_keypair(&mut banks_client, &payer).await;
+    let result = send(
+        &mut banks_client,
+        &relayer,
+        &[
+            signed(&[3, 2], 1),
+            relayed(vec![3, 2], &relayer.pubkey(), 1),
+        ],
+        &[],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::StaleRelayNonce as u32)
+    );
+}
+
+#[tokio::test]
+async fn scheduled_quiz_starts_from_crank() {
+    let (program_test, program_id) = program_test();
+    let mut context = program_test.start_with_context().await;
+    let payer = context.payer.insecure_clone();
+    let host = payer.pubkey();
+    let quiz = client::find_quiz_address(&program_id, &host, QUIZ_ID);
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[
+            client::initialize_quiz_ix(&program_id, &host, QUIZ_ID, 1),
+            question(&program_id, &host, &quiz, 0, 2),
+            client::schedule_start_ix(&program_id, &host, &quiz, 50),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    // The crank is rejected until the start slot
+    let cranker = funded_keypair(&mut context.banks_client, &payer).await;
+    let result = send(
+        &mut context.banks_client,
+        &cranker,
+        &[client::activate_quiz_ix(&program_id, &quiz)],
+        &[],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::StartSlotNotReached as u32)
+    );
+
+    context.warp_to_slot(50).unwrap();
+    send(
+        &mut context.banks_client,
+        &cranker,
+        &[client::activate_quiz_ix(&program_id, &quiz)],
+        &[],
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        quiz_session(&mut context.banks_client, quiz).await.status,
+        QuizStatus::Active
+    );
+}
+
+#[tokio::test]
+async fn presigned_start_runs_on_a_durable_nonce() {
+    let (program_test, program_id) = program_test();
+    let mut context = program_test.start_with_context().await;
+    let payer = context.payer.insecure_clone();
+    let host = payer.pubkey();
+    let quiz = client::find_quiz_address(&program_id, &host, QUIZ_ID);
+    let nonce_account = Keypair::new();
+    let mut setup = vec![
+        client::initialize_quiz_ix(&program_id, &host, QUIZ_ID, 1),
+        question(&program_id, &host, &quiz, 0, 2),
+    ];
+    setup.extend(client::create_nonce_account_ixs(
+        &host,
+        &nonce_account.pubkey(),
+        &host,
+        Rent::default().minimum_balance(client::NONCE_ACCOUNT_SIZE),
+    ));
+    send(&mut context.banks_client, &payer, &setup, &[&nonce_account])
+        .await
+        .unwrap();
+
+    let account = context
+        .banks_client
+        .get_account(nonce_account.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    let (authority, nonce) = client::read_nonce_account(&account.data).unwrap();
+    assert_eq!(authority, host);
+    let message = client::durable_start_quiz_message(
+        &program_id,
+        &host,
+        &quiz,
+        &nonce_account.pubkey(),
+        &nonce,
+    );
+    let presigned = Transaction::new(&[&payer], message, nonce);
+
+    // Long after any blockhash from signing time would have expired
+    context.warp_to_slot(500).unwrap();
+    context
+        .banks_client
+        .process_transaction(presigned.clone())
+        .await
+        .unwrap();
+    assert_eq!(
+        quiz_session(&mut context.banks_client, quiz).await.status,
+        QuizStatus::Active
+    );
+
+    // Sending advanced the nonce, so the same transaction can't run again
+    let account = context
+        .banks_client
+        .get_account(nonce_account.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_ne!(client::read_nonce_account(&account.data).unwrap().1, nonce);
+    assert!(context
+        .banks_client
+        .process_transaction(presigned)
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn closed_submissions_reject_answers() {
+    let (program_test, program_id) = program_test();
+    let (mut banks_client, payer, _) = program_test.start().await;
+    let base_layer = DelegationConfig {
+        base_layer: true,
+        ..DelegationConfig::default()
+    };
+    let quiz = started_quiz_with(
+        &mut banks_client,
+        &payer,
+        &program_id,
+        QuizMode::Async,
+        &[3, 1],
+        base_layer,
+    )
+    .await;
+    let player = funded_keypair(&mut banks_client, &payer).await;
+    send(
+        &mut banks_client,
+        &payer,
+        &[client::join_quiz_ix(
+            &program_id,
+            &player.pubkey(),
+            &quiz,
+            0,
+            None,
+        )],
+        &[&player],
+    )
+    .await
+    .unwrap();
+
+    // Without a passed deadline only the host can close
+    let result = send(
+        &mut banks_client,
+        &payer,
+        &[client::close_submissions_ix(
+            &program_id,
+            &player.pubkey(),
+            &quiz,
+        )],
+        &[&player],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::InvalidAccountData
+    );
+
+    send(
+        &mut banks_client,
+        &payer,
+        &[client::close_submissions_ix(
+            &program_id,
+            &payer.pubkey(),
+            &quiz,
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+    let result = send(
+        &mut banks_client,
+        &payer,
+        &[client::submit_answers_ix(
+            &program_id,
+            &player.pubkey(),
+            &quiz,
+            vec![3, 1],
+        )],
+        &[&player],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::SubmissionsClosed as u32)
+    );
+    assert_eq!(
+        quiz_session(&mut banks_client, quiz).await.status,
+        QuizStatus::SubmissionsClosed
+    );
+
+    // A closed quiz can still be called off, but never reopened
+    send(
+        &mut banks_client,
+        &payer,
+        &[client::cancel_quiz_ix(&program_id, &payer.pubkey(), &quiz)],
+        &[],
+    )
+    .await
+    .unwrap();
+    let result = send(
+        &mut banks_client,
+        &payer,
+        &[client::start_quiz_ix(&program_id, &payer.pubkey(), &quiz)],
+        &[],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::InvalidAccountData
+    );
+    assert_eq!(
+        quiz_session(&mut banks_client, quiz).await.status,
+        QuizStatus::Cancelled
+    );
+    let address = client::find_host_stats_address(&program_id, &payer.pubkey());
+    let account = banks_client.get_account(address).await.unwrap().unwrap();
+    let host_stats = HostStats::try_from_slice(&account.data).unwrap();
+    assert_eq!(host_stats.quizzes_cancelled, 1);
+    assert_eq!(host_stats.reputation_bps(), 0);
+}
+
+#[tokio::test]
+async fn late_joins_close_after_grace_window() {
+    let (program_test, program_id) = program_test();
+    let mut context = program_test.start_with_context().await;
+    let payer = context.payer.insecure_clone();
+    let host = payer.pubkey();
+    let quiz = client::find_quiz_address(&program_id, &host, QUIZ_ID);
+    let base_layer = DelegationConfig {
+        base_layer: true,
+        ..DelegationConfig::default()
+    };
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[
+            client::initialize_quiz_with_delegation_ix(&program_id, &host, QUIZ_ID, 1, base_layer),
+            question(&program_id, &host, &quiz, 0, 2),
+            client::set_submission_policy_ix(&program_id, &host, &quiz, false, false, 0, 20),
+            client::start_quiz_ix(&program_id, &host, &quiz),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    let start_slot = quiz_session(&mut context.banks_client, quiz)
+        .await
+        .start_slot;
+
+    let early = funded_keypair(&mut context.banks_client, &payer).await;
+    let late = funded_keypair(&mut context.banks_client, &payer).await;
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[client::join_quiz_ix(
+            &program_id,
+            &early.pubkey(),
+            &quiz,
+            0,
+            None,
+        )],
+        &[&early],
+    )
+    .await
+    .unwrap();
+
+    context.warp_to_slot(start_slot + 21).unwrap();
+    let result = send(
+        &mut context.banks_client,
+        &payer,
+        &[client::join_quiz_ix(
+            &program_id,
+            &late.pubkey(),
+            &quiz,
+            0,
+            None,
+        )],
+        &[&late],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::JoinWindowClosed as u32)
+    );
+    assert_eq!(
+        quiz_session(&mut context.banks_client, quiz)
+            .await
+            .player_count,
+        1
+    );
+}
+
+#[tokio::test]
+async fn late_sheets_lose_points() {
+    let (program_test, program_id) = program_test();
+    let mut context = program_test.start_with_context().await;
+    let payer = context.payer.insecure_clone();
+    let host = payer.pubkey();
+    let quiz = client::find_quiz_address(&program_id, &host, QUIZ_ID);
+    let base_layer = DelegationConfig {
+        base_layer: true,
+        ..DelegationConfig::default()
+    };
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[
+            client::initialize_quiz_with_delegation_ix(&program_id, &host, QUIZ_ID, 2, base_layer),
+            question(&program_id, &host, &quiz, 0, 3),
+            question(&program_id, &host, &quiz, 1, 1),
+            client::set_submission_policy_ix(&program_id, &host, &quiz, false, false, 200, 0),
+            client::set_late_policy_ix(&program_id, &host, &quiz, 100, 20),
+            client::start_quiz_ix(&program_id, &host, &quiz),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    // 12 slots late costs one point
+    let player = funded_keypair(&mut context.banks_client, &payer).await;
+    context.warp_to_slot(212).unwrap();
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[
+            client::join_quiz_ix(&program_id, &player.pubkey(), &quiz, 0, None),
+            client::submit_answers_ix(&program_id, &player.pubkey(), &quiz, vec![3, 1]),
+        ],
+        &[&player],
+    )
+    .await
+    .unwrap();
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[client::calculate_scores_ix(
+            &program_id,
+            &host,
+            &quiz,
+            2,
+            &[player.pubkey()],
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let score_address = client::find_player_score_address(&program_id, &quiz, &player.pubkey());
+    let score = player_score(&mut context.banks_client, score_address).await;
+    assert_eq!(score.late_penalty, 1);
+    assert_eq!(score.score, 1);
+}
+
+#[tokio::test]
+async fn skipped_answers_are_counted_apart() {
+    let (program_test, program_id) = program_test();
+    let (mut banks_client, payer, _) = program_test.start().await;
+    let base_layer = DelegationConfig {
+        base_layer: true,
+        ..DelegationConfig::default()
+    };
+    let quiz = started_quiz_with(
+        &mut banks_client,
+        &payer,
+        &program_id,
+        QuizMode::Async,
+        &[3, 1, 0],
+        base_layer,
+    )
+    .await;
+    let player = funded_keypair(&mut banks_client, &payer).await;
+
+    // Anything past the options other than the skip value is still rejected
+    let result = send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::join_quiz_ix(&program_id, &player.pubkey(), &quiz, 0, None),
+            client::submit_answers_ix(&program_id, &player.pubkey(), &quiz, vec![3, 7, 0]),
+        ],
+        &[&player],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::InvalidAnswerIndex as u32)
+    );
+
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::join_quiz_ix(&program_id, &player.pubkey(), &quiz, 0, None),
+            client::submit_answers_ix(
+                &program_id,
+                &player.pubkey(),
+                &quiz,
+                vec![3, QuizQuestion::SKIPPED, 2],
+            ),
+        ],
+        &[&player],
+    )
+    .await
+    .unwrap();
+    send(
+        &mut banks_client,
+        &payer,
+        &[client::calculate_scores_ix(
+            &program_id,
+            &payer.pubkey(),
+            &quiz,
+            3,
+            &[player.pubkey()],
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let score_address = client::find_player_score_address(&program_id, &quiz, &player.pubkey());
+    let score = player_score(&mut banks_client, score_address).await;
+    assert_eq!(score.score, 1);
+    assert_eq!(score.skipped, 1);
+}
+
+#[tokio::test]
+async fn confidence_weights_each_answer() {
+    let (program_test, program_id) = program_test();
+    let (mut banks_client, payer, _) = program_test.start().await;
+    let host = payer.pubkey();
+    let quiz = client::find_quiz_address(&program_id, &host, QUIZ_ID);
+    let base_layer = DelegationConfig {
+        base_layer: true,
+        ..DelegationConfig::default()
+    };
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::initialize_quiz_with_delegation_ix(&program_id, &host, QUIZ_ID, 3, base_layer),
+            question(&program_id, &host, &quiz, 0, 3),
+            question(&program_id, &host, &quiz, 1, 1),
+            question(&program_id, &host, &quiz, 2, 0),
+            client::set_confidence_scoring_ix(&program_id, &host, &quiz, true),
+            client::start_quiz_ix(&program_id, &host, &quiz),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    let player = funded_keypair(&mut banks_client, &payer).await;
+
+    // Weighted quizzes need a level for every answer
+    let result = send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::join_quiz_ix(&program_id, &player.pubkey(), &quiz, 0, None),
+            client::submit_weighted_answers_ix(
+                &program_id,
+                &player.pubkey(),
+                &quiz,
+                vec![3, 2, 0],
+                vec![3, 4, 2],
+            ),
+        ],
+        &[&player],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::InvalidConfidence as u32)
+    );
+
+    // Two right at 3 and 2, one wrong at 1
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::join_quiz_ix(&program_id, &player.pubkey(), &quiz, 0, None),
+            client::submit_weighted_answers_ix(
+                &program_id,
+                &player.pubkey(),
+                &quiz,
+                vec![3, 2, 0],
+                vec![3, 1, 2],
+            ),
+        ],
+        &[&player],
+    )
+    .await
+    .unwrap();
+    send(
+        &mut banks_client,
+        &payer,
+        &[client::calculate_scores_ix(
+            &program_id,
+            &host,
+            &quiz,
+            3,
+            &[player.pubkey()],
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let score_address = client::find_player_score_address(&program_id, &quiz, &player.pubkey());
+    let score = player_score(&mut banks_client, score_address).await;
+    assert_eq!(score.score, 4);
+}
+
+#[tokio::test]
+async fn bought_hints_cost_points_and_lamports() {
+    let (program_test, program_id) = program_test();
+    let (mut banks_client, payer, _) = program_test.start().await;
+    let host = payer.pubkey();
+    let quiz = client::find_quiz_address(&program_id, &host, QUIZ_ID);
+    let base_layer = DelegationConfig {
+        base_layer: true,
+        ..DelegationConfig::default()
+    };
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::initialize_quiz_with_delegation_ix(&program_id, &host, QUIZ_ID, 2, base_layer),
+            question(&program_id, &host, &quiz, 0, 3),
+            question(&program_id, &host, &quiz, 1, 1),
+            client::set_hint_ix(
+                &program_id,
+                &host,
+                &quiz,
+                0,
+                "Count the corners".to_string(),
+                1,
+                1_000_000,
+            ),
+            client::start_quiz_ix(&program_id, &host, &quiz),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    let player = funded_keypair(&mut banks_client, &payer).await;
+
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::join_quiz_ix(&program_id, &player.pubkey(), &quiz, 0, None),
+            client::buy_hint_ix(&program_id, &player.pubkey(), &quiz, 0, 1_000_000),
+        ],
+        &[&player],
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        quiz_session(&mut banks_client, quiz).await.prize_pool,
+        1_000_000
+    );
+
+    // Each hint is only bought once
+    let result = send(
+        &mut banks_client,
+        &payer,
+        &[client::buy_hint_ix(
+            &program_id,
+            &player.pubkey(),
+            &quiz,
+            0,
+            1_000_000,
+        )],
+        &[&player],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::HintAlreadyBought as u32)
+    );
+
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::submit_answers_ix(&program_id, &player.pubkey(), &quiz, vec![3, 1]),
+            client::calculate_scores_ix(&program_id, &host, &quiz, 2, &[player.pubkey()]),
+        ],
+        &[&player],
+    )
+    .await
+    .unwrap();
+
+    let score_address = client::find_player_score_address(&program_id, &quiz, &player.pubkey());
+    let score = player_score(&mut banks_client, score_address).await;
+    assert_eq!(score.score, 1);
+}
+
+#[tokio::test]
+async fn bonus_round_counts_double() {
+    let (program_test, program_id) = program_test();
+    let (mut banks_client, payer, _) = program_test.start().await;
+    let host = payer.pubkey();
+    let quiz = client::find_quiz_address(&program_id, &host, QUIZ_ID);
+    let base_layer = DelegationConfig {
+        base_layer: true,
+        ..DelegationConfig::default()
+    };
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::initialize_quiz_with_delegation_ix(&program_id, &host, QUIZ_ID, 3, base_layer),
+            question(&program_id, &host, &quiz, 0, 3),
+            question(&program_id, &host, &quiz, 1, 1),
+            question(&program_id, &host, &quiz, 2, 0),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let result = send(
+        &mut banks_client,
+        &payer,
+        &[client::set_round_multiplier_ix(
+            &program_id,
+            &host,
+            &quiz,
+            1,
+            2,
+            4,
+        )],
+        &[],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::InvalidMultiplier as u32)
+    );
+
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::set_round_multiplier_ix(&program_id, &host, &quiz, 1, 2, 2),
+            client::start_quiz_ix(&program_id, &host, &quiz),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    // One plain point and one doubled, the last answer is wrong
+    let player = funded_keypair(&mut banks_client, &payer).await;
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::join_quiz_ix(&program_id, &player.pubkey(), &quiz, 0, None),
+            client::submit_answers_ix(&program_id, &player.pubkey(), &quiz, vec![3, 1, 2]),
+            client::calculate_scores_ix(&program_id, &host, &quiz, 3, &[player.pubkey()]),
+        ],
+        &[&player],
+    )
+    .await
+    .unwrap();
+
+    let score_address = client::find_player_score_address(&program_id, &quiz, &player.pubkey());
+    let score = player_score(&mut banks_client, score_address).await;
+    assert_eq!(score.score, 3);
+}
+
+#[tokio::test]
+async fn hard_questions_are_worth_more() {
+    let (program_test, program_id) = program_test();
+    let (mut banks_client, payer, _) = program_test.start().await;
+    let host = payer.pubkey();
+    let quiz = client::find_quiz_address(&program_id, &host, QUIZ_ID);
+    let base_layer = DelegationConfig {
+        base_layer: true,
+        ..DelegationConfig::default()
+    };
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::initialize_quiz_with_delegation_ix(&program_id, &host, QUIZ_ID, 3, base_layer),
+            question(&program_id, &host, &quiz, 0, 3),
+            question(&program_id, &host, &quiz, 1, 1),
+            question(&program_id, &host, &quiz, 2, 0),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let result = send(
+        &mut banks_client,
+        &payer,
+        &[client::set_question_difficulty_ix(
+            &program_id,
+            &host,
+            &quiz,
+            1,
+            2,
+            QuizQuestion::MAX_DIFFICULTY + 1,
+        )],
+        &[],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::InvalidDifficulty as u32)
+    );
+
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::set_question_difficulty_ix(&program_id, &host, &quiz, 1, 2, 3),
+            client::set_difficulty_scoring_ix(&program_id, &host, &quiz, true),
+            client::start_quiz_ix(&program_id, &host, &quiz),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    let question_address = client::find_question_address(&program_id, &quiz, 1);
+    let account = banks_client
+        .get_account(question_address)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        QuizQuestion::try_from_slice(&account.data)
+            .unwrap()
+            .difficulty,
+        3
+    );
+
+    // An easy point and a hard one, the last answer is wrong
+    let player = funded_keypair(&mut banks_client, &payer).await;
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::join_quiz_ix(&program_id, &player.pubkey(), &quiz, 0, None),
+            client::submit_answers_ix(&program_id, &player.pubkey(), &quiz, vec![3, 1, 2]),
+            client::calculate_scores_ix(&program_id, &host, &quiz, 3, &[player.pubkey()]),
+        ],
+        &[&player],
+    )
+    .await
+    .unwrap();
+
+    let score_address = client::find_player_score_address(&program_id, &quiz, &player.pubkey());
+    let score = player_score(&mut banks_client, score_address).await;
+    assert_eq!(score.score, 4);
+}
+
+#[tokio::test]
+async fn adaptive_quizzes_serve_questions_by_level() {
+    let (program_test, program_id) = program_test();
+    let (mut banks_client, payer, _) = program_test.start().await;
+    let host = payer.pubkey();
+    let quiz = client::find_quiz_address(&program_id, &host, QUIZ_ID);
+    let base_layer = DelegationConfig {
+        base_layer: true,
+        ..DelegationConfig::default()
+    };
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::initialize_quiz_with_delegation_ix(&program_id, &host, QUIZ_ID, 4, base_layer),
+            question(&program_id, &host, &quiz, 0, 3),
+            question(&program_id, &host, &quiz, 1, 1),
+            question(&program_id, &host, &quiz, 2, 0),
+            question(&program_id, &host, &quiz, 3, 0),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    // The bank needs an adaptive quiz
+    let result = send(
+        &mut banks_client,
+        &payer,
+        &[client::init_adaptive_bank_ix(
+            &program_id,
+            &host,
+            &quiz,
+            4,
+            3,
+        )],
+        &[],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::WrongQuizMode as u32)
+    );
+
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::set_quiz_mode_ix(&program_id, &host, &quiz, QuizMode::Adaptive, 0),
+            client::set_question_difficulty_ix(&program_id, &host, &quiz, 1, 1, 2),
+            client::set_question_difficulty_ix(&program_id, &host, &quiz, 2, 2, 3),
+            client::init_adaptive_bank_ix(&program_id, &host, &quiz, 4, 3),
+            client::start_quiz_ix(&program_id, &host, &quiz),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let player = funded_keypair(&mut banks_client, &payer).await;
+    let answer = |question_index: u8, answer: u8| {
+        client::with_adaptive_bank(
+            &program_id,
+            &quiz,
+            client::submit_answer_ix(
+                &program_id,
+                &player.pubkey(),
+                &quiz,
+                question_index,
+                answer,
+                false,
+            ),
+        )
+    };
+    send(
+        &mut banks_client,
+        &payer,
+        &[client::join_quiz_ix(
+            &program_id,
+            &player.pubkey(),
+            &quiz,
+            0,
+            None,
+        )],
+        &[&player],
+    )
+    .await
+    .unwrap();
+
+    // Players start on the easiest question
+    let result = send(&mut banks_client, &payer, &[answer(1, 1)], &[&player]).await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::QuestionNotServed as u32)
+    );
+
+    // A right answer serves a harder question, a wrong one an easier one
+    send(
+        &mut banks_client,
+        &payer,
+        &[answer(0, 3), answer(1, 2), answer(3, 0)],
+        &[&player],
+    )
+    .await
+    .unwrap();
+
+    let sheet_address = client::find_player_answer_address(&program_id, &quiz, &player.pubkey());
+    let account = banks_client
+        .get_account(sheet_address)
+        .await
+        .unwrap()
+        .unwrap();
+    let sheet = PlayerAnswer::try_from_slice(&account.data).unwrap();
+    assert_eq!(sheet.adaptive_level, 2);
+    assert!(sheet.submitted);
+    assert!(!sheet.is_answered(2));
+
+    // Nothing is served past the player's share
+    let result = send(&mut banks_client, &payer, &[answer(2, 0)], &[&player]).await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::QuestionNotServed as u32)
+    );
+}
+
+#[tokio::test]
+async fn players_answer_their_own_question_subset() {
+    let (program_test, program_id) = program_test();
+    let (mut banks_client, payer, _) = program_test.start().await;
+    let host = payer.pubkey();
+    let quiz = client::find_quiz_address(&program_id, &host, QUIZ_ID);
+    let base_layer = DelegationConfig {
+        base_layer: true,
+        ..DelegationConfig::default()
+    };
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::initialize_quiz_with_delegation_ix(&program_id, &host, QUIZ_ID, 4, base_layer),
+            question(&program_id, &host, &quiz, 0, 0),
+            question(&program_id, &host, &quiz, 1, 1),
+            question(&program_id, &host, &quiz, 2, 2),
+            question(&program_id, &host, &quiz, 3, 3),
+            client::set_question_subset_ix(&program_id, &host, &quiz, 2, [7; 32]),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    // Subsets need whole sheets
+    let result = send(
+        &mut banks_client,
+        &payer,
+        &[client::set_quiz_mode_ix(
+            &program_id,
+            &host,
+            &quiz,
+            QuizMode::Live,
+            10,
+        )],
+        &[],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::WrongQuizMode as u32)
+    );
+
+    send(
+        &mut banks_client,
+        &payer,
+        &[client::start_quiz_ix(&program_id, &host, &quiz)],
+        &[],
+    )
+    .await
+    .unwrap();
+    let player = funded_keypair(&mut banks_client, &payer).await;
+    let subset = quiz_session(&mut banks_client, quiz)
+        .await
+        .question_subset(&player.pubkey());
+    assert_eq!(subset.len(), 2);
+    assert_ne!(subset[0], subset[1]);
+
+    // One answer per served question, not per question of the quiz
+    let result = send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::join_quiz_ix(&program_id, &player.pubkey(), &quiz, 0, None),
+            client::submit_answers_ix(&program_id, &player.pubkey(), &quiz, vec![0, 1, 2, 3]),
+        ],
+        &[&player],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::InvalidInstructionData
+    );
+
+    // Answered one at a time, only served questions are taken and the
+    // sheet is complete once they all are
+    let other = funded_keypair(&mut banks_client, &payer).await;
+    let other_subset = quiz_session(&mut banks_client, quiz)
+        .await
+        .question_subset(&other.pubkey());
+    let unserved = (0..4).find(|index| !other_subset.contains(index)).unwrap();
+    let answer = |question_index| {
+        client::submit_answer_ix(
+            &program_id,
+            &other.pubkey(),
+            &quiz,
+            question_index,
+            0,
+            false,
+        )
+    };
+    let result = send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::join_quiz_ix(&program_id, &other.pubkey(), &quiz, 0, None),
+            answer(unserved),
+        ],
+        &[&other],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::QuestionNotServed as u32)
+    );
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::join_quiz_ix(&program_id, &other.pubkey(), &quiz, 0, None),
+            answer(other_subset[0]),
+            answer(other_subset[1]),
+        ],
+        &[&other],
+    )
+    .await
+    .unwrap();
+    let address = client::find_player_answer_address(&program_id, &quiz, &other.pubkey());
+    let account = banks_client.get_account(address).await.unwrap().unwrap();
+    assert!(
+        PlayerAnswer::try_from_slice(&account.data)
+            .unwrap()
+            .submitted
+    );
+
+    // The first served question is answered right and the second wrong
+    let wrong = (subset[1] + 1) % 4;
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::join_quiz_ix(&program_id, &player.pubkey(), &quiz, 0, None),
+            client::submit_answers_ix(&program_id, &player.pubkey(), &quiz, vec![subset[0], wrong]),
+            client::calculate_scores_ix(
+                &program_id,
+                &host,
+                &quiz,
+                4,
+                &[player.pubkey(), other.pubkey()],
+            ),
+        ],
+        &[&player],
+    )
+    .await
+    .unwrap();
+
+    let sheet_address = client::find_player_answer_address(&program_id, &quiz, &player.pubkey());
+    let account = banks_client
+        .get_account(sheet_address)
+        .await
+        .unwrap()
+        .unwrap();
+    let sheet = PlayerAnswer::try_from_slice(&account.data).unwrap();
+    assert_eq!(sheet.answered_count(), 2);
+    assert_eq!(sheet.answers[subset[1] as usize], wrong);
+
+    let score_address = client::find_player_score_address(&program_id, &quiz, &player.pubkey());
+    let score = player_score(&mut banks_client, score_address).await;
+    assert_eq!(score.score, 1);
+}
+
+#[tokio::test]
+async fn blind_questions_are_checked_against_their_commitment() {
+    let (program_test, program_id) = program_test();
+    let (mut banks_client, payer, _) = program_test.start().await;
+    let host = payer.pubkey();
+    let quiz = client::find_quiz_address(&program_id, &host, QUIZ_ID);
+    let content = QuestionData {
+        question_text: "Which planet is largest?".to_string(),
+        options: ["Mars", "Jupiter", "Venus", "Earth"].map(String::from),
+        correct_answer_index: 1,
+    };
+    let base_layer = DelegationConfig {
+        base_layer: true,
+        ..DelegationConfig::default()
+    };
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::initialize_quiz_with_delegation_ix(&program_id, &host, QUIZ_ID, 1, base_layer),
+            client::set_quiz_mode_ix(&program_id, &host, &quiz, QuizMode::Live, 0),
+            client::commit_question_ix(&program_id, &host, &quiz, 0, &content),
+            client::start_quiz_ix(&program_id, &host, &quiz),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    // Only the hash is on chain until the reveal
+    let question_address = client::find_question_address(&program_id, &quiz, 0);
+    assert!(banks_client
+        .get_account(question_address)
+        .await
+        .unwrap()
+        .is_none());
+
+    let forged = QuestionData {
+        correct_answer_index: 2,
+        ..content.clone()
+    };
+    let result = send(
+        &mut banks_client,
+        &payer,
+        &[client::reveal_blind_question_ix(
+            &program_id,
+            &host,
+            &quiz,
+            0,
+            forged,
+        )],
+        &[],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::QuestionHashMismatch as u32)
+    );
+
+    send(
+        &mut banks_client,
+        &payer,
+        &[client::reveal_blind_question_ix(
+            &program_id,
+            &host,
+            &quiz,
+            0,
+            content,
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+    let account = banks_client
+        .get_account(question_address)
+        .await
+        .unwrap()
+        .unwrap();
+    let question = QuizQuestion::try_from_slice(&account.data).unwrap();
+    assert_eq!(question.question_text, "Which planet is largest?");
+    assert_eq!(question.correct_answer_index, 1);
+    let commitment_address = client::find_question_commitment_address(&program_id, &quiz, 0);
+    assert!(banks_client
+        .get_account(commitment_address)
+        .await
+        .unwrap()
+        .is_none());
+
+    // The revealed question takes answers like any other
+    let player = funded_keypair(&mut banks_client, &payer).await;
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::join_quiz_ix(&program_id, &player.pubkey(), &quiz, 0, None),
+            client::submit_answer_ix(&program_id, &player.pubkey(), &quiz, 0, 1, false),
+        ],
+        &[&player],
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn live_answers_only_count_inside_the_question_window() {
+    let (program_test, program_id) = program_test();
+    let mut context = program_test.start_with_context().await;
+    let payer = context.payer.insecure_clone();
+    let host = payer.pubkey();
+    let base_layer = DelegationConfig {
+        base_layer: true,
+        ..DelegationConfig::default()
+    };
+    let quiz = started_quiz_with(
+        &mut context.banks_client,
+        &payer,
+        &program_id,
+        QuizMode::Live,
+        &[2, 1],
+        base_layer,
+    )
+    .await;
+    let player = funded_keypair(&mut context.banks_client, &payer).await;
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[
+            client::join_quiz_ix(&program_id, &player.pubkey(), &quiz, 0, None),
+            client::open_question_ix(&program_id, &host, &quiz, 0, 100, 110),
+        ],
+        &[&player],
+    )
+    .await
+    .unwrap();
+
+    // Before the window opens
+    let answer = client::submit_answer_ix(&program_id, &player.pubkey(), &quiz, 0, 2, false);
+    let result = send(
+        &mut context.banks_client,
+        &payer,
+        std::slice::from_ref(&answer),
+        &[&player],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::AnswerWindowClosed as u32)
+    );
+
+    // Inside it
+    context.warp_to_slot(105).unwrap();
+    send(&mut context.banks_client, &payer, &[answer], &[&player])
+        .await
+        .unwrap();
+
+    // And after it closes
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[client::open_question_ix(
+            &program_id,
+            &host,
+            &quiz,
+            1,
+            0,
+            120,
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+    context.warp_to_slot(121).unwrap();
+    let result = send(
+        &mut context.banks_client,
+        &payer,
+        &[client::submit_answer_ix(
+            &program_id,
+            &player.pubkey(),
+            &quiz,
+            1,
+            1,
+            false,
+        )],
+        &[&player],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::AnswerWindowClosed as u32)
+    );
+}
+
+#[tokio::test]
+async fn scoring_tallies_each_option() {
+    let (program_test, program_id) = program_test();
+    let (mut banks_client, payer, _) = program_test.start().await;
+    let base_layer = DelegationConfig {
+        base_layer: true,
+        ..DelegationConfig::default()
+    };
+    let quiz = started_quiz_with(
+        &mut banks_client,
+        &payer,
+        &program_id,
+        QuizMode::Async,
+        &[1, 2],
+        base_layer,
+    )
+    .await;
+
+    let mut players = Vec::new();
+    // The last player joins but never answers
+    for answers in [Some(vec![1, 2]), Some(vec![1, QuizQuestion::SKIPPED]), None] {
+        let player = funded_keypair(&mut banks_client, &payer).await;
+        let mut instructions = vec![client::join_quiz_ix(
+            &program_id,
+            &player.pubkey(),
+            &quiz,
+            0,
+            None,
+        )];
+        instructions.extend(answers.map(|answers| {
+            client::submit_answers_ix(&program_id, &player.pubkey(), &quiz, answers)
+        }));
+        send(&mut banks_client, &payer, &instructions, &[&player])
+            .await
+            .unwrap();
+        players.push(player.pubkey());
+    }
+    send(
+        &mut banks_client,
+        &payer,
+        &[client::with_question_stats(
+            &program_id,
+            client::calculate_scores_ix(&program_id, &payer.pubkey(), &quiz, 2, &players),
+            2,
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let stats_of = |index| client::find_question_stats_address(&program_id, &quiz, index);
+    let account = banks_client
+        .get_account(stats_of(0))
+        .await
+        .unwrap()
+        .unwrap();
+    let first = QuestionStats::try_from_slice(&account.data).unwrap();
+    assert_eq!(first.option_counts, [0, 2, 0, 0]);
+    assert_eq!(first.unanswered, 1);
+    let account = banks_client
+        .get_account(stats_of(1))
+        .await
+        .unwrap()
+        .unwrap();
+    let second = QuestionStats::try_from_slice(&account.data).unwrap();
+    assert_eq!(second.option_counts, [0, 0, 1, 0]);
+    assert_eq!((second.skipped, second.unanswered), (1, 1));
+    assert_eq!(second.player_count(), 3);
+
+    let address = client::find_host_stats_address(&program_id, &payer.pubkey());
+    let account = banks_client.get_account(address).await.unwrap().unwrap();
+    let host_stats = HostStats::try_from_slice(&account.data).unwrap();
+    assert_eq!(host_stats.quizzes_hosted, 1);
+    assert_eq!(host_stats.players_served, 3);
+    assert_eq!(host_stats.participation_bps(), 6_666);
+    assert_eq!(host_stats.quizzes_cancelled, 0);
+    assert_eq!(host_stats.reputation_bps(), 10_000);
+
+    // Scores of 2, 1 and 0
+    send(
+        &mut banks_client,
+        &payer,
+        &[client::compute_analytics_ix(
+            &program_id,
+            &payer.pubkey(),
+            &quiz,
+            2,
+            &players,
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+    let address = client::find_quiz_analytics_address(&program_id, &quiz);
+    let account = banks_client.get_account(address).await.unwrap().unwrap();
+    let analytics = QuizAnalytics::try_from_slice(&account.data).unwrap();
+    assert_eq!(analytics.player_count, 3);
+    assert_eq!(analytics.average_score_x100, 100);
+    assert_eq!(analytics.median_score_x100, 100);
+    assert_eq!(analytics.hardest_question, 1);
+    assert_eq!(analytics.easiest_question, 0);
+    assert_eq!(analytics.participation_bps, 6_666);
+}
+
+#[tokio::test]
+async fn players_flag_questions_during_the_dispute_window() {
+    let (program_test, program_id) = program_test();
+    let mut context = program_test.start_with_context().await;
+    let payer = context.payer.insecure_clone();
+    let host = payer.pubkey();
+    let quiz = client::find_quiz_address(&program_id, &host, QUIZ_ID);
+    let base_layer = DelegationConfig {
+        base_layer: true,
+        ..DelegationConfig::default()
+    };
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[
+            client::initialize_quiz_with_delegation_ix(&program_id, &host, QUIZ_ID, 1, base_layer),
+            question(&program_id, &host, &quiz, 0, 2),
+            client::set_dispute_window_ix(&program_id, &host, &quiz, 50),
+            client::start_quiz_ix(&program_id, &host, &quiz),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    let player = funded_keypair(&mut context.banks_client, &payer).await;
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[
+            client::join_quiz_ix(&program_id, &player.pubkey(), &quiz, 0, None),
+            client::submit_answers_ix(&program_id, &player.pubkey(), &quiz, vec![1]),
+            client::calculate_scores_ix(&program_id, &host, &quiz, 1, &[player.pubkey()]),
+        ],
+        &[&player],
+    )
+    .await
+    .unwrap();
+    let scored_slot = quiz_session(&mut context.banks_client, quiz)
+        .await
+        .scored_slot;
+
+    let flag = |reason_code| {
+        client::flag_question_ix(&program_id, &player.pubkey(), &quiz, 0, reason_code)
+    };
+    let result = send(&mut context.banks_client, &payer, &[flag(4)], &[&player]).await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::InvalidReasonCode as u32)
+    );
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[flag(QuestionDispute::REASON_WRONG_ANSWER)],
+        &[&player],
+    )
+    .await
+    .unwrap();
+
+    // One flag per player and question
+    let result = send(
+        &mut context.banks_client,
+        &payer,
+        &[flag(QuestionDispute::REASON_TYPO)],
+        &[&player],
+    )
+    .await;
+    assert!(result.is_err());
+
+    let address = client::find_question_dispute_address(&program_id, &quiz, 0);
+    let account = context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .unwrap();
+    let dispute = QuestionDispute::try_from_slice(&account.data).unwrap();
+    assert_eq!(dispute.reason_counts, [1, 0, 0, 0]);
+
+    // Nobody flags once the window is over
+    context.warp_to_slot(scored_slot + 51).unwrap();
+    let result = send(
+        &mut context.banks_client,
+        &payer,
+        &[flag(QuestionDispute::REASON_OTHER)],
+        &[&player],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::DisputeWindowClosed as u32)
+    );
+}
+
+#[tokio::test]
+async fn voided_questions_are_regraded() {
+    let (program_test, program_id) = program_test();
+    let mut context = program_test.start_with_context().await;
+    let payer = context.payer.insecure_clone();
+    let host = payer.pubkey();
+    let quiz = client::find_quiz_address(&program_id, &host, QUIZ_ID);
+    let base_layer = DelegationConfig {
+        base_layer: true,
+        ..DelegationConfig::default()
+    };
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[
+            client::initialize_quiz_with_delegation_ix(&program_id, &host, QUIZ_ID, 2, base_layer),
+            question(&program_id, &host, &quiz, 0, 2),
+            question(&program_id, &host, &quiz, 1, 0),
+            client::start_quiz_ix(&program_id, &host, &quiz),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    // The player misses question 0
+    let player = funded_keypair(&mut context.banks_client, &payer).await;
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[
+            client::join_quiz_ix(&program_id, &player.pubkey(), &quiz, 0, None),
+            client::submit_answers_ix(&program_id, &player.pubkey(), &quiz, vec![1, 0]),
+            client::calculate_scores_ix(&program_id, &host, &quiz, 2, &[player.pubkey()]),
+        ],
+        &[&player],
+    )
+    .await
+    .unwrap();
+    let score_address = client::find_player_score_address(&program_id, &quiz, &player.pubkey());
+    assert_eq!(
+        player_score(&mut context.banks_client, score_address)
+            .await
+            .score,
+        1
+    );
+
+    // Voiding alone leaves the score as it was
+    let void = |mode| client::void_question_ix(&program_id, &host, &quiz, 0, mode);
+    let regrade = |correction_count| {
+        client::regrade_ix(
+            &program_id,
+            &host,
+            &quiz,
+            2,
+            &[player.pubkey()],
+            correction_count,
+        )
+    };
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[void(VoidMode::AwardAll)],
+        &[],
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        player_score(&mut context.banks_client, score_address)
+            .await
+            .score,
+        1
+    );
+    send(&mut context.banks_client, &payer, &[regrade(0)], &[])
+        .await
+        .unwrap();
+    assert_eq!(
+        player_score(&mut context.banks_client, score_address)
+            .await
+            .score,
+        2
+    );
+
+    // An excluded question counts for nobody, right or wrong
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[void(VoidMode::Excluded), regrade(1)],
+        &[],
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        player_score(&mut context.banks_client, score_address)
+            .await
+            .score,
+        1
+    );
+
+    // Every player must be regraded
+    let result = send(
+        &mut context.banks_client,
+        &payer,
+        &[client::regrade_ix(&program_id, &host, &quiz, 2, &[], 2)],
+        &[],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::NotEnoughAccountKeys
+    );
+}
+
+#[tokio::test]
+async fn score_corrections_are_audited() {
+    let (program_test, program_id) = program_test();
+    let mut context = program_test.start_with_context().await;
+    let payer = context.payer.insecure_clone();
+    let host = payer.pubkey();
+    let quiz = client::find_quiz_address(&program_id, &host, QUIZ_ID);
+    let base_layer = DelegationConfig {
+        base_layer: true,
+        ..DelegationConfig::default()
+    };
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[
+            client::initialize_quiz_with_delegation_ix(&program_id, &host, QUIZ_ID, 1, base_layer),
+            question(&program_id, &host, &quiz, 0, 2),
+            client::start_quiz_ix(&program_id, &host, &quiz),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    let player = funded_keypair(&mut context.banks_client, &payer).await;
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[
+            client::join_quiz_ix(&program_id, &player.pubkey(), &quiz, 0, None),
+            client::submit_answers_ix(&program_id, &player.pubkey(), &quiz, vec![2]),
+            client::calculate_scores_ix(&program_id, &host, &quiz, 1, &[player.pubkey()]),
+        ],
+        &[&player],
+    )
+    .await
+    .unwrap();
+
+    let correct = |new_score, reason, correction_count| {
+        client::correct_score_ix(
+            &program_id,
+            &host,
+            &quiz,
+            &player.pubkey(),
+            new_score,
+            reason,
+            correction_count,
+        )
+    };
+
+    // Only regrades may claim to be regrades
+    let result = send(
+        &mut context.banks_client,
+        &payer,
+        &[correct(0, ScoreCorrection::REASON_REGRADE, 0)],
+        &[],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::InvalidReasonCode as u32)
+    );
+
+    // One more correction than a page holds spills onto a second page
+    let total = ScoreAudit::MAX_RECORDS as u32 + 1;
+    for count in 0..total {
+        send(
+            &mut context.banks_client,
+            &payer,
+            &[correct(
+                count % 2,
+                ScoreCorrection::REASON_SCORING_ERROR,
+                count,
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+    }
+    assert_eq!(
+        quiz_session(&mut context.banks_client, quiz)
+            .await
+            .correction_count,
+        total
+    );
+
+    let mut pages = Vec::new();
+    for page in 0..2 {
+        let address = client::find_score_audit_address(&program_id, &quiz, page);
+        let account = context
+            .banks_client
+            .get_account(address)
+            .await
+            .unwrap()
+            .unwrap();
+        pages.push(ScoreAudit::try_from_slice(&account.data).unwrap());
+    }
+    assert_eq!(pages[0].records().len(), ScoreAudit::MAX_RECORDS);
+    assert_eq!(
+        pages[0].records()[0],
+        ScoreCorrection {
+            player: player.pubkey(),
+            corrected_by: host,
+            slot: pages[0].records()[0].slot,
+            old_score: 1,
+            new_score: 0,
+            reason: ScoreCorrection::REASON_SCORING_ERROR,
+        }
+    );
+    assert_eq!(pages[1].page, 1);
+    assert_eq!(pages[1].records().len(), 1);
+    assert_eq!(pages[1].records()[0].new_score, 0);
+    assert_eq!(
+        player_score(
+            &mut context.banks_client,
+            client::find_player_score_address(&program_id, &quiz, &player.pubkey())
+        )
+        .await
+        .score,
+        0
+    );
+}
+
+#[tokio::test]
+async fn event_log_records_the_quiz_lifecycle() {
+    let (program_test, program_id) = program_test();
+    let mut context = program_test.start_with_context().await;
+    let payer = context.payer.insecure_clone();
+    let host = payer.pubkey();
+    let quiz = client::find_quiz_address(&program_id, &host, QUIZ_ID);
+    let base_layer = DelegationConfig {
+        base_layer: true,
+        ..DelegationConfig::default()
+    };
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[
+            client::initialize_quiz_with_delegation_ix(&program_id, &host, QUIZ_ID, 2, base_layer),
+            question(&program_id, &host, &quiz, 0, 2),
+            question(&program_id, &host, &quiz, 1, 0),
+            client::init_event_log_ix(&program_id, &host, &quiz),
+            client::start_quiz_ix(&program_id, &host, &quiz),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    let player = funded_keypair(&mut context.banks_client, &payer).await;
+
+    // Once the quiz has a log, joining without it fails
+    let join = client::join_quiz_ix(&program_id, &player.pubkey(), &quiz, 0, None);
+    let result = send(
+        &mut context.banks_client,
+        &payer,
+        std::slice::from_ref(&join),
+        &[&player],
+    )
+    .await;
+    assert!(result.is_err());
+
+    let logged = |ix| client::with_event_log(&program_id, &quiz, ix);
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[
+            logged(join),
+            logged(client::submit_answers_ix(
+                &program_id,
+                &player.pubkey(),
+                &quiz,
+                vec![2, 1],
+            )),
+            logged(client::calculate_scores_ix(
+                &program_id,
+                &host,
+                &quiz,
+                2,
+                &[player.pubkey()],
+            )),
+        ],
+        &[&player],
+    )
+    .await
+    .unwrap();
+
+    let address = client::find_event_log_address(&program_id, &quiz);
+    let account = context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .unwrap();
+    let log = QuizEventLog::try_from_slice(&account.data).unwrap();
+    let events: Vec<(u8, Pubkey, u8)> = log
+        .events()
+        .map(|event| (event.kind, event.actor, event.value))
+        .collect();
+    assert_eq!(
+        events,
+        [
+            (LoggedEvent::KIND_JOINED, player.pubkey(), 0),
+            (LoggedEvent::KIND_SUBMITTED, player.pubkey(), 2),
+            (LoggedEvent::KIND_FINALIZED, host, 1),
+        ]
+    );
+    assert_eq!(log.dropped(), 0);
+}
+
+#[tokio::test]
+async fn leaderboard_prizes_are_claimed_with_merkle_proofs() {
+    let (program_test, program_id) = program_test();
+    let mut context = program_test.start_with_context().await;
+    let payer = context.payer.insecure_clone();
+    let host = payer.pubkey();
+    let quiz = client::find_quiz_address(&program_id, &host, QUIZ_ID);
+    let base_layer = DelegationConfig {
+        base_layer: true,
+        ..DelegationConfig::default()
+    };
+    let entry_fee = 1_000_000;
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[
+            client::initialize_quiz_with_delegation_ix(&program_id, &host, QUIZ_ID, 1, base_layer),
+            question(&program_id, &host, &quiz, 0, 2),
+            client::set_entry_fee_ix(&program_id, &host, &quiz, entry_fee),
+            client::set_prize_split_ix(&program_id, &host, &quiz, [6_000, 4_000, 0]),
+            client::start_quiz_ix(&program_id, &host, &quiz),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    let mut players = Vec::new();
+    for _ in 0..3 {
+        let player = funded_keypair(&mut context.banks_client, &payer).await;
+        send(
+            &mut context.banks_client,
+            &payer,
+            &[client::join_quiz_ix(
+                &program_id,
+                &player.pubkey(),
+                &quiz,
+                entry_fee,
+                None,
+            )],
+            &[&player],
+        )
+        .await
+        .unwrap();
+        players.push(player);
+    }
+
+    // The host ranks the players off chain and posts only the root
+    let standings: Vec<(Pubkey, u32, u32)> = players
+        .iter()
+        .zip(1..)
+        .map(|(player, rank)| (player.pubkey(), 4 - rank, rank))
+        .collect();
+    let leaves: Vec<[u8; 32]> = standings
+        .iter()
+        .map(|(player, score, rank)| merkle::leaderboard_leaf(player, *score, *rank))
+        .collect();
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[client::post_leaderboard_root_ix(
+            &program_id,
+            &host,
+            &quiz,
+            merkle::root(&leaves),
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        quiz_session(&mut context.banks_client, quiz).await.status,
+        QuizStatus::Completed
+    );
+
+    let claim = |index: usize, score: u32| {
+        let (player, _, rank) = standings[index];
+        client::claim_leaderboard_prize_ix(
+            &program_id,
+            &player,
+            &quiz,
+            score,
+            rank,
+            merkle::proof(&leaves, index),
+        )
+    };
+
+    // A standing that isn't in the tree doesn't verify
+    let result = send(
+        &mut context.banks_client,
+        &payer,
+        &[claim(1, 3)],
+        &[&players[1]],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::InvalidMerkleProof as u32)
+    );
+
+    // Third place has no share of the pool
+    let result = send(
+        &mut context.banks_client,
+        &payer,
+        &[claim(2, 1)],
+        &[&players[2]],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::NoPrizeForRank as u32)
+    );
+
+    let vault = client::find_quiz_vault_address(&program_id, &quiz);
+    let vault_before = context.banks_client.get_balance(vault).await.unwrap();
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[claim(0, 3), claim(1, 2)],
+        &[&players[0], &players[1]],
+    )
+    .await
+    .unwrap();
+    let vault_after = context.banks_client.get_balance(vault).await.unwrap();
+    assert_eq!(vault_before - vault_after, 3 * entry_fee);
+
+    // Each standing pays out once
+    let result = send(
+        &mut context.banks_client,
+        &payer,
+        &[claim(0, 3)],
+        &[&players[0]],
+    )
+    .await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn prizes_below_the_threshold_roll_over() {
+    let (program_test, program_id) = program_test();
+    let mut context = program_test.start_with_context().await;
+    let payer = context.payer.insecure_clone();
+    let host = payer.pubkey();
+    let quiz = client::find_quiz_address(&program_id, &host, QUIZ_ID);
+    let base_layer = DelegationConfig {
+        base_layer: true,
+        ..DelegationConfig::default()
+    };
+    let entry_fee = 1_000_000;
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[
+            client::initialize_quiz_with_delegation_ix(&program_id, &host, QUIZ_ID, 2, base_layer),
+            question(&program_id, &host, &quiz, 0, 2),
+            question(&program_id, &host, &quiz, 1, 0),
+            client::set_entry_fee_ix(&program_id, &host, &quiz, entry_fee),
+            client::set_prize_split_ix(&program_id, &host, &quiz, [5_000, 3_000, 2_000]),
+            // Half the questions right, so 1 point
+            client::set_prize_threshold_ix(
+                &program_id,
+                &host,
+                &quiz,
+                0,
+                5_000,
+                PrizeRemainder::RollOver,
+            ),
+            client::start_quiz_ix(&program_id, &host, &quiz),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        quiz_session(&mut context.banks_client, quiz)
+            .await
+            .prize_threshold(),
+        1
+    );
+    let mut players = Vec::new();
+    for _ in 0..2 {
+        let player = funded_keypair(&mut context.banks_client, &payer).await;
+        send(
+            &mut context.banks_client,
+            &payer,
+            &[client::join_quiz_ix(
+                &program_id,
+                &player.pubkey(),
+                &quiz,
+                entry_fee,
+                None,
+            )],
+            &[&player],
+        )
+        .await
+        .unwrap();
+        players.push(player);
+    }
+
+    // Only first place reaches the threshold, nobody holds third
+    let standings = [(players[0].pubkey(), 2, 1), (players[1].pubkey(), 0, 2)];
+    let leaves: Vec<[u8; 32]> = standings
+        .iter()
+        .map(|(player, score, rank)| merkle::leaderboard_leaf(player, *score, *rank))
+        .collect();
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[client::post_leaderboard_root_ix(
+            &program_id,
+            &host,
+            &quiz,
+            merkle::root(&leaves),
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let result = send(
+        &mut context.banks_client,
+        &payer,
+        &[client::claim_leaderboard_prize_ix(
+            &program_id,
+            &players[1].pubkey(),
+            &quiz,
+            0,
+            2,
+            merkle::proof(&leaves, 1),
+        )],
+        &[&players[1]],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::BelowPrizeThreshold as u32)
+    );
+
+    let forfeit = |index: usize| {
+        let (player, score, rank) = standings[index];
+        client::forfeit_prize_ix(
+            &program_id,
+            &host,
+            &quiz,
+            rank,
+            player,
+            score,
+            merkle::proof(&leaves, index),
+        )
+    };
+    let result = send(&mut context.banks_client, &payer, &[forfeit(0)], &[]).await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::PrizeRankQualified as u32)
+    );
+
+    // Second place falls short and third is empty, so both shares stay in
+    // the vault for the rollover
+    let empty_rank =
+        client::forfeit_prize_ix(&program_id, &host, &quiz, 3, Pubkey::default(), 0, vec![]);
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[forfeit(1), empty_rank.clone()],
+        &[],
+    )
+    .await
+    .unwrap();
+    let session = quiz_session(&mut context.banks_client, quiz).await;
+    // 30% and 20% of two entry fees
+    assert_eq!(session.rollover_lamports, entry_fee);
+    assert!(session.rank_forfeited(2) && session.rank_forfeited(3));
+
+    // Paid by the player so it isn't a duplicate of the transaction above
+    let result = send(&mut context.banks_client, &players[1], &[empty_rank], &[]).await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::NoPrizeForRank as u32)
+    );
+
+    let vault = client::find_quiz_vault_address(&program_id, &quiz);
+    let vault_before = context.banks_client.get_balance(vault).await.unwrap();
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[client::claim_leaderboard_prize_ix(
+            &program_id,
+            &players[0].pubkey(),
+            &quiz,
+            2,
+            1,
+            merkle::proof(&leaves, 0),
+        )],
+        &[&players[0]],
+    )
+    .await
+    .unwrap();
+    let vault_after = context.banks_client.get_balance(vault).await.unwrap();
+    assert_eq!(vault_before - vault_after, entry_fee);
+}
+
+#[tokio::test]
+async fn proportional_prizes_pay_out_the_whole_pool() {
+    let (program_test, program_id) = program_test();
+    let mut context = program_test.start_with_context().await;
+    let payer = context.payer.insecure_clone();
+    let host = payer.pubkey();
+    let quiz = client::find_quiz_address(&program_id, &host, QUIZ_ID);
+    let base_layer = DelegationConfig {
+        base_layer: true,
+        ..DelegationConfig::default()
+    };
+    // Three fees that don't split evenly
+    let entry_fee = 1_000_001;
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[
+            client::initialize_quiz_with_delegation_ix(&program_id, &host, QUIZ_ID, 3, base_layer),
+            question(&program_id, &host, &quiz, 0, 2),
+            question(&program_id, &host, &quiz, 1, 0),
+            question(&program_id, &host, &quiz, 2, 1),
+            client::set_entry_fee_ix(&program_id, &host, &quiz, entry_fee),
+            client::set_proportional_prizes_ix(&program_id, &host, &quiz, true),
+            client::start_quiz_ix(&program_id, &host, &quiz),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    // Scores of 3, 1 and 0
+    let mut players = Vec::new();
+    for answers in [vec![2, 0, 1], vec![2, 1, 0], vec![0, 1, 0]] {
+        let player = funded_keypair(&mut context.banks_client, &payer).await;
+        send(
+            &mut context.banks_client,
+            &payer,
+            &[
+                client::join_quiz_ix(&program_id, &player.pubkey(), &quiz, entry_fee, None),
+                client::submit_answers_ix(&program_id, &player.pubkey(), &quiz, answers),
+            ],
+            &[&player],
+        )
+        .await
+        .unwrap();
+        players.push(player);
+    }
+    let player_keys: Vec<Pubkey> = players.iter().map(|player| player.pubkey()).collect();
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[client::calculate_scores_ix(
+            &program_id,
+            &host,
+            &quiz,
+            3,
+            &player_keys,
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        quiz_session(&mut context.banks_client, quiz)
+            .await
+            .total_prize_score,
+        4
+    );
+
+    // Corrections before payout move the total along
+    let correct = |new_score, correction_count| {
+        client::correct_score_ix(
+            &program_id,
+            &host,
+            &quiz,
+            &player_keys[2],
+            new_score,
+            ScoreCorrection::REASON_SCORING_ERROR,
+            correction_count,
+        )
+    };
+    send(&mut context.banks_client, &payer, &[correct(1, 0)], &[])
+        .await
+        .unwrap();
+    assert_eq!(
+        quiz_session(&mut context.banks_client, quiz)
+            .await
+            .total_prize_score,
+        5
+    );
+
+    // Shares of 3/5, 1/5 and 1/5, the last claim picks up the dust
+    let vault = client::find_quiz_vault_address(&program_id, &quiz);
+    let mut paid = Vec::new();
+    for player in &players {
+        let vault_before = context.banks_client.get_balance(vault).await.unwrap();
+        send(
+            &mut context.banks_client,
+            &payer,
+            &[client::claim_score_prize_ix(
+                &program_id,
+                &player.pubkey(),
+                &quiz,
+            )],
+            &[player],
+        )
+        .await
+        .unwrap();
+        let vault_after = context.banks_client.get_balance(vault).await.unwrap();
+        paid.push(vault_before - vault_after);
+    }
+    assert_eq!(paid, [1_800_001, 600_001, 600_001]);
+    assert_eq!(paid.iter().sum::<u64>(), 3 * entry_fee);
+
+    // Paying out settles the scores
+    let result = send(&mut context.banks_client, &payer, &[correct(0, 1)], &[]).await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::AlreadySettled as u32)
+    );
+}
+
+#[tokio::test]
+async fn unclaimed_prizes_are_swept_after_the_claim_window() {
+    let (program_test, program_id) = program_test();
+    let mut context = program_test.start_with_context().await;
+    let payer = context.payer.insecure_clone();
+    let host = payer.pubkey();
+    let quiz = client::find_quiz_address(&program_id, &host, QUIZ_ID);
+    let base_layer = DelegationConfig {
+        base_layer: true,
+        ..DelegationConfig::default()
+    };
+    let entry_fee = 1_000_000;
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[
+            client::initialize_quiz_with_delegation_ix(&program_id, &host, QUIZ_ID, 1, base_layer),
+            question(&program_id, &host, &quiz, 0, 2),
+            client::set_entry_fee_ix(&program_id, &host, &quiz, entry_fee),
+            client::set_proportional_prizes_ix(&program_id, &host, &quiz, true),
+            client::set_claim_window_ix(&program_id, &host, &quiz, 100),
+            client::start_quiz_ix(&program_id, &host, &quiz),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    let player = funded_keypair(&mut context.banks_client, &payer).await;
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[
+            client::join_quiz_ix(&program_id, &player.pubkey(), &quiz, entry_fee, None),
+            client::submit_answers_ix(&program_id, &player.pubkey(), &quiz, vec![2]),
+            client::calculate_scores_ix(&program_id, &host, &quiz, 1, &[player.pubkey()]),
+        ],
+        &[&player],
+    )
+    .await
+    .unwrap();
+    let scored_slot = quiz_session(&mut context.banks_client, quiz)
+        .await
+        .scored_slot;
+
+    let treasury = Pubkey::new_unique();
+    let sweep = || client::sweep_unclaimed_ix(&program_id, &host, &quiz, &treasury);
+    let result = send(&mut context.banks_client, &payer, &[sweep()], &[]).await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::ClaimWindowOpen as u32)
+    );
+
+    // The winner never turns up
+    context.warp_to_slot(scored_slot + 101).unwrap();
+    let result = send(
+        &mut context.banks_client,
+        &payer,
+        &[client::claim_score_prize_ix(
+            &program_id,
+            &player.pubkey(),
+            &quiz,
+        )],
+        &[&player],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::ClaimWindowClosed as u32)
+    );
+
+    send(&mut context.banks_client, &payer, &[sweep()], &[])
+        .await
+        .unwrap();
+    assert_eq!(
+        context.banks_client.get_balance(treasury).await.unwrap(),
+        entry_fee
+    );
+    let session = quiz_session(&mut context.banks_client, quiz).await;
+    assert_eq!(session.prize_pool, 0);
+    assert!(session.settled);
+}
+
+#[tokio::test]
+async fn large_prizes_vest_over_time() {
+    let (program_test, program_id) = program_test();
+    let mut context = program_test.start_with_context().await;
+    let payer = context.payer.insecure_clone();
+    let host = payer.pubkey();
+    let quiz = client::find_quiz_address(&program_id, &host, QUIZ_ID);
+    let base_layer = DelegationConfig {
+        base_layer: true,
+        ..DelegationConfig::default()
+    };
+    let entry_fee = 1_000_000;
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[
+            client::initialize_quiz_with_delegation_ix(&program_id, &host, QUIZ_ID, 1, base_layer),
+            question(&program_id, &host, &quiz, 0, 2),
+            client::set_entry_fee_ix(&program_id, &host, &quiz, entry_fee),
+            client::set_proportional_prizes_ix(&program_id, &host, &quiz, true),
+            client::set_prize_vesting_ix(&program_id, &host, &quiz, entry_fee, 100),
+            client::set_claim_window_ix(&program_id, &host, &quiz, 10),
+            client::start_quiz_ix(&program_id, &host, &quiz),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    let player = funded_keypair(&mut context.banks_client, &payer).await;
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[
+            client::join_quiz_ix(&program_id, &player.pubkey(), &quiz, entry_fee, None),
+            client::submit_answers_ix(&program_id, &player.pubkey(), &quiz, vec![2]),
+            client::calculate_scores_ix(&program_id, &host, &quiz, 1, &[player.pubkey()]),
+        ],
+        &[&player],
+    )
+    .await
+    .unwrap();
+
+    // Claiming starts the clock instead of paying
+    let vault = client::find_quiz_vault_address(&program_id, &quiz);
+    let vault_balance = context.banks_client.get_balance(vault).await.unwrap();
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[client::claim_score_prize_ix(
+            &program_id,
+            &player.pubkey(),
+            &quiz,
+        )],
+        &[&player],
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        context.banks_client.get_balance(vault).await.unwrap(),
+        vault_balance
+    );
+    let claim_address =
+        client::find_leaderboard_claim_address(&program_id, &quiz, &player.pubkey());
+    let account = context
+        .banks_client
+        .get_account(claim_address)
+        .await
+        .unwrap()
+        .unwrap();
+    let claim = LeaderboardClaim::try_from_slice(&account.data).unwrap();
+    assert_eq!((claim.lamports, claim.vesting_slots), (entry_fee, 100));
+    assert_eq!(
+        quiz_session(&mut context.banks_client, quiz)
+            .await
+            .vesting_owed,
+        entry_fee
+    );
+
+    let release = || client::claim_vested_ix(&program_id, &player.pubkey(), &quiz);
+
+    // Half way through
+    context.warp_to_slot(claim.vesting_start_slot + 50).unwrap();
+    let vault_before = context.banks_client.get_balance(vault).await.unwrap();
+    send(&mut context.banks_client, &payer, &[release()], &[&player])
+        .await
+        .unwrap();
+    let vault_after = context.banks_client.get_balance(vault).await.unwrap();
+    assert_eq!(vault_before - vault_after, entry_fee / 2);
+
+    // The claim window is long closed, but a sweep leaves the rest vesting
+    context
+        .warp_to_slot(claim.vesting_start_slot + 100)
+        .unwrap();
+    let treasury = Pubkey::new_unique();
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[client::sweep_unclaimed_ix(
+            &program_id,
+            &host,
+            &quiz,
+            &treasury,
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        context.banks_client.get_balance(vault).await.unwrap(),
+        vault_after
+    );
+    send(&mut context.banks_client, &payer, &[release()], &[&player])
+        .await
+        .unwrap();
+    assert_eq!(
+        context.banks_client.get_balance(vault).await.unwrap(),
+        vault_after - entry_fee / 2
+    );
+    assert_eq!(
+        quiz_session(&mut context.banks_client, quiz)
+            .await
+            .vesting_owed,
+        0
+    );
+
+    // Paid by the player so it isn't a duplicate of the last release
+    let result = send(&mut context.banks_client, &player, &[release()], &[]).await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::NothingVested as u32)
+    );
+}
+
+#[tokio::test]
+async fn wrong_bonded_scores_are_challenged() {
+    let (program_test, program_id) = program_test();
+    let mut context = program_test.start_with_context().await;
+    let payer = context.payer.insecure_clone();
+    let host = payer.pubkey();
+    let quiz = client::find_quiz_address(&program_id, &host, QUIZ_ID);
+    let base_layer = DelegationConfig {
+        base_layer: true,
+        ..DelegationConfig::default()
+    };
+    let scorer = funded_keypair(&mut context.banks_client, &payer).await;
+    let bond = 5_000_000;
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[
+            client::initialize_quiz_with_delegation_ix(&program_id, &host, QUIZ_ID, 1, base_layer),
+            question(&program_id, &host, &quiz, 0, 2),
+            client::set_dispute_window_ix(&program_id, &host, &quiz, 100),
+            client::appoint_scorer_ix(&program_id, &host, &quiz, &scorer.pubkey(), bond),
+            client::start_quiz_ix(&program_id, &host, &quiz),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    // The first player answers right, the second wrong
+    let mut players = Vec::new();
+    for answer in [2, 1] {
+        let player = funded_keypair(&mut context.banks_client, &payer).await;
+        send(
+            &mut context.banks_client,
+            &payer,
+            &[
+                client::join_quiz_ix(&program_id, &player.pubkey(), &quiz, 0, None),
+                client::submit_answers_ix(&program_id, &player.pubkey(), &quiz, vec![answer]),
+            ],
+            &[&player],
+        )
+        .await
+        .unwrap();
+        players.push(player.pubkey());
+    }
+
+    // The scorer posts a tie, the second player's point is made up
+    let leaves = [
+        merkle::leaderboard_leaf(&players[0], 1, 1),
+        merkle::leaderboard_leaf(&players[1], 1, 2),
+    ];
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[client::post_bonded_root_ix(
+            &program_id,
+            &scorer.pubkey(),
+            &quiz,
+            merkle::root(&leaves),
+        )],
+        &[&scorer],
+    )
+    .await
+    .unwrap();
+    let bond_address = client::find_scorer_bond_address(&program_id, &quiz);
+    let bond_balance = context
+        .banks_client
+        .get_balance(bond_address)
+        .await
+        .unwrap();
+    assert!(bond_balance >= bond);
+
+    // The bond stays locked while challenges are possible
+    let result = send(
+        &mut context.banks_client,
+        &payer,
+        &[client::release_bond_ix(
+            &program_id,
+            &scorer.pubkey(),
+            &quiz,
+        )],
+        &[&scorer],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::DisputeWindowOpen as u32)
+    );
+
+    let challenger = funded_keypair(&mut context.banks_client, &payer).await;
+    let challenge = |index: usize| {
+        client::challenge_score_ix(
+            &program_id,
+            &challenger.pubkey(),
+            &host,
+            &quiz,
+            &players[index],
+            1,
+            1,
+            index as u32 + 1,
+            merkle::proof(&leaves, index),
+        )
+    };
+    let result = send(
+        &mut context.banks_client,
+        &payer,
+        &[challenge(0)],
+        &[&challenger],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::ScoreNotFraudulent as u32)
+    );
+
+    let challenger_before = context
+        .banks_client
+        .get_balance(challenger.pubkey())
+        .await
+        .unwrap();
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[challenge(1)],
+        &[&challenger],
+    )
+    .await
+    .unwrap();
+    let challenger_after = context
+        .banks_client
+        .get_balance(challenger.pubkey())
+        .await
+        .unwrap();
+    // The challenger also opens the host's stats, which count the dispute
+    let stats_rent = Rent::default().minimum_balance(HostStats::SIZE);
+    assert_eq!(
+        challenger_after - challenger_before,
+        bond_balance - stats_rent
+    );
+    let stats_address = client::find_host_stats_address(&program_id, &host);
+    let account = context
+        .banks_client
+        .get_account(stats_address)
+        .await
+        .unwrap()
+        .unwrap();
+    let stats = HostStats::try_from_slice(&account.data).unwrap();
+    assert_eq!(stats.disputes_upheld, 1);
+    assert_eq!(stats.reputation_bps(), 0);
+
+    // The root is thrown out and the host scores the quiz again
+    let session = quiz_session(&mut context.banks_client, quiz).await;
+    assert_eq!(session.status, QuizStatus::Scoring);
+    assert_eq!(session.leaderboard_root, [0; 32]);
+    assert_eq!(session.scorer, Pubkey::default());
+    let leaves = [
+        merkle::leaderboard_leaf(&players[0], 1, 1),
+        merkle::leaderboard_leaf(&players[1], 0, 2),
+    ];
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[client::post_leaderboard_root_ix(
+            &program_id,
+            &host,
+            &quiz,
+            merkle::root(&leaves),
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        quiz_session(&mut context.banks_client, quiz).await.status,
+        QuizStatus::Completed
+    );
+}
+
+#[tokio::test]
+async fn compressed_sheets_are_graded_with_inclusion_proofs() {
+    let (program_test, program_id) = program_test();
+    let mut context = program_test.start_with_context().await;
+    let payer = context.payer.insecure_clone();
+    let host = payer.pubkey();
+    let quiz = client::find_quiz_address(&program_id, &host, QUIZ_ID);
+    let base_layer = DelegationConfig {
+        base_layer: true,
+        ..DelegationConfig::default()
+    };
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[
+            client::initialize_quiz_with_delegation_ix(&program_id, &host, QUIZ_ID, 2, base_layer),
+            question(&program_id, &host, &quiz, 0, 3),
+            question(&program_id, &host, &quiz, 1, 1),
+            client::init_answer_tree_ix(&program_id, &host, &quiz),
+            client::start_quiz_ix(&program_id, &host, &quiz),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    // Players submit without joining, so no answer sheet PDA is created
+    context.warp_to_slot(100).unwrap();
+    let mut sheets = Vec::new();
+    let mut players = Vec::new();
+    for answers in [vec![3, 1], vec![3, 0]] {
+        let player = funded_keypair(&mut context.banks_client, &payer).await;
+        send(
+            &mut context.banks_client,
+            &payer,
+            &[client::submit_compressed_answers_ix(
+                &program_id,
+                &player.pubkey(),
+                &quiz,
+                answers.clone(),
+                Vec::new(),
+            )],
+            &[&player],
+        )
+        .await
+        .unwrap();
+        let answer_address =
+            client::find_player_answer_address(&program_id, &quiz, &player.pubkey());
+        assert!(context
+            .banks_client
+            .get_account(answer_address)
+            .await
+            .unwrap()
+            .is_none());
+        sheets.push(CompressedAnswers {
+            player: player.pubkey(),
+            confidence: vec![1; answers.len()],
+            answers,
+            submitted_slot: 100,
+        });
+        players.push(player);
+    }
+
+    // A player can't add a second sheet to choose from
+    let result = send(
+        &mut context.banks_client,
+        &payer,
+        &[client::submit_compressed_answers_ix(
+            &program_id,
+            &players[1].pubkey(),
+            &quiz,
+            vec![3, 1],
+            Vec::new(),
+        )],
+        &[&players[1]],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::AnswersAlreadySubmitted as u32)
+    );
+    let leaves: Vec<[u8; 32]> = sheets.iter().map(merkle::answer_leaf).collect();
+    let tree_address = client::find_answer_tree_address(&program_id, &quiz);
+    let tree_account = context
+        .banks_client
+        .get_account(tree_address)
+        .await
+        .unwrap()
+        .unwrap();
+    let tree = AnswerTree::try_from_slice(&tree_account.data).unwrap();
+    assert_eq!(tree.leaf_count, 2);
+    assert_eq!(tree.root, merkle::padded_root(&leaves));
+
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[client::calculate_scores_ix(
+            &program_id,
+            &host,
+            &quiz,
+            2,
+            &[],
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    // Questions stay until every sheet is graded
+    let close = || client::close_questions_ix(&program_id, &host, &quiz, 2);
+    let result = send(&mut context.banks_client, &payer, &[close()], &[]).await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::SheetsUngraded as u32)
+    );
+
+    // A sheet that was never submitted doesn't prove against the root
+    let mut forged = sheets[1].clone();
+    forged.answers = vec![3, 1];
+    let result = send(
+        &mut context.banks_client,
+        &payer,
+        &[client::grade_compressed_answers_ix(
+            &program_id,
+            &host,
+            &quiz,
+            2,
+            forged,
+            merkle::padded_proof(&leaves, 1),
+        )],
+        &[],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::InvalidMerkleProof as u32)
+    );
+
+    for (index, (sheet, expected)) in sheets.iter().zip([2, 1]).enumerate() {
+        send(
+            &mut context.banks_client,
+            &payer,
+            &[client::grade_compressed_answers_ix(
+                &program_id,
+                &host,
+                &quiz,
+                2,
+                sheet.clone(),
+                merkle::padded_proof(&leaves, index),
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+        let score_address = client::find_player_score_address(&program_id, &quiz, &sheet.player);
+        assert_eq!(
+            player_score(&mut context.banks_client, score_address)
+                .await
+                .score,
+            expected
+        );
+    }
+
+    // Each player is graded once
+    let result = send(
+        &mut context.banks_client,
+        &payer,
+        &[client::grade_compressed_answers_ix(
+            &program_id,
+            &host,
+            &quiz,
+            2,
+            sheets[0].clone(),
+            merkle::padded_proof(&leaves, 0),
+        )],
+        &[],
+    )
+    .await;
+    assert!(result.is_err());
+
+    // Paid by someone else so it isn't a duplicate of the refused close
+    let fee_payer = funded_keypair(&mut context.banks_client, &payer).await;
+    send(&mut context.banks_client, &fee_payer, &[close()], &[&payer])
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn rent_top_ups_restore_exemption() {
+    let (program_test, program_id) = program_test();
+    let mut context = program_test.start_with_context().await;
+    let payer = context.payer.insecure_clone();
+    let quiz = started_quiz(
+        &mut context.banks_client,
+        &payer,
+        &program_id,
+        QuizMode::Async,
+        &[1],
+    )
+    .await;
+
+    // Stand in for an account grown past its rent reserve
+    let shortfall = 1_000;
+    let mut account = context
+        .banks_client
+        .get_account(quiz)
+        .await
+        .unwrap()
+        .unwrap();
+    let exempt_balance = account.lamports;
+    account.lamports -= shortfall;
+    context.set_account(&quiz, &account.into());
+
+    let result = send(
+        &mut context.banks_client,
+        &payer,
+        &[client::top_up_rent_ix(
+            &program_id,
+            &payer.pubkey(),
+            &quiz,
+            shortfall / 2,
+        )],
+        &[],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::AccountNotRentExempt
+    );
+
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[client::top_up_rent_ix(
+            &program_id,
+            &payer.pubkey(),
+            &quiz,
+            shortfall,
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        context.banks_client.get_balance(quiz).await.unwrap(),
+        exempt_balance
+    );
+
+    // Only the program's own accounts take top-ups
+    let result = send(
+        &mut context.banks_client,
+        &payer,
+        &[client::top_up_rent_ix(
+            &program_id,
+            &payer.pubkey(),
+            &Pubkey::new_unique(),
+            shortfall,
+        )],
+        &[],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::InvalidAccountOwner as u32)
+    );
+}
+
+#[tokio::test]
+async fn pre_funded_addresses_are_still_created() {
+    let (program_test, program_id) = program_test();
+    let (mut banks_client, payer, _) = program_test.start().await;
+    let host = payer.pubkey();
+    let quiz = client::find_quiz_address(&program_id, &host, QUIZ_ID);
+    let question_address = client::find_question_address(&program_id, &quiz, 0);
+    let player = funded_keypair(&mut banks_client, &payer).await;
+    let answer_address = client::find_player_answer_address(&program_id, &quiz, &player.pubkey());
+
+    // Lamports sent ahead, below and above what the accounts need for rent
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            system_instruction::transfer(&host, &quiz, 1_000_000),
+            system_instruction::transfer(&host, &question_address, 100_000_000),
+            system_instruction::transfer(&host, &answer_address, 1_000_000),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let base_layer = DelegationConfig {
+        base_layer: true,
+        ..DelegationConfig::default()
+    };
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::initialize_quiz_with_delegation_ix(&program_id, &host, QUIZ_ID, 1, base_layer),
+            question(&program_id, &host, &quiz, 0, 2),
+            client::start_quiz_ix(&program_id, &host, &quiz),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    send(
+        &mut banks_client,
+        &payer,
+        &[client::join_quiz_ix(
+            &program_id,
+            &player.pubkey(),
+            &quiz,
+            0,
+            None,
+        )],
+        &[&player],
+    )
+    .await
+    .unwrap();
+
+    let session = quiz_session(&mut banks_client, quiz).await;
+    assert_eq!(session.status, QuizStatus::Active);
+    assert_eq!(session.player_count, 1);
+    let rent = Rent::default();
+    for address in [quiz, question_address, answer_address] {
+        let account = banks_client.get_account(address).await.unwrap().unwrap();
+        assert_eq!(account.owner, program_id);
+        assert!(rent.is_exempt(account.lamports, account.data.len()));
+    }
+}
+
+#[tokio::test]
+async fn questions_are_closed_after_the_dispute_window() {
+    let (program_test, program_id) = program_test();
+    let mut context = program_test.start_with_context().await;
+    let payer = context.payer.insecure_clone();
+    let host = payer.pubkey();
+    let quiz = client::find_quiz_address(&program_id, &host, QUIZ_ID);
+    let base_layer = DelegationConfig {
+        base_layer: true,
+        ..DelegationConfig::default()
+    };
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[
+            client::initialize_quiz_with_delegation_ix(&program_id, &host, QUIZ_ID, 2, base_layer),
+            question(&program_id, &host, &quiz, 0, 2),
+            question(&program_id, &host, &quiz, 1, 0),
+            client::set_dispute_window_ix(&program_id, &host, &quiz, 50),
+            client::start_quiz_ix(&program_id, &host, &quiz),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    // Questions stay until the quiz is scored and can't be disputed
+    let close = || client::close_questions_ix(&program_id, &host, &quiz, 2);
+    let result = send(&mut context.banks_client, &payer, &[close()], &[]).await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::InvalidAccountData
+    );
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[client::calculate_scores_ix(
+            &program_id,
+            &host,
+            &quiz,
+            2,
+            &[],
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+    let result = send(&mut context.banks_client, &payer, &[close()], &[]).await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::DisputeWindowOpen as u32)
+    );
+
+    let scored_slot = quiz_session(&mut context.banks_client, quiz)
+        .await
+        .scored_slot;
+    context.warp_to_slot(scored_slot + 51).unwrap();
+    let questions = [0, 1].map(|index| client::find_question_address(&program_id, &quiz, index));
+    let mut refund = 0;
+    for address in questions {
+        refund += context.banks_client.get_balance(address).await.unwrap();
+    }
+    let host_before = context.banks_client.get_balance(host).await.unwrap();
+    send(&mut context.banks_client, &payer, &[close()], &[])
+        .await
+        .unwrap();
+
+    for address in questions {
+        assert!(context
+            .banks_client
+            .get_account(address)
+            .await
+            .unwrap()
+            .is_none());
+    }
+    // The host also paid the transaction fee
+    let host_after = context.banks_client.get_balance(host).await.unwrap();
+    assert!(host_after > host_before + refund - 10_000);
+}
+
+#[tokio::test]
+async fn completed_quizzes_are_cloned() {
+    let (program_test, program_id) = program_test();
+    let (mut banks_client, payer, _) = program_test.start().await;
+    let host = payer.pubkey();
+    let source = client::find_quiz_address(&program_id, &host, QUIZ_ID);
+    let base_layer = DelegationConfig {
+        base_layer: true,
+        ..DelegationConfig::default()
+    };
+    let charity = Pubkey::new_unique();
+    let (entry_fee, stake) = (10_000_000, 1_000_000);
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::initialize_quiz_with_delegation_ix(&program_id, &host, QUIZ_ID, 2, base_layer),
+            question(&program_id, &host, &source, 0, 1),
+            question(&program_id, &host, &source, 1, 3),
+            client::set_entry_fee_ix(&program_id, &host, &source, entry_fee),
+            client::set_join_stake_ix(&program_id, &host, &source, stake),
+            client::set_charity_split_ix(&program_id, &host, &source, &charity, 1_000),
+            client::create_team_ix(&program_id, &host, &source, 1, "Hosts".to_string()),
+            client::start_quiz_ix(&program_id, &host, &source),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    let player = funded_keypair(&mut banks_client, &payer).await;
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::join_quiz_ix(
+                &program_id,
+                &player.pubkey(),
+                &source,
+                entry_fee + stake,
+                None,
+            ),
+            client::submit_answers_ix(&program_id, &player.pubkey(), &source, vec![1, 3]),
+        ],
+        &[&player],
+    )
+    .await
+    .unwrap();
+
+    // Only completed quizzes are copied
+    let clone = client::clone_quiz_ix(&program_id, &host, &source, QUIZ_ID + 1, 2);
+    let result = send(&mut banks_client, &payer, &[clone], &[]).await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::InvalidAccountData
+    );
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::calculate_scores_ix(&program_id, &host, &source, 2, &[player.pubkey()]),
+            client::pay_charity_ix(&program_id, &source, &charity),
+            client::reveal_answers_ix(&program_id, &source, 2),
+            client::clone_quiz_ix(&program_id, &host, &source, QUIZ_ID + 1, 2),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    // Settings carry over, the source's run doesn't
+    let quiz = client::find_quiz_address(&program_id, &host, QUIZ_ID + 1);
+    let session = quiz_session(&mut banks_client, quiz).await;
+    assert_eq!(session.quiz_id, QUIZ_ID + 1);
+    assert_eq!(session.status, QuizStatus::Draft);
+    assert_eq!(session.question_count, 2);
+    assert_eq!(session.scored_slot, 0);
+    assert_eq!(
+        (session.entry_fee, session.join_stake, session.charity_bps),
+        (entry_fee, stake, 1_000)
+    );
+    assert_eq!(session.charity, charity);
+    assert_eq!((session.prize_pool, session.stakes_held), (0, 0));
+    assert_eq!(session.team_count, 0);
+    assert!(!session.charity_paid);
+    assert!(!session.answers_revealed);
+    for index in 0..2 {
+        let mut questions = Vec::new();
+        for quiz in [source, quiz] {
+            let address = client::find_question_address(&program_id, &quiz, index);
+            let account = banks_client.get_account(address).await.unwrap().unwrap();
+            questions.push(QuizQuestion::try_from_slice(&account.data).unwrap());
+        }
+        assert_eq!(questions[0].question_text, questions[1].question_text);
+        assert_eq!(
+            questions[0].correct_answer_index,
+            questions[1].correct_answer_index
+        );
+    }
+
+    // The copy runs like any other quiz
+    send(
+        &mut banks_client,
+        &payer,
+        &[client::start_quiz_ix(&program_id, &host, &quiz)],
+        &[],
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn templates_are_instantiated_by_permitted_hosts() {
+    let (program_test, program_id) = program_test();
+    let (mut banks_client, payer, _) = program_test.start().await;
+    let author = payer.pubkey();
+    let source = client::find_quiz_address(&program_id, &author, QUIZ_ID);
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::initialize_quiz_ix(&program_id, &author, QUIZ_ID, 2),
+            question(&program_id, &author, &source, 0, 1),
+            question(&program_id, &author, &source, 1, 2),
+            client::set_dispute_window_ix(&program_id, &author, &source, 30),
+            client::create_template_ix(
+                &program_id,
+                &author,
+                &source,
+                2,
+                1,
+                "Weekly trivia".to_string(),
+                false,
+            ),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    let template = client::find_template_address(&program_id, &author, 1);
+
+    // Private templates are for the author and the hosts they allow
+    let host = funded_keypair(&mut banks_client, &payer).await;
+    let create = || {
+        client::create_from_template_ix(
+            &program_id,
+            &host.pubkey(),
+            &template,
+            2,
+            QUIZ_ID,
+            DelegationConfig::default(),
+        )
+    };
+    let result = send(&mut banks_client, &payer, &[create()], &[&host]).await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::TemplateAccessDenied as u32)
+    );
+    send(
+        &mut banks_client,
+        &payer,
+        &[client::set_template_access_ix(
+            &program_id,
+            &author,
+            &template,
+            false,
+            vec![host.pubkey()],
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+    send(&mut banks_client, &payer, &[create()], &[&host])
+        .await
+        .unwrap();
+
+    let quiz = client::find_quiz_address(&program_id, &host.pubkey(), QUIZ_ID);
+    let session = quiz_session(&mut banks_client, quiz).await;
+    assert_eq!(session.host, host.pubkey());
+    assert_eq!(session.status, QuizStatus::Draft);
+    assert_eq!(session.dispute_window_slots, 30);
+    for (index, correct) in [(0, 1), (1, 2)] {
+        let address = client::find_question_address(&program_id, &quiz, index);
+        let account = banks_client.get_account(address).await.unwrap().unwrap();
+        let question = QuizQuestion::try_from_slice(&account.data).unwrap();
+        assert_eq!(question.correct_answer_index, correct);
+    }
+    let account = banks_client.get_account(template).await.unwrap().unwrap();
+    let template_data = QuizTemplate::try_from_slice(&account.data).unwrap();
+    assert_eq!(template_data.instance_count, 1);
+    assert_eq!(&template_data.title[..13], b"Weekly trivia");
+}
+
+#[tokio::test]
+async fn questions_are_imported_in_bulk() {
+    let (program_test, program_id) = program_test();
+    let (mut banks_client, payer, _) = program_test.start().await;
+    let host = payer.pubkey();
+    let quiz = client::find_quiz_address(&program_id, &host, QUIZ_ID);
+    let imported = |index: u8| QuestionData {
+        question_text: format!("Imported {}", index),
+        options: ["A", "B", "C", "D"].map(String::from),
+        correct_answer_index: index % QuizQuestion::OPTION_COUNT,
+    };
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::initialize_quiz_ix(&program_id, &host, QUIZ_ID, 4),
+            client::import_questions_ix(
+                &program_id,
+                &host,
+                &quiz,
+                0,
+                (0..3).map(imported).collect(),
+            ),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    // A batch running past the question count is rejected whole
+    let result = send(
+        &mut banks_client,
+        &payer,
+        &[client::import_questions_ix(
+            &program_id,
+            &host,
+            &quiz,
+            3,
+            (3..5).map(imported).collect(),
+        )],
+        &[],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::InvalidQuestionIndex as u32)
+    );
+
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::import_questions_ix(&program_id, &host, &quiz, 3, vec![imported(3)]),
+            client::start_quiz_ix(&program_id, &host, &quiz),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    for index in 0..4 {
+        let address = client::find_question_address(&program_id, &quiz, index);
+        let account = banks_client.get_account(address).await.unwrap().unwrap();
+        let question = QuizQuestion::try_from_slice(&account.data).unwrap();
+        let source = imported(index);
+        assert_eq!(question.question_text, source.question_text);
+        assert_eq!(question.options, source.options);
+        assert_eq!(question.correct_answer_index, source.correct_answer_index);
+        assert_eq!(question.multiplier, 1);
+    }
+    let session = quiz_session(&mut banks_client, quiz).await;
+    assert_eq!(session.status, QuizStatus::Active);
+}
+
+#[tokio::test]
+async fn signed_question_packs_are_verified_before_import() {
+    let (program_test, program_id) = program_test();
+    let (mut banks_client, payer, _) = program_test.start().await;
+    let host = payer.pubkey();
+    let quiz = client::find_quiz_address(&program_id, &host, QUIZ_ID);
+    let curator = Keypair::new();
+    let pack = vec![QuestionData {
+        question_text: "Longest river?".to_string(),
+        options: ["Nile", "Amazon", "Yangtze", "Danube"].map(String::from),
+        correct_answer_index: 0,
+    }];
+    let sign = |signer: &Keypair, first_index: u8, questions: &[QuestionData]| {
+        let message = QuestionData::pack_message(first_index, questions);
+        let signature: [u8; 64] = signer.sign_message(&message).as_ref().try_into().unwrap();
+        client::ed25519_signature_ix(&signer.pubkey(), &signature, &message)
+    };
+    send(
+        &mut banks_client,
+        &payer,
+        &[client::initialize_quiz_ix(&program_id, &host, QUIZ_ID, 2)],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    // Without a verified signature nothing is imported
+    let result = send(
+        &mut banks_client,
+        &payer,
+        &[client::import_signed_questions_ix(
+            &program_id,
+            &host,
+            &quiz,
+            &curator.pubkey(),
+            0,
+            pack.clone(),
+        )],
+        &[],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::SignatureNotVerified as u32)
+    );
+
+    // Nor when the host changes an answer after the curator signed
+    let mut tampered = pack.clone();
+    tampered[0].correct_answer_index = 1;
+    let result = send(
+        &mut banks_client,
+        &payer,
+        &[
+            sign(&curator, 0, &pack),
+            client::import_signed_questions_ix(
+                &program_id,
+                &host,
+                &quiz,
+                &curator.pubkey(),
+                0,
+                tampered,
+            ),
+        ],
+        &[],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::SignatureNotVerified as u32)
+    );
+
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            sign(&curator, 0, &pack),
+            client::import_signed_questions_ix(
+                &program_id,
+                &host,
+                &quiz,
+                &curator.pubkey(),
+                0,
+                pack.clone(),
+            ),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    let address = client::find_question_address(&program_id, &quiz, 0);
+    let account = banks_client.get_account(address).await.unwrap().unwrap();
+    let question = QuizQuestion::try_from_slice(&account.data).unwrap();
+    assert_eq!(question.question_text, "Longest river?");
+    let session = quiz_session(&mut banks_client, quiz).await;
+    assert_eq!(session.curator, curator.pubkey());
+
+    // The rest of the quiz can't come from another curator
+    let other = Keypair::new();
+    let result = send(
+        &mut banks_client,
+        &payer,
+        &[
+            sign(&other, 1, &pack),
+            client::import_signed_questions_ix(&program_id, &host, &quiz, &other.pubkey(), 1, pack),
+        ],
+        &[],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::CuratorMismatch as u32)
+    );
+}
+
+#[tokio::test]
+async fn questions_carry_translations() {
+    let (program_test, program_id) = program_test();
+    let (mut banks_client, payer, _) = program_test.start().await;
+    let host = payer.pubkey();
+    let quiz = client::find_quiz_address(&program_id, &host, QUIZ_ID);
+    let translate = |language: [u8; 2]| {
+        client::add_translation_ix(
+            &program_id,
+            &host,
+            &quiz,
+            0,
+            language,
+            "Câu hỏi 0".into(),
+            ["Một", "Hai", "Ba", "Bốn"].map(String::from),
+        )
+    };
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::initialize_quiz_ix(&program_id, &host, QUIZ_ID, 1),
+            question(&program_id, &host, &quiz, 0, 2),
+            translate(*b"vi"),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let address = client::find_translation_address(&program_id, &quiz, 0, *b"vi");
+    let account = banks_client.get_account(address).await.unwrap().unwrap();
+    let translation = QuestionTranslation::try_from_slice(&account.data).unwrap();
+    assert_eq!(translation.question_index, 0);
+    assert_eq!(&translation.language, b"vi");
+    assert_eq!(translation.options[2], "Ba");
+
+    let result = send(&mut banks_client, &payer, &[translate(*b"EN")], &[]).await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::InvalidLanguageCode as u32)
+    );
+
+    // Translations are frozen once the quiz starts
+    send(
+        &mut banks_client,
+        &payer,
+        &[client::start_quiz_ix(&program_id, &host, &quiz)],
+        &[],
+    )
+    .await
+    .unwrap();
+    let result = send(&mut banks_client, &payer, &[translate(*b"fr")], &[]).await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::InvalidAccountData
+    );
+}
+
+#[tokio::test]
+async fn reserved_nicknames_are_unique() {
+    let (program_test, program_id) = program_test();
+    let (mut banks_client, payer, _) = program_test.start().await;
+    let alice = funded_keypair(&mut banks_client, &payer).await;
+    let bob = funded_keypair(&mut banks_client, &payer).await;
+    let set_nickname = |player: &Keypair, nickname: &str, reserve, reserved| {
+        client::set_nickname_ix(
+            &program_id,
+            &player.pubkey(),
+            nickname.into(),
+            reserve,
+            reserved,
+        )
+    };
+
+    send(
+        &mut banks_client,
+        &payer,
+        &[set_nickname(&alice, "Alice", true, None)],
+        &[&alice],
+    )
+    .await
+    .unwrap();
+    let profile_address = client::find_profile_address(&program_id, &alice.pubkey());
+    let account = banks_client
+        .get_account(profile_address)
+        .await
+        .unwrap()
+        .unwrap();
+    let profile = PlayerProfile::try_from_slice(&account.data).unwrap();
+    assert_eq!(profile.nickname(), "Alice");
+    assert!(profile.reserved);
+
+    // A name differing only in case can't be reserved, only displayed
+    let result = send(
+        &mut banks_client,
+        &payer,
+        &[set_nickname(&bob, "ALICE", true, None)],
+        &[&bob],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::NicknameTaken as u32)
+    );
+    send(
+        &mut banks_client,
+        &payer,
+        &[set_nickname(&bob, "ALICE", false, None)],
+        &[&bob],
+    )
+    .await
+    .unwrap();
+
+    // Renaming releases the old claim for others to take
+    send(
+        &mut banks_client,
+        &payer,
+        &[set_nickname(&alice, "Carol", true, Some("Alice"))],
+        &[&alice],
+    )
+    .await
+    .unwrap();
+    let released = client::find_nickname_claim_address(&program_id, "alice");
+    assert!(banks_client.get_account(released).await.unwrap().is_none());
+    send(
+        &mut banks_client,
+        &payer,
+        &[set_nickname(&bob, "ALICE", true, None)],
+        &[&bob],
+    )
+    .await
+    .unwrap();
+
+    let account = banks_client.get_account(released).await.unwrap().unwrap();
+    let claim = NicknameClaim::try_from_slice(&account.data).unwrap();
+    assert_eq!(claim.player, bob.pubkey());
+}
+
+#[tokio::test]
+async fn live_leaderboard_follows_each_answer() {
+    let (program_test, program_id) = program_test();
+    let (mut banks_client, payer, _) = program_test.start().await;
+    let host = payer.pubkey();
+    let quiz = client::find_quiz_address(&program_id, &host, QUIZ_ID);
+    let base_layer = DelegationConfig {
+        base_layer: true,
+        ..DelegationConfig::default()
+    };
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::initialize_quiz_with_delegation_ix(&program_id, &host, QUIZ_ID, 2, base_layer),
+            question(&program_id, &host, &quiz, 0, 2),
+            question(&program_id, &host, &quiz, 1, 1),
+            client::set_quiz_mode_ix(&program_id, &host, &quiz, QuizMode::Live, 0),
+            client::init_live_leaderboard_ix(&program_id, &host, &quiz, true, None),
+            client::start_quiz_ix(&program_id, &host, &quiz),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let alice = funded_keypair(&mut banks_client, &payer).await;
+    let bob = funded_keypair(&mut banks_client, &payer).await;
+    for player in [&alice, &bob] {
+        send(
+            &mut banks_client,
+            &payer,
+            &[client::join_quiz_ix(
+                &program_id,
+                &player.pubkey(),
+                &quiz,
+                0,
+                None,
+            )],
+            &[player],
+        )
+        .await
+        .unwrap();
+    }
+
+    let answer = |player: &Keypair, question_index, answer| {
+        client::with_live_leaderboard(
+            &program_id,
+            &quiz,
+            client::submit_answer_ix(
+                &program_id,
+                &player.pubkey(),
+                &quiz,
+                question_index,
+                answer,
+                false,
+            ),
+        )
+    };
+    let board_address = client::find_live_leaderboard_address(&program_id, &quiz);
+
+    send(
+        &mut banks_client,
+        &payer,
+        &[client::open_question_ix(
+            &program_id,
+            &host,
+            &quiz,
+            0,
+            0,
+            1_000,
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+    send(
+        &mut banks_client,
+        &payer,
+        &[answer(&bob, 0, 0), answer(&alice, 0, 2)],
+        &[&alice, &bob],
+    )
+    .await
+    .unwrap();
+    let account = banks_client
+        .get_account(board_address)
+        .await
+        .unwrap()
+        .unwrap();
+    let board = LiveLeaderboard::try_from_slice(&account.data).unwrap();
+    assert_eq!(board.graded_count, 2);
+    assert_eq!(board.correct_count, 1);
+    let standings: Vec<_> = board.top().iter().map(|e| (e.player, e.score)).collect();
+    assert_eq!(standings, [(alice.pubkey(), 1), (bob.pubkey(), 0)]);
+
+    send(
+        &mut banks_client,
+        &payer,
+        &[client::open_question_ix(
+            &program_id,
+            &host,
+            &quiz,
+            1,
+            0,
+            1_000,
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+    send(&mut banks_client, &payer, &[answer(&bob, 1, 1)], &[&bob])
+        .await
+        .unwrap();
+    let account = banks_client
+        .get_account(board_address)
+        .await
+        .unwrap()
+        .unwrap();
+    let board = LiveLeaderboard::try_from_slice(&account.data).unwrap();
+    assert_eq!(board.graded_count, 3);
+    assert_eq!(board.correct_count, 2);
+    let standings: Vec<_> = board.top().iter().map(|e| (e.player, e.score)).collect();
+    assert_eq!(standings, [(alice.pubkey(), 1), (bob.pubkey(), 1)]);
+
+    let sheet_address = client::find_player_answer_address(&program_id, &quiz, &bob.pubkey());
+    let account = banks_client
+        .get_account(sheet_address)
+        .await
+        .unwrap()
+        .unwrap();
+    let sheet = PlayerAnswer::try_from_slice(&account.data).unwrap();
+    assert_eq!(sheet.live_score, 1);
+
+    // Without the board the answer is rejected
+    let result = send(
+        &mut banks_client,
+        &payer,
+        &[client::submit_answer_ix(
+            &program_id,
+            &alice.pubkey(),
+            &quiz,
+            1,
+            1,
+            false,
+        )],
+        &[&alice],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::NotEnoughAccountKeys
+    );
+}
+
+#[tokio::test]
+async fn reactions_are_counted_and_rate_limited() {
+    let (program_test, program_id) = program_test();
+    let mut context = program_test.start_with_context().await;
+    let payer = context.payer.insecure_clone();
+    let host = payer.pubkey();
+    let base_layer = DelegationConfig {
+        base_layer: true,
+        ..DelegationConfig::default()
+    };
+    let quiz = started_quiz_with(
+        &mut context.banks_client,
+        &payer,
+        &program_id,
+        QuizMode::Live,
+        &[2],
+        base_layer,
+    )
+    .await;
+    let player = funded_keypair(&mut context.banks_client, &payer).await;
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[
+            client::init_reactions_ix(&program_id, &host, &quiz, true, 100, None),
+            client::join_quiz_ix(&program_id, &player.pubkey(), &quiz, 0, None),
+        ],
+        &[&player],
+    )
+    .await
+    .unwrap();
+
+    let react =
+        |emoji_code| client::send_reaction_ix(&program_id, &player.pubkey(), &quiz, emoji_code);
+    let result = send(&mut context.banks_client, &payer, &[react(8)], &[&player]).await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::InvalidReaction as u32)
+    );
+    send(&mut context.banks_client, &payer, &[react(3)], &[&player])
+        .await
+        .unwrap();
+    let result = send(&mut context.banks_client, &payer, &[react(4)], &[&player]).await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::ReactionCooldown as u32)
+    );
+
+    // After the cooldown the player reacts again
+    let sheet_address = client::find_player_answer_address(&program_id, &quiz, &player.pubkey());
+    let account = context
+        .banks_client
+        .get_account(sheet_address)
+        .await
+        .unwrap()
+        .unwrap();
+    let reacted_slot = PlayerAnswer::try_from_slice(&account.data)
+        .unwrap()
+        .last_reaction_slot;
+    context.warp_to_slot(reacted_slot + 100).unwrap();
+    send(&mut context.banks_client, &payer, &[react(4)], &[&player])
+        .await
+        .unwrap();
+
+    let address = client::find_reactions_address(&program_id, &quiz);
+    let account = context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .unwrap();
+    let reactions = QuizReactions::try_from_slice(&account.data).unwrap();
+    assert_eq!(reactions.counts[3], 1);
+    assert_eq!(reactions.counts[4], 1);
+    assert_eq!(reactions.total, 2);
+}
+
+#[tokio::test]
+async fn series_standings_add_up_across_quizzes() {
+    let (program_test, program_id) = program_test();
+    let (mut banks_client, payer, _) = program_test.start().await;
+    let host = payer.pubkey();
+    let organizer = funded_keypair(&mut banks_client, &payer).await;
+    let series = client::find_series_address(&program_id, &organizer.pubkey(), 1);
+    let quizzes =
+        [QUIZ_ID, QUIZ_ID + 1].map(|id| client::find_quiz_address(&program_id, &host, id));
+    let base_layer = DelegationConfig {
+        base_layer: true,
+        ..DelegationConfig::default()
+    };
+    let mut setup = vec![client::create_series_ix(
+        &program_id,
+        &organizer.pubkey(),
+        1,
+        2,
+    )];
+    for (position, (id, quiz)) in [QUIZ_ID, QUIZ_ID + 1].iter().zip(&quizzes).enumerate() {
+        setup.extend([
+            client::initialize_quiz_with_delegation_ix(&program_id, &host, *id, 2, base_layer),
+            question(&program_id, &host, quiz, 0, 1),
+            question(&program_id, &host, quiz, 1, 2),
+            client::link_series_quiz_ix(
+                &program_id,
+                &organizer.pubkey(),
+                &host,
+                &series,
+                quiz,
+                position as u8,
+            ),
+        ]);
+    }
+    setup.push(client::fund_series_ix(
+        &program_id,
+        &organizer.pubkey(),
+        &series,
+        500_000,
+    ));
+    send(&mut banks_client, &payer, &setup, &[&organizer])
+        .await
+        .unwrap();
+
+    // A position holds one quiz and a quiz one position
+    let relink = client::link_series_quiz_ix(
+        &program_id,
+        &organizer.pubkey(),
+        &host,
+        &series,
+        &quizzes[0],
+        1,
+    );
+    let result = send(&mut banks_client, &payer, &[relink], &[&organizer]).await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::WrongSeriesQuiz as u32)
+    );
+
+    // The first player wins the opening quiz, the second the series
+    let players = [
+        funded_keypair(&mut banks_client, &payer).await,
+        funded_keypair(&mut banks_client, &payer).await,
+    ];
+    let keys = players.each_ref().map(|player| player.pubkey());
+    for (quiz, answers) in quizzes
+        .iter()
+        .zip([[vec![1, 2], vec![1, 0]], [vec![0, 0], vec![1, 2]]])
+    {
+        let mut round = vec![client::start_quiz_ix(&program_id, &host, quiz)];
+        for (player, answers) in keys.iter().zip(answers) {
+            round.push(client::join_quiz_ix(&program_id, player, quiz, 0, None));
+            round.push(client::submit_answers_ix(
+                &program_id,
+                player,
+                quiz,
+                answers,
+            ));
+        }
+        round.push(client::calculate_scores_ix(
+            &program_id,
+            &host,
+            quiz,
+            2,
+            &keys,
+        ));
+        send(
+            &mut banks_client,
+            &payer,
+            &round,
+            &[&players[0], &players[1]],
+        )
+        .await
+        .unwrap();
+    }
+
+    let claim = |leader: &Pubkey| client::claim_series_prize_ix(&program_id, leader, &series);
+    let result = send(
+        &mut banks_client,
+        &payer,
+        &[claim(&keys[0])],
+        &[&players[0]],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::SeriesNotComplete as u32)
+    );
+
+    // Each quiz is recorded once, by anyone
+    let record = |quiz: &Pubkey| client::record_series_quiz_ix(&program_id, &series, quiz, &keys);
+    send(&mut banks_client, &payer, &[record(&quizzes[0])], &[])
+        .await
+        .unwrap();
+    // Paid by the organizer so it isn't a duplicate of the first transaction
+    let result = send(&mut banks_client, &organizer, &[record(&quizzes[0])], &[]).await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::WrongSeriesQuiz as u32)
+    );
+    send(&mut banks_client, &payer, &[record(&quizzes[1])], &[])
+        .await
+        .unwrap();
+
+    let account = banks_client.get_account(series).await.unwrap().unwrap();
+    let standings = Series::try_from_slice(&account.data).unwrap();
+    assert!(standings.is_complete());
+    let totals: Vec<_> = standings
+        .standings()
+        .iter()
+        .map(|entry| (entry.player, entry.total_score))
+        .collect();
+    assert_eq!(totals, vec![(keys[1], 3), (keys[0], 2)]);
+
+    let result = send(&mut banks_client, &players[0], &[claim(&keys[0])], &[]).await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::InvalidAccountData
+    );
+    let before = banks_client.get_balance(keys[1]).await.unwrap();
+    send(
+        &mut banks_client,
+        &payer,
+        &[claim(&keys[1])],
+        &[&players[1]],
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        banks_client.get_balance(keys[1]).await.unwrap(),
+        before + 500_000
+    );
+    let result = send(&mut banks_client, &players[1], &[claim(&keys[1])], &[]).await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::AlreadySettled as u32)
+    );
+
+    // The next season starts from empty standings, the archive keeps this one
+    send(
+        &mut banks_client,
+        &payer,
+        &[client::end_series_season_ix(
+            &program_id,
+            &organizer.pubkey(),
+            &series,
+            0,
+            3,
+        )],
+        &[&organizer],
+    )
+    .await
+    .unwrap();
+    let archive = client::find_season_archive_address(&program_id, &series, 0);
+    let account = banks_client.get_account(archive).await.unwrap().unwrap();
+    let archive = SeasonArchive::try_from_slice(&account.data).unwrap();
+    assert_eq!((archive.board, archive.season), (series, 0));
+    assert_eq!(archive.entries().len(), 2);
+    assert_eq!(archive.entries()[0].player, keys[1]);
+    let account = banks_client.get_account(series).await.unwrap().unwrap();
+    let next = Series::try_from_slice(&account.data).unwrap();
+    assert_eq!((next.season, next.quiz_count), (1, 3));
+    assert!(next.standings().is_empty());
+    assert!(!next.is_complete());
+}
+
+#[tokio::test]
+async fn jackpots_roll_over_to_the_next_series_quiz() {
+    let (program_test, program_id) = program_test();
+    let (mut banks_client, payer, _) = program_test.start().await;
+    let host = payer.pubkey();
+    let organizer = funded_keypair(&mut banks_client, &payer).await;
+    let series = client::find_series_address(&program_id, &organizer.pubkey(), 1);
+    let quizzes =
+        [QUIZ_ID, QUIZ_ID + 1].map(|id| client::find_quiz_address(&program_id, &host, id));
+    let base_layer = DelegationConfig {
+        base_layer: true,
+        ..DelegationConfig::default()
+    };
+    let entry_fee = 1_000_000;
+    send(
+        &mut banks_client,
+        &payer,
+        &[client::create_series_ix(
+            &program_id,
+            &organizer.pubkey(),
+            1,
+            2,
+        )],
+        &[&organizer],
+    )
+    .await
+    .unwrap();
+    for (position, (id, quiz)) in [QUIZ_ID, QUIZ_ID + 1].iter().zip(&quizzes).enumerate() {
+        send(
+            &mut banks_client,
+            &payer,
+            &[
+                client::initialize_quiz_with_delegation_ix(&program_id, &host, *id, 2, base_layer),
+                question(&program_id, &host, quiz, 0, 1),
+                question(&program_id, &host, quiz, 1, 2),
+                client::link_series_quiz_ix(
+                    &program_id,
+                    &organizer.pubkey(),
+                    &host,
+                    &series,
+                    quiz,
+                    position as u8,
+                ),
+            ],
+            &[&organizer],
+        )
+        .await
+        .unwrap();
+    }
+
+    // The opening quiz only pays a perfect score
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::set_entry_fee_ix(&program_id, &host, &quizzes[0], entry_fee),
+            client::set_proportional_prizes_ix(&program_id, &host, &quizzes[0], true),
+            client::set_prize_threshold_ix(
+                &program_id,
+                &host,
+                &quizzes[0],
+                0,
+                10_000,
+                PrizeRemainder::RollOver,
+            ),
+            client::start_quiz_ix(&program_id, &host, &quizzes[0]),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    let players = [
+        funded_keypair(&mut banks_client, &payer).await,
+        funded_keypair(&mut banks_client, &payer).await,
+    ];
+    let keys = players.each_ref().map(|player| player.pubkey());
+    let mut round = Vec::new();
+    for (player, answers) in keys.iter().zip([vec![1, 0], vec![0, 2]]) {
+        round.push(client::join_quiz_ix(
+            &program_id,
+            player,
+            &quizzes[0],
+            entry_fee,
+            None,
+        ));
+        round.push(client::submit_answers_ix(
+            &program_id,
+            player,
+            &quizzes[0],
+            answers,
+        ));
+    }
+    round.push(client::calculate_scores_ix(
+        &program_id,
+        &host,
+        &quizzes[0],
+        2,
+        &keys,
+    ));
+    send(
+        &mut banks_client,
+        &payer,
+        &round,
+        &[&players[0], &players[1]],
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        quiz_session(&mut banks_client, quizzes[0])
+            .await
+            .total_prize_score,
+        0
+    );
+
+    // Nobody can claim, so the pot moves one position along the series
+    let result = send(
+        &mut banks_client,
+        &payer,
+        &[client::claim_score_prize_ix(
+            &program_id,
+            &keys[0],
+            &quizzes[0],
+        )],
+        &[&players[0]],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::BelowPrizeThreshold as u32)
+    );
+    let rollover = |quiz: &Pubkey, next_quiz: &Pubkey| {
+        client::rollover_jackpot_ix(&program_id, &host, &series, quiz, next_quiz)
+    };
+    let result = send(
+        &mut banks_client,
+        &payer,
+        &[rollover(&quizzes[0], &quizzes[0])],
+        &[],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::WrongSeriesQuiz as u32)
+    );
+    send(
+        &mut banks_client,
+        &payer,
+        &[rollover(&quizzes[0], &quizzes[1])],
+        &[],
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        quiz_session(&mut banks_client, quizzes[0]).await.prize_pool,
+        0
+    );
+    assert_eq!(
+        quiz_session(&mut banks_client, quizzes[1]).await.prize_pool,
+        2 * entry_fee
+    );
+    let next_vault = client::find_quiz_vault_address(&program_id, &quizzes[1]);
+    assert_eq!(
+        banks_client.get_balance(next_vault).await.unwrap(),
+        Rent::default().minimum_balance(0) + 2 * entry_fee
+    );
+
+    // Paid by a player so it isn't a duplicate of the rollover
+    let result = send(
+        &mut banks_client,
+        &players[0],
+        &[rollover(&quizzes[0], &quizzes[1])],
+        &[&payer],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::NoJackpot as u32)
+    );
+}
+
+#[tokio::test]
+async fn daily_seasons_reset_the_leaderboard() {
+    let (program_test, program_id) = program_test();
+    let mut context = program_test.start_with_context().await;
+    let payer = context.payer.insecure_clone();
+    let authority = payer.pubkey();
+    let player = funded_keypair(&mut context.banks_client, &payer).await;
+    let leaderboard_address = client::find_daily_leaderboard_address(&program_id);
+    set_upgrade_authority(&mut context, &program_id, &authority);
+
+    // Nobody but the upgrade authority can claim the config
+    let result = send(
+        &mut context.banks_client,
+        &player,
+        &[client::initialize_daily_quiz_ix(
+            &program_id,
+            &player.pubkey(),
+            player.pubkey(),
+        )],
+        &[],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::NotUpgradeAuthority as u32)
+    );
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[client::initialize_daily_quiz_ix(
+            &program_id,
+            &authority,
+            authority,
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    // One correct answer on each of two days, a season apart
+    let clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    let today = DailyQuiz::day_index(clock.unix_timestamp);
+    let play = |day_index: u64| {
+        [
+            client::publish_daily_question_ix(
+                &program_id,
+                &authority,
+                day_index,
+                0,
+                "Question 0".to_string(),
+                ["A", "B", "C", "D"].map(String::from),
+                1,
+            ),
+            client::play_daily_quiz_ix(&program_id, &player.pubkey(), day_index, vec![1]),
+        ]
+    };
+    send(&mut context.banks_client, &payer, &play(today), &[&player])
+        .await
+        .unwrap();
+
+    // Only the authority ends a season early
+    let end = || client::end_daily_season_ix(&program_id, &player.pubkey(), 0);
+    let result = send(&mut context.banks_client, &payer, &[end()], &[&player]).await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::SeasonNotOver as u32)
+    );
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[client::set_daily_season_length_ix(
+            &program_id,
+            &authority,
+            1,
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    // A day later anyone may end it
+    context.warp_to_slot(clock.slot + 100).unwrap();
+    let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    clock.unix_timestamp += DailyQuiz::SECONDS_PER_DAY;
+    context.set_sysvar(&clock);
+    send(&mut context.banks_client, &payer, &[end()], &[&player])
+        .await
+        .unwrap();
+
+    let archive = client::find_season_archive_address(&program_id, &leaderboard_address, 0);
+    let account = context
+        .banks_client
+        .get_account(archive)
+        .await
+        .unwrap()
+        .unwrap();
+    let archive = SeasonArchive::try_from_slice(&account.data).unwrap();
+    assert_eq!(archive.board, leaderboard_address);
+    assert_eq!(archive.entries()[0].player, player.pubkey());
+    assert_eq!(archive.entries()[0].total_score, 1);
+
+    send(
+        &mut context.banks_client,
+        &payer,
+        &play(today + 1),
+        &[&player],
+    )
+    .await
+    .unwrap();
+    let account = context
+        .banks_client
+        .get_account(leaderboard_address)
+        .await
+        .unwrap()
+        .unwrap();
+    let leaderboard = DailyLeaderboard::try_from_slice(&account.data).unwrap();
+    assert_eq!(leaderboard.season, 1);
+    assert_eq!(leaderboard.entries()[0].total_score, 1);
+    let streak_address = client::find_daily_streak_address(&program_id, &player.pubkey());
+    let account = context
+        .banks_client
+        .get_account(streak_address)
+        .await
+        .unwrap()
+        .unwrap();
+    let streak = DailyStreak::try_from_slice(&account.data).unwrap();
+    assert_eq!((streak.current_streak, streak.total_score), (2, 2));
+    assert_eq!((streak.season, streak.season_score), (1, 1));
+}
+
+#[tokio::test]
+async fn referrers_are_paid_a_cut_of_the_entry_fee() {
+    let (program_test, program_id) = program_test();
+    let (mut banks_client, payer, _) = program_test.start().await;
+    let host = payer.pubkey();
+    let quiz = client::find_quiz_address(&program_id, &host, QUIZ_ID);
+    let base_layer = DelegationConfig {
+        base_layer: true,
+        ..DelegationConfig::default()
+    };
+    let entry_fee = 1_000_000;
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::initialize_quiz_with_delegation_ix(&program_id, &host, QUIZ_ID, 1, base_layer),
+            question(&program_id, &host, &quiz, 0, 2),
+            client::set_entry_fee_ix(&program_id, &host, &quiz, entry_fee),
+            client::set_referral_cut_ix(&program_id, &host, &quiz, 1_000),
+            client::start_quiz_ix(&program_id, &host, &quiz),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let referrer = funded_keypair(&mut banks_client, &payer).await;
+    let referred = funded_keypair(&mut banks_client, &payer).await;
+    let unreferred = funded_keypair(&mut banks_client, &payer).await;
+    let join = |player: &Pubkey| client::join_quiz_ix(&program_id, player, &quiz, entry_fee, None);
+    let result = send(
+        &mut banks_client,
+        &payer,
+        &[client::referred_by(
+            join(&referred.pubkey()),
+            &referred.pubkey(),
+        )],
+        &[&referred],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::InvalidReferrer as u32)
+    );
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::referred_by(join(&referred.pubkey()), &referrer.pubkey()),
+            join(&unreferred.pubkey()),
+        ],
+        &[&referred, &unreferred],
+    )
+    .await
+    .unwrap();
+
+    // The cut is held back from the prize pool
+    let session = quiz_session(&mut banks_client, quiz).await;
+    assert_eq!(session.prize_pool, 2 * entry_fee - entry_fee / 10);
+    let pay =
+        |player: &Pubkey| client::pay_referral_ix(&program_id, &referrer.pubkey(), &quiz, player);
+    let result = send(&mut banks_client, &payer, &[pay(&referred.pubkey())], &[]).await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::InvalidAccountData
+    );
+
+    let players = [referred.pubkey(), unreferred.pubkey()];
+    send(
+        &mut banks_client,
+        &payer,
+        &[client::calculate_scores_ix(
+            &program_id,
+            &host,
+            &quiz,
+            1,
+            &players,
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+    let result = send(&mut banks_client, &payer, &[pay(&unreferred.pubkey())], &[]).await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::InvalidAccountData
+    );
+
+    // Anyone sends the payout, once
+    let before = banks_client.get_balance(referrer.pubkey()).await.unwrap();
+    send(
+        &mut banks_client,
+        &referred,
+        &[pay(&referred.pubkey())],
+        &[],
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        banks_client.get_balance(referrer.pubkey()).await.unwrap(),
+        before + entry_fee / 10
+    );
+    let result = send(
+        &mut banks_client,
+        &unreferred,
+        &[pay(&referred.pubkey())],
+        &[],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::AlreadySettled as u32)
+    );
+}
+
+/// A fully verified Pyth SOL/USD update of `price * 10^-8` USD.
+fn set_sol_price(
+    context: &mut ProgramTestContext,
+    address: &Pubkey,
+    price: i64,
+    conf: u64,
+    publish_time: i64,
+) {
+    let mut data = pyth::PRICE_UPDATE_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&[0; 32]); // write authority
+    data.push(1); // full verification
+    data.extend_from_slice(&pyth::SOL_USD_FEED_ID);
+    data.extend_from_slice(&price.to_le_bytes());
+    data.extend_from_slice(&conf.to_le_bytes());
+    data.extend_from_slice(&(-8i32).to_le_bytes());
+    data.extend_from_slice(&publish_time.to_le_bytes());
+    data.extend_from_slice(&[0; 8 + 8 + 8 + 8]); // prev_publish_time + ema_price + ema_conf + posted_slot
+    context.set_account(
+        address,
+        &Account {
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            owner: pyth::RECEIVER_PROGRAM_ID,
+            executable: false,
+            rent_epoch: 0,
+        }
+        .into(),
+    );
+}
+
+#[tokio::test]
+async fn usd_entry_fees_are_priced_at_join() {
+    let (program_test, program_id) = program_test();
+    let mut context = program_test.start_with_context().await;
+    let payer = context.payer.insecure_clone();
+    let host = payer.pubkey();
+    let quiz = client::find_quiz_address(&program_id, &host, QUIZ_ID);
+    let base_layer = DelegationConfig {
+        base_layer: true,
+        ..DelegationConfig::default()
+    };
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[
+            client::initialize_quiz_with_delegation_ix(&program_id, &host, QUIZ_ID, 1, base_layer),
+            question(&program_id, &host, &quiz, 0, 2),
+            client::set_usd_entry_fee_ix(&program_id, &host, &quiz, 500, pyth::SOL_USD_FEED_ID),
+            client::start_quiz_ix(&program_id, &host, &quiz),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let player = funded_keypair(&mut context.banks_client, &payer).await;
+    let price_update = Pubkey::new_unique();
+    let join = || {
+        client::with_price_update(
+            &program_id,
+            &quiz,
+            &price_update,
+            client::join_quiz_ix(&program_id, &player.pubkey(), &quiz, 0, None),
+        )
+    };
+    let clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    set_sol_price(
+        &mut context,
+        &price_update,
+        15_000_000_000,
+        10_000_000,
+        clock.unix_timestamp - pyth::MAX_PRICE_AGE_SECS - 1,
+    );
+    let result = send(&mut context.banks_client, &payer, &[join()], &[&player]).await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::StalePrice as u32)
+    );
+
+    // $150 give or take $4 is too vague to charge by
+    set_sol_price(
+        &mut context,
+        &price_update,
+        15_000_000_000,
+        400_000_000,
+        clock.unix_timestamp,
+    );
+    // Paid by the player so it isn't a duplicate of the stale attempt
+    let result = send(&mut context.banks_client, &player, &[join()], &[]).await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::PriceUncertain as u32)
+    );
+
+    context.warp_to_slot(clock.slot + 10).unwrap();
+    let clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    set_sol_price(
+        &mut context,
+        &price_update,
+        15_000_000_000,
+        10_000_000,
+        clock.unix_timestamp,
+    );
+    let vault = client::find_quiz_vault_address(&program_id, &quiz);
+    let before = context.banks_client.get_balance(vault).await.unwrap();
+    send(&mut context.banks_client, &payer, &[join()], &[&player])
+        .await
+        .unwrap();
+
+    // $5 at $150 a SOL, rounded up
+    let entry_fee = 33_333_334;
+    assert_eq!(
+        context.banks_client.get_balance(vault).await.unwrap(),
+        before + entry_fee
+    );
+    let session = quiz_session(&mut context.banks_client, quiz).await;
+    assert_eq!(session.prize_pool, entry_fee);
+}
+
+#[tokio::test]
+async fn anyone_can_top_up_the_prize_pool() {
+    let (program_test, program_id) = program_test();
+    let (mut banks_client, payer, _) = program_test.start().await;
+    let host = payer.pubkey();
+    let quiz = client::find_quiz_address(&program_id, &host, QUIZ_ID);
+    let base_layer = DelegationConfig {
+        base_layer: true,
+        ..DelegationConfig::default()
+    };
+    let sponsor = funded_keypair(&mut banks_client, &payer).await;
+    let contribute = |lamports: u64| {
+        client::contribute_to_prize_pool_ix(&program_id, &sponsor.pubkey(), &quiz, lamports)
+    };
+
+    // A free quiz gets its vault from the first contribution
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::initialize_quiz_with_delegation_ix(&program_id, &host, QUIZ_ID, 1, base_layer),
+            question(&program_id, &host, &quiz, 0, 2),
+            contribute(200_000_000),
+            client::start_quiz_ix(&program_id, &host, &quiz),
+        ],
+        &[&sponsor],
+    )
+    .await
+    .unwrap();
+    let player = funded_keypair(&mut banks_client, &payer).await;
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::join_quiz_ix(&program_id, &player.pubkey(), &quiz, 0, None),
+            contribute(100_000_000),
+            client::submit_answers_ix(&program_id, &player.pubkey(), &quiz, vec![2]),
+        ],
+        &[&player, &sponsor],
+    )
+    .await
+    .unwrap();
+    let vault = client::find_quiz_vault_address(&program_id, &quiz);
+    let rent = Rent::default().minimum_balance(0);
+    assert_eq!(
+        banks_client.get_balance(vault).await.unwrap(),
+        rent + 300_000_000
+    );
+    assert_eq!(
+        quiz_session(&mut banks_client, quiz).await.prize_pool,
+        300_000_000
+    );
+
+    send(
+        &mut banks_client,
+        &payer,
+        &[client::calculate_scores_ix(
+            &program_id,
+            &host,
+            &quiz,
+            1,
+            &[player.pubkey()],
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+    let result = send(&mut banks_client, &payer, &[contribute(1)], &[&sponsor]).await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::InvalidAccountData
+    );
+}
+
+#[tokio::test]
+async fn contributions_are_refunded_when_the_quiz_is_cancelled() {
+    let (program_test, program_id) = program_test();
+    let (mut banks_client, payer, _) = program_test.start().await;
+    let host = payer.pubkey();
+    let quiz = client::find_quiz_address(&program_id, &host, QUIZ_ID);
+    let sponsor = funded_keypair(&mut banks_client, &payer).await;
+    let backer = funded_keypair(&mut banks_client, &payer).await;
+    let contribute = |contributor: &Keypair, lamports: u64| {
+        client::contribute_to_prize_pool_ix(&program_id, &contributor.pubkey(), &quiz, lamports)
+    };
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::initialize_quiz_ix(&program_id, &host, QUIZ_ID, 1),
+            question(&program_id, &host, &quiz, 0, 2),
+            contribute(&sponsor, 200_000_000),
+            contribute(&backer, 100_000_000),
+            client::start_quiz_ix(&program_id, &host, &quiz),
+            contribute(&sponsor, 100_000_000),
+        ],
+        &[&sponsor, &backer],
+    )
+    .await
+    .unwrap();
+
+    // Contributions add up per contributor
+    let record = client::find_prize_contribution_address(&program_id, &quiz, &sponsor.pubkey());
+    let account = banks_client.get_account(record).await.unwrap().unwrap();
+    let contribution = PrizeContribution::try_from_slice(&account.data).unwrap();
+    assert_eq!(contribution.contributor, sponsor.pubkey());
+    assert_eq!(contribution.lamports, 300_000_000);
+    let session = quiz_session(&mut banks_client, quiz).await;
+    assert_eq!(session.prize_pool, 400_000_000);
+    assert_eq!(session.contributed_lamports, 400_000_000);
+
+    let refund = |contributor: &Keypair| {
+        client::refund_contribution_ix(&program_id, &contributor.pubkey(), &quiz)
+    };
+    // Paid by the backer so it isn't a duplicate of the refund below
+    let result = send(&mut banks_client, &backer, &[refund(&sponsor)], &[]).await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::InvalidAccountData
+    );
+
+    send(
+        &mut banks_client,
+        &payer,
+        &[client::cancel_quiz_ix(&program_id, &host, &quiz)],
+        &[],
+    )
+    .await
+    .unwrap();
+    let record_rent = Rent::default().minimum_balance(PrizeContribution::SIZE);
+    for (contributor, lamports) in [(&sponsor, 300_000_000), (&backer, 100_000_000)] {
+        let before = banks_client
+            .get_balance(contributor.pubkey())
+            .await
+            .unwrap();
+        send(&mut banks_client, &payer, &[refund(contributor)], &[])
+            .await
+            .unwrap();
+        assert_eq!(
+            banks_client
+                .get_balance(contributor.pubkey())
+                .await
+                .unwrap(),
+            before + lamports + record_rent
+        );
+    }
+    let session = quiz_session(&mut banks_client, quiz).await;
+    assert_eq!(session.prize_pool, 0);
+    assert_eq!(session.contributed_lamports, 0);
+    assert!(banks_client.get_account(record).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn the_charity_is_paid_before_prizes() {
+    let (program_test, program_id) = program_test();
+    let (mut banks_client, payer, _) = program_test.start().await;
+    let host = payer.pubkey();
+    let quiz = client::find_quiz_address(&program_id, &host, QUIZ_ID);
+    let base_layer = DelegationConfig {
+        base_layer: true,
+        ..DelegationConfig::default()
+    };
+    let charity = Pubkey::new_unique();
+    let entry_fee = 10_000_000;
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::initialize_quiz_with_delegation_ix(&program_id, &host, QUIZ_ID, 1, base_layer),
+            question(&program_id, &host, &quiz, 0, 2),
+            client::set_entry_fee_ix(&program_id, &host, &quiz, entry_fee),
+            client::set_proportional_prizes_ix(&program_id, &host, &quiz, true),
+            client::set_charity_split_ix(&program_id, &host, &quiz, &charity, 1_000),
+            client::start_quiz_ix(&program_id, &host, &quiz),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    let player = funded_keypair(&mut banks_client, &payer).await;
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::join_quiz_ix(&program_id, &player.pubkey(), &quiz, entry_fee, None),
+            client::submit_answers_ix(&program_id, &player.pubkey(), &quiz, vec![2]),
+            client::calculate_scores_ix(&program_id, &host, &quiz, 1, &[player.pubkey()]),
+        ],
+        &[&player],
+    )
+    .await
+    .unwrap();
+    let session = quiz_session(&mut banks_client, quiz).await;
+    assert_eq!(session.charity, charity);
+    assert_eq!(session.charity_bps, 1_000);
+
+    let claim = || client::claim_score_prize_ix(&program_id, &player.pubkey(), &quiz);
+    // Paid by the player so it isn't a duplicate of the claim below
+    let result = send(&mut banks_client, &player, &[claim()], &[]).await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::CharityUnpaid as u32)
+    );
+
+    let pay = client::pay_charity_ix(&program_id, &quiz, &charity);
+    let result = send(
+        &mut banks_client,
+        &payer,
+        &[client::pay_charity_ix(
+            &program_id,
+            &quiz,
+            &Pubkey::new_unique(),
+        )],
+        &[],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::InvalidAccountData
+    );
+    send(&mut banks_client, &payer, &[pay], &[]).await.unwrap();
+    assert_eq!(
+        banks_client.get_balance(charity).await.unwrap(),
+        entry_fee / 10
+    );
+
+    // The winner shares what is left
+    let before = banks_client.get_balance(player.pubkey()).await.unwrap();
+    send(&mut banks_client, &payer, &[claim()], &[&player])
+        .await
+        .unwrap();
+    let claim_rent = Rent::default().minimum_balance(LeaderboardClaim::SIZE);
+    assert_eq!(
+        banks_client.get_balance(player.pubkey()).await.unwrap(),
+        before + entry_fee - entry_fee / 10 - claim_rent
+    );
+}
+
+#[tokio::test]
+async fn hosts_cannot_play_their_own_prize_quizzes() {
+    let (program_test, program_id) = program_test();
+    let (mut banks_client, payer, _) = program_test.start().await;
+    let host = payer.pubkey();
+    let base_layer = DelegationConfig {
+        base_layer: true,
+        ..DelegationConfig::default()
+    };
+    let entry_fee = 1_000_000;
+    let setup = |quiz_id: u64, policy: HostPlay| {
+        let quiz = client::find_quiz_address(&program_id, &host, quiz_id);
+        vec![
+            client::initialize_quiz_with_delegation_ix(&program_id, &host, quiz_id, 1, base_layer),
+            question(&program_id, &host, &quiz, 0, 2),
+            client::set_entry_fee_ix(&program_id, &host, &quiz, entry_fee),
+            client::set_host_play_ix(&program_id, &host, &quiz, policy),
+            client::start_quiz_ix(&program_id, &host, &quiz),
+        ]
+    };
+    let join = |quiz_id: u64| {
+        let quiz = client::find_quiz_address(&program_id, &host, quiz_id);
+        client::join_quiz_ix(&program_id, &host, &quiz, entry_fee, None)
+    };
+
+    // Prize quizzes bar the host by default
+    send(
+        &mut banks_client,
+        &payer,
+        &setup(QUIZ_ID, HostPlay::BarredFromPrizes),
+        &[],
+    )
+    .await
+    .unwrap();
+    let result = send(&mut banks_client, &payer, &[join(QUIZ_ID)], &[]).await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::HostCannotPlay as u32)
+    );
+
+    send(
+        &mut banks_client,
+        &payer,
+        &setup(QUIZ_ID + 1, HostPlay::Allowed),
+        &[],
+    )
+    .await
+    .unwrap();
+    send(&mut banks_client, &payer, &[join(QUIZ_ID + 1)], &[])
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn join_stakes_are_returned_after_the_quiz() {
+    let (program_test, program_id) = program_test();
+    let (mut banks_client, payer, _) = program_test.start().await;
+    let host = payer.pubkey();
+    let quiz = client::find_quiz_address(&program_id, &host, QUIZ_ID);
+    let base_layer = DelegationConfig {
+        base_layer: true,
+        ..DelegationConfig::default()
+    };
+    let stake = 50_000_000;
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::initialize_quiz_with_delegation_ix(&program_id, &host, QUIZ_ID, 1, base_layer),
+            question(&program_id, &host, &quiz, 0, 2),
+            client::set_join_stake_ix(&program_id, &host, &quiz, stake),
+            client::start_quiz_ix(&program_id, &host, &quiz),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    let player = funded_keypair(&mut banks_client, &payer).await;
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::join_quiz_ix(&program_id, &player.pubkey(), &quiz, stake, None),
+            client::submit_answers_ix(&program_id, &player.pubkey(), &quiz, vec![2]),
+        ],
+        &[&player],
+    )
+    .await
+    .unwrap();
+
+    // The stake is locked outside the prize pool
+    let session = quiz_session(&mut banks_client, quiz).await;
+    assert_eq!(session.prize_pool, 0);
+    assert_eq!(session.stakes_held, stake);
+    let reclaim = || client::reclaim_stake_ix(&program_id, &player.pubkey(), &quiz);
+    // Paid by the player so it isn't a duplicate of the reclaim below
+    let result = send(&mut banks_client, &player, &[reclaim()], &[]).await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::InvalidAccountData
+    );
+
+    send(
+        &mut banks_client,
+        &payer,
+        &[client::calculate_scores_ix(
+            &program_id,
+            &host,
+            &quiz,
+            1,
+            &[player.pubkey()],
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+    // Only the player can take their stake back
+    let mut unsigned = reclaim();
+    unsigned.accounts[0].is_signer = false;
+    let result = send(&mut banks_client, &payer, &[unsigned], &[]).await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::MissingRequiredSignature
+    );
+
+    let before = banks_client.get_balance(player.pubkey()).await.unwrap();
+    send(&mut banks_client, &payer, &[reclaim()], &[&player])
+        .await
+        .unwrap();
+    assert_eq!(
+        banks_client.get_balance(player.pubkey()).await.unwrap(),
+        before + stake
+    );
+    assert_eq!(quiz_session(&mut banks_client, quiz).await.stakes_held, 0);
+
+    // Paid by someone else so it isn't a duplicate of the reclaim above
+    let stranger = funded_keypair(&mut banks_client, &payer).await;
+    let result = send(&mut banks_client, &stranger, &[reclaim()], &[&player]).await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::AlreadySettled as u32)
+    );
+}
+
+#[tokio::test]
+async fn streaks_of_right_answers_earn_bonus_points() {
+    let (program_test, program_id) = program_test();
+    let (mut banks_client, payer, _) = program_test.start().await;
+    let host = payer.pubkey();
+    let quiz = client::find_quiz_address(&program_id, &host, QUIZ_ID);
+    let base_layer = DelegationConfig {
+        base_layer: true,
+        ..DelegationConfig::default()
+    };
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::initialize_quiz_with_delegation_ix(&program_id, &host, QUIZ_ID, 4, base_layer),
+            question(&program_id, &host, &quiz, 0, 0),
+            question(&program_id, &host, &quiz, 1, 1),
+            question(&program_id, &host, &quiz, 2, 2),
+            question(&program_id, &host, &quiz, 3, 3),
+            client::set_scoring_mode_ix(&program_id, &host, &quiz, ScoringMode::Streak),
+            client::start_quiz_ix(&program_id, &host, &quiz),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        quiz_session(&mut banks_client, quiz).await.scoring_mode,
+        ScoringMode::Streak
+    );
+
+    // Players are scored the way they were told when they joined
+    let result = send(
+        &mut banks_client,
+        &payer,
+        &[client::set_scoring_mode_ix(
+            &program_id,
+            &host,
+            &quiz,
+            ScoringMode::Quadratic,
+        )],
+        &[],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::InvalidAccountData
+    );
+
+    let player = funded_keypair(&mut banks_client, &payer).await;
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::join_quiz_ix(&program_id, &player.pubkey(), &quiz, 0, None),
+            client::submit_answers_ix(&program_id, &player.pubkey(), &quiz, vec![0, 1, 3, 3]),
+            client::calculate_scores_ix(&program_id, &host, &quiz, 4, &[player.pubkey()]),
+        ],
+        &[&player],
+    )
+    .await
+    .unwrap();
+
+    // 1, then 1 + 1 for the streak, nothing for the miss, and 1 again
+    let score_address = client::find_player_score_address(&program_id, &quiz, &player.pubkey());
+    assert_eq!(
+        player_score(&mut banks_client, score_address).await.score,
+        4
+    );
+}
+
+#[tokio::test]
+async fn legacy_scores_are_migrated() {
+    let (program_test, program_id) = program_test();
+    let mut context = program_test.start_with_context().await;
+    let payer = context.payer.insecure_clone();
+    let quiz = Pubkey::new_unique();
+    let player = Pubkey::new_unique();
+    let address = client::find_player_score_address(&program_id, &quiz, &player);
+
+    // A score written while scores were a u8
+    let mut data = player.to_bytes().to_vec();
+    data.push(200);
+    data.extend_from_slice(quiz.as_ref());
+    data.extend_from_slice(&[3, 1]);
+    assert_eq!(data.len(), PlayerScore::LEGACY_SIZE);
+    context.set_account(
+        &address,
+        &Account {
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            owner: program_id,
+            executable: false,
+            rent_epoch: 0,
+        }
+        .into(),
+    );
+
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[client::migrate_player_score_ix(
+            &program_id,
+            &payer.pubkey(),
+            &address,
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+    let account = context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(account.data.len(), PlayerScore::SIZE);
+    assert_eq!(
+        account.lamports,
+        Rent::default().minimum_balance(PlayerScore::SIZE)
+    );
+    let score = PlayerScore::try_from_slice(&account.data).unwrap();
+    assert_eq!(score.player, player);
+    assert_eq!(score.score, 200);
+    assert_eq!(score.quiz, quiz);
+    assert_eq!(score.late_penalty, 3);
+    assert_eq!(score.skipped, 1);
+
+    // Scores already in the current layout are left alone
+    let other = funded_keypair(&mut context.banks_client, &payer).await;
+    let result = send(
+        &mut context.banks_client,
+        &other,
+        &[client::migrate_player_score_ix(
+            &program_id,
+            &other.pubkey(),
+            &address,
+        )],
+        &[],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::InvalidAccountData
+    );
+}
+
+#[tokio::test]
+async fn unfilled_duels_refund_their_only_player() {
+    let (program_test, program_id) = program_test();
+    let (mut banks_client, payer, _) = program_test.start().await;
+    let host = payer.pubkey();
+    let quiz = client::find_quiz_address(&program_id, &host, QUIZ_ID);
+    let base_layer = DelegationConfig {
+        base_layer: true,
+        ..DelegationConfig::default()
+    };
+    let entry_fee = 1_000_000;
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::initialize_quiz_with_delegation_ix(&program_id, &host, QUIZ_ID, 1, base_layer),
+            question(&program_id, &host, &quiz, 0, 2),
+            client::set_quiz_mode_ix(&program_id, &host, &quiz, QuizMode::Duel, 0),
+            client::set_entry_fee_ix(&program_id, &host, &quiz, entry_fee),
+            client::start_quiz_ix(&program_id, &host, &quiz),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    let player = funded_keypair(&mut banks_client, &payer).await;
+    send(
+        &mut banks_client,
+        &payer,
+        &[client::join_quiz_ix(
+            &program_id,
+            &player.pubkey(),
+            &quiz,
+            entry_fee,
+            None,
+        )],
+        &[&player],
+    )
+    .await
+    .unwrap();
+
+    // Someone may still take the second seat
+    let refund = client::refund_duel_ix(&program_id, &player.pubkey(), &quiz);
+    let result = send(
+        &mut banks_client,
+        &player,
+        std::slice::from_ref(&refund),
+        &[],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::DuelStillOpen as u32)
+    );
+
+    send(
+        &mut banks_client,
+        &payer,
+        &[client::close_submissions_ix(&program_id, &host, &quiz)],
+        &[],
+    )
+    .await
+    .unwrap();
+    let before = banks_client.get_balance(player.pubkey()).await.unwrap();
+    send(
+        &mut banks_client,
+        &payer,
+        std::slice::from_ref(&refund),
+        &[&player],
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        banks_client.get_balance(player.pubkey()).await.unwrap(),
+        before + entry_fee
+    );
+    let session = quiz_session(&mut banks_client, quiz).await;
+    assert_eq!(session.status, QuizStatus::Cancelled);
+    assert_eq!(session.prize_pool, 0);
+
+    // Paid by someone else so it isn't a duplicate of the refund above
+    let stranger = funded_keypair(&mut banks_client, &payer).await;
+    let result = send(&mut banks_client, &stranger, &[refund], &[&player]).await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::AlreadySettled as u32)
+    );
+}
+
+#[tokio::test]
+async fn rounds_nobody_played_do_not_advance() {
+    let (program_test, program_id) = program_test();
+    let (mut banks_client, payer, _) = program_test.start().await;
+    let host = payer.pubkey();
+    let quiz = client::find_quiz_address(&program_id, &host, QUIZ_ID);
+    let tournament = client::find_tournament_address(&program_id, &host, 1);
+    send(
+        &mut banks_client,
+        &payer,
+        &[
+            client::create_tournament_ix(&program_id, &host, 1, 1, 1),
+            client::initialize_quiz_ix(&program_id, &host, QUIZ_ID, 1),
+            question(&program_id, &host, &quiz, 0, 2),
+            client::link_tournament_round_ix(&program_id, &host, &host, &tournament, &quiz, 0),
+            client::start_quiz_ix(&program_id, &host, &quiz),
+            client::calculate_scores_ix(&program_id, &host, &quiz, 1, &[]),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let result = send(
+        &mut banks_client,
+        &payer,
+        &[client::advance_bracket_ix(
+            &program_id,
+            &tournament,
+            &quiz,
+            &[],
+        )],
+        &[],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::EmptyRound as u32)
+    );
+}
+
+/// A one-question base layer quiz scored by `scorer` under a bond, started.
+async fn bonded_quiz(
+    context: &mut ProgramTestContext,
+    payer: &Keypair,
+    program_id: &Pubkey,
+    quiz_id: u64,
+    scorer: &Pubkey,
+) -> Pubkey {
+    let host = payer.pubkey();
+    let quiz = client::find_quiz_address(program_id, &host, quiz_id);
+    let base_layer = DelegationConfig {
+        base_layer: true,
+        ..DelegationConfig::default()
+    };
+    send(
+        &mut context.banks_client,
+        payer,
+        &[
+            client::initialize_quiz_with_delegation_ix(program_id, &host, quiz_id, 1, base_layer),
+            question(program_id, &host, &quiz, 0, 2),
+            client::set_dispute_window_ix(program_id, &host, &quiz, 100),
+            client::appoint_scorer_ix(program_id, &host, &quiz, scorer, 5_000_000),
+            client::start_quiz_ix(program_id, &host, &quiz),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    quiz
+}
+
+#[tokio::test]
+async fn made_up_and_misranked_standings_are_challenged() {
+    let (program_test, program_id) = program_test();
+    let mut context = program_test.start_with_context().await;
+    let payer = context.payer.insecure_clone();
+    let host = payer.pubkey();
+    let scorer = funded_keypair(&mut context.banks_client, &payer).await;
+
+    // A bond nobody has time to challenge is no bond
+    let draft = client::find_quiz_address(&program_id, &host, QUIZ_ID + 2);
+    let result = send(
+        &mut context.banks_client,
+        &payer,
+        &[
+            client::initialize_quiz_ix(&program_id, &host, QUIZ_ID + 2, 1),
+            client::appoint_scorer_ix(&program_id, &host, &draft, &scorer.pubkey(), 1),
+        ],
+        &[],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::NoDisputeWindow as u32)
+    );
+
+    let quizzes = [QUIZ_ID, QUIZ_ID + 1];
+    let mut bonded = Vec::new();
+    for quiz_id in quizzes {
+        bonded
+            .push(bonded_quiz(&mut context, &payer, &program_id, quiz_id, &scorer.pubkey()).await);
+    }
+
+    // The first player answers right, the second wrong, in both quizzes
+    let mut players = Vec::new();
+    for answer in [2, 1] {
+        let player = funded_keypair(&mut context.banks_client, &payer).await;
+        for quiz in &bonded {
+            send(
+                &mut context.banks_client,
+                &payer,
+                &[
+                    client::join_quiz_ix(&program_id, &player.pubkey(), quiz, 0, None),
+                    client::submit_answers_ix(&program_id, &player.pubkey(), quiz, vec![answer]),
+                ],
+                &[&player],
+            )
+            .await
+            .unwrap();
+        }
+        players.push(player.pubkey());
+    }
+
+    // One root pays a player who never played, the other ranks the wrong
+    // player first
+    let ghost = Pubkey::new_unique();
+    let roots = [
+        vec![(players[0], 1, 1), (ghost, 1, 2), (players[1], 0, 3)],
+        vec![(players[1], 0, 1), (players[0], 1, 2)],
+    ];
+    let mut standings = Vec::new();
+    for (quiz, root) in bonded.iter().zip(&roots) {
+        let leaves: Vec<[u8; 32]> = root
+            .iter()
+            .map(|(player, score, rank)| merkle::leaderboard_leaf(player, *score, *rank))
+            .collect();
+        send(
+            &mut context.banks_client,
+            &payer,
+            &[client::post_bonded_root_ix(
+                &program_id,
+                &scorer.pubkey(),
+                quiz,
+                merkle::root(&leaves),
+            )],
+            &[&scorer],
+        )
+        .await
+        .unwrap();
+        standings.push(
+            root.iter()
+                .enumerate()
+                .map(|(index, &(player, score, rank))| PostedStanding {
+                    player,
+                    score,
+                    rank,
+                    proof: merkle::proof(&leaves, index),
+                })
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    let challenger = funded_keypair(&mut context.banks_client, &payer).await;
+    let missing_sheet = |index: usize| {
+        client::challenge_missing_sheet_ix(
+            &program_id,
+            &challenger.pubkey(),
+            &host,
+            &bonded[0],
+            standings[0][index].clone(),
+        )
+    };
+    let ranks = |quiz: usize, first: usize, second: usize| {
+        client::challenge_ranks_ix(
+            &program_id,
+            &challenger.pubkey(),
+            &host,
+            &bonded[quiz],
+            standings[quiz][first].clone(),
+            standings[quiz][second].clone(),
+        )
+    };
+
+    // Standings of real players in a consistent order stand
+    let challenges = [
+        (missing_sheet(0), false),
+        (ranks(0, 0, 2), false),
+        (missing_sheet(1), true),
+        (ranks(1, 0, 1), true),
+    ];
+    for (instruction, upheld) in challenges {
+        let result = send(
+            &mut context.banks_client,
+            &payer,
+            &[instruction],
+            &[&challenger],
+        )
+        .await;
+        if upheld {
+            result.unwrap();
+        } else {
+            assert_eq!(
+                instruction_error(result),
+                InstructionError::Custom(QuizError::ScoreNotFraudulent as u32)
+            );
+        }
+    }
+    for quiz in bonded {
+        let session = quiz_session(&mut context.banks_client, quiz).await;
+        assert_eq!(session.status, QuizStatus::Scoring);
+        assert_eq!(session.leaderboard_root, [0; 32]);
+        assert_eq!(session.scorer, Pubkey::default());
+    }
+}
+
+#[tokio::test]
+async fn team_prizes_are_split_between_members() {
+    let (program_test, program_id) = program_test();
+    let mut context = program_test.start_with_context().await;
+    let payer = context.payer.insecure_clone();
+    let host = payer.pubkey();
+    let quiz = client::find_quiz_address(&program_id, &host, QUIZ_ID);
+    let base_layer = DelegationConfig {
+        base_layer: true,
+        ..DelegationConfig::default()
+    };
+    let entry_fee = 1_000_000;
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[
+            client::initialize_quiz_with_delegation_ix(&program_id, &host, QUIZ_ID, 1, base_layer),
+            question(&program_id, &host, &quiz, 0, 2),
+            client::set_entry_fee_ix(&program_id, &host, &quiz, entry_fee),
+            client::set_prize_split_ix(&program_id, &host, &quiz, [6_000, 4_000, 0]),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    let mut players = Vec::new();
+    for _ in 0..4 {
+        players.push(funded_keypair(&mut context.banks_client, &payer).await);
+    }
+    let [first, second, third, fourth] = [0, 1, 2, 3].map(|index| players[index].pubkey());
+    let team = |team_id| client::find_team_address(&program_id, &quiz, team_id);
+
+    // A leaving captain hands the team to the member left behind
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[
+            client::create_team_ix(&program_id, &first, &quiz, 1, "Owls".to_string()),
+            client::join_team_ix(&program_id, &second, &quiz, 1),
+            client::leave_team_ix(&program_id, &first, &quiz, 1),
+        ],
+        &[&players[0], &players[1]],
+    )
+    .await
+    .unwrap();
+    let account = context
+        .banks_client
+        .get_account(team(1))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(Team::try_from_slice(&account.data).unwrap().captain, second);
+
+    // The last member out closes the team
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[
+            client::create_team_ix(&program_id, &first, &quiz, 2, "Larks".to_string()),
+            client::join_team_ix(&program_id, &third, &quiz, 2),
+            client::create_team_ix(&program_id, &fourth, &quiz, 3, "Wrens".to_string()),
+            client::leave_team_ix(&program_id, &fourth, &quiz, 3),
+        ],
+        &[&players[0], &players[2], &players[3]],
+    )
+    .await
+    .unwrap();
+    assert!(context
+        .banks_client
+        .get_account(team(3))
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(
+        quiz_session(&mut context.banks_client, quiz)
+            .await
+            .team_count,
+        2
+    );
+
+    // Both teams score 1
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[client::start_quiz_ix(&program_id, &host, &quiz)],
+        &[],
+    )
+    .await
+    .unwrap();
+    for (player, answer) in players.iter().zip([2, 2, 0, 0]) {
+        send(
+            &mut context.banks_client,
+            &payer,
+            &[
+                client::join_quiz_ix(&program_id, &player.pubkey(), &quiz, entry_fee, None),
+                client::submit_answers_ix(&program_id, &player.pubkey(), &quiz, vec![answer]),
+            ],
+            &[player],
+        )
+        .await
+        .unwrap();
+    }
+    let player_keys: Vec<Pubkey> = players.iter().map(|player| player.pubkey()).collect();
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[
+            client::calculate_scores_ix(&program_id, &host, &quiz, 1, &player_keys),
+            client::finalize_team_score_ix(&program_id, &quiz, 1, &[second]),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    let claim = |player: &Pubkey, team_id, team_ids: &[u8]| {
+        client::claim_team_prize_ix(&program_id, player, &quiz, team_id, team_ids)
+    };
+
+    // Every team is ranked, finalized
+    let result = send(
+        &mut context.banks_client,
+        &payer,
+        &[claim(&second, 1, &[1, 2])],
+        &[&players[1]],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::TeamNotFinalized as u32)
+    );
+    send(
+        &mut context.banks_client,
+        &payer,
+        &[client::finalize_team_score_ix(
+            &program_id,
+            &quiz,
+            2,
+            &[first, third],
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+    let result = send(
+        &mut context.banks_client,
+        &payer,
+        &[claim(&second, 1, &[1])],
+        &[&players[1]],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::NotEnoughAccountKeys
+    );
+
+    // Only members claim a team's share
+    let result = send(
+        &mut context.banks_client,
+        &payer,
+        &[claim(&fourth, 2, &[2, 1])],
+        &[&players[3]],
+    )
+    .await;
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(QuizError::NotTeamMember as u32)
+    );
+
+    // The tie goes to team 1, team 2's members halve second place
+    let vault = client::find_quiz_vault_address(&program_id, &quiz);
+    let mut paid = Vec::new();
+    for (player, team_id) in players.iter().zip([2, 1, 2]) {
+        let vault_before = context.banks_client.get_balance(vault).await.unwrap();
+        send(
+            &mut context.banks_client,
+            &payer,
+            &[claim(&player.pubkey(), team_id, &[2, 1])],
+            &[player],
+        )
+        .await
+        .unwrap();
+        let vault_after = context.banks_client.get_balance(vault).await.unwrap();
+        paid.push(vault_before - vault_after);
+    }
+    assert_eq!(paid, [800_000, 2_400_000, 800_000]);
+}
@@ -0,0 +1,69 @@
+use quiz_game::{
+    merkle,
+    state::{AnswerTree, CompressedAnswers},
+};
+use solana_program::pubkey::Pubkey;
+
+#[test]
+fn every_standing_proves_against_the_root() {
+    for player_count in 1..=9u32 {
+        let players: Vec<Pubkey> = (0..player_count).map(|_| Pubkey::new_unique()).collect();
+        let leaves: Vec<[u8; 32]> = players
+            .iter()
+            .zip(1..)
+            .map(|(player, rank)| merkle::leaderboard_leaf(player, 10, rank))
+            .collect();
+        let root = merkle::root(&leaves);
+
+        for (index, leaf) in leaves.iter().enumerate() {
+            let proof = merkle::proof(&leaves, index);
+            assert!(merkle::verify(&root, *leaf, &proof));
+
+            // The same proof can't vouch for a different score or rank
+            let rank = index as u32 + 1;
+            let forged = merkle::leaderboard_leaf(&players[index], 11, rank);
+            assert!(!merkle::verify(&root, forged, &proof));
+            let forged = merkle::leaderboard_leaf(&players[index], 10, rank + 1);
+            assert!(!merkle::verify(&root, forged, &proof));
+        }
+    }
+    assert_eq!(merkle::root(&[]), [0; 32]);
+}
+
+#[test]
+fn answer_tree_appends_match_the_padded_tree() {
+    let quiz = Pubkey::new_unique();
+    let mut tree = AnswerTree::new(quiz);
+    assert_eq!(tree.root, merkle::padded_root(&[]));
+
+    let mut leaves = Vec::new();
+    for slot in 0..9u64 {
+        let sheet = CompressedAnswers {
+            player: Pubkey::new_unique(),
+            answers: vec![slot as u8 % 4, 1],
+            confidence: vec![1, 1],
+            submitted_slot: slot,
+        };
+        leaves.push(merkle::answer_leaf(&sheet));
+        assert_eq!(tree.append(leaves[slot as usize]), Some(slot as u32));
+        assert_eq!(tree.root, merkle::padded_root(&leaves));
+
+        // Earlier sheets still prove against the grown tree
+        for (index, leaf) in leaves.iter().enumerate() {
+            let proof = merkle::padded_proof(&leaves, index);
+            assert_eq!(proof.len(), merkle::TREE_DEPTH);
+            assert!(merkle::verify(&tree.root, *leaf, &proof));
+        }
+
+        // A sheet with a different answer doesn't
+        let mut forged = sheet;
+        forged.answers[1] = 2;
+        let proof = merkle::padded_proof(&leaves, slot as usize);
+        assert!(!merkle::verify(
+            &tree.root,
+            merkle::answer_leaf(&forged),
+            &proof
+        ));
+    }
+    assert_eq!(tree.leaf_count, 9);
+}
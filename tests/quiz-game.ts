@@ -3,6 +3,7 @@ import * as fs from "fs";
 import { Suite } from "mocha";
 import {
   QuizSession,
+  QuizStatus,
   PlayerScore,
   InitializeQuizArgs,
   AddQuestionArgs,
@@ -55,6 +56,8 @@ describe("Quiz Game Tests:", function (this: Suite) {
     connectionEphemeralRollup.rpcEndpoint
   );
 
+  const QUIZ_ID = BigInt(0);
+
   // Variables to be initialized in before hook
   let hostKeypair: web3.Keypair;
   let player1Keypair: web3.Keypair;
@@ -113,8 +116,14 @@ describe("Quiz Game Tests:", function (this: Suite) {
     ]);
 
     // Get PDAs
+    const quizIdBuffer = Buffer.alloc(8);
+    quizIdBuffer.writeBigUInt64LE(QUIZ_ID, 0);
     [quizSessionPda] = web3.PublicKey.findProgramAddressSync(
-      [Buffer.from("quiz_session"), hostKeypair.publicKey.toBuffer()],
+      [
+        Buffer.from("quiz_session"),
+        hostKeypair.publicKey.toBuffer(),
+        quizIdBuffer,
+      ],
       PROGRAM_ID
     );
 
@@ -216,6 +225,7 @@ describe("Quiz Game Tests:", function (this: Suite) {
 
     const initializeArgs = new InitializeQuizArgs({
       question_count: 2,
+      quiz_id: QUIZ_ID,
     }); // 2 questions
     const serializedData = initializeArgs.serialize();
 
@@ -283,8 +293,7 @@ describe("Quiz Game Tests:", function (this: Suite) {
       const quizSession = QuizSession.deserialize(
         Buffer.from(quizAccountInfo.data)
       );
-      console.log("Quiz session active:", quizSession.active);
-      console.log("Quiz session completed:", quizSession.completed);
+      console.log("Quiz session status:", QuizStatus[quizSession.status]);
       console.log(
         "Quiz session host:",
         Buffer.from(quizSession.host).toString("hex")
@@ -806,7 +815,18 @@ describe("Quiz Game Tests:", function (this: Suite) {
       {
         pubkey: MAGIC_CONTEXT_ID,
         isSigner: false,
-        isWritable: false,
+        isWritable: true,
+      },
+      // Answer sheets to commit
+      {
+        pubkey: player1AnswerPda,
+        isSigner: false,
+        isWritable: true,
+      },
+      {
+        pubkey: player2AnswerPda,
+        isSigner: false,
+        isWritable: true,
       },
     ];
 
@@ -822,7 +842,7 @@ describe("Quiz Game Tests:", function (this: Suite) {
     tx.add(commitAnswersIx);
 
     const txHash = await web3.sendAndConfirmTransaction(
-      connectionBaseLayer,
+      connectionEphemeralRollup,
       tx,
       [hostKeypair],
       {
@@ -832,12 +852,17 @@ describe("Quiz Game Tests:", function (this: Suite) {
     );
 
     const duration = Date.now() - start;
-    console.log(`${duration}ms (Base Layer) Commit Answers txHash: ${txHash}`);
+    console.log(`${duration}ms (ER) Commit Answers txHash: ${txHash}`);
   });
 
   it("Calculate scores on Solana", async function () {
     const start = Date.now();
 
+    const [hostStatsPda] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("host_stats"), hostKeypair.publicKey.toBuffer()],
+      PROGRAM_ID
+    );
+
     const tx = new web3.Transaction();
     const keys = [
       // Host
@@ -852,6 +877,18 @@ describe("Quiz Game Tests:", function (this: Suite) {
         isSigner: false,
         isWritable: true,
       },
+      // Host Stats Account
+      {
+        pubkey: hostStatsPda,
+        isSigner: false,
+        isWritable: true,
+      },
+      // System Program
+      {
+        pubkey: web3.SystemProgram.programId,
+        isSigner: false,
+        isWritable: false,
+      },
       // Question 1 Account
       {
         pubkey: question1Pda,
@@ -951,7 +988,7 @@ describe("Quiz Game Tests:", function (this: Suite) {
       const quizSession = QuizSession.deserialize(
         Buffer.from(quizSessionAccountInfo.data)
       );
-      console.log(`Quiz completed: ${quizSession.completed}`);
+      console.log(`Quiz status: ${QuizStatus[quizSession.status]}`);
       console.log(`Total players: ${quizSession.player_count}`);
     }
   });
@@ -0,0 +1,187 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    time::Duration,
+};
+
+use quiz_game::{
+    client,
+    rpc::{self, QuizRpc, RetryPolicy, SendError},
+    state::{DelegationConfig, QuizSession, QuizStatus},
+};
+use solana_sdk::{
+    hash::Hash,
+    instruction::InstructionError,
+    pubkey::Pubkey,
+    signature::{Keypair, Signature, Signer},
+    transaction::{Transaction, TransactionError},
+};
+
+/// A node where the `lands`-th signed transaction ends with `outcome` once
+/// its status was checked `landing_delay` times, and every blockhash expires
+/// after `blockhash_lifetime` validity checks.
+#[derive(Default)]
+struct FakeRpc {
+    lands: usize,
+    outcome: Option<Result<(), TransactionError>>,
+    landing_delay: u32,
+    blockhash_lifetime: u32,
+    /// Blockhash requests that fail before one succeeds.
+    flaky_blockhashes: Cell<u32>,
+    quiz: Option<Vec<u8>>,
+    issued: Cell<u8>,
+    validity_checks: RefCell<HashMap<Hash, u32>>,
+    status_checks: Cell<u32>,
+    /// First signature of every distinct transaction received, in order.
+    received: RefCell<Vec<Signature>>,
+}
+
+impl QuizRpc for FakeRpc {
+    async fn latest_blockhash(&self) -> Result<Hash, SendError> {
+        if self.flaky_blockhashes.get() > 0 {
+            self.flaky_blockhashes.set(self.flaky_blockhashes.get() - 1);
+            return Err(SendError::Rpc("timed out".to_string()));
+        }
+        self.issued.set(self.issued.get() + 1);
+        Ok(Hash::new_from_array([self.issued.get(); 32]))
+    }
+
+    async fn is_blockhash_valid(&self, blockhash: &Hash) -> Result<bool, SendError> {
+        let mut checks = self.validity_checks.borrow_mut();
+        let count = checks.entry(*blockhash).or_default();
+        *count += 1;
+        Ok(*count <= self.blockhash_lifetime)
+    }
+
+    async fn send_transaction(&self, transaction: &Transaction) -> Result<Signature, SendError> {
+        let signature = transaction.signatures[0];
+        let mut received = self.received.borrow_mut();
+        if !received.contains(&signature) {
+            received.push(signature);
+        }
+        Ok(signature)
+    }
+
+    async fn signature_status(
+        &self,
+        signature: &Signature,
+    ) -> Result<Option<Result<(), TransactionError>>, SendError> {
+        if self.received.borrow().get(self.lands) != Some(signature) {
+            return Ok(None);
+        }
+        self.status_checks.set(self.status_checks.get() + 1);
+        Ok(self
+            .outcome
+            .clone()
+            .filter(|_| self.status_checks.get() > self.landing_delay))
+    }
+
+    async fn account_data(&self, _address: &Pubkey) -> Result<Option<Vec<u8>>, SendError> {
+        Ok(self.quiz.clone())
+    }
+}
+
+fn policy() -> RetryPolicy {
+    RetryPolicy {
+        max_attempts: 3,
+        initial_backoff: Duration::ZERO,
+        max_backoff: Duration::ZERO,
+        poll_interval: Duration::ZERO,
+    }
+}
+
+async fn start_quiz(rpc: &FakeRpc) -> Result<Signature, SendError> {
+    let host = Keypair::new();
+    let ix = client::start_quiz_ix(&Pubkey::new_unique(), &host.pubkey(), &Pubkey::new_unique());
+    rpc::send_and_confirm(rpc, &host, &[ix], &[], &policy()).await
+}
+
+#[tokio::test]
+async fn expired_attempts_are_signed_again_with_a_fresh_blockhash() {
+    let rpc = FakeRpc {
+        lands: 1,
+        outcome: Some(Ok(())),
+        blockhash_lifetime: 1,
+        ..FakeRpc::default()
+    };
+    let signature = start_quiz(&rpc).await.unwrap();
+    let received = rpc.received.borrow();
+    assert_eq!(received.len(), 2);
+    assert_eq!(signature, received[1]);
+    assert_eq!(rpc.issued.get(), 2);
+}
+
+#[tokio::test]
+async fn late_landings_are_not_sent_again() {
+    // Only visible once the blockhash expired and the next attempt checks
+    let rpc = FakeRpc {
+        lands: 0,
+        outcome: Some(Ok(())),
+        landing_delay: 2,
+        ..FakeRpc::default()
+    };
+    let signature = start_quiz(&rpc).await.unwrap();
+    assert_eq!(*rpc.received.borrow(), vec![signature]);
+    assert_eq!(rpc.issued.get(), 1);
+}
+
+#[tokio::test]
+async fn failed_transactions_are_not_retried() {
+    let error = TransactionError::InstructionError(0, InstructionError::Custom(3));
+    let rpc = FakeRpc {
+        lands: 0,
+        outcome: Some(Err(error.clone())),
+        blockhash_lifetime: u32::MAX,
+        ..FakeRpc::default()
+    };
+    match start_quiz(&rpc).await {
+        Err(SendError::Transaction(failed)) => assert_eq!(failed, error),
+        other => panic!("expected the transaction error, got {:?}", other),
+    }
+    assert_eq!(rpc.received.borrow().len(), 1);
+}
+
+#[tokio::test]
+async fn flaky_nodes_are_retried_until_attempts_run_out() {
+    let rpc = FakeRpc {
+        lands: 0,
+        outcome: Some(Ok(())),
+        flaky_blockhashes: Cell::new(2),
+        blockhash_lifetime: u32::MAX,
+        ..FakeRpc::default()
+    };
+    start_quiz(&rpc).await.unwrap();
+
+    let rpc = FakeRpc {
+        flaky_blockhashes: Cell::new(3),
+        ..FakeRpc::default()
+    };
+    assert!(matches!(
+        start_quiz(&rpc).await,
+        Err(SendError::Exhausted(3))
+    ));
+    assert!(rpc.received.borrow().is_empty());
+}
+
+#[tokio::test]
+async fn scored_quizzes_are_not_scored_again() {
+    let host = Keypair::new();
+    let mut quiz_data = QuizSession::new(host.pubkey(), 1, 2, DelegationConfig::default());
+    quiz_data.status = QuizStatus::Completed;
+    let rpc = FakeRpc {
+        quiz: Some(borsh::to_vec(&quiz_data).unwrap()),
+        ..FakeRpc::default()
+    };
+    let signature = rpc::calculate_scores(
+        &rpc,
+        &Pubkey::new_unique(),
+        &host,
+        &Pubkey::new_unique(),
+        &[Pubkey::new_unique()],
+        &policy(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(signature, None);
+    assert!(rpc.received.borrow().is_empty());
+}
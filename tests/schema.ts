@@ -4,15 +4,27 @@ export class QuizQuestion {
   question_text: string;
   options: [string, string, string, string];
   correct_answer_index: number;
+  buzzer_winner: Uint8Array;
+  multiplier: number;
+  void: VoidMode;
+  difficulty: number;
 
   constructor(props: {
     question_text: string;
     options: [string, string, string, string];
     correct_answer_index: number;
+    buzzer_winner: Uint8Array;
+    multiplier: number;
+    void: VoidMode;
+    difficulty: number;
   }) {
     this.question_text = props.question_text;
     this.options = props.options;
     this.correct_answer_index = props.correct_answer_index;
+    this.buzzer_winner = props.buzzer_winner;
+    this.multiplier = props.multiplier;
+    this.void = props.void;
+    this.difficulty = props.difficulty;
   }
 
   static schema = new Map([
@@ -24,31 +36,239 @@ export class QuizQuestion {
           ["question_text", "string"],
           ["options", ["string", 4]],
           ["correct_answer_index", "u8"],
+          ["buzzer_winner", [32]],
+          ["multiplier", "u8"],
+          ["void", "u8"], // Rust enum without fields serializes as u8
+          ["difficulty", "u8"], // 1 to 5
         ],
       },
     ],
   ]);
 }
 
+// Mirrors the Rust VoidMode enum
+export enum VoidMode {
+  NotVoided,
+  AwardAll,
+  Excluded,
+}
+
+// Mirrors the Rust QuizStatus enum
+export enum QuizStatus {
+  Draft,
+  Lobby,
+  Active,
+  SubmissionsClosed,
+  Scoring,
+  Completed,
+  Cancelled,
+}
+
 export class QuizSession {
   host: Uint8Array;
+  quiz_id: bigint;
   question_count: number;
   player_count: number;
-  active: boolean;
-  completed: boolean;
+  status: QuizStatus;
+  allow_resubmission: boolean;
+  refresh_slot_on_update: boolean;
+  deadline_slot: bigint;
+  mode: number;
+  revealed_question: number;
+  reveal_slot: bigint;
+  answer_window_slots: bigint;
+  rounds_revealed: number;
+  entry_fee: bigint;
+  prize_pool: bigint;
+  settled: boolean;
+  tournament: Uint8Array;
+  tournament_round: number;
+  commit_frequency_ms: number;
+  validator: Uint8Array;
+  base_layer: boolean;
+  start_slot: bigint;
+  join_grace_slots: bigint;
+  late_window_slots: bigint;
+  late_slots_per_point: bigint;
+  confidence_weighted: boolean;
+  close_slot: bigint;
+  dispute_window_slots: bigint;
+  scored_slot: bigint;
+  correction_count: number;
+  event_log: boolean;
+  leaderboard_root: Uint8Array;
+  prize_split_bps: number[];
+  scorer: Uint8Array;
+  scorer_bond: bigint;
+  live_leaderboard: boolean;
+  referral_bps: number;
+  min_prize_score: number;
+  min_prize_score_bps: number;
+  prize_remainder: number;
+  forfeited_ranks: number;
+  rollover_lamports: bigint;
+  proportional_prizes: boolean;
+  total_prize_score: number;
+  claimed_prize_score: number;
+  claim_window_slots: bigint;
+  vesting_min_lamports: bigint;
+  vesting_slots: bigint;
+  vesting_owed: bigint;
+  entry_fee_usd_cents: bigint;
+  price_feed_id: Uint8Array;
+  contributed_lamports: bigint;
+  charity: Uint8Array;
+  charity_bps: number;
+  charity_paid: boolean;
+  host_play: number;
+  join_stake: bigint;
+  stakes_held: bigint;
+  scoring_mode: number;
+  difficulty_weighted: boolean;
+  subset_size: number;
+  subset_seed: Uint8Array;
+  answers_revealed: boolean;
+  question_key_hash: Uint8Array;
+  question_key: Uint8Array;
+  curator: Uint8Array;
+  team_count: number;
 
   constructor(props: {
     host: Uint8Array;
+    quiz_id: bigint;
     question_count: number;
     player_count: number;
-    active: boolean;
-    completed: boolean;
+    status: QuizStatus;
+    allow_resubmission: boolean;
+    refresh_slot_on_update: boolean;
+    deadline_slot: bigint;
+    mode: number;
+    revealed_question: number;
+    reveal_slot: bigint;
+    answer_window_slots: bigint;
+    rounds_revealed: number;
+    entry_fee: bigint;
+    prize_pool: bigint;
+    settled: boolean;
+    tournament: Uint8Array;
+    tournament_round: number;
+    commit_frequency_ms: number;
+    validator: Uint8Array;
+    base_layer: boolean;
+    start_slot: bigint;
+    join_grace_slots: bigint;
+    late_window_slots: bigint;
+    late_slots_per_point: bigint;
+    confidence_weighted: boolean;
+    close_slot: bigint;
+    dispute_window_slots: bigint;
+    scored_slot: bigint;
+    correction_count: number;
+    event_log: boolean;
+    leaderboard_root: Uint8Array;
+    prize_split_bps: number[];
+    scorer: Uint8Array;
+    scorer_bond: bigint;
+    live_leaderboard: boolean;
+    referral_bps: number;
+    min_prize_score: number;
+    min_prize_score_bps: number;
+    prize_remainder: number;
+    forfeited_ranks: number;
+    rollover_lamports: bigint;
+    proportional_prizes: boolean;
+    total_prize_score: number;
+    claimed_prize_score: number;
+    claim_window_slots: bigint;
+    vesting_min_lamports: bigint;
+    vesting_slots: bigint;
+    vesting_owed: bigint;
+    entry_fee_usd_cents: bigint;
+    price_feed_id: Uint8Array;
+    contributed_lamports: bigint;
+    charity: Uint8Array;
+    charity_bps: number;
+    charity_paid: boolean;
+    host_play: number;
+    join_stake: bigint;
+    stakes_held: bigint;
+    scoring_mode: number;
+    difficulty_weighted: boolean;
+    subset_size: number;
+    subset_seed: Uint8Array;
+    answers_revealed: boolean;
+    question_key_hash: Uint8Array;
+    question_key: Uint8Array;
+    curator: Uint8Array;
+    team_count: number;
   }) {
     this.host = props.host;
+    this.quiz_id = props.quiz_id;
     this.question_count = props.question_count;
     this.player_count = props.player_count;
-    this.active = props.active;
-    this.completed = props.completed;
+    this.status = props.status;
+    this.allow_resubmission = props.allow_resubmission;
+    this.refresh_slot_on_update = props.refresh_slot_on_update;
+    this.deadline_slot = props.deadline_slot;
+    this.mode = props.mode;
+    this.revealed_question = props.revealed_question;
+    this.reveal_slot = props.reveal_slot;
+    this.answer_window_slots = props.answer_window_slots;
+    this.rounds_revealed = props.rounds_revealed;
+    this.entry_fee = props.entry_fee;
+    this.prize_pool = props.prize_pool;
+    this.settled = props.settled;
+    this.tournament = props.tournament;
+    this.tournament_round = props.tournament_round;
+    this.commit_frequency_ms = props.commit_frequency_ms;
+    this.validator = props.validator;
+    this.base_layer = props.base_layer;
+    this.start_slot = props.start_slot;
+    this.join_grace_slots = props.join_grace_slots;
+    this.late_window_slots = props.late_window_slots;
+    this.late_slots_per_point = props.late_slots_per_point;
+    this.confidence_weighted = props.confidence_weighted;
+    this.close_slot = props.close_slot;
+    this.dispute_window_slots = props.dispute_window_slots;
+    this.scored_slot = props.scored_slot;
+    this.correction_count = props.correction_count;
+    this.event_log = props.event_log;
+    this.leaderboard_root = props.leaderboard_root;
+    this.prize_split_bps = props.prize_split_bps;
+    this.scorer = props.scorer;
+    this.scorer_bond = props.scorer_bond;
+    this.live_leaderboard = props.live_leaderboard;
+    this.referral_bps = props.referral_bps;
+    this.min_prize_score = props.min_prize_score;
+    this.min_prize_score_bps = props.min_prize_score_bps;
+    this.prize_remainder = props.prize_remainder;
+    this.forfeited_ranks = props.forfeited_ranks;
+    this.rollover_lamports = props.rollover_lamports;
+    this.proportional_prizes = props.proportional_prizes;
+    this.total_prize_score = props.total_prize_score;
+    this.claimed_prize_score = props.claimed_prize_score;
+    this.claim_window_slots = props.claim_window_slots;
+    this.vesting_min_lamports = props.vesting_min_lamports;
+    this.vesting_slots = props.vesting_slots;
+    this.vesting_owed = props.vesting_owed;
+    this.entry_fee_usd_cents = props.entry_fee_usd_cents;
+    this.price_feed_id = props.price_feed_id;
+    this.contributed_lamports = props.contributed_lamports;
+    this.charity = props.charity;
+    this.charity_bps = props.charity_bps;
+    this.charity_paid = props.charity_paid;
+    this.host_play = props.host_play;
+    this.join_stake = props.join_stake;
+    this.stakes_held = props.stakes_held;
+    this.scoring_mode = props.scoring_mode;
+    this.difficulty_weighted = props.difficulty_weighted;
+    this.subset_size = props.subset_size;
+    this.subset_seed = props.subset_seed;
+    this.answers_revealed = props.answers_revealed;
+    this.question_key_hash = props.question_key_hash;
+    this.question_key = props.question_key;
+    this.curator = props.curator;
+    this.team_count = props.team_count;
   }
 
   static schema = new Map([
@@ -58,10 +278,72 @@ export class QuizSession {
         kind: "struct",
         fields: [
           ["host", [32]],
+          ["quiz_id", "u64"],
           ["question_count", "u8"],
           ["player_count", "u8"],
-          ["active", "u8"], // Rust bool serializes as u8
-          ["completed", "u8"], // Rust bool serializes as u8
+          ["status", "u8"], // Rust enum without fields serializes as u8
+          ["allow_resubmission", "u8"], // Rust bool serializes as u8
+          ["refresh_slot_on_update", "u8"], // Rust bool serializes as u8
+          ["deadline_slot", "u64"],
+          ["mode", "u8"], // Rust enum without fields serializes as u8
+          ["revealed_question", "u8"],
+          ["reveal_slot", "u64"],
+          ["answer_window_slots", "u64"],
+          ["rounds_revealed", "u8"],
+          ["entry_fee", "u64"],
+          ["prize_pool", "u64"],
+          ["settled", "u8"], // Rust bool serializes as u8
+          ["tournament", [32]],
+          ["tournament_round", "u8"],
+          ["commit_frequency_ms", "u32"],
+          ["validator", [32]],
+          ["base_layer", "u8"], // Rust bool serializes as u8
+          ["start_slot", "u64"],
+          ["join_grace_slots", "u64"],
+          ["late_window_slots", "u64"],
+          ["late_slots_per_point", "u64"],
+          ["confidence_weighted", "u8"], // Rust bool serializes as u8
+          ["close_slot", "u64"],
+          ["dispute_window_slots", "u64"],
+          ["scored_slot", "u64"],
+          ["correction_count", "u32"],
+          ["event_log", "u8"], // Rust bool serializes as u8
+          ["leaderboard_root", [32]],
+          ["prize_split_bps", ["u16", 3]],
+          ["scorer", [32]],
+          ["scorer_bond", "u64"],
+          ["live_leaderboard", "u8"], // Rust bool serializes as u8
+          ["referral_bps", "u16"],
+          ["min_prize_score", "u8"],
+          ["min_prize_score_bps", "u16"],
+          ["prize_remainder", "u8"], // Rust enum without fields serializes as u8
+          ["forfeited_ranks", "u8"],
+          ["rollover_lamports", "u64"],
+          ["proportional_prizes", "u8"], // Rust bool serializes as u8
+          ["total_prize_score", "u32"],
+          ["claimed_prize_score", "u32"],
+          ["claim_window_slots", "u64"],
+          ["vesting_min_lamports", "u64"],
+          ["vesting_slots", "u64"],
+          ["vesting_owed", "u64"],
+          ["entry_fee_usd_cents", "u64"],
+          ["price_feed_id", [32]],
+          ["contributed_lamports", "u64"],
+          ["charity", [32]],
+          ["charity_bps", "u16"],
+          ["charity_paid", "u8"], // Rust bool serializes as u8
+          ["host_play", "u8"], // Rust enum without fields serializes as u8
+          ["join_stake", "u64"],
+          ["stakes_held", "u64"],
+          ["scoring_mode", "u8"], // Rust enum without fields serializes as u8
+          ["difficulty_weighted", "u8"], // Rust bool serializes as u8
+          ["subset_size", "u8"],
+          ["subset_seed", [32]],
+          ["answers_revealed", "u8"], // Rust bool serializes as u8
+          ["question_key_hash", [32]],
+          ["question_key", [32]],
+          ["curator", [32]],
+          ["team_count", "u8"],
         ],
       },
     ],
@@ -74,10 +356,72 @@ export class QuizSession {
       const session = result as any;
       return new QuizSession({
         host: session.host,
+        quiz_id: BigInt(session.quiz_id.toString()),
         question_count: session.question_count,
         player_count: session.player_count,
-        active: Boolean(session.active),
-        completed: Boolean(session.completed),
+        status: session.status,
+        allow_resubmission: Boolean(session.allow_resubmission),
+        refresh_slot_on_update: Boolean(session.refresh_slot_on_update),
+        deadline_slot: BigInt(session.deadline_slot.toString()),
+        mode: session.mode,
+        revealed_question: session.revealed_question,
+        reveal_slot: BigInt(session.reveal_slot.toString()),
+        answer_window_slots: BigInt(session.answer_window_slots.toString()),
+        rounds_revealed: session.rounds_revealed,
+        entry_fee: BigInt(session.entry_fee.toString()),
+        prize_pool: BigInt(session.prize_pool.toString()),
+        settled: Boolean(session.settled),
+        tournament: session.tournament,
+        tournament_round: session.tournament_round,
+        commit_frequency_ms: session.commit_frequency_ms,
+        validator: session.validator,
+        base_layer: Boolean(session.base_layer),
+        start_slot: BigInt(session.start_slot.toString()),
+        join_grace_slots: BigInt(session.join_grace_slots.toString()),
+        late_window_slots: BigInt(session.late_window_slots.toString()),
+        late_slots_per_point: BigInt(session.late_slots_per_point.toString()),
+        confidence_weighted: Boolean(session.confidence_weighted),
+        close_slot: BigInt(session.close_slot.toString()),
+        dispute_window_slots: BigInt(session.dispute_window_slots.toString()),
+        scored_slot: BigInt(session.scored_slot.toString()),
+        correction_count: session.correction_count,
+        event_log: Boolean(session.event_log),
+        leaderboard_root: session.leaderboard_root,
+        prize_split_bps: session.prize_split_bps,
+        scorer: session.scorer,
+        scorer_bond: BigInt(session.scorer_bond.toString()),
+        live_leaderboard: Boolean(session.live_leaderboard),
+        referral_bps: session.referral_bps,
+        min_prize_score: session.min_prize_score,
+        min_prize_score_bps: session.min_prize_score_bps,
+        prize_remainder: session.prize_remainder,
+        forfeited_ranks: session.forfeited_ranks,
+        rollover_lamports: BigInt(session.rollover_lamports.toString()),
+        proportional_prizes: Boolean(session.proportional_prizes),
+        total_prize_score: session.total_prize_score,
+        claimed_prize_score: session.claimed_prize_score,
+        claim_window_slots: BigInt(session.claim_window_slots.toString()),
+        vesting_min_lamports: BigInt(session.vesting_min_lamports.toString()),
+        vesting_slots: BigInt(session.vesting_slots.toString()),
+        vesting_owed: BigInt(session.vesting_owed.toString()),
+        entry_fee_usd_cents: BigInt(session.entry_fee_usd_cents.toString()),
+        price_feed_id: session.price_feed_id,
+        contributed_lamports: BigInt(session.contributed_lamports.toString()),
+        charity: session.charity,
+        charity_bps: session.charity_bps,
+        charity_paid: Boolean(session.charity_paid),
+        host_play: session.host_play,
+        join_stake: BigInt(session.join_stake.toString()),
+        stakes_held: BigInt(session.stakes_held.toString()),
+        scoring_mode: session.scoring_mode,
+        difficulty_weighted: Boolean(session.difficulty_weighted),
+        subset_size: session.subset_size,
+        subset_seed: session.subset_seed,
+        answers_revealed: Boolean(session.answers_revealed),
+        question_key_hash: session.question_key_hash,
+        question_key: session.question_key,
+        curator: session.curator,
+        team_count: session.team_count,
       });
     } catch (error) {
       console.error("Failed to deserialize QuizSession:", error);
@@ -89,10 +433,67 @@ export class QuizSession {
 export class PlayerAnswer {
   player: Uint8Array;
   answers: number[];
+  answered: number[];
+  answer_slots: bigint[];
+  eliminated: number;
+  rounds_survived: number;
+  joined: number;
+  submitted: number;
+  submitted_slot: bigint;
+  committed: number;
+  confidence: number[];
+  hints: number[];
+  hint_penalty: number;
+  live_score: number;
+  last_reaction_slot: bigint;
+  referrer: Uint8Array;
+  referral_cut: bigint;
+  stake: bigint;
+  adaptive_level: number;
+  relay_nonce: bigint;
 
-  constructor(props: { player: Uint8Array; answers: number[] }) {
+  constructor(props: {
+    player: Uint8Array;
+    answers: number[];
+    answered: number[];
+    answer_slots: bigint[];
+    eliminated: number;
+    rounds_survived: number;
+    joined: number;
+    submitted: number;
+    submitted_slot: bigint;
+    committed: number;
+    confidence: number[];
+    hints: number[];
+    hint_penalty: number;
+    live_score: number;
+    last_reaction_slot: bigint;
+    referrer: Uint8Array;
+    referral_cut: bigint;
+    stake: bigint;
+    adaptive_level: number;
+    relay_nonce: bigint;
+  }) {
     this.player = props.player;
     this.answers = props.answers;
+    this.answered = props.answered;
+    this.answer_slots = props.answer_slots;
+    this.eliminated = props.eliminated;
+    this.rounds_survived = props.rounds_survived;
+    this.joined = props.joined;
+    this.submitted = props.submitted;
+    this.submitted_slot = props.submitted_slot;
+    this.committed = props.committed;
+    this.confidence = props.confidence;
+    this.hints = props.hints;
+    this.hint_penalty = props.hint_penalty;
+    this.live_score = props.live_score;
+    this.last_reaction_slot = props.last_reaction_slot;
+    this.referrer = props.referrer;
+    this.referral_cut = props.referral_cut;
+    this.stake = props.stake;
+    this.adaptive_level = props.adaptive_level;
+    this.relay_nonce = props.relay_nonce;
   }
 
   static schema = new Map([
@@ -103,6 +504,24 @@ export class PlayerAnswer {
         fields: [
           ["player", [32]],
           ["answers", ["u8"]],
+          ["answered", ["u8"]], // bitmap, one bit per question
+          ["answer_slots", ["u64"]],
+          ["eliminated", "u8"], // Rust bool serializes as u8
+          ["rounds_survived", "u8"],
+          ["joined", "u8"], // Rust bool serializes as u8
+          ["submitted", "u8"], // Rust bool serializes as u8
+          ["submitted_slot", "u64"],
+          ["committed", "u8"], // Rust bool serializes as u8
+          ["confidence", ["u8"]], // 1-3 per answer
+          ["hints", ["u8"]], // bitmap, one bit per question
+          ["hint_penalty", "u8"],
+          ["live_score", "u32"],
+          ["last_reaction_slot", "u64"],
+          ["referrer", [32]],
+          ["referral_cut", "u64"],
+          ["stake", "u64"],
+          ["adaptive_level", "u8"], // difficulty served next in adaptive quizzes
+          ["relay_nonce", "u64"], // last relayed submission, the next must be higher
         ],
       },
     ],
@@ -122,10 +541,22 @@ export class PlayerAnswer {
 export class PlayerScore {
   player: Uint8Array;
   score: number;
+  quiz: Uint8Array;
+  late_penalty: number;
+  skipped: number;
 
-  constructor(props: { player: Uint8Array; score: number }) {
+  constructor(props: {
+    player: Uint8Array;
+    score: number;
+    quiz: Uint8Array;
+    late_penalty: number;
+    skipped: number;
+  }) {
     this.player = props.player;
     this.score = props.score;
+    this.quiz = props.quiz;
+    this.late_penalty = props.late_penalty;
+    this.skipped = props.skipped;
   }
 
   static schema = new Map([
@@ -135,7 +566,10 @@ export class PlayerScore {
         kind: "struct",
         fields: [
           ["player", [32]],
-          ["score", "u8"],
+          ["score", "u32"],
+          ["quiz", [32]],
+          ["late_penalty", "u32"],
+          ["skipped", "u8"],
         ],
       },
     ],
@@ -152,6 +586,283 @@ export class PlayerScore {
   }
 }
 
+export class QuestionHint {
+  point_cost: number;
+  lamport_cost: bigint;
+  hint: string;
+
+  constructor(props: { point_cost: number; lamport_cost: bigint; hint: string }) {
+    this.point_cost = props.point_cost;
+    this.lamport_cost = props.lamport_cost;
+    this.hint = props.hint;
+  }
+
+  static schema = new Map([
+    [
+      QuestionHint,
+      {
+        kind: "struct",
+        fields: [
+          ["point_cost", "u8"],
+          ["lamport_cost", "u64"],
+          ["hint", "string"],
+        ],
+      },
+    ],
+  ]);
+
+  static deserialize(data: Buffer): QuestionHint {
+    try {
+      const result = borsh.deserialize(QuestionHint.schema, QuestionHint, data);
+      return result as QuestionHint;
+    } catch (error) {
+      console.error("Failed to deserialize QuestionHint:", error);
+      throw error;
+    }
+  }
+}
+
+// Only shown once the quiz session's answers_revealed is set
+export class QuestionExplanation {
+  question_index: number;
+  explanation: string;
+
+  constructor(props: { question_index: number; explanation: string }) {
+    this.question_index = props.question_index;
+    this.explanation = props.explanation;
+  }
+
+  static schema = new Map([
+    [
+      QuestionExplanation,
+      {
+        kind: "struct",
+        fields: [
+          ["question_index", "u8"],
+          ["explanation", "string"],
+        ],
+      },
+    ],
+  ]);
+
+  static deserialize(data: Buffer): QuestionExplanation {
+    try {
+      const result = borsh.deserialize(QuestionExplanation.schema, QuestionExplanation, data);
+      return result as QuestionExplanation;
+    } catch (error) {
+      console.error("Failed to deserialize QuestionExplanation:", error);
+      throw error;
+    }
+  }
+}
+
+export class QuestionStats {
+  quiz: Uint8Array;
+  question_index: number;
+  option_counts: number[];
+  skipped: number;
+  unanswered: number;
+
+  constructor(props: {
+    quiz: Uint8Array;
+    question_index: number;
+    option_counts: number[];
+    skipped: number;
+    unanswered: number;
+  }) {
+    this.quiz = props.quiz;
+    this.question_index = props.question_index;
+    this.option_counts = props.option_counts;
+    this.skipped = props.skipped;
+    this.unanswered = props.unanswered;
+  }
+
+  static schema = new Map([
+    [
+      QuestionStats,
+      {
+        kind: "struct",
+        fields: [
+          ["quiz", [32]],
+          ["question_index", "u8"],
+          ["option_counts", ["u32", 4]], // players who picked each option
+          ["skipped", "u32"],
+          ["unanswered", "u32"],
+        ],
+      },
+    ],
+  ]);
+
+  static deserialize(data: Buffer): QuestionStats {
+    try {
+      const result = borsh.deserialize(QuestionStats.schema, QuestionStats, data);
+      return result as QuestionStats;
+    } catch (error) {
+      console.error("Failed to deserialize QuestionStats:", error);
+      throw error;
+    }
+  }
+}
+
+export class QuizAnalytics {
+  quiz: Uint8Array;
+  player_count: number;
+  average_score_x100: number;
+  median_score_x100: number;
+  hardest_question: number;
+  easiest_question: number;
+  participation_bps: number;
+  correct_bps_by_difficulty: number[];
+
+  constructor(props: {
+    quiz: Uint8Array;
+    player_count: number;
+    average_score_x100: number;
+    median_score_x100: number;
+    hardest_question: number;
+    easiest_question: number;
+    participation_bps: number;
+    correct_bps_by_difficulty: number[];
+  }) {
+    this.quiz = props.quiz;
+    this.player_count = props.player_count;
+    this.average_score_x100 = props.average_score_x100;
+    this.median_score_x100 = props.median_score_x100;
+    this.hardest_question = props.hardest_question;
+    this.easiest_question = props.easiest_question;
+    this.participation_bps = props.participation_bps;
+    this.correct_bps_by_difficulty = props.correct_bps_by_difficulty;
+  }
+
+  static schema = new Map([
+    [
+      QuizAnalytics,
+      {
+        kind: "struct",
+        fields: [
+          ["quiz", [32]],
+          ["player_count", "u8"],
+          ["average_score_x100", "u32"], // hundredths of a point
+          ["median_score_x100", "u32"], // hundredths of a point
+          ["hardest_question", "u8"],
+          ["easiest_question", "u8"],
+          ["participation_bps", "u16"],
+          ["correct_bps_by_difficulty", ["u16", 5]], // difficulty 1 to 5
+        ],
+      },
+    ],
+  ]);
+
+  static deserialize(data: Buffer): QuizAnalytics {
+    try {
+      const result = borsh.deserialize(QuizAnalytics.schema, QuizAnalytics, data);
+      return result as QuizAnalytics;
+    } catch (error) {
+      console.error("Failed to deserialize QuizAnalytics:", error);
+      throw error;
+    }
+  }
+}
+
+export class HostStats {
+  host: Uint8Array;
+  quizzes_hosted: number;
+  players_served: bigint;
+  active_players: bigint;
+  quizzes_cancelled: number;
+  disputes_upheld: number;
+  late_scorings: number;
+  scoring_delay_slots: bigint;
+
+  constructor(props: {
+    host: Uint8Array;
+    quizzes_hosted: number;
+    players_served: bigint;
+    active_players: bigint;
+    quizzes_cancelled: number;
+    disputes_upheld: number;
+    late_scorings: number;
+    scoring_delay_slots: bigint;
+  }) {
+    this.host = props.host;
+    this.quizzes_hosted = props.quizzes_hosted;
+    this.players_served = props.players_served;
+    this.active_players = props.active_players;
+    this.quizzes_cancelled = props.quizzes_cancelled;
+    this.disputes_upheld = props.disputes_upheld;
+    this.late_scorings = props.late_scorings;
+    this.scoring_delay_slots = props.scoring_delay_slots;
+  }
+
+  static schema = new Map([
+    [
+      HostStats,
+      {
+        kind: "struct",
+        fields: [
+          ["host", [32]],
+          ["quizzes_hosted", "u32"],
+          ["players_served", "u64"],
+          ["active_players", "u64"], // answered at least one question
+          ["quizzes_cancelled", "u32"],
+          ["disputes_upheld", "u32"],
+          ["late_scorings", "u32"],
+          ["scoring_delay_slots", "u64"],
+        ],
+      },
+    ],
+  ]);
+
+  static deserialize(data: Buffer): HostStats {
+    try {
+      const result = borsh.deserialize(HostStats.schema, HostStats, data);
+      return result as HostStats;
+    } catch (error) {
+      console.error("Failed to deserialize HostStats:", error);
+      throw error;
+    }
+  }
+}
+
+export class QuestionDispute {
+  quiz: Uint8Array;
+  question_index: number;
+  reason_counts: number[];
+
+  constructor(props: { quiz: Uint8Array; question_index: number; reason_counts: number[] }) {
+    this.quiz = props.quiz;
+    this.question_index = props.question_index;
+    this.reason_counts = props.reason_counts;
+  }
+
+  static schema = new Map([
+    [
+      QuestionDispute,
+      {
+        kind: "struct",
+        fields: [
+          ["quiz", [32]],
+          ["question_index", "u8"],
+          ["reason_counts", ["u32", 4]], // wrong answer, ambiguous, typo, other
+        ],
+      },
+    ],
+  ]);
+
+  static deserialize(data: Buffer): QuestionDispute {
+    try {
+      const result = borsh.deserialize(QuestionDispute.schema, QuestionDispute, data);
+      return result as QuestionDispute;
+    } catch (error) {
+      console.error("Failed to deserialize QuestionDispute:", error);
+      throw error;
+    }
+  }
+}
+
+// Answer value that skips a question, mirrors QuizQuestion::SKIPPED
+export const SKIPPED_ANSWER = 255;
+
 // Instruction argument classes
 export class AddQuestionData {
   question_index: number;
@@ -190,17 +901,42 @@ export class AddQuestionData {
 export class InitializeQuizArgs {
   instruction: Uint8Array;
   question_count: number;
+  quiz_id: bigint;
+  commit_frequency_ms: number;
+  validator: Uint8Array;
+  base_layer: boolean;
 
-  constructor(props: { question_count: number }) {
-    this.instruction = new Uint8Array([0, 0, 0, 0, 0, 0, 0, 0]); // 8-byte discriminator
+  constructor(props: {
+    question_count: number;
+    quiz_id: bigint;
+    commit_frequency_ms?: number;
+    validator?: Uint8Array;
+    base_layer?: boolean;
+  }) {
+    this.instruction = new Uint8Array([119, 91, 73, 7, 108, 157, 18, 42]); // sha256("global:initialize_quiz")[..8]
     this.question_count = props.question_count;
+    this.quiz_id = props.quiz_id;
+    this.commit_frequency_ms = props.commit_frequency_ms ?? 1000;
+    this.validator = props.validator ?? new Uint8Array(32); // any validator
+    this.base_layer = props.base_layer ?? false;
   }
 
   serialize(): Buffer {
     try {
       const instrBuffer = Buffer.from(this.instruction);
       const questionCountBuffer = Buffer.from([this.question_count]);
-      return Buffer.concat([instrBuffer, questionCountBuffer]);
+      const quizIdBuffer = Buffer.alloc(8);
+      quizIdBuffer.writeBigUInt64LE(this.quiz_id, 0);
+      const commitFrequencyBuffer = Buffer.alloc(4);
+      commitFrequencyBuffer.writeUInt32LE(this.commit_frequency_ms, 0);
+      return Buffer.concat([
+        instrBuffer,
+        questionCountBuffer,
+        quizIdBuffer,
+        commitFrequencyBuffer,
+        Buffer.from(this.validator),
+        Buffer.from([this.base_layer ? 1 : 0]),
+      ]);
     } catch (error) {
       console.error("Failed to serialize InitializeQuizArgs:", error);
       throw error;
@@ -221,7 +957,7 @@ export class AddQuestionArgs {
     options: [string, string, string, string];
     correct_answer_index: number;
   }) {
-    this.instruction = new Uint8Array([1, 0, 0, 0, 0, 0, 0, 0]); // 8-byte discriminator
+    this.instruction = new Uint8Array([164, 157, 121, 228, 98, 242, 205, 193]); // sha256("global:add_question")[..8]
     this.question_index = props.question_index;
     this.question_text = props.question_text;
     this.options = props.options;
@@ -256,7 +992,7 @@ export class StartQuizArgs {
   instruction: Uint8Array;
 
   constructor() {
-    this.instruction = new Uint8Array([2, 0, 0, 0, 0, 0, 0, 0]); // 8-byte discriminator
+    this.instruction = new Uint8Array([26, 247, 82, 180, 175, 38, 4, 22]); // sha256("global:start_quiz")[..8]
   }
 
   serialize(): Buffer {
@@ -271,14 +1007,22 @@ export class StartQuizArgs {
 
 export class DelegateArgs {
   instruction: Uint8Array;
+  validator: Uint8Array | null;
 
-  constructor() {
-    this.instruction = new Uint8Array([3, 0, 0, 0, 0, 0, 0, 0]); // 8-byte discriminator
+  constructor(props: { validator?: Uint8Array } = {}) {
+    this.instruction = new Uint8Array([235, 159, 245, 102, 161, 199, 254, 89]); // sha256("global:delegate_player")[..8]
+    this.validator = props.validator ?? null; // null defers to the quiz's validator
   }
 
   serialize(): Buffer {
     try {
-      return Buffer.from(this.instruction);
+      const instrBuffer = Buffer.from(this.instruction);
+      // Borsh Option: 0 for None, 1 followed by the value for Some
+      const validatorBuffer =
+        this.validator === null
+          ? Buffer.from([0])
+          : Buffer.concat([Buffer.from([1]), Buffer.from(this.validator)]);
+      return Buffer.concat([instrBuffer, validatorBuffer]);
     } catch (error) {
       console.error("Failed to serialize DelegateArgs:", error);
       throw error;
@@ -289,20 +1033,24 @@ export class DelegateArgs {
 export class SubmitAnswersArgs {
   instruction: Uint8Array;
   answers: number[];
+  confidence: number[]; // 1-3 per answer, leave empty unless the quiz is confidence-weighted
 
-  constructor(props: { answers: number[] }) {
-    this.instruction = new Uint8Array([4, 0, 0, 0, 0, 0, 0, 0]); // 8-byte discriminator
+  constructor(props: { answers: number[]; confidence?: number[] }) {
+    this.instruction = new Uint8Array([142, 178, 58, 248, 113, 129, 119, 142]); // sha256("global:submit_answers")[..8]
     this.answers = props.answers;
+    this.confidence = props.confidence ?? [];
   }
 
   serialize(): Buffer {
     try {
       const instrBuffer = Buffer.from(this.instruction);
       // Borsh Vec<u8> serialization: length (4 bytes little-endian) + data
-      const lengthBuffer = Buffer.alloc(4);
-      lengthBuffer.writeUInt32LE(this.answers.length, 0);
-      const answersBuffer = Buffer.from(this.answers);
-      return Buffer.concat([instrBuffer, lengthBuffer, answersBuffer]);
+      const vec = (bytes: number[]) => {
+        const lengthBuffer = Buffer.alloc(4);
+        lengthBuffer.writeUInt32LE(bytes.length, 0);
+        return Buffer.concat([lengthBuffer, Buffer.from(bytes)]);
+      };
+      return Buffer.concat([instrBuffer, vec(this.answers), vec(this.confidence)]);
     } catch (error) {
       console.error("Failed to serialize SubmitAnswersArgs:", error);
       throw error;
@@ -314,7 +1062,7 @@ export class CommitAnswersArgs {
   instruction: Uint8Array;
 
   constructor() {
-    this.instruction = new Uint8Array([5, 0, 0, 0, 0, 0, 0, 0]); // 8-byte discriminator
+    this.instruction = new Uint8Array([162, 175, 44, 59, 113, 46, 207, 166]); // sha256("global:commit_answers")[..8]
   }
 
   serialize(): Buffer {
@@ -331,7 +1079,7 @@ export class CalculateScoresArgs {
   instruction: Uint8Array;
 
   constructor() {
-    this.instruction = new Uint8Array([6, 0, 0, 0, 0, 0, 0, 0]); // 8-byte discriminator
+    this.instruction = new Uint8Array([97, 247, 73, 155, 47, 127, 58, 65]); // sha256("global:calculate_scores")[..8]
   }
 
   serialize(): Buffer {
@@ -343,3 +1091,39 @@ export class CalculateScoresArgs {
     }
   }
 }
+
+export class SetSubmissionPolicyArgs {
+  instruction: Uint8Array;
+  allow_resubmission: boolean;
+  refresh_slot_on_update: boolean;
+  deadline_slot: bigint;
+  join_grace_slots: bigint;
+
+  constructor(props: {
+    allow_resubmission: boolean;
+    refresh_slot_on_update: boolean;
+    deadline_slot: bigint;
+    join_grace_slots?: bigint;
+  }) {
+    this.instruction = new Uint8Array([30, 48, 31, 182, 3, 63, 197, 166]); // sha256("global:set_submission_policy")[..8]
+    this.allow_resubmission = props.allow_resubmission;
+    this.refresh_slot_on_update = props.refresh_slot_on_update;
+    this.deadline_slot = props.deadline_slot;
+    this.join_grace_slots = props.join_grace_slots ?? BigInt(0);
+  }
+
+  serialize(): Buffer {
+    try {
+      const instrBuffer = Buffer.from(this.instruction);
+      const policyBuffer = Buffer.alloc(18);
+      policyBuffer.writeUInt8(this.allow_resubmission ? 1 : 0, 0);
+      policyBuffer.writeUInt8(this.refresh_slot_on_update ? 1 : 0, 1);
+      policyBuffer.writeBigUInt64LE(this.deadline_slot, 2);
+      policyBuffer.writeBigUInt64LE(this.join_grace_slots, 10);
+      return Buffer.concat([instrBuffer, policyBuffer]);
+    } catch (error) {
+      console.error("Failed to serialize SetSubmissionPolicyArgs:", error);
+      throw error;
+    }
+  }
+}
@@ -0,0 +1,143 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex,
+};
+
+use futures::{stream, FutureExt, StreamExt};
+use quiz_game::{
+    client,
+    state::{DelegationConfig, LiveLeaderboard, PlayerScore, QuizSession, QuizStatus},
+    subscribe::{self, AccountStream, AccountUpdate, QuizPubsub, SubscribeError, Unsubscribe},
+};
+use solana_sdk::pubkey::Pubkey;
+
+/// A node that sends `updates` for the first subscribed address and then
+/// either closes the stream or, when `hold_open`, keeps it open.
+#[derive(Default)]
+struct FakePubsub {
+    updates: Mutex<Vec<AccountUpdate<Vec<u8>>>>,
+    hold_open: bool,
+    subscribed: Mutex<Vec<Pubkey>>,
+    unsubscribed: Arc<AtomicBool>,
+}
+
+impl QuizPubsub for FakePubsub {
+    async fn account_updates(
+        &self,
+        address: Pubkey,
+    ) -> Result<(AccountStream<'_>, Unsubscribe), SubscribeError> {
+        self.subscribed.lock().unwrap().push(address);
+        let updates = stream::iter(std::mem::take(&mut *self.updates.lock().unwrap()));
+        let updates = if self.hold_open {
+            updates.chain(stream::pending()).boxed()
+        } else {
+            updates.boxed()
+        };
+        let unsubscribed = self.unsubscribed.clone();
+        let unsubscribe: Unsubscribe =
+            Box::new(move || async move { unsubscribed.store(true, Ordering::SeqCst) }.boxed());
+        Ok((updates, unsubscribe))
+    }
+}
+
+fn update(slot: u64, data: Option<Vec<u8>>) -> AccountUpdate<Vec<u8>> {
+    AccountUpdate {
+        slot,
+        account: data,
+    }
+}
+
+fn fake(updates: Vec<AccountUpdate<Vec<u8>>>) -> Arc<FakePubsub> {
+    Arc::new(FakePubsub {
+        updates: Mutex::new(updates),
+        ..FakePubsub::default()
+    })
+}
+
+#[tokio::test]
+async fn quiz_updates_are_decoded_until_the_account_closes() {
+    let host = Pubkey::new_unique();
+    let quiz = Pubkey::new_unique();
+    let mut quiz_data = QuizSession::new(host, 1, 2, DelegationConfig::default());
+    let draft = borsh::to_vec(&quiz_data).unwrap();
+    quiz_data.status = QuizStatus::Active;
+    let active = borsh::to_vec(&quiz_data).unwrap();
+    let pubsub = fake(vec![
+        update(10, Some(draft)),
+        update(11, Some(active)),
+        update(12, None),
+    ]);
+
+    let mut subscription = subscribe::subscribe_quiz(pubsub.clone(), quiz);
+    let first = subscription.updates.recv().await.unwrap();
+    assert_eq!(first.slot, 10);
+    assert_eq!(first.account.unwrap().status, QuizStatus::Draft);
+    let second = subscription.updates.recv().await.unwrap();
+    assert_eq!(second.account.unwrap().status, QuizStatus::Active);
+    let closed = subscription.updates.recv().await.unwrap();
+    assert_eq!(closed.slot, 12);
+    assert!(closed.account.is_none());
+
+    assert!(subscription.updates.recv().await.is_none());
+    subscription.task.await.unwrap().unwrap();
+    assert_eq!(*pubsub.subscribed.lock().unwrap(), vec![quiz]);
+    assert!(pubsub.unsubscribed.load(Ordering::SeqCst));
+}
+
+#[tokio::test]
+async fn leaderboards_and_scores_are_found_by_their_addresses() {
+    let program_id = Pubkey::new_unique();
+    let quiz = Pubkey::new_unique();
+    let player = Pubkey::new_unique();
+    let mut board = LiveLeaderboard::new(quiz);
+    board.record(player, 3);
+    let pubsub = fake(vec![update(5, Some(borsh::to_vec(&board).unwrap()))]);
+    let mut subscription =
+        subscribe::subscribe_live_leaderboard(pubsub.clone(), &program_id, &quiz);
+    let top = subscription.updates.recv().await.unwrap().account.unwrap();
+    assert_eq!(top.top()[0].player, player);
+    assert_eq!(
+        pubsub.subscribed.lock().unwrap()[0],
+        client::find_live_leaderboard_address(&program_id, &quiz)
+    );
+
+    // Scores not migrated yet still decode
+    let mut legacy = vec![0; PlayerScore::LEGACY_SIZE];
+    legacy[..32].copy_from_slice(player.as_ref());
+    legacy[32] = 7;
+    legacy[33..65].copy_from_slice(quiz.as_ref());
+    let pubsub = fake(vec![update(6, Some(legacy))]);
+    let mut subscription =
+        subscribe::subscribe_player_score(pubsub.clone(), &program_id, &quiz, &player);
+    let score = subscription.updates.recv().await.unwrap().account.unwrap();
+    assert_eq!((score.player, score.score), (player, 7));
+    assert_eq!(
+        pubsub.subscribed.lock().unwrap()[0],
+        client::find_player_score_address(&program_id, &quiz, &player)
+    );
+}
+
+#[tokio::test]
+async fn undecodable_updates_end_the_subscription() {
+    let quiz = Pubkey::new_unique();
+    let pubsub = fake(vec![update(1, Some(vec![1, 2, 3]))]);
+    let mut subscription = subscribe::subscribe_quiz(pubsub.clone(), quiz);
+    assert!(subscription.updates.recv().await.is_none());
+    match subscription.task.await.unwrap() {
+        Err(SubscribeError::Undecodable(address)) => assert_eq!(address, quiz),
+        other => panic!("expected a decoding error, got {:?}", other),
+    }
+    assert!(pubsub.unsubscribed.load(Ordering::SeqCst));
+}
+
+#[tokio::test]
+async fn dropping_the_receiver_unsubscribes() {
+    let pubsub = Arc::new(FakePubsub {
+        hold_open: true,
+        ..FakePubsub::default()
+    });
+    let subscription = subscribe::subscribe_quiz(pubsub.clone(), Pubkey::new_unique());
+    drop(subscription.updates);
+    subscription.task.await.unwrap().unwrap();
+    assert!(pubsub.unsubscribed.load(Ordering::SeqCst));
+}
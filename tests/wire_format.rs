@@ -0,0 +1,457 @@
+use ephemeral_rollups_sdk::consts::EXTERNAL_UNDELEGATE_DISCRIMINATOR;
+use quiz_game::{
+    instruction::{QuizInstruction, ANCHOR_DISCRIMINATORS},
+    state::{
+        CompressedAnswers, DelegationConfig, HostPlay, PostedStanding, PrizeRemainder,
+        QuestionData, QuizMode, ScoringMode, VoidMode,
+    },
+};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+fn options() -> [String; 4] {
+    ["A", "B", "C", "D"].map(String::from)
+}
+
+fn standing() -> PostedStanding {
+    PostedStanding {
+        player: Pubkey::new_unique(),
+        score: 4,
+        rank: 2,
+        proof: vec![[7; 32], [8; 32]],
+    }
+}
+
+/// One instance of every variant, in declaration order.
+fn every_instruction() -> Vec<QuizInstruction> {
+    vec![
+        QuizInstruction::InitializeQuiz {
+            question_count: 3,
+            quiz_id: u64::MAX,
+            delegation: DelegationConfig {
+                commit_frequency_ms: 250,
+                validator: Pubkey::new_unique(),
+                base_layer: true,
+            },
+        },
+        QuizInstruction::AddQuestion {
+            question_index: 1,
+            question_text: "Capital of France?".to_string(),
+            options: options(),
+            correct_answer_index: 2,
+        },
+        QuizInstruction::StartQuiz,
+        QuizInstruction::DelegatePlayer {
+            validator: Some(Pubkey::new_unique()),
+            referrer: None,
+        },
+        QuizInstruction::SubmitAnswers {
+            confidence: vec![1, 3, 2],
+            answers: vec![0, 1, 2],
+        },
+        QuizInstruction::CommitAnswers,
+        QuizInstruction::CalculateScores,
+        QuizInstruction::UndelegatePlayer {
+            pda_seeds: vec![b"player_answer".to_vec(), vec![7; 32]],
+        },
+        QuizInstruction::SetSubmissionPolicy {
+            allow_resubmission: true,
+            refresh_slot_on_update: false,
+            deadline_slot: 1_000,
+            join_grace_slots: 150,
+        },
+        QuizInstruction::UpdateAnswers {
+            confidence: Vec::new(),
+            answers: vec![3, 2, 1],
+        },
+        QuizInstruction::SubmitAnswer {
+            question_index: 2,
+            answer: 3,
+        },
+        QuizInstruction::SetQuizMode {
+            mode: QuizMode::Elimination,
+            answer_window_slots: 30,
+        },
+        QuizInstruction::RevealQuestion { index: 4 },
+        QuizInstruction::CreateTeam {
+            team_id: 5,
+            name: "Owls".to_string(),
+        },
+        QuizInstruction::JoinTeam { team_id: 5 },
+        QuizInstruction::LeaveTeam { team_id: 5 },
+        QuizInstruction::FinalizeTeamScore { team_id: 5 },
+        QuizInstruction::SetEntryFee { lamports: 10_000 },
+        QuizInstruction::SettleDuel,
+        QuizInstruction::CreateTournament {
+            tournament_id: 9,
+            round_count: 3,
+            advance_count: 4,
+        },
+        QuizInstruction::LinkTournamentRound { round: 2 },
+        QuizInstruction::FundTournament { lamports: 5_000 },
+        QuizInstruction::AdvanceBracket,
+        QuizInstruction::ClaimTournamentPrize,
+        QuizInstruction::InitializeDailyQuiz {
+            curator: Pubkey::new_from_array([3; 32]),
+        },
+        QuizInstruction::PublishDailyQuestion {
+            day_index: 20_000,
+            question_index: 0,
+            question_text: "2 + 2?".to_string(),
+            options: options(),
+            correct_answer_index: 1,
+        },
+        QuizInstruction::PlayDailyQuiz {
+            answers: vec![1, 1],
+        },
+        QuizInstruction::PracticeQuiz { answers: vec![0] },
+        QuizInstruction::ListQuiz {
+            shard_index: 2,
+            start_slot: 500,
+        },
+        QuizInstruction::DelistQuiz { shard_index: 2 },
+        QuizInstruction::CommitAndUndelegateAnswers,
+        QuizInstruction::DelegateQuiz { validator: None },
+        QuizInstruction::CommitAndUndelegateQuiz,
+        QuizInstruction::DelegateScoreboard {
+            validator: Some(Pubkey::new_unique()),
+        },
+        QuizInstruction::GradeAnswers,
+        QuizInstruction::CommitScoreboard,
+        QuizInstruction::JoinQuiz {
+            referrer: Some(Pubkey::new_unique()),
+        },
+        QuizInstruction::CreateSession {
+            expires_slot: 900,
+            lamports: 5_000_000,
+        },
+        QuizInstruction::ScheduleStart { start_slot: 4_200 },
+        QuizInstruction::ActivateQuiz,
+        QuizInstruction::CloseSubmissions,
+        QuizInstruction::CancelQuiz,
+        QuizInstruction::SetLatePolicy {
+            late_window_slots: 300,
+            late_slots_per_point: 25,
+        },
+        QuizInstruction::SetConfidenceScoring { enabled: true },
+        QuizInstruction::SetHint {
+            question_index: 1,
+            hint: "Think of the tides".to_string(),
+            point_cost: 1,
+            lamport_cost: 5_000,
+        },
+        QuizInstruction::BuyHint { question_index: 1 },
+        QuizInstruction::SetRoundMultiplier {
+            first_question: 8,
+            last_question: 9,
+            multiplier: 2,
+        },
+        QuizInstruction::OpenQuestion {
+            index: 3,
+            open_slot: 1_000,
+            close_slot: 1_150,
+        },
+        QuizInstruction::ComputeAnalytics,
+        QuizInstruction::SetDisputeWindow { slots: 1_500 },
+        QuizInstruction::FlagQuestion {
+            question_index: 2,
+            reason_code: 1,
+        },
+        QuizInstruction::VoidQuestion {
+            question_index: 2,
+            mode: VoidMode::AwardAll,
+        },
+        QuizInstruction::Regrade,
+        QuizInstruction::CorrectScore {
+            new_score: 7,
+            reason: 1,
+        },
+        QuizInstruction::InitEventLog,
+        QuizInstruction::SetPrizeSplit {
+            split_bps: [5_000, 3_000, 2_000],
+        },
+        QuizInstruction::PostLeaderboardRoot { root: [9; 32] },
+        QuizInstruction::ClaimLeaderboardPrize {
+            score: 4,
+            rank: 2,
+            proof: vec![[1; 32], [2; 32]],
+        },
+        QuizInstruction::AppointScorer {
+            scorer: Pubkey::new_from_array([4; 32]),
+            bond_lamports: 5_000_000,
+        },
+        QuizInstruction::ChallengeScore {
+            score: 3,
+            rank: 1,
+            proof: vec![[3; 32]],
+        },
+        QuizInstruction::ReleaseBond,
+        QuizInstruction::InitAnswerTree,
+        QuizInstruction::SubmitCompressedAnswers {
+            answers: vec![0, 3],
+            confidence: Vec::new(),
+        },
+        QuizInstruction::GradeCompressedAnswers {
+            sheet: CompressedAnswers {
+                player: Pubkey::new_from_array([5; 32]),
+                answers: vec![0, 3],
+                confidence: vec![1, 1],
+                submitted_slot: 42,
+            },
+            proof: vec![[6; 32]; 20],
+        },
+        QuizInstruction::TopUpRent { lamports: 1_000 },
+        QuizInstruction::CloseQuestions,
+        QuizInstruction::CloneQuiz { quiz_id: 8 },
+        QuizInstruction::CreateTemplate {
+            template_id: 2,
+            title: "Algebra I midterm".to_string(),
+            public: false,
+        },
+        QuizInstruction::SetTemplateAccess {
+            public: false,
+            allowed: vec![Pubkey::new_from_array([7; 32])],
+        },
+        QuizInstruction::CreateFromTemplate {
+            quiz_id: 9,
+            delegation: DelegationConfig::default(),
+        },
+        QuizInstruction::ImportQuestions {
+            first_index: 1,
+            questions: vec![QuestionData {
+                question_text: "Q".into(),
+                options: ["A".into(), "B".into(), "C".into(), "D".into()],
+                correct_answer_index: 2,
+            }],
+        },
+        QuizInstruction::AddTranslation {
+            question_index: 1,
+            language: *b"vi",
+            question_text: "Q".into(),
+            options: ["A".into(), "B".into(), "C".into(), "D".into()],
+        },
+        QuizInstruction::SetNickname {
+            nickname: "Quizzer".into(),
+            reserve: true,
+        },
+        QuizInstruction::InitLiveLeaderboard {
+            validator: Some(Pubkey::new_unique()),
+        },
+        QuizInstruction::CommitLiveLeaderboard,
+        QuizInstruction::InitReactions {
+            cooldown_slots: 10,
+            validator: None,
+        },
+        QuizInstruction::SendReaction { emoji_code: 3 },
+        QuizInstruction::CommitReactions,
+        QuizInstruction::CreateSeries {
+            series_id: 4,
+            quiz_count: 6,
+        },
+        QuizInstruction::LinkSeriesQuiz { position: 5 },
+        QuizInstruction::FundSeries { lamports: 250_000 },
+        QuizInstruction::RecordSeriesQuiz,
+        QuizInstruction::ClaimSeriesPrize,
+        QuizInstruction::SetDailySeasonLength { season_days: 30 },
+        QuizInstruction::EndDailySeason,
+        QuizInstruction::EndSeriesSeason { quiz_count: 3 },
+        QuizInstruction::SetReferralCut { bps: 1_500 },
+        QuizInstruction::PayReferral,
+        QuizInstruction::SetPrizeThreshold {
+            min_score: 3,
+            min_score_bps: 5_000,
+            remainder: PrizeRemainder::RollOver,
+        },
+        QuizInstruction::ForfeitPrize {
+            rank: 2,
+            player: Pubkey::new_from_array([9; 32]),
+            score: 1,
+            proof: vec![[3; 32], [4; 32]],
+        },
+        QuizInstruction::SetProportionalPrizes { enabled: true },
+        QuizInstruction::ClaimScorePrize,
+        QuizInstruction::RolloverJackpot,
+        QuizInstruction::SetClaimWindow { slots: 43_200 },
+        QuizInstruction::SweepUnclaimed,
+        QuizInstruction::SetPrizeVesting {
+            min_lamports: 10_000_000,
+            slots: 216_000,
+        },
+        QuizInstruction::ClaimVested,
+        QuizInstruction::SetUsdEntryFee {
+            cents: 500,
+            feed_id: [7; 32],
+        },
+        QuizInstruction::ContributeToPrizePool {
+            lamports: 1_000_000,
+        },
+        QuizInstruction::RefundContribution,
+        QuizInstruction::SetCharitySplit {
+            charity: Pubkey::new_from_array([5; 32]),
+            bps: 1_000,
+        },
+        QuizInstruction::PayCharity,
+        QuizInstruction::SetHostPlay {
+            policy: HostPlay::Barred,
+        },
+        QuizInstruction::SetJoinStake {
+            lamports: 10_000_000,
+        },
+        QuizInstruction::ReclaimStake,
+        QuizInstruction::SetScoringMode {
+            mode: ScoringMode::Streak,
+        },
+        QuizInstruction::MigratePlayerScore,
+        QuizInstruction::SetQuestionDifficulty {
+            first_question: 0,
+            last_question: 4,
+            difficulty: 5,
+        },
+        QuizInstruction::SetDifficultyScoring { enabled: true },
+        QuizInstruction::InitAdaptiveBank {
+            questions_per_player: 3,
+        },
+        QuizInstruction::SetQuestionSubset {
+            questions_per_player: 2,
+            seed: [9; 32],
+        },
+        QuizInstruction::RevealAnswers,
+        QuizInstruction::SetExplanation {
+            question_index: 1,
+            explanation: "Paris has been the capital since 987".to_string(),
+        },
+        QuizInstruction::SetQuestionKey { key_hash: [4; 32] },
+        QuizInstruction::RevealQuestions { key: [5; 32] },
+        QuizInstruction::CommitQuestion {
+            question_index: 2,
+            question_hash: [6; 32],
+        },
+        QuizInstruction::RevealBlindQuestion {
+            index: 2,
+            question: QuestionData {
+                question_text: "Tallest mountain?".to_string(),
+                options: ["Everest", "K2", "Denali", "Fuji"].map(String::from),
+                correct_answer_index: 0,
+            },
+        },
+        QuizInstruction::ImportSignedQuestions {
+            first_index: 1,
+            curator: Pubkey::new_from_array([8; 32]),
+            questions: vec![QuestionData {
+                question_text: "Largest ocean?".to_string(),
+                options: ["Pacific", "Atlantic", "Indian", "Arctic"].map(String::from),
+                correct_answer_index: 0,
+            }],
+        },
+        QuizInstruction::SubmitRelayedAnswers {
+            answers: vec![1, 0, 3],
+            confidence: vec![],
+            nonce: 7,
+        },
+        QuizInstruction::RefundDuel,
+        QuizInstruction::ChallengeMissingSheet {
+            standing: standing(),
+        },
+        QuizInstruction::ChallengeRanks {
+            first: standing(),
+            second: standing(),
+        },
+        QuizInstruction::ClaimTeamPrize { team_id: 4 },
+    ]
+}
+
+#[test]
+fn every_variant_round_trips() {
+    let instructions = every_instruction();
+    assert_eq!(instructions.len(), ANCHOR_DISCRIMINATORS.len());
+
+    for (index, instruction) in instructions.into_iter().enumerate() {
+        let packed = instruction.pack();
+        assert_eq!(
+            packed[..8],
+            ANCHOR_DISCRIMINATORS[index],
+            "{:?}",
+            instruction
+        );
+        assert_eq!(QuizInstruction::unpack(&packed).unwrap(), instruction);
+    }
+}
+
+#[test]
+fn compact_tag_round_trips() {
+    for (index, instruction) in every_instruction().into_iter().enumerate() {
+        let compact = instruction.pack_compact();
+        assert_eq!(compact[0] as usize, index, "{:?}", instruction);
+        assert_eq!(compact[1..], instruction.pack()[8..]);
+        assert_eq!(QuizInstruction::unpack(&compact).unwrap(), instruction);
+    }
+}
+
+#[test]
+fn zero_heavy_compact_data_is_not_read_as_legacy() {
+    // Starts with [25, 0, 0, 0, 0, 0, 0, 0], a legacy discriminator, and the
+    // remainder also decodes as PublishDailyQuestion fields, for day 524288
+    let instruction = QuizInstruction::PublishDailyQuestion {
+        day_index: 0,
+        question_index: 0,
+        question_text: "\0\0\0\0\0\0\0\u{1}".to_string(),
+        options: ["", "", "", ""].map(String::from),
+        correct_answer_index: 0,
+    };
+    let compact = instruction.pack_compact();
+    assert_eq!(compact[..8], [25, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(QuizInstruction::unpack(&compact).unwrap(), instruction);
+}
+
+#[test]
+fn legacy_discriminator_decodes_the_same() {
+    for (index, instruction) in every_instruction().into_iter().enumerate() {
+        let mut legacy = (index as u64).to_le_bytes().to_vec();
+        legacy.extend_from_slice(&instruction.pack()[8..]);
+        assert_eq!(QuizInstruction::unpack(&legacy).unwrap(), instruction);
+    }
+}
+
+#[test]
+fn undelegation_callback_decodes_to_undelegate_player() {
+    let pda_seeds = vec![b"player_answer".to_vec(), vec![1; 32], vec![2; 32]];
+    let mut callback = EXTERNAL_UNDELEGATE_DISCRIMINATOR.to_vec();
+    callback.extend_from_slice(&borsh::to_vec(&pda_seeds).unwrap());
+    assert_eq!(
+        QuizInstruction::unpack(&callback).unwrap(),
+        QuizInstruction::UndelegatePlayer { pda_seeds }
+    );
+
+    callback.push(0);
+    assert_eq!(
+        QuizInstruction::unpack(&callback),
+        Err(ProgramError::InvalidInstructionData)
+    );
+}
+
+#[test]
+fn malformed_data_is_rejected() {
+    let invalid = Err(ProgramError::InvalidInstructionData);
+
+    // Too short for a discriminator
+    assert_eq!(QuizInstruction::unpack(&[0; 7]), invalid);
+
+    // Unknown discriminators, sighash or legacy
+    assert_eq!(QuizInstruction::unpack(&[0xff; 8]), invalid);
+    let past_last = (ANCHOR_DISCRIMINATORS.len() as u64).to_le_bytes();
+    assert_eq!(QuizInstruction::unpack(&past_last), invalid);
+
+    // Truncated and trailing fields
+    let packed = QuizInstruction::InitializeQuiz {
+        question_count: 3,
+        quiz_id: 1,
+        delegation: DelegationConfig::default(),
+    }
+    .pack();
+    assert_eq!(
+        QuizInstruction::unpack(&packed[..packed.len() - 1]),
+        invalid
+    );
+    let mut trailing = packed.clone();
+    trailing.push(0);
+    assert_eq!(QuizInstruction::unpack(&trailing), invalid);
+}